@@ -0,0 +1,25 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use javarust::virtual_machine::{JavaVm, VmOptions};
+use jvmrs_lib::method;
+
+/// `benches/Fibonacci.class`, compiled from `benches/Fibonacci.java`
+const FIBONACCI: &[u8] = include_bytes!("Fibonacci.class");
+
+fn fibonacci(c: &mut Criterion) {
+    let vm = JavaVm::new(vec![FIBONACCI.to_vec()], VmOptions::default(), false).unwrap();
+    let descriptor = method!((int) -> int);
+    assert_eq!(
+        vm.invoke_static("Fibonacci", "fib", &descriptor, &[20], false)
+            .unwrap(),
+        [6765]
+    );
+    c.bench_function("fib(20)", |b| {
+        b.iter(|| {
+            vm.invoke_static("Fibonacci", "fib", &descriptor, &[black_box(20)], false)
+                .unwrap()
+        });
+    });
+}
+
+criterion_group!(benches, fibonacci);
+criterion_main!(benches);
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::fmt::Debug;
 use std::sync::{Arc, Mutex, Once, OnceLock};
 
@@ -25,6 +26,8 @@ pub struct Class {
     pub version: ClassVersion,
     /// run-time constant pool
     pub constants: Vec<Constant>,
+    /// dynamically-computed constants by their index in the constant pool
+    pub dynamic_constants: BTreeMap<u16, DynamicConstant>,
     pub access: AccessFlags,
     /// current class name
     pub this: Arc<str>,
@@ -49,6 +52,8 @@ pub struct Class {
     /// signature including generics
     pub signature: Option<Arc<str>>,
     pub inner_classes: Vec<InnerClass>,
+    /// components of a record class, in declaration order
+    pub record_components: Vec<RecordComponent>,
     pub attributes: Vec<Attribute>,
 }
 
@@ -58,6 +63,7 @@ impl Default for Class {
             initialized: Once::new(),
             version: ClassVersion::default(),
             constants: Vec::new(),
+            dynamic_constants: BTreeMap::new(),
             access: access!(public),
             this: "".into(),
             super_class: "".into(),
@@ -72,6 +78,7 @@ impl Default for Class {
             source_file: None,
             signature: None,
             inner_classes: Vec::new(),
+            record_components: Vec::new(),
             attributes: Vec::new(),
         }
     }
@@ -99,9 +106,15 @@ impl Debug for Class {
             .field("statics", &self.statics)
             .field("vtable", &self.vtable)
             .field("bootstrap_methods", &self.bootstrap_methods);
+        if !self.dynamic_constants.is_empty() {
+            s.field("dynamic_constants", &self.dynamic_constants);
+        }
         if !self.inner_classes.is_empty() {
             s.field("inner_classes", &self.inner_classes);
         }
+        if !self.record_components.is_empty() {
+            s.field("record_components", &self.record_components);
+        }
         if let Some(source_file) = &self.source_file {
             s.field("source_file", source_file);
         }
@@ -224,6 +237,16 @@ pub struct BootstrapMethod {
     pub args: Vec<Constant>,
 }
 
+/// a `CONSTANT_Dynamic`, whose value comes from running its bootstrap method the first time it's
+/// loaded. `Constant` has no variant for one, so its index in the constant pool holds a
+/// `Constant::Placeholder`
+#[derive(Debug, Clone)]
+pub struct DynamicConstant {
+    pub bootstrap_index: u16,
+    pub name: Arc<str>,
+    pub field_type: FieldType,
+}
+
 #[derive(Debug, Clone)]
 pub struct InnerClass {
     pub this: Arc<str>,
@@ -231,3 +254,11 @@ pub struct InnerClass {
     pub name: Option<Arc<str>>,
     pub flags: AccessFlags,
 }
+
+#[derive(Debug, Clone)]
+pub struct RecordComponent {
+    pub name: Arc<str>,
+    pub descriptor: FieldType,
+    pub signature: Option<Arc<str>>,
+    pub attributes: Vec<Attribute>,
+}
@@ -152,6 +152,8 @@ pub type NativeReturn<T> = error::Result<Option<T>>;
 ///  - `NativeVoid`
 ///  - `NativeNoop`
 ///  - `native_property`
+///
+/// and wrapped in `Staged` if it calls back into java
 pub trait NativeMethod: Send + Sync + Debug + 'static {
     /// # Native Method
     /// Called each tick while the native method is in the current stackframe
@@ -159,6 +161,37 @@ pub trait NativeMethod: Send + Sync + Debug + 'static {
     fn run(&self, thread: &mut Thread, is_verbose: bool) -> error::Result<()>;
 
     fn args(&self) -> u16;
+
+    /// the number of stages the method's state machine has. Its pc is always one of these, whether
+    /// it set the pc itself or pushed it as the return address of a call
+    fn stages(&self) -> usize {
+        1
+    }
+}
+
+#[derive(Clone, Copy)]
+/// A native method that runs in the given number of stages, numbered from 0. Each time it calls a
+/// java method, it resumes at the stage it pushed as the return address
+pub struct Staged<T>(pub usize, pub T);
+
+impl<T: Debug> Debug for Staged<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} in {} stages", self.1, self.0)
+    }
+}
+
+impl<T: NativeMethod> NativeMethod for Staged<T> {
+    fn run(&self, thread: &mut Thread, is_verbose: bool) -> error::Result<()> {
+        self.1.run(thread, is_verbose)
+    }
+
+    fn args(&self) -> u16 {
+        self.1.args()
+    }
+
+    fn stages(&self) -> usize {
+        self.0
+    }
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -373,6 +406,8 @@ pub struct ByteCode {
     pub max_stack: u16,
     /// List of bytecode instructions
     pub code: Vec<Instruction>,
+    /// The byte offset and opcode each instruction had in the class file
+    pub opcodes: Vec<(u16, u8)>,
     /// List of exception handlers
     pub exception_table: Vec<ExceptionTableEntry>,
     /// List of line numbers (unused)
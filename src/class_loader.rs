@@ -1,4 +1,11 @@
-use std::{iter::Peekable, sync::Arc};
+use std::{
+    collections::{btree_map::Entry, BTreeMap, HashSet, VecDeque},
+    fs::{self, File},
+    io::{BufReader, Read, Seek},
+    iter::Peekable,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
 
 use crate::{
     class::{
@@ -6,10 +13,10 @@ use crate::{
             ByteCode, ExceptionTableEntry, LineTableEntry, LocalVarEntry, LocalVarTypeEntry,
             StackMapFrame, VerificationTypeInfo,
         },
-        Attribute, BootstrapMethod, Field, InnerClass,
+        Attribute, BootstrapMethod, DynamicConstant, Field, InnerClass, RecordComponent,
     },
     data::{SharedClassArea, WorkingClassArea, WorkingMethodArea},
-    virtual_machine::{add_native_methods, hydrate_code},
+    virtual_machine::{add_native_methods, error, hydrate_code},
 };
 
 mod raw_class;
@@ -19,6 +26,9 @@ pub use raw_class::{MethodName, RawClass, RawCode, RawMethod};
 
 // TODO: Attributes: EnclosingMethod, NestHost, NestMembers
 
+/// the access flag that marks a `module-info` class
+const ACC_MODULE: u16 = 0x8000;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MethodHandleKind {
     GetField,
@@ -79,19 +89,22 @@ pub enum RawConstant {
     MethodType {
         index: u16,
     },
-    // Dynamic {
-    //     constant: u32,
-    // },
+    Dynamic {
+        bootstrap_index: u16,
+        name_type_index: u16,
+    },
     InvokeDynamic {
         bootstrap_index: u16,
         name_type_index: u16,
     },
-    // Module {
-    //     identity: u16,
-    // },
-    // Package {
-    //     identity: u16,
-    // },
+    Module {
+        /// index in constant pool for a String value (module name)
+        name_index: u16,
+    },
+    Package {
+        /// index in constant pool for a String value (internally-qualified package name)
+        name_index: u16,
+    },
     /// Index taken up by the second part of a long or double
     Placeholder,
 }
@@ -104,6 +117,275 @@ pub fn load_environment() -> (WorkingMethodArea, WorkingClassArea) {
     (method_area, class_area)
 }
 
+/// Whether an entry of a jar file is a class that should be loaded.
+///
+/// Multi-release jars keep overrides for newer java versions under `META-INF/versions/N/`. Only the
+/// base classes are loaded, so the overrides can't clash with them.
+#[must_use]
+pub fn is_class_entry(entry: &str) -> bool {
+    std::path::Path::new(entry)
+        .extension()
+        .is_some_and(|ext| ext == "class")
+        && !entry.starts_with("META-INF/")
+        && !entry.ends_with("module-info.class")
+}
+
+/// The class files packed into a jar
+#[derive(Debug, Default)]
+pub struct Jar {
+    /// the contents of every class entry, in the order they're stored
+    pub classes: Vec<Vec<u8>>,
+    /// the internal name of the class named by `Main-Class` in the manifest, like `com/example/App`
+    pub main_class: Option<String>,
+    /// the URLs of the other jars and directories listed by `Class-Path` in the manifest, relative
+    /// to this jar; [`manifest_class_path`] turns them into paths
+    pub class_path: Vec<String>,
+}
+
+/// the jars that classes and resources have been read out of, so each one is only opened once
+static JAR_ARCHIVES: Mutex<BTreeMap<PathBuf, zip::ZipArchive<BufReader<File>>>> =
+    Mutex::new(BTreeMap::new());
+
+/// Read every class out of a jar, skipping the entries [`is_class_entry`] rejects, along with the
+/// main class and class path from `META-INF/MANIFEST.MF`
+/// # Errors
+/// Returns an error if the file isn't a valid zip archive or an entry can't be decompressed
+pub fn read_jar(reader: impl Read + Seek) -> zip::result::ZipResult<Jar> {
+    let mut archive = zip::ZipArchive::new(reader)?;
+    let mut jar = Jar::default();
+    for idx in 0..archive.len() {
+        let mut entry = archive.by_index(idx)?;
+        if entry.name() == "META-INF/MANIFEST.MF" {
+            let mut manifest = String::new();
+            entry.read_to_string(&mut manifest)?;
+            jar.main_class = manifest_attribute(&manifest, "Main-Class")
+                .map(|main_class| main_class.replace('.', "/"));
+            jar.class_path = class_path_attribute(&manifest);
+        } else if is_class_entry(entry.name()) {
+            let mut bytes = Vec::with_capacity(entry.size() as usize);
+            entry.read_to_end(&mut bytes)?;
+            jar.classes.push(bytes);
+        }
+    }
+    Ok(jar)
+}
+
+/// Load every class that the loaded classes refer to but that wasn't given up front, along with the
+/// classes in `wanted`. Each one is read from the first `classpath` entry that has it, as described
+/// in [`find_on_classpath`], and the classes it refers to are loaded in turn. Classes that no directory
+/// has are left alone, since the native library or a class that's never used might account for them
+/// # Errors
+/// Returns an error if a class file on the classpath is malformed
+pub fn load_classpath(
+    method_area: &mut WorkingMethodArea,
+    class_area: &mut WorkingClassArea,
+    classpath: &[PathBuf],
+    wanted: impl IntoIterator<Item = Arc<str>>,
+    verbose: bool,
+) -> error::Result<()> {
+    let mut pending = class_area
+        .classes()
+        .flat_map(referenced_classes)
+        .chain(wanted)
+        .collect::<Vec<_>>();
+    let mut searched = HashSet::new();
+    while let Some(name) = pending.pop() {
+        if class_area.search(&name).is_some() || !searched.insert(name.clone()) {
+            continue;
+        }
+        let Some(bytes) = find_on_classpath(classpath, &name) else {
+            continue;
+        };
+        if verbose {
+            println!("Loading {name} from the classpath...");
+        }
+        let class = load_class(method_area, &mut bytes.into_iter(), verbose)?;
+        pending.extend(referenced_classes(&class));
+        class_area.push(class);
+    }
+    Ok(())
+}
+
+/// read a class from the first entry in `classpath` that has it, as described in [`find_resource`]
+#[must_use]
+pub fn find_on_classpath(classpath: &[PathBuf], class: &str) -> Option<Vec<u8>> {
+    find_resource(classpath, &format!("{class}.class"))
+}
+
+/// read the file at `name`, like `com/example/data.txt`, from the first entry in `classpath` that
+/// has it. An entry is either a jar holding an entry with that name or a directory holding it at
+/// that path
+#[must_use]
+pub fn find_resource(classpath: &[PathBuf], name: &str) -> Option<Vec<u8>> {
+    classpath.iter().find_map(|entry| {
+        if entry.extension().is_some_and(|ext| ext == "jar") {
+            read_jar_entry(entry, name).ok()
+        } else {
+            fs::read(entry.join(name)).ok()
+        }
+    })
+}
+
+/// read a single entry out of a jar file, opening it if it hasn't been opened yet
+fn read_jar_entry(jar: &Path, name: &str) -> zip::result::ZipResult<Vec<u8>> {
+    let mut archives = JAR_ARCHIVES.lock().unwrap();
+    let archive = match archives.entry(jar.to_path_buf()) {
+        Entry::Occupied(archive) => archive.into_mut(),
+        Entry::Vacant(vacant) => {
+            vacant.insert(zip::ZipArchive::new(BufReader::new(File::open(jar)?))?)
+        }
+    };
+    let mut entry = archive.by_name(name)?;
+    let mut bytes = Vec::with_capacity(entry.size() as usize);
+    entry.read_to_end(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// the classes a class extends, implements, or names in its constant pool, including the ones
+/// that only show up in a descriptor, like the functional interface an `invokedynamic` returns.
+/// Arrays stand for their element class, and arrays of primitives are left out
+fn referenced_classes(class: &RawClass) -> Vec<Arc<str>> {
+    let mut classes = Vec::new();
+    for constant in &class.constants {
+        match constant {
+            Constant::ClassRef(name) => {
+                if let Some(element) = name.strip_prefix('[') {
+                    classes.extend(descriptor_classes(element));
+                } else {
+                    classes.push(name.clone());
+                }
+            }
+            Constant::NameTypeDescriptor {
+                type_descriptor, ..
+            } => classes.extend(descriptor_classes(type_descriptor)),
+            Constant::MethodType(method_type) => {
+                classes.extend(method_descriptor_classes(method_type));
+            }
+            _ => {}
+        }
+    }
+    for (field, _) in class.fields.iter().chain(&class.statics) {
+        classes.extend(field_type_class(&field.descriptor));
+    }
+    for method in &class.methods {
+        classes.extend(method_descriptor_classes(&method.descriptor));
+    }
+    classes.push(class.super_class.clone());
+    classes.extend(class.interfaces.iter().cloned());
+    classes
+}
+
+/// the classes named in a field or method descriptor, like `(Ljava/lang/String;[I)Ljava/util/List;`
+fn descriptor_classes(descriptor: &str) -> Vec<Arc<str>> {
+    let mut classes = Vec::new();
+    let mut chars = descriptor.chars();
+    // everything outside of an `L...;` is a single character, so any `L` starts a class name
+    while let Some(c) = chars.next() {
+        if c == 'L' {
+            let name = chars.by_ref().take_while(|&c| c != ';').collect::<String>();
+            classes.push(name.into());
+        }
+    }
+    classes
+}
+
+fn method_descriptor_classes(descriptor: &MethodDescriptor) -> Vec<Arc<str>> {
+    descriptor
+        .parameters
+        .iter()
+        .chain(&descriptor.return_type)
+        .filter_map(field_type_class)
+        .collect()
+}
+
+/// the class of an object or of an array's elements
+fn field_type_class(field_type: &FieldType) -> Option<Arc<str>> {
+    match field_type {
+        FieldType::Object(class) => Some(class.clone()),
+        FieldType::Array(element) => field_type_class(element),
+        _ => None,
+    }
+}
+
+/// The jars and directories that the `Class-Path` of the jar at `jar` adds to the classpath, given
+/// the URLs it lists, followed by the ones that the manifests of those jars add in turn
+#[must_use]
+pub fn manifest_class_path(jar: &Path, class_path: &[String]) -> Vec<PathBuf> {
+    let mut found = Vec::new();
+    let mut pending = VecDeque::from(class_path_entries(jar, class_path));
+    while let Some(entry) = pending.pop_front() {
+        if entry == jar || found.contains(&entry) {
+            continue;
+        }
+        if entry.extension().is_some_and(|ext| ext == "jar") {
+            if let Ok(manifest) = read_jar_entry(&entry, "META-INF/MANIFEST.MF") {
+                let nested = class_path_attribute(&String::from_utf8_lossy(&manifest));
+                pending.extend(class_path_entries(&entry, &nested));
+            }
+        }
+        found.push(entry);
+    }
+    found
+}
+
+/// the URLs listed by `Class-Path` in a jar manifest
+fn class_path_attribute(manifest: &str) -> Vec<String> {
+    manifest_attribute(manifest, "Class-Path")
+        .map(|class_path| class_path.split_whitespace().map(String::from).collect())
+        .unwrap_or_default()
+}
+
+/// resolve `Class-Path` URLs against the directory of the jar that lists them. Escapes like `%20`
+/// are decoded, and URLs that aren't local files are left out
+fn class_path_entries(jar: &Path, urls: &[String]) -> Vec<PathBuf> {
+    let dir = jar.parent().unwrap_or(Path::new(""));
+    urls.iter()
+        .filter_map(|url| {
+            let path = match url.split_once(':') {
+                Some(("file", path)) => path.strip_prefix("//").unwrap_or(path),
+                Some((scheme, _)) if !scheme.contains('/') => return None,
+                _ => url,
+            };
+            Some(dir.join(percent_decode(path)?))
+        })
+        .collect()
+}
+
+/// decode the `%XX` escapes in a URL path
+fn percent_decode(path: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(path.len());
+    let mut rest = path.bytes();
+    while let Some(byte) = rest.next() {
+        if byte == b'%' {
+            let hex = [rest.next()?, rest.next()?];
+            bytes.push(u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?);
+        } else {
+            bytes.push(byte);
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+/// Find an attribute in the main section of a jar manifest. Long values are wrapped onto lines
+/// that start with a space, which get joined back together
+#[must_use]
+pub fn manifest_attribute(manifest: &str, key: &str) -> Option<String> {
+    let mut lines = manifest.lines();
+    // the main section ends at the first blank line
+    let value = lines
+        .by_ref()
+        .take_while(|line| !line.is_empty())
+        .find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            name.eq_ignore_ascii_case(key).then_some(value.trim_start())
+        })?;
+    let mut value = String::from(value);
+    for continuation in lines.map_while(|line| line.strip_prefix(' ')) {
+        value.push_str(continuation);
+    }
+    Some(value.trim_end().to_string())
+}
+
 #[allow(clippy::too_many_lines)]
 /// # Errors
 /// # Panics
@@ -111,9 +393,9 @@ pub fn load_class(
     method_area: &mut WorkingMethodArea,
     bytes: &mut impl Iterator<Item = u8>,
     verbose: bool,
-) -> Result<RawClass, String> {
+) -> error::Result<RawClass> {
     let 0xCAFE_BABE = get_u32(bytes)? else {
-        return Err(String::from("Invalid header"));
+        return Err(error::Error::class_format("Invalid header"));
     };
     let [minor_version, major_version, mut const_count] = get_u16_array(bytes)?;
     let version = ClassVersion {
@@ -189,7 +471,7 @@ pub fn load_class(
             }
             Some(15) => {
                 let Some(descriptor) = bytes.next() else {
-                    return Err(String::from("Unexpected EOF"));
+                    return Err(error::Error::class_format("Unexpected EOF"));
                 };
                 let descriptor = match descriptor {
                     1 => MethodHandleKind::GetField,
@@ -201,7 +483,11 @@ pub fn load_class(
                     7 => MethodHandleKind::InvokeSpecial,
                     8 => MethodHandleKind::NewInvokeSpecial,
                     9 => MethodHandleKind::InvokeInterface,
-                    _ => return Err(format!("Invalid MethodHandleKind: {descriptor}")),
+                    _ => {
+                        return Err(error::Error::class_format(format!(
+                            "Invalid MethodHandleKind: {descriptor}"
+                        )))
+                    }
                 };
                 let index = get_u16(bytes)?;
                 raw_constants.push(RawConstant::MethodHandle { descriptor, index });
@@ -210,6 +496,13 @@ pub fn load_class(
                 let index = get_u16(bytes)?;
                 raw_constants.push(RawConstant::MethodType { index });
             }
+            Some(17) => {
+                let [bootstrap_index, name_type_index] = get_u16_array(bytes)?;
+                raw_constants.push(RawConstant::Dynamic {
+                    bootstrap_index,
+                    name_type_index,
+                });
+            }
             Some(18) => {
                 let [bootstrap_index, name_type_index] = get_u16_array(bytes)?;
                 raw_constants.push(RawConstant::InvokeDynamic {
@@ -217,10 +510,18 @@ pub fn load_class(
                     name_type_index,
                 });
             }
+            Some(19) => {
+                let name_index = get_u16(bytes)?;
+                raw_constants.push(RawConstant::Module { name_index });
+            }
+            Some(20) => {
+                let name_index = get_u16(bytes)?;
+                raw_constants.push(RawConstant::Package { name_index });
+            }
             other => {
                 println!("{raw_constants:?}");
                 println!("{}", raw_constants.len());
-                return Err(format!("Ugh, {other:?}"));
+                return Err(error::Error::class_format(format!("Ugh, {other:?}")));
             }
         }
     }
@@ -229,6 +530,27 @@ pub fn load_class(
         .iter()
         .map(|constant| cook_constant(&raw_constants, constant))
         .collect::<Result<Vec<_>, _>>()?;
+    let mut dynamic_constants = BTreeMap::new();
+    for (index, constant) in raw_constants.iter().enumerate() {
+        let &RawConstant::Dynamic {
+            bootstrap_index,
+            name_type_index,
+        } = constant
+        else {
+            continue;
+        };
+        let (name, field_type) = raw_name_type_index(&raw_constants, name_type_index as usize)?;
+        let field_type = parse_field_type(&mut field_type.chars().peekable())?;
+        // constant pool indices start at 1
+        dynamic_constants.insert(
+            index as u16 + 1,
+            DynamicConstant {
+                bootstrap_index,
+                name,
+                field_type,
+            },
+        );
+    }
     if verbose {
         println!("{constants:?}");
     }
@@ -237,7 +559,24 @@ pub fn load_class(
 
     let access = AccessFlags(access);
     let this_class = raw_class_index(&raw_constants, this_class as usize)?;
-    let super_class = raw_class_index(&raw_constants, super_class as usize)?;
+    if access.0 & ACC_MODULE == 0 {
+        if let Some(constant) = raw_constants.iter().find(|constant| {
+            matches!(
+                constant,
+                RawConstant::Module { .. } | RawConstant::Package { .. }
+            )
+        }) {
+            return Err(error::Error::class_format(format!(
+                "{this_class} isn't a module, so it can't have {constant:?} in its constant pool"
+            )));
+        }
+    }
+    // only java/lang/Object and module-info have no superclass
+    let super_class = if super_class == 0 {
+        Arc::from("java/lang/Object")
+    } else {
+        raw_class_index(&raw_constants, super_class as usize)?
+    };
     let mut interfaces = Vec::new();
     for _ in 0..interface_count {
         interfaces.push(raw_class_index(&raw_constants, get_u16(bytes)? as usize)?);
@@ -261,12 +600,12 @@ pub fn load_class(
             match constant_value {
                 Some(const_idx) => {
                     let [b0, b1] = const_idx[..] else {
-                        return Err(String::from(
+                        return Err(error::Error::class_format(
                             "`ConstantValue` attribute must have exactly two bytes",
                         ));
                     };
                     let Some(constant) = constants.get((b0 as usize) << 8 | b1 as usize) else {
-                        return Err(String::from(
+                        return Err(error::Error::class_format(
                             "`ConstantValue` attribute has invalid constant index",
                         ));
                     };
@@ -311,15 +650,15 @@ pub fn load_class(
         let code = match code_attributes {
             None if access_flags.is_abstract() => RawCode::Abstract,
             Some(_) if access_flags.is_abstract() => {
-                return Err(format!(
+                return Err(error::Error::class_format(format!(
                     "Abstract method {descriptor:?} {this_class}.{name} must not contain code"
-                ));
+                )));
             }
             Some(bytes) => RawCode::Code(bytes),
             None => {
-                return Err(format!(
+                return Err(error::Error::class_format(format!(
                     "Non-Abstract method {descriptor:?} {this_class}.{name} must contain code"
-                ))
+                )))
             }
         };
 
@@ -370,7 +709,7 @@ pub fn load_class(
                     constants[method_ref as usize - 1].clone()
                 else {
                     println!("{method_ref}: {:?}", constants[method_ref as usize - 1]);
-                    return Err(String::from(
+                    return Err(error::Error::class_format(
                         "Bootstrap method needs to lead to a MethodHandle",
                     ));
                 };
@@ -421,20 +760,20 @@ pub fn load_class(
                     this: if let Constant::ClassRef(class) = constants[this_idx - 1].clone() {
                         class
                     } else {
-                        return Err(format!(
+                        return Err(error::Error::class_format(format!(
                             "Expected class ref for InnerClass.inner_class_info_index; got {:?}",
                             constants[this_idx - 1]
-                        ));
+                        )));
                     },
                     outer: if outer_idx == 0 {
                         None
                     } else if let Constant::ClassRef(class) = constants[outer_idx - 1].clone() {
                         Some(class)
                     } else {
-                        return Err(format!(
+                        return Err(error::Error::class_format(format!(
                             "Expected class ref for InnerClass.outer_class_info_index; got {:?}",
                             constants[outer_idx - 1]
-                        ));
+                        )));
                     },
                     name: if name_idx == 0 {
                         None
@@ -449,6 +788,33 @@ pub fn load_class(
         Vec::new()
     };
 
+    let (record, attributes) = single_attribute(attributes, "Record")?;
+
+    let record_components = if let Some(record) = record {
+        let mut bytes = record.into_iter();
+        let count = get_u16(&mut bytes)?;
+        (0..count)
+            .map(|_| {
+                let [name_idx, descriptor_idx, attrs_count] = get_u16_array(&mut bytes)?;
+                let name = str_index(&constants, name_idx as usize)?;
+                let descriptor = str_index(&constants, descriptor_idx as usize)?;
+                let descriptor = parse_field_type(&mut descriptor.chars().peekable())?;
+                let attributes = (0..attrs_count)
+                    .map(|_| get_attribute(&constants, &mut bytes))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let (signature, attributes) = get_signature(&constants, attributes)?;
+                Ok(RecordComponent {
+                    name,
+                    descriptor,
+                    signature,
+                    attributes,
+                })
+            })
+            .collect::<error::Result<Vec<_>>>()?
+    } else {
+        Vec::new()
+    };
+
     let (nest_host, attributes) = single_attribute(attributes, "NestHost")?;
 
     let _nest_host = match nest_host {
@@ -482,22 +848,34 @@ pub fn load_class(
             let field_location = statics_size;
             statics_size += field.descriptor.get_size();
             if let Some(constant) = &field.constant_value {
-                // include any constant values
-                static_data.extend(constant.bytes());
+                // include any constant values; boolean, byte, short, and char constants are stored
+                // as ints, so they take one slot just like the field does
+                let bytes = constant.bytes();
+                if bytes.len() != field.descriptor.get_size() {
+                    return Err(error::Error::class_format(format!(
+                        "Constant value {constant:?} takes {} slots, but static field {} {} takes {}",
+                        bytes.len(),
+                        field.descriptor,
+                        field.name,
+                        field.descriptor.get_size()
+                    )));
+                }
+                static_data.extend(bytes);
             } else {
                 // put zeroes otherwise
                 static_data.extend(std::iter::repeat(0).take(field.descriptor.get_size()));
             }
-            (field, field_location)
+            Ok((field, field_location))
         })
-        .collect();
+        .collect::<Result<_, _>>()?;
 
     if static_data.len() != statics_size {
-        return Err(String::from("Static data size error"));
+        return Err(error::Error::class_format("Static data size error"));
     }
 
     let class = RawClass {
         constants,
+        dynamic_constants,
         access,
         this: this_class.clone(),
         super_class,
@@ -518,6 +896,7 @@ pub fn load_class(
         version,
         signature,
         inner_classes,
+        record_components,
         source_file,
         attributes,
     };
@@ -531,14 +910,14 @@ pub fn load_class(
 fn single_attribute(
     attributes: Vec<Attribute>,
     compare: &str,
-) -> Result<(Option<Vec<u8>>, Vec<Attribute>), String> {
+) -> error::Result<(Option<Vec<u8>>, Vec<Attribute>)> {
     let (mut single_attribute, attributes) = split_attributes(attributes, compare);
     match &mut single_attribute[..] {
         [] => Ok((None, attributes)),
         [attr] => Ok((Some(core::mem::take(&mut attr.data)), attributes)),
-        _ => Err(format!(
+        _ => Err(error::Error::class_format(format!(
             "A class should have at most one {compare} attribute"
-        )),
+        ))),
     }
 }
 
@@ -551,16 +930,16 @@ fn split_attributes(attributes: Vec<Attribute>, compare: &str) -> (Vec<Attribute
 fn get_signature(
     constants: &[Constant],
     attributes: Vec<Attribute>,
-) -> Result<(Option<Arc<str>>, Vec<Attribute>), String> {
+) -> error::Result<(Option<Arc<str>>, Vec<Attribute>)> {
     let (signature_attrs, attributes) = single_attribute(attributes, "Signature")?;
     let signature = match signature_attrs {
         Some(signature) => {
             let signature_ref = get_u16(&mut signature.into_iter())? as usize;
             let Constant::String(signature) = constants[signature_ref - 1].clone() else {
-                return Err(format!(
+                return Err(error::Error::class_format(format!(
                     "Expected string for signature; got {:?}",
                     constants[signature_ref - 1]
-                ));
+                )));
             };
             Some(signature)
         }
@@ -572,10 +951,11 @@ fn get_signature(
 fn get_attribute(
     constants: &[Constant],
     bytes: &mut impl Iterator<Item = u8>,
-) -> Result<Attribute, String> {
+) -> error::Result<Attribute> {
     let name_idx = get_u16(bytes)? as usize;
-    let name = str_index(constants, name_idx)
-        .map_err(|err| format!("While getting attribute name: {err}"))?;
+    let name = str_index(constants, name_idx).map_err(|err| {
+        error::Error::class_format(format!("While getting attribute name: {err}"))
+    })?;
     let attr_length = get_u32(bytes)? as usize;
     Ok(Attribute {
         name,
@@ -587,9 +967,10 @@ fn get_attribute(
 fn parse_code_attribute(
     class_area: &SharedClassArea,
     constants: &[Constant],
+    dynamic_constants: &BTreeMap<u16, DynamicConstant>,
     bytes: Vec<u8>,
     verbose: bool,
-) -> Result<(ByteCode, u16), String> {
+) -> error::Result<(ByteCode, u16)> {
     let mut bytes = bytes.into_iter();
     let [max_stack, max_locals] = get_u16_array(&mut bytes)?;
     let code_length = get_u32(&mut bytes)?;
@@ -684,7 +1065,11 @@ fn parse_code_attribute(
                             stack,
                         }
                     }
-                    other => return Err(format!("Bad stackmap discriminator; {other:?}")),
+                    other => {
+                        return Err(error::Error::class_format(format!(
+                            "Bad stackmap discriminator; {other:?}"
+                        )))
+                    }
                 });
             }
             stack_map
@@ -701,7 +1086,7 @@ fn parse_code_attribute(
             (0..table_count)
                 .map(|_| {
                     let [line, pc] = get_u16_array(&mut bytes)?;
-                    Ok::<_, String>(LineTableEntry { line, pc })
+                    Ok::<_, error::Error>(LineTableEntry { line, pc })
                 })
                 .collect::<Result<Vec<_>, _>>()?
         }
@@ -720,7 +1105,7 @@ fn parse_code_attribute(
             (0..table_count)
                 .map(|_| {
                     let [pc, length, name_idx, ty_idx, index] = get_u16_array(&mut bytes)?;
-                    Ok::<LocalVarTypeEntry, String>(LocalVarTypeEntry {
+                    Ok::<LocalVarTypeEntry, error::Error>(LocalVarTypeEntry {
                         pc,
                         length,
                         name: str_index(constants, name_idx as usize)?,
@@ -744,7 +1129,7 @@ fn parse_code_attribute(
             (0..table_count)
                 .map(|_| {
                     let [pc, length, name_idx, ty_idx, index] = get_u16_array(&mut bytes)?;
-                    Ok::<LocalVarEntry, String>(LocalVarEntry {
+                    Ok::<LocalVarEntry, error::Error>(LocalVarEntry {
                         pc,
                         length,
                         name: str_index(constants, name_idx as usize)?,
@@ -763,12 +1148,20 @@ fn parse_code_attribute(
         println!("Hydrating code...");
     }
 
-    let code = hydrate_code(class_area, constants, code, &mut exception_table, verbose)?;
+    let (code, opcodes) = hydrate_code(
+        class_area,
+        constants,
+        dynamic_constants,
+        code,
+        &mut exception_table,
+        verbose,
+    )?;
 
     Ok((
         ByteCode {
             max_stack,
             code,
+            opcodes,
             exception_table,
             line_number_table,
             local_type_table,
@@ -783,7 +1176,7 @@ fn parse_code_attribute(
 fn parse_verification_type(
     constants: &[Constant],
     bytes: &mut impl Iterator<Item = u8>,
-) -> Result<VerificationTypeInfo, String> {
+) -> error::Result<VerificationTypeInfo> {
     match bytes.next() {
         Some(0) => Ok(VerificationTypeInfo::Top),
         Some(1) => Ok(VerificationTypeInfo::Integer),
@@ -801,21 +1194,23 @@ fn parse_verification_type(
         }
         Some(4) => Ok(VerificationTypeInfo::Long),
         Some(3) => Ok(VerificationTypeInfo::Double),
-        other => Err(format!("Invalid verification type info: `{other:?}`")),
+        other => Err(error::Error::class_format(format!(
+            "Invalid verification type info: `{other:?}`"
+        ))),
     }
 }
 
-fn get_bytes<const N: usize>(bytes: &mut impl Iterator<Item = u8>) -> Result<[u8; N], String> {
+fn get_bytes<const N: usize>(bytes: &mut impl Iterator<Item = u8>) -> error::Result<[u8; N]> {
     <[u8; N]>::try_from(bytes.take(N).collect::<Vec<_>>())
-        .map_err(|_| String::from("Unexpected EOF"))
+        .map_err(|_| error::Error::class_format("Unexpected EOF"))
 }
 
-fn get_u16(bytes: &mut impl Iterator<Item = u8>) -> Result<u16, String> {
+fn get_u16(bytes: &mut impl Iterator<Item = u8>) -> error::Result<u16> {
     let bytes = get_bytes::<2>(bytes)?;
     Ok(u16::from_be_bytes(bytes))
 }
 
-fn get_u16_array<const N: usize>(bytes: &mut impl Iterator<Item = u8>) -> Result<[u16; N], String> {
+fn get_u16_array<const N: usize>(bytes: &mut impl Iterator<Item = u8>) -> error::Result<[u16; N]> {
     let mut arr = [0; N];
     for i in &mut arr {
         *i = get_u16(bytes)?;
@@ -823,65 +1218,76 @@ fn get_u16_array<const N: usize>(bytes: &mut impl Iterator<Item = u8>) -> Result
     Ok(arr)
 }
 
-fn get_u32(bytes: &mut impl Iterator<Item = u8>) -> Result<u32, String> {
+fn get_u32(bytes: &mut impl Iterator<Item = u8>) -> error::Result<u32> {
     let bytes = get_bytes::<4>(bytes)?;
     Ok(u32::from_be_bytes(bytes))
 }
 
-fn get_u64(bytes: &mut impl Iterator<Item = u8>) -> Result<u64, String> {
+fn get_u64(bytes: &mut impl Iterator<Item = u8>) -> error::Result<u64> {
     let bytes = get_bytes::<8>(bytes)?;
     Ok(u64::from_be_bytes(bytes))
 }
 
-fn raw_str_index(constants: &[RawConstant], idx: usize) -> Result<Arc<str>, String> {
+fn raw_str_index(constants: &[RawConstant], idx: usize) -> error::Result<Arc<str>> {
     match constants.get(idx - 1) {
         Some(RawConstant::String(str)) => Ok(str.clone()),
-        Some(other) => Err(format!("Expected a string; got `{other:?}`")),
-        None => Err(String::from("Constant index out of range")),
+        Some(other) => Err(error::Error::class_format(format!(
+            "Expected a string; got `{other:?}`"
+        ))),
+        None => Err(error::Error::class_format("Constant index out of range")),
     }
 }
 
-fn raw_class_index(constants: &[RawConstant], idx: usize) -> Result<Arc<str>, String> {
+fn raw_class_index(constants: &[RawConstant], idx: usize) -> error::Result<Arc<str>> {
     match constants.get(idx - 1) {
         Some(RawConstant::ClassRef { string_addr }) => {
             raw_str_index(constants, *string_addr as usize)
         }
-        Some(other) => Err(format!("Expected a class reference; got `{other:?}`")),
-        None => Err(String::from("Constant index out of range")),
+        Some(other) => Err(error::Error::class_format(format!(
+            "Expected a class reference; got `{other:?}`"
+        ))),
+        None => Err(error::Error::class_format("Constant index out of range")),
     }
 }
-fn str_index(constants: &[Constant], idx: usize) -> Result<Arc<str>, String> {
+fn str_index(constants: &[Constant], idx: usize) -> error::Result<Arc<str>> {
     match constants.get(idx - 1) {
         Some(Constant::String(str)) => Ok(str.clone()),
-        Some(other) => Err(format!("Expected a string; got `{other:?}`")),
-        None => Err(String::from("Constant index out of range")),
+        Some(other) => Err(error::Error::class_format(format!(
+            "Expected a string; got `{other:?}`"
+        ))),
+        None => Err(error::Error::class_format("Constant index out of range")),
     }
 }
 
-fn class_index(constants: &[Constant], idx: usize) -> Result<Arc<str>, String> {
+fn class_index(constants: &[Constant], idx: usize) -> error::Result<Arc<str>> {
     match constants.get(idx - 1) {
         Some(Constant::ClassRef(str)) => Ok(str.clone()),
-        Some(other) => Err(format!("Expected a class reference; got `{other:?}`")),
-        None => Err(String::from("Constant index out of range")),
+        Some(other) => Err(error::Error::class_format(format!(
+            "Expected a class reference; got `{other:?}`"
+        ))),
+        None => Err(error::Error::class_format("Constant index out of range")),
     }
 }
 
-fn parse_java_string(bytes: Vec<u8>) -> Result<String, String> {
+fn parse_java_string(bytes: Vec<u8>) -> error::Result<String> {
     let mut bytes = bytes.into_iter();
     let bytes = &mut bytes;
     let mut str = String::new();
     while let Some(b) = bytes.next() {
         if b == 0 {
-            return Err(String::from("No byte can have the value zero"));
+            return Err(error::Error::class_format(
+                "No byte can have the value zero",
+            ));
         } else if b < 128 {
             str.push(b as char);
         } else if b & 0b1110_0000 == 0b1100_0000 {
             let Some(y) = bytes.next() else {
-                return Err(String::from("Unexpected end of string"));
+                return Err(error::Error::class_format("Unexpected end of string"));
             };
             let chr = ((b as u16 & 0x1f) << 6) | (y as u16 & 0x3f);
             str.push(
-                char::from_u32(chr as u32).ok_or_else(|| String::from("Invalid character code"))?,
+                char::from_u32(chr as u32)
+                    .ok_or_else(|| error::Error::class_format("Invalid character code"))?,
             );
         } else if b == 0b1110_1101 {
             let [v, w, _x, y, z] = get_bytes(bytes)?;
@@ -890,17 +1296,19 @@ fn parse_java_string(bytes: Vec<u8>) -> Result<String, String> {
                 | ((w as u32 & 0x3f) << 10)
                 | ((y as u32 & 0x0f) << 6)
                 | (z as u32 & 0x3f);
-            let chr = char::from_u32(chr).ok_or_else(|| String::from("Invalid character code"))?;
+            let chr = char::from_u32(chr)
+                .ok_or_else(|| error::Error::class_format("Invalid character code"))?;
             str.push(chr);
         } else if b & 0b1111_0000 == 0b1110_0000 {
             let Some(y) = bytes.next() else {
-                return Err(String::from("Unexpected end of string"));
+                return Err(error::Error::class_format("Unexpected end of string"));
             };
             let Some(z) = bytes.next() else {
-                return Err(String::from("Unexpected end of string"));
+                return Err(error::Error::class_format("Unexpected end of string"));
             };
             let chr = ((b as u32 & 0xf) << 12) | ((y as u32 & 0x3f) << 6) | (z as u32 & 0x3f);
-            let chr = char::from_u32(chr).ok_or_else(|| String::from("Invalid character code"))?;
+            let chr = char::from_u32(chr)
+                .ok_or_else(|| error::Error::class_format("Invalid character code"))?;
             str.push(chr);
         }
     }
@@ -908,15 +1316,15 @@ fn parse_java_string(bytes: Vec<u8>) -> Result<String, String> {
 }
 
 #[allow(clippy::too_many_lines)]
-fn cook_constant(constants: &[RawConstant], constant: &RawConstant) -> Result<Constant, String> {
+fn cook_constant(constants: &[RawConstant], constant: &RawConstant) -> error::Result<Constant> {
     Ok(match constant {
         RawConstant::ClassRef { string_addr } => {
             Constant::ClassRef(raw_str_index(constants, *string_addr as usize)?)
         }
         RawConstant::Double(d) => Constant::Double(*d),
-        // RawConstant::Dynamic { constant } => Constant::Dynamic {
-        //     constant: *constant,
-        // },
+        // `Constant` has nothing to hold these; `load_class` keeps them in the class's dynamic
+        // constants instead
+        RawConstant::Dynamic { .. } => Constant::Placeholder,
         RawConstant::FieldRef {
             class_ref_addr,
             name_type_addr,
@@ -999,11 +1407,18 @@ fn cook_constant(constants: &[RawConstant], constant: &RawConstant) -> Result<Co
                     })
                 }
                 (
-                    handle_kind
-                    @ (MethodHandleKind::InvokeStatic | MethodHandleKind::InvokeSpecial),
+                    handle_kind @ (MethodHandleKind::InvokeStatic
+                    | MethodHandleKind::InvokeSpecial
+                    | MethodHandleKind::InvokeVirtual
+                    | MethodHandleKind::NewInvokeSpecial
+                    | MethodHandleKind::InvokeInterface),
                     RawConstant::MethodRef {
                         class_ref_addr,
                         name_type_addr,
+                    }
+                    | RawConstant::InterfaceRef {
+                        class_ref_addr,
+                        name_type_addr,
                     },
                 ) => {
                     let class = raw_class_index(constants, *class_ref_addr as usize)?;
@@ -1021,13 +1436,28 @@ fn cook_constant(constants: &[RawConstant], constant: &RawConstant) -> Result<Co
                             name,
                             method_type,
                         },
+                        MethodHandleKind::InvokeVirtual => MethodHandle::InvokeVirtual {
+                            class,
+                            name,
+                            method_type,
+                        },
+                        MethodHandleKind::NewInvokeSpecial => MethodHandle::NewInvokeSpecial {
+                            class,
+                            name,
+                            method_type,
+                        },
+                        MethodHandleKind::InvokeInterface => MethodHandle::InvokeInterface {
+                            class,
+                            name,
+                            method_type,
+                        },
                         _ => unreachable!(),
                     })
                 }
                 (descriptor, constant) => {
-                    return Err(format!(
+                    return Err(error::Error::class_format(format!(
                         "Invalid constant {constant:?} for method handle {descriptor:?}"
-                    ))
+                    )))
                 }
             }
         }
@@ -1048,7 +1478,6 @@ fn cook_constant(constants: &[RawConstant], constant: &RawConstant) -> Result<Co
             let type_descriptor = raw_str_index(constants, index as usize)?;
             Constant::MethodType(parse_method_descriptor(&type_descriptor)?)
         }
-        // &RawConstant::Module { identity } => Constant::Module { identity },
         RawConstant::NameTypeDescriptor {
             name_desc_addr,
             type_addr,
@@ -1060,7 +1489,12 @@ fn cook_constant(constants: &[RawConstant], constant: &RawConstant) -> Result<Co
                 type_descriptor,
             }
         }
-        // &RawConstant::Package { identity } => Constant::Package { identity },
+        // `Constant` has nothing to hold these, and nothing at run time needs them; only a module's
+        // attributes refer to them, and `load_class` rejects them in any other class
+        &RawConstant::Module { name_index } | &RawConstant::Package { name_index } => {
+            raw_str_index(constants, name_index as usize)?;
+            Constant::Placeholder
+        }
         RawConstant::String(string) => Constant::String(string.clone()),
         RawConstant::StringRef { string_addr } => {
             let string = raw_str_index(constants, *string_addr as usize)?;
@@ -1073,13 +1507,13 @@ fn cook_constant(constants: &[RawConstant], constant: &RawConstant) -> Result<Co
 fn raw_name_type_index(
     constants: &[RawConstant],
     idx: usize,
-) -> Result<(Arc<str>, Arc<str>), String> {
+) -> error::Result<(Arc<str>, Arc<str>)> {
     let Some(RawConstant::NameTypeDescriptor {
         name_desc_addr,
         type_addr,
     }) = constants.get(idx - 1)
     else {
-        return Err(String::from("Invalid NameTypeDescriptor"));
+        return Err(error::Error::class_format("Invalid NameTypeDescriptor"));
     };
     let name = raw_str_index(constants, *name_desc_addr as usize)?;
     let type_name = raw_str_index(constants, *type_addr as usize)?;
@@ -1087,11 +1521,11 @@ fn raw_name_type_index(
 }
 
 /// # Errors
-pub fn parse_method_descriptor(src: &str) -> Result<MethodDescriptor, String> {
+pub fn parse_method_descriptor(src: &str) -> error::Result<MethodDescriptor> {
     let mut chars = src.chars().peekable();
     let chars = &mut chars;
     let Some('(') = chars.next() else {
-        return Err(String::from("Expected `(`"));
+        return Err(error::Error::class_format("Expected `(`"));
     };
     let mut parameters = Vec::new();
     while chars.peek() != Some(&')') {
@@ -1114,7 +1548,7 @@ pub fn parse_method_descriptor(src: &str) -> Result<MethodDescriptor, String> {
 /// # Errors
 pub fn parse_field_type(
     chars: &mut Peekable<impl Iterator<Item = char>>,
-) -> Result<FieldType, String> {
+) -> error::Result<FieldType> {
     match chars.next() {
         Some('B') => Ok(FieldType::Byte),
         Some('C') => Ok(FieldType::Char),
@@ -1136,6 +1570,8 @@ pub fn parse_field_type(
         Some('S') => Ok(FieldType::Short),
         Some('Z') => Ok(FieldType::Boolean),
         Some('[') => Ok(FieldType::Array(Box::new(parse_field_type(chars)?))),
-        other => Err(format!("bad field type {other:?}")),
+        other => Err(error::Error::class_format(format!(
+            "bad field type {other:?}"
+        ))),
     }
 }
@@ -1,4 +1,5 @@
 use std::{
+    collections::BTreeMap,
     fmt::Debug,
     sync::{Arc, Mutex, Once, OnceLock},
 };
@@ -8,9 +9,11 @@ use jvmrs_lib::{access, method, AccessFlags, ClassVersion, Constant, FieldType,
 use crate::{
     class::{
         code::{ByteCode, NativeMethod, NativeStringMethod, NativeTodo, NativeVoid},
-        Attribute, BootstrapMethod, Class, Code, Field, InnerClass, Method, VTableEntry,
+        Attribute, BootstrapMethod, Class, Code, DynamicConstant, Field, InnerClass, Method,
+        RecordComponent, VTableEntry,
     },
     data::{SharedClassArea, WorkingClassArea, WorkingMethodArea, NULL},
+    virtual_machine::error,
 };
 
 use super::parse_code_attribute;
@@ -20,6 +23,8 @@ pub struct RawClass {
     pub version: ClassVersion,
     /// run-time constant pool
     pub constants: Vec<Constant>,
+    /// dynamically-computed constants by their index in the constant pool
+    pub dynamic_constants: BTreeMap<u16, DynamicConstant>,
     pub access: AccessFlags,
     /// current class name
     pub this: Arc<str>,
@@ -41,6 +46,8 @@ pub struct RawClass {
     pub source_file: Option<Arc<str>>,
     pub signature: Option<Arc<str>>,
     pub inner_classes: Vec<InnerClass>,
+    /// components of a record class, in declaration order
+    pub record_components: Vec<RecordComponent>,
     pub attributes: Vec<Attribute>,
 }
 
@@ -50,8 +57,6 @@ impl RawClass {
     /// # Panics
     pub fn to_class(&self, class_area: &WorkingClassArea) -> Class {
         let mut methods = self.methods.clone();
-        let mut fields = self.fields.clone();
-        let mut field_size = self.field_size;
         let mut class = self.super_class.clone();
         let mut supers = vec![self];
         while &*class != "java/lang/Object" {
@@ -65,12 +70,20 @@ impl RawClass {
                     methods.push(method.clone());
                 }
             }
-            for (field, _) in &class_ref.fields {
-                fields.push((field.clone(), field_size));
-                field_size += field.descriptor.get_size();
-            }
             class = class_ref.super_class.clone();
         }
+        // lay out superclass fields first so every field sits at the same index in subclasses. A
+        // field shadowed by a subclass keeps its own slot, and the subclass's field is listed first
+        // so references through the subclass find it
+        let field_size: usize = supers.iter().map(|class| class.field_size).sum();
+        let mut fields = Vec::new();
+        let mut base = field_size;
+        for super_class in &supers {
+            base -= super_class.field_size;
+            for (field, idx) in &super_class.fields {
+                fields.push((field.clone(), base + idx));
+            }
+        }
         if &*self.this != "java/lang/Object" {
             supers.push(
                 class_area
@@ -78,14 +91,13 @@ impl RawClass {
                     .expect("java/lang/Object"),
             );
         }
-        let initial_fields = self
-            .fields
-            .iter()
-            .flat_map(|(field, _idx)| match &field.descriptor {
-                FieldType::Array(_) | FieldType::Object(_) => std::iter::repeat(NULL).take(1),
-                other => std::iter::repeat(0).take(other.get_size()),
-            })
-            .collect::<Vec<_>>();
+        // inherited fields need slots too, so lay out every field at its index
+        let mut initial_fields = vec![0; field_size];
+        for (field, idx) in &fields {
+            if let FieldType::Array(_) | FieldType::Object(_) = field.descriptor {
+                initial_fields[*idx] = NULL;
+            }
+        }
         let mut vtable: Vec<VTableEntry> = Vec::new();
         // start at the highest level
         for super_class in supers.into_iter().rev() {
@@ -106,6 +118,7 @@ impl RawClass {
             initialized: Once::new(),
             version: self.version,
             constants: self.constants.clone(),
+            dynamic_constants: self.dynamic_constants.clone(),
             access: self.access,
             this: self.this.clone(),
             super_class: self.super_class.clone(),
@@ -120,6 +133,7 @@ impl RawClass {
             source_file: self.source_file.clone(),
             signature: self.signature.clone(),
             inner_classes: self.inner_classes.clone(),
+            record_components: self.record_components.clone(),
             attributes: self.attributes.clone(),
         }
     }
@@ -133,6 +147,7 @@ impl RawClass {
                 major_version: 0,
             },
             constants: Vec::new(),
+            dynamic_constants: BTreeMap::new(),
             access,
             this,
             super_class,
@@ -146,6 +161,7 @@ impl RawClass {
             signature: None,
             source_file: None,
             inner_classes: Vec::new(),
+            record_components: Vec::new(),
             attributes: Vec::new(),
         }
     }
@@ -187,9 +203,15 @@ impl Debug for RawClass {
             .field("statics", &self.statics)
             .field("methods", &self.methods)
             .field("bootstrap_methods", &self.bootstrap_methods);
+        if !self.dynamic_constants.is_empty() {
+            s.field("dynamic_constants", &self.dynamic_constants);
+        }
         if !self.inner_classes.is_empty() {
             s.field("inner_classes", &self.inner_classes);
         }
+        if !self.record_components.is_empty() {
+            s.field("record_components", &self.record_components);
+        }
         if let Some(source_file) = &self.source_file {
             s.field("source_file", source_file);
         }
@@ -299,8 +321,9 @@ impl RawMethod {
         self,
         class_area: &SharedClassArea,
         constants: &[Constant],
+        dynamic_constants: &BTreeMap<u16, DynamicConstant>,
         verbose: bool,
-    ) -> Result<Method, String> {
+    ) -> error::Result<Method> {
         let (code, max_locals) = match &self.code {
             RawCode::Abstract => (Code::Abstract, self.descriptor.parameter_size as u16),
             RawCode::Native(native_method, args) => (Code::Native(native_method.clone()), *args),
@@ -308,8 +331,13 @@ impl RawMethod {
                 if verbose {
                     println!("Cooking method {:?} {}", self.descriptor, self.name);
                 }
-                let (bytecode, max_locals) =
-                    parse_code_attribute(class_area, constants, code.clone(), verbose)?;
+                let (bytecode, max_locals) = parse_code_attribute(
+                    class_area,
+                    constants,
+                    dynamic_constants,
+                    code.clone(),
+                    verbose,
+                )?;
                 (Code::Code(bytecode), max_locals)
             }
             RawCode::ByteCode(code, locals) => (Code::Code(code.clone()), *locals),
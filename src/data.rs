@@ -2,6 +2,7 @@ use std::{
     collections::HashMap,
     fmt::Debug,
     hash::{BuildHasher, DefaultHasher, Hash, Hasher},
+    io::{self, Write},
     sync::{Arc, Mutex},
 };
 
@@ -11,11 +12,16 @@ use jvmrs_lib::{FieldType, MethodDescriptor};
 use crate::{
     class::{Class, Method},
     class_loader::{RawClass, RawMethod},
-    virtual_machine::object::{Array1, ArrayFields, Object, ObjectFinder, StringObj},
+    virtual_machine::{
+        error,
+        object::{Array1, Array2, ArrayFields, Object, ObjectFinder, StringObj},
+    },
 };
 
 pub const NULL: u32 = 0;
 pub const HEAP_START: u32 = 0x8000;
+/// the fewest allocations between two tracing collections
+const GC_THRESHOLD: usize = 4096;
 
 pub type SharedHeap = Arc<Mutex<Heap>>;
 
@@ -24,6 +30,18 @@ pub struct Heap {
     refcounts: Vec<u32>,
     string_cache: HashMap<Arc<str>, u32>,
     string_cache_mirror: HashMap<u32, Arc<str>>,
+    /// how many times each object's monitor has been entered
+    monitors: HashMap<u32, u32>,
+    /// the hash code handed out to each object that has asked for a deterministic identity hash
+    identity_hashes: HashMap<u32, u32>,
+    /// the `Class` object made for each class name, so `getClass` always gives the same one
+    class_objects: HashMap<Arc<str>, u32>,
+    /// stop counting references, so nothing is ever collected
+    gc_disabled: bool,
+    /// objects allocated since the last tracing collection
+    allocations: usize,
+    /// how many allocations to wait for before the next tracing collection
+    gc_threshold: usize,
     class_area: SharedClassArea,
 }
 
@@ -37,6 +55,7 @@ impl Heap {
     pub fn allocate(&mut self, obj: Object) -> u32 {
         self.contents.push(Some(Arc::new(Mutex::new(obj))));
         self.refcounts.push(0);
+        self.allocations += 1;
         (self.contents.len() - 1 + HEAP_START as usize) as u32
     }
 
@@ -51,6 +70,74 @@ impl Heap {
         idx
     }
 
+    /// get the canonical pointer for a string, making `ptr` canonical if the string isn't cached yet
+    #[must_use]
+    pub fn intern(&mut self, ptr: u32, string: Arc<str>) -> u32 {
+        if let Some(&idx) = self.string_cache.get(&string) {
+            return idx;
+        }
+        self.string_cache.insert(string.clone(), ptr);
+        self.string_cache_mirror.insert(ptr, string);
+        ptr
+    }
+
+    /// whether `ptr` refers to a string allocated through the string cache
+    #[must_use]
+    pub fn is_cached_string(&self, ptr: u32) -> bool {
+        self.string_cache_mirror.contains_key(&ptr)
+    }
+
+    /// enter the monitor of the object at `ptr`. There's only ever one interpreter thread, so this
+    /// can't block; it only tracks how deeply the monitor is held
+    pub fn monitor_enter(&mut self, ptr: u32) {
+        *self.monitors.entry(ptr).or_default() += 1;
+    }
+
+    /// exit the monitor of the object at `ptr`, releasing it once every entry has exited. Returns
+    /// false if the monitor wasn't held
+    pub fn monitor_exit(&mut self, ptr: u32) -> bool {
+        let Some(count) = self.monitors.get_mut(&ptr) else {
+            return false;
+        };
+        *count -= 1;
+        if *count == 0 {
+            self.monitors.remove(&ptr);
+        }
+        true
+    }
+
+    /// whether the monitor of the object at `ptr` is held
+    #[must_use]
+    pub fn holds_monitor(&self, ptr: u32) -> bool {
+        self.monitors.contains_key(&ptr)
+    }
+
+    /// a hash code for the object at `ptr` that only depends on the order objects first asked for
+    /// one, so output that prints identity hashes is the same on every run
+    pub fn identity_hash(&mut self, ptr: u32) -> u32 {
+        let next = self.identity_hashes.len() as u32 + 1;
+        *self.identity_hashes.entry(ptr).or_insert(next)
+    }
+
+    /// the `Class` object that was made for `class`, if there is one
+    #[must_use]
+    pub fn class_object(&self, class: &str) -> Option<u32> {
+        self.class_objects.get(class).copied()
+    }
+
+    /// remember the `Class` object made for `class`. It holds a reference, so the object lives as
+    /// long as the heap does
+    pub fn cache_class_object(&mut self, class: Arc<str>, ptr: u32) {
+        self.inc_ref(ptr);
+        self.class_objects.insert(class, ptr);
+    }
+
+    /// the number of objects that haven't been collected yet
+    #[must_use]
+    pub fn live_count(&self) -> usize {
+        self.contents.iter().filter(|obj| obj.is_some()).count()
+    }
+
     #[must_use]
     pub fn new(class_area: SharedClassArea) -> Self {
         Self {
@@ -58,6 +145,12 @@ impl Heap {
             refcounts: Vec::new(),
             string_cache: HashMap::new(),
             string_cache_mirror: HashMap::new(),
+            monitors: HashMap::new(),
+            identity_hashes: HashMap::new(),
+            class_objects: HashMap::new(),
+            gc_disabled: false,
+            allocations: 0,
+            gc_threshold: GC_THRESHOLD,
             class_area,
         }
     }
@@ -67,25 +160,36 @@ impl Heap {
         Arc::new(Mutex::new(self))
     }
 
+    /// the index of `ptr` in the heap, or `None` for `null` and any other value that doesn't point
+    /// into the heap
+    fn ref_index(&self, ptr: u32) -> Option<usize> {
+        let idx = ptr.checked_sub(HEAP_START)? as usize;
+        (idx < self.refcounts.len()).then_some(idx)
+    }
+
+    /// leak every object from now on, so a crash that goes away without collection points to a
+    /// reference counting bug
+    pub fn disable_gc(&mut self) {
+        self.gc_disabled = true;
+    }
+
     pub fn inc_ref(&mut self, ptr: u32) {
-        if ptr == NULL {
+        if self.gc_disabled {
             return;
         }
-        let idx = (ptr - HEAP_START) as usize;
-        if idx >= self.refcounts.len() {
+        let Some(idx) = self.ref_index(ptr) else {
             return;
-        }
+        };
         self.refcounts[idx] += 1;
     }
 
     pub fn dec_ref(&mut self, ptr: u32) {
-        if ptr == NULL {
+        if self.gc_disabled {
             return;
         }
-        let idx = (ptr - HEAP_START) as usize;
-        if idx >= self.refcounts.len() {
+        let Some(idx) = self.ref_index(ptr) else {
             return;
-        }
+        };
         self.refcounts[idx] -= 1;
         if self.refcounts[idx] == 0 {
             // println!("Deallocating {idx}");
@@ -93,6 +197,154 @@ impl Heap {
         }
     }
 
+    /// whether enough objects have been allocated since the last tracing collection to run another
+    #[must_use]
+    pub const fn gc_due(&self) -> bool {
+        !self.gc_disabled && self.allocations >= self.gc_threshold
+    }
+
+    /// Free every object that can't be reached, including cycles that reference counting never
+    /// frees. `roots` are the values in every stack frame; static fields are added here. Any value
+    /// that points at a live object is treated as a reference, so roots don't need to be typed.
+    ///
+    /// Native code can hold pointers that aren't traced. Those pointers still count towards the
+    /// object's reference count, so an object with more references than tracing finds is a root too.
+    /// Objects whose monitors are held are roots as well
+    /// # Panics
+    pub fn gc(&mut self, roots: impl IntoIterator<Item = u32>) {
+        self.allocations = 0;
+        if self.gc_disabled {
+            return;
+        }
+        let edges = self
+            .contents
+            .iter()
+            .map(|obj| {
+                obj.as_ref()
+                    .map(|obj| self.trace(&mut obj.lock().unwrap()))
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<_>>();
+        let mut traced_refs = vec![0; self.contents.len()];
+        for (counted, _) in &edges {
+            for &ptr in counted {
+                if let Some(idx) = self.ref_index(ptr) {
+                    traced_refs[idx] += 1;
+                }
+            }
+        }
+
+        let statics = self
+            .class_area
+            .classes()
+            .flat_map(|class| class.static_data.lock().unwrap().clone())
+            .collect::<Vec<_>>();
+        let mut pending = roots
+            .into_iter()
+            .chain(statics)
+            .chain(self.monitors.keys().copied())
+            .filter_map(|ptr| self.ref_index(ptr))
+            .chain((0..self.contents.len()).filter(|&idx| self.refcounts[idx] > traced_refs[idx]))
+            .collect::<Vec<_>>();
+        let mut marked = vec![false; self.contents.len()];
+        while let Some(idx) = pending.pop() {
+            if marked[idx] || self.contents[idx].is_none() {
+                continue;
+            }
+            marked[idx] = true;
+            let (counted, uncounted) = &edges[idx];
+            pending.extend(
+                counted
+                    .iter()
+                    .chain(uncounted)
+                    .filter_map(|&ptr| self.ref_index(ptr)),
+            );
+        }
+
+        for idx in 0..self.contents.len() {
+            if marked[idx] || self.contents[idx].is_none() {
+                continue;
+            }
+            let ptr = idx as u32 + HEAP_START;
+            if let Some(str) = self.string_cache_mirror.remove(&ptr) {
+                self.string_cache.remove(&str);
+            }
+            self.contents[idx] = None;
+            self.refcounts[idx] = 0;
+            // the survivors lose the references the dead objects held
+            for &target in &edges[idx].0 {
+                if let Some(target) = self.ref_index(target).filter(|&target| marked[target]) {
+                    self.refcounts[target] = self.refcounts[target].saturating_sub(1);
+                }
+            }
+        }
+        self.gc_threshold = self.live_count().max(GC_THRESHOLD);
+    }
+
+    /// the pointers an object holds. The first list has its reference fields, the contents of
+    /// reference arrays, and counted native pointers, which are all counted in their targets'
+    /// reference counts. The second has other values from native fields that might be pointers
+    fn trace(&self, obj: &mut Object) -> (Vec<u32>, Vec<u32>) {
+        if let Ok(contents) = Array1.extract(obj, |fields: ArrayFields<u32>| {
+            if fields.arr_type.is_reference() {
+                fields.contents.to_vec()
+            } else {
+                Vec::new()
+            }
+        }) {
+            return (contents, Vec::new());
+        }
+        let mut counted = Vec::new();
+        if let Some(class) = self.class_area.search(&obj.class) {
+            for (field, idx) in &class.fields {
+                if field.descriptor.is_reference() {
+                    counted.extend(obj.fields.get(*idx));
+                }
+            }
+        }
+        counted.extend(obj.counted_native_pointers());
+        (counted, obj.native_pointers())
+    }
+
+    /// write every live object to `out` with its class, reference count, fields, and any string or
+    /// array contents
+    /// # Errors
+    /// # Panics
+    pub fn dump(&self, out: &mut impl Write) -> io::Result<()> {
+        writeln!(out, "{} live objects", self.live_count())?;
+        for (idx, obj) in self.contents.iter().enumerate() {
+            let Some(obj) = obj else {
+                continue;
+            };
+            let ptr = idx as u32 + HEAP_START;
+            let mut obj = obj.lock().unwrap();
+            writeln!(
+                out,
+                "{ptr:0>8X} {} (refs: {})",
+                obj.class, self.refcounts[idx]
+            )?;
+            if let Ok(str) = StringObj::SELF.extract(&mut obj, |str| str.clone()) {
+                writeln!(out, "    {str:?}")?;
+            } else if let Ok(contents) = Array1.extract(&mut obj, |fields: ArrayFields<u32>| {
+                format!("{}[] {:?}", fields.arr_type, fields.contents)
+            }) {
+                writeln!(out, "    {contents}")?;
+            } else if let Ok(contents) = Array2.extract(&mut obj, |fields: ArrayFields<u64>| {
+                format!("{}[] {:?}", fields.arr_type, fields.contents)
+            }) {
+                writeln!(out, "    {contents}")?;
+            }
+            if let Some(class) = self.class_area.search(&obj.class) {
+                for (field, field_idx) in &class.fields {
+                    if let Some(value) = obj.fields.get(*field_idx) {
+                        writeln!(out, "    {} {} = {value}", field.descriptor, field.name)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
     fn deallocate(&mut self, ptr: u32, idx: usize) {
         // get rid of its cached string value
         if let Some(str) = self.string_cache_mirror.remove(&ptr) {
@@ -109,6 +361,10 @@ impl Heap {
                 self.dec_ref(obj.fields[*idx]);
             }
         }
+        // and any that its native fields held
+        for ptr in obj.counted_native_pointers() {
+            self.dec_ref(ptr);
+        }
         // deallocate any references that an array had within it
         if let Ok(Some(contents)) = Array1.extract(&mut obj, |fields: ArrayFields<u32>| {
             if fields.arr_type.is_reference() {
@@ -146,6 +402,11 @@ impl SharedClassArea {
     pub fn search(&self, class: &str) -> Option<Arc<Class>> {
         self.classes.get(class).cloned()
     }
+
+    /// every loaded class
+    pub fn classes(&self) -> impl Iterator<Item = &Arc<Class>> {
+        self.classes.values()
+    }
 }
 
 /// we have nothing to lose but our chains
@@ -186,6 +447,11 @@ impl WorkingClassArea {
     pub fn search(&self, class: &str) -> Option<&RawClass> {
         self.classes.get(class)
     }
+
+    /// every class loaded so far
+    pub fn classes(&self) -> impl Iterator<Item = &RawClass> {
+        self.classes.values()
+    }
 }
 
 impl Default for WorkingClassArea {
@@ -297,7 +563,7 @@ impl WorkingMethodArea {
         self,
         class_area: &SharedClassArea,
         verbose: bool,
-    ) -> Result<SharedMethodArea, String> {
+    ) -> error::Result<SharedMethodArea> {
         Ok(MethodArea {
             methods: self
                 .methods
@@ -308,14 +574,19 @@ impl WorkingMethodArea {
                         .map(|(class, method)| {
                             let class = class_area
                                 .search(&class)
-                                .ok_or_else(|| format!("Couldn't find class {class}"))?;
-                            let cooked = method.cook(class_area, &class.constants, verbose)?;
+                                .ok_or_else(|| error::Error::class_resolution(&class))?;
+                            let cooked = method.cook(
+                                class_area,
+                                &class.constants,
+                                &class.dynamic_constants,
+                                verbose,
+                            )?;
                             Ok((class, Arc::new(cooked)))
                         })
-                        .collect::<Result<Vec<_>, String>>()?;
+                        .collect::<error::Result<Vec<_>>>()?;
                     Ok((h, vec))
                 })
-                .collect::<Result<_, String>>()?,
+                .collect::<error::Result<_>>()?,
         }
         .to_shared())
     }
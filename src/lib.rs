@@ -0,0 +1,16 @@
+#![warn(clippy::nursery, clippy::pedantic)]
+#![allow(
+    clippy::cast_lossless,
+    clippy::cast_possible_truncation,
+    clippy::cast_possible_wrap,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss,
+    clippy::module_name_repetitions
+)]
+
+pub mod class;
+pub mod class_loader;
+pub mod data;
+pub mod virtual_machine;
+
+pub use virtual_machine::{JavaVm, VmOptions};
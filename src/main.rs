@@ -8,29 +8,85 @@
     clippy::module_name_repetitions
 )]
 
-use std::{error::Error, fs, path::PathBuf};
+use std::{
+    env,
+    error::Error,
+    ffi::OsString,
+    fs::{self, File},
+    io::{self, BufReader},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 use clap::Parser;
-use data::Heap;
-
-pub mod class;
-pub mod class_loader;
-pub mod data;
-pub mod virtual_machine;
+use javarust::{
+    class_loader::{self, Jar},
+    virtual_machine::{self, FloatMode, JavaVm, VmOptions},
+};
 
 #[derive(Parser, Debug)]
-#[allow(clippy::struct_field_names)]
+#[allow(clippy::struct_field_names, clippy::struct_excessive_bools)]
 struct Args {
-    /// the filenames of the classes to run. The first filename will be treated as the main class
+    /// the filenames of the classes or jars to run. The first filename will be treated as the main
+    /// class; for a jar, that's the `Main-Class` in its manifest
     filenames: Vec<PathBuf>,
     #[clap(short, long)]
     /// whether to run the main method of the first resolved class
     skip: bool,
     #[clap(short, long)]
     verbose: bool,
+    /// print each bytecode instruction to stderr as it runs, with the top of the operand stack
+    /// afterwards
+    #[clap(long)]
+    trace: bool,
+    /// look for classes that the loaded classes use but that weren't given in these directories and
+    /// jars, separated like the `PATH` variable
+    #[clap(long)]
+    classpath: Option<OsString>,
+    /// run the `main` method of this class instead of the first file's, e.g. `com.example.App`
+    #[clap(long)]
+    main_class: Option<String>,
     /// use this option to read dependencies from a file containing one relative path per line
     #[clap(short, long)]
     project: Option<PathBuf>,
+    /// run the program on a dedicated thread with this many megabytes of stack
+    #[clap(long)]
+    stack_size: Option<usize>,
+    /// read the program's standard input from this file instead of the terminal
+    #[clap(long)]
+    stdin: Option<PathBuf>,
+    /// load resources for `Class.getResourceAsStream` from this directory instead of the working
+    /// directory. Resources it doesn't have are looked for in the jars being run and on the
+    /// classpath
+    #[clap(long)]
+    resources: Option<PathBuf>,
+    /// throw an `ArithmeticException` when int or long arithmetic overflows instead of wrapping
+    #[clap(long)]
+    trap_overflow: bool,
+    /// dump every live heap object to stderr when the program exits
+    #[clap(long)]
+    dump_heap: bool,
+    /// never collect objects; if a crash goes away with this, it's a bug in the garbage collector
+    #[clap(long)]
+    no_gc: bool,
+    /// check that no method's operand stack grows past the `max_stack` declared in its class file
+    #[clap(long)]
+    verify: bool,
+    /// number objects' default hash codes in the order they're requested, so output is the same on
+    /// every run
+    #[clap(long)]
+    deterministic_hash: bool,
+    /// only flush the program's standard output at the end of each line, instead of after every
+    /// `print`
+    #[clap(long)]
+    line_buffered: bool,
+    /// instead of running `main`, read calls like `Class.method(1, "two")` from standard input and
+    /// print what they return
+    #[clap(long)]
+    repl: bool,
+    /// throw a `StackOverflowError` when calls nest deeper than this; the default is 4096
+    #[clap(long)]
+    max_depth: Option<usize>,
     /// pass these values as arguments to the java program
     #[clap(last = true, allow_hyphen_values = true)]
     program_args: Vec<String>,
@@ -41,8 +97,6 @@ fn main() -> Result<(), Box<dyn Error>> {
     if args.verbose {
         println!("{args:?}");
     }
-    let mut firstclass = None;
-    let (mut method_area, mut class_area) = class_loader::load_environment();
     let mut filenames = args.filenames;
     // include any paths from a project file
     if let Some(projpath) = args.project {
@@ -56,50 +110,118 @@ fn main() -> Result<(), Box<dyn Error>> {
         .into_iter()
         .map(|filename| filename.canonicalize())
         .collect::<Result<Vec<_>, _>>()?;
-    let first_file = filenames.remove(0);
-    filenames.sort();
-    filenames.dedup();
-    filenames.retain(|p| p != &first_file);
-    filenames.insert(0, first_file);
-    for filename in filenames {
+    if !filenames.is_empty() {
+        let first_file = filenames.remove(0);
+        filenames.sort();
+        filenames.dedup();
+        filenames.retain(|p| p != &first_file);
+        filenames.insert(0, first_file);
+    }
+    let stack_size = args
+        .stack_size
+        .map(|megabytes| {
+            megabytes
+                .checked_mul(1024 * 1024)
+                .ok_or_else(|| format!("--stack-size {megabytes} is too many megabytes"))
+        })
+        .transpose()?;
+    let options = VmOptions {
+        stack_size,
+        trap_overflow: args.trap_overflow,
+        verify: args.verify,
+        deterministic_hash: args.deterministic_hash,
+        disable_gc: args.no_gc,
+        trace: args.trace,
+        max_depth: args.max_depth,
+        float_mode: FloatMode::Strict,
+    };
+    let mut classpath: Vec<PathBuf> = args
+        .classpath
+        .as_deref()
+        .map(|classpath| env::split_paths(classpath).collect())
+        .unwrap_or_default();
+    let mut class_files = Vec::new();
+    let mut jar_main_class = None;
+    for filename in &filenames {
         if args.verbose {
             println!("Reading class from {filename:?}...");
         }
-        let bytes = fs::read(filename)?;
-        // let bytes = [
-        //     0xCA, 0xFE, 0xBA, 0xBE, 0, 0, 0, 0, 0, 3, 1, 0, 2, 0x30, 0x30, 3, 0, 0, 0, 0xFF, 0, 0, 0,
-        //     1, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-        // ];
-        let class =
-            class_loader::load_class(&mut method_area, &mut bytes.into_iter(), args.verbose)
-                .unwrap();
-        if args.verbose {
-            println!("{class:#?}");
+        let jar = read_class_files(filename)?;
+        // a jar's manifest only picks the main class if the jar comes first
+        if class_files.is_empty() {
+            jar_main_class = jar.main_class;
         }
-        if firstclass.is_none() {
-            firstclass = Some(class.this.clone());
+        class_files.extend(jar.classes);
+        classpath.extend(class_loader::manifest_class_path(filename, &jar.class_path));
+    }
+    // resources can also come from the jars being run and from the classpath
+    virtual_machine::set_resource_classpath(
+        filenames
+            .iter()
+            .filter(|filename| filename.extension().is_some_and(|ext| ext == "jar"))
+            .cloned()
+            .chain(classpath.iter().cloned())
+            .collect(),
+    );
+    let mut vm = JavaVm::with_classpath(class_files, classpath, options, args.verbose)?;
+    if let Some(resources) = args.resources {
+        virtual_machine::set_resource_root(resources);
+    }
+    if args.repl {
+        virtual_machine::repl(&vm, io::stdin().lock(), io::stdout(), args.verbose)?;
+        return Ok(());
+    }
+    let mut firstclass = jar_main_class.map(Arc::from).or_else(|| vm.first_class());
+    if let Some(main_class) = args.main_class {
+        let main_class = main_class.replace('.', "/");
+        vm.load_from_classpath(&main_class, args.verbose)?;
+        if vm.class_area.search(&main_class).is_none() {
+            eprintln!("Error: main class `{main_class}` was not loaded");
+            std::process::exit(1);
         }
-        class_area.push(class);
+        firstclass = Some(main_class.into());
+    } else if let Some(class) = &firstclass {
+        vm.load_from_classpath(class, args.verbose)?;
     }
     let Some(class) = firstclass else {
-        println!("Error: no class specified");
-        return Ok(());
+        eprintln!("Error: no class specified");
+        std::process::exit(1);
     };
-    let class_area = class_area.to_shared();
-    let method_area = method_area.to_shared(&class_area, args.verbose)?;
-    let heap = Heap::new(class_area.clone()).make_shared();
-    if args.verbose {
-        println!("{method_area:#?}");
+    if let Some(stdin) = args.stdin {
+        virtual_machine::set_stdin(BufReader::new(File::open(stdin)?));
     }
+    virtual_machine::set_line_buffered(args.line_buffered);
+    let mut exit_code = None;
     if !args.skip {
-        virtual_machine::start_vm(
-            &class,
-            method_area,
-            class_area,
-            heap,
-            args.program_args,
-            args.verbose,
-        );
+        exit_code = match vm.run_main(&class, args.program_args, args.verbose) {
+            Ok(code) => Some(code),
+            // the main thread never started, so nothing has reported this yet
+            Err(virtual_machine::error::Error::LinkageError(err)) => {
+                eprintln!("Error: {err}");
+                Some(1)
+            }
+            // the main thread's error has already been reported
+            Err(_) => None,
+        };
+    }
+    if args.dump_heap {
+        vm.heap.lock().unwrap().dump(&mut io::stderr())?;
+    }
+    if let Some(code) = exit_code.filter(|&code| code != 0) {
+        std::process::exit(code);
     }
     Ok(())
 }
+
+/// read every class in a jar along with its manifest, or a single class file as if it were a jar
+/// without one
+fn read_class_files(path: &Path) -> Result<Jar, Box<dyn Error>> {
+    if path.extension().is_some_and(|ext| ext == "jar") {
+        Ok(class_loader::read_jar(BufReader::new(File::open(path)?))?)
+    } else {
+        Ok(Jar {
+            classes: vec![fs::read(path)?],
+            ..Default::default()
+        })
+    }
+}
@@ -1,7 +1,9 @@
 pub mod error;
 pub mod instruction;
+mod java_vm;
 mod native;
 pub mod object;
+mod repl;
 pub mod thread;
 
 use std::sync::Arc;
@@ -11,12 +13,22 @@ use jvmrs_lib::{FieldType, MethodDescriptor};
 use crate::class::{Class, Method};
 use crate::data::{SharedClassArea, SharedHeap, SharedMethodArea};
 
-pub use self::native::add_native_methods;
+pub use self::native::{
+    add_native_methods,
+    input::set_stdin,
+    print_stream::set_line_buffered,
+    reflect::{set_resource_classpath, set_resource_root},
+};
 
 use self::object::Array1;
-pub use self::thread::Thread;
+pub use self::thread::{
+    frames::{FrameInfo, JValue},
+    set_trace_output, Thread,
+};
 
-pub use self::instruction::{hydrate_code, Cmp, Instruction, Op};
+pub use self::instruction::{hydrate_code, mnemonic, Cmp, Instruction, Op};
+pub use self::java_vm::JavaVm;
+pub use self::repl::repl;
 
 #[derive(Debug)]
 pub struct StackFrame {
@@ -26,6 +38,8 @@ pub struct StackFrame {
     pub operand_stack: Vec<u32>,
     /// list of pointers that should be collected at the end of execution
     pub garbage: Vec<u32>,
+    /// object whose monitor is held for the duration of a synchronized method
+    pub monitor: Option<u32>,
     /// current method
     pub method: Arc<Method>,
     /// current class
@@ -44,12 +58,61 @@ impl StackFrame {
                     .unwrap_or_default(),
             ),
             garbage: Vec::new(),
+            monitor: None,
             class,
             method,
         }
     }
 }
 
+/// how many calls deep a thread can go before it throws a `StackOverflowError`, if the options
+/// don't say
+pub const DEFAULT_MAX_DEPTH: usize = 4096;
+
+/// Settings that change how the VM runs a program
+#[derive(Clone, Copy, Debug, Default)]
+pub struct VmOptions {
+    /// run the interpreter on a dedicated OS thread with this many bytes of stack, which keeps deep
+    /// recursion from overflowing the host thread
+    pub stack_size: Option<usize>,
+    /// throw an `ArithmeticException` when int or long arithmetic overflows instead of wrapping
+    pub trap_overflow: bool,
+    /// fail with a `VerifyError` when a method's operand stack grows past its declared `max_stack`
+    pub verify: bool,
+    /// make `Object.hashCode` count up from 1 in the order objects are hashed, instead of hashing
+    /// the object's pointer
+    pub deterministic_hash: bool,
+    /// never collect objects, to tell reference counting bugs apart from bugs in the program
+    pub disable_gc: bool,
+    /// print each bytecode instruction to stderr as it runs, or to the writer given to
+    /// [`set_trace_output`]
+    pub trace: bool,
+    /// throw a `StackOverflowError` when a call would nest deeper than this. Defaults to
+    /// [`DEFAULT_MAX_DEPTH`]
+    pub max_depth: Option<usize>,
+    /// how much precision float and double arithmetic may keep between operations
+    pub float_mode: FloatMode,
+}
+
+/// The floating-point semantics a program asks for. Before java 17, methods that weren't `strictfp`
+/// could keep intermediate results in an extended exponent range. Java 17 made every method strict,
+/// and the interpreter computes in `f32` and `f64`, which round every result, so both modes give
+/// the same bit-for-bit results. The setting documents that contract rather than changing anything
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FloatMode {
+    /// round every operation to `float` or `double`, as `strictfp` and java 17 require
+    #[default]
+    Strict,
+    /// the pre-java 17 default, which allows extended-exponent intermediates. It's run as `Strict`,
+    /// which that model permits
+    Default,
+}
+
+/// Run the `main` method of the given class.
+/// # Errors
+/// Returns a `LinkageError` if the class isn't loaded or has no `main` method, an `Internal` error if
+/// the dedicated main thread can't be spawned or panics, or else the error that stopped the main
+/// thread, if any
 /// # Panics
 pub fn start_vm(
     class: &str,
@@ -57,28 +120,12 @@ pub fn start_vm(
     class_area: SharedClassArea,
     heap: SharedHeap,
     argv: Vec<String>,
+    options: VmOptions,
     verbose: bool,
-) {
-    // set the static classes
-    unsafe {
-        native::ARRAY_CLASS = class_area.search("java/lang/Array");
-        native::OBJECT_CLASS = class_area.search("java/lang/Object");
-        native::RANDOM_CLASS = class_area.search("java/util/Random");
-        native::STRING_BUILDER_CLASS = class_area.search("java/lang/StringBuilder");
-        native::STRING_CLASS = class_area.search("java/lang/String");
-    }
-
-    let mut heap_borrow = heap.lock().unwrap();
-    let arg_ptrs: Vec<u32> = argv
-        .into_iter()
-        .map(|arg| heap_borrow.allocate_str(arg.into()))
-        .collect();
-    let argv_ptr = heap_borrow.allocate(Array1::from_vec(
-        arg_ptrs,
-        FieldType::Object("java/lang/String".into()),
-    ));
-    drop(heap_borrow);
+) -> error::Result<()> {
+    set_static_classes(&class_area);
 
+    // look for `main(String[])`, then fall back to a `main()` that takes no arguments
     let (class, method) = method_area
         .search(
             class,
@@ -91,7 +138,30 @@ pub fn start_vm(
                 return_type: None,
             },
         )
-        .expect("No `main` function found");
+        .or_else(|| method_area.search(class, "main", &MethodDescriptor::EMPTY))
+        .ok_or_else(|| {
+            error::Error::LinkageError(if class_area.search(class).is_some() {
+                format!("Class `{class}` doesn't have a `main` method")
+            } else {
+                format!("Couldn't find main class `{class}`")
+            })
+        })?;
+
+    let mut heap_borrow = heap.lock().unwrap();
+    if options.disable_gc {
+        heap_borrow.disable_gc();
+    }
+    let arg_ptrs: Vec<u32> = argv
+        .into_iter()
+        .map(|arg| heap_borrow.allocate_str(arg.into()))
+        .collect();
+    let argv_ptr = heap_borrow.allocate(Array1::from_vec(
+        arg_ptrs,
+        FieldType::Object("java/lang/String".into()),
+    ));
+    drop(heap_borrow);
+
+    let takes_argv = method.descriptor.parameter_size == 1;
     let mut primary_thread = Thread {
         pc_register: 0,
         stack: Vec::new(),
@@ -99,8 +169,47 @@ pub fn start_vm(
         method_area,
         class_area,
         heap,
+        trap_overflow: options.trap_overflow,
+        verify: options.verify,
+        deterministic_hash: options.deterministic_hash,
+        trace: options.trace,
+        max_depth: options.max_depth.unwrap_or(DEFAULT_MAX_DEPTH),
     };
-    primary_thread.stackframe.locals[0] = argv_ptr;
+    if takes_argv {
+        primary_thread.stackframe.locals[0] = argv_ptr;
+    }
+    if let Some(stack_size) = options.stack_size {
+        std::thread::Builder::new()
+            .name(String::from("main"))
+            .stack_size(stack_size)
+            .spawn(move || run_thread(primary_thread, verbose))
+            .map_err(|err| {
+                let err = format!(
+                    "Couldn't spawn the main thread with {stack_size} bytes of stack: {err}"
+                );
+                eprintln!("Error: {err}");
+                error::Error::Internal(err)
+            })?
+            .join()
+            // the panic has already been reported
+            .map_err(|_| error::Error::Internal(String::from("The main thread panicked")))?
+    } else {
+        run_thread(primary_thread, verbose)
+    }
+}
+
+/// point the native code's shortcuts at the classes it uses most
+fn set_static_classes(class_area: &SharedClassArea) {
+    unsafe {
+        native::ARRAY_CLASS = class_area.search("java/lang/Array");
+        native::OBJECT_CLASS = class_area.search("java/lang/Object");
+        native::RANDOM_CLASS = class_area.search("java/util/Random");
+        native::STRING_BUILDER_CLASS = class_area.search("java/lang/StringBuilder");
+        native::STRING_CLASS = class_area.search("java/lang/String");
+    }
+}
+
+fn run_thread(mut primary_thread: Thread, verbose: bool) -> error::Result<()> {
     loop {
         // println!(
         //     "{:?}",
@@ -112,10 +221,18 @@ pub fn start_vm(
         // }
         match primary_thread.tick(verbose) {
             Ok(()) => {}
-            Err(error::Error::ThreadKill) => break,
+            Err(error::Error::ThreadKill) => return Ok(()),
+            Err(error::Error::Exit(code)) => return Err(error::Error::Exit(code)),
+            Err(error::Error::UncaughtException(ptr)) => {
+                eprintln!(
+                    "Exception in thread \"main\" {}",
+                    native::throwable::describe_throwable(&primary_thread.heap, ptr)?
+                );
+                return Err(error::Error::UncaughtException(ptr));
+            }
             Err(other) => {
-                println!("Error in main thread: {other:?}");
-                break;
+                eprintln!("Error in main thread: {other}");
+                return Err(other);
             }
         }
     }
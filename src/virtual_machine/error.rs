@@ -1,19 +1,52 @@
-#[derive(Debug, Clone)]
+use std::fmt::Display;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Error {
+    /// the main thread returned from its last method
     ThreadKill,
+    /// a class couldn't be found in the class area
     ClassResolution(String),
-    Misc(String),
+    /// a class was found, but a field or method it should have wasn't
+    LinkageError(String),
+    /// a java exception unwound past `main`; holds the pointer to the exception object
+    UncaughtException(u32),
+    /// the program called `System.exit` with this status code
+    Exit(i32),
+    /// a class file is malformed or uses something the VM doesn't support
+    ClassFormat(String),
+    /// a problem with the VM itself
+    Internal(String),
 }
 
 impl Error {
     pub fn class_resolution(class: &impl ToString) -> Self {
         Self::ClassResolution(class.to_string())
     }
+
+    pub fn class_format(msg: impl Into<String>) -> Self {
+        Self::ClassFormat(msg.into())
+    }
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ThreadKill => write!(f, "Thread exited"),
+            Self::ClassResolution(class) => write!(f, "Couldn't resolve class `{class}`"),
+            Self::LinkageError(msg) | Self::ClassFormat(msg) | Self::Internal(msg) => {
+                write!(f, "{msg}")
+            }
+            Self::UncaughtException(ptr) => write!(f, "Uncaught exception at {ptr}"),
+            Self::Exit(code) => write!(f, "Exited with status {code}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
 impl From<String> for Error {
     fn from(value: String) -> Self {
-        Self::Misc(value)
+        Self::Internal(value)
     }
 }
 
@@ -1,4 +1,5 @@
 use std::{
+    collections::BTreeMap,
     fmt::Debug,
     iter::Peekable,
     sync::{Arc, OnceLock},
@@ -7,9 +8,10 @@ use std::{
 use jvmrs_lib::{Constant, FieldType, MethodDescriptor};
 
 use crate::{
-    class::{code::ExceptionTableEntry, Class, Method},
+    class::{code::ExceptionTableEntry, Class, DynamicConstant, Method},
     class_loader::parse_field_type,
     data::{SharedClassArea, NULL},
+    virtual_machine::error,
 };
 
 #[derive(Clone)]
@@ -48,11 +50,37 @@ pub enum Instruction {
     /// compare one integer to zero
     IfCmpZ(Cmp, i16),
     Goto(i32),
+    /// return from a subroutine to the address in a local
+    Ret(usize),
+    /// jump to `offsets[key - low]` if `low <= key <= high`, or to `default` otherwise
+    TableSwitch {
+        default: i32,
+        low: i32,
+        high: i32,
+        offsets: Vec<i32>,
+    },
+    /// jump to the offset paired with the key, or to `default` if there isn't one. Pairs are sorted
+    /// by key
+    LookupSwitch {
+        default: i32,
+        pairs: Vec<(i32, i32)>,
+    },
     Return0,
     Return1,
     Return2,
-    GetStatic(Arc<str>, Arc<str>, FieldType, Arc<OnceLock<usize>>),
-    PutStatic(Arc<str>, Arc<str>, FieldType, Arc<OnceLock<usize>>),
+    /// class, field name, field type, and the declaring class and index of the field once resolved
+    GetStatic(
+        Arc<str>,
+        Arc<str>,
+        FieldType,
+        Arc<OnceLock<(Arc<str>, usize)>>,
+    ),
+    PutStatic(
+        Arc<str>,
+        Arc<str>,
+        FieldType,
+        Arc<OnceLock<(Arc<str>, usize)>>,
+    ),
     GetField(Option<usize>, Arc<str>, Arc<str>, FieldType),
     PutField(Option<usize>, Arc<str>, Arc<str>, FieldType),
     InvokeVirtual(Option<usize>, Arc<str>, Arc<str>, MethodDescriptor),
@@ -65,6 +93,8 @@ pub enum Instruction {
         Arc<OnceLock<(Arc<Class>, Arc<Method>)>>,
     ),
     InvokeDynamic(u16, Arc<str>, MethodDescriptor),
+    /// load a dynamic constant, resolving it through its bootstrap method the first time
+    LoadDynamic(u16, Arc<str>, FieldType, Arc<OnceLock<u64>>),
     New(Arc<str>, Arc<OnceLock<Arc<Class>>>),
     NewArray1(FieldType),
     NewArray2(FieldType),
@@ -78,6 +108,8 @@ pub enum Instruction {
     IfNull(bool, i16),
     Instanceof(Arc<str>),
     CheckedCast(Arc<str>),
+    MonitorEnter,
+    MonitorExit,
 }
 
 impl Instruction {
@@ -123,6 +155,19 @@ impl Debug for Instruction {
             Self::ICmp(cmp, y) => write!(f, "if_i{cmp:?} {y:+}"),
             Self::IfCmpZ(cmp, y) => write!(f, "if{cmp:?}z {y:+}"),
             Self::Goto(y) => write!(f, "goto {y:+}"),
+            Self::Ret(idx) => write!(f, "ret {idx}"),
+            Self::TableSwitch {
+                default,
+                low,
+                high,
+                offsets,
+            } => write!(
+                f,
+                "tableswitch {low}..={high} {offsets:?} default {default:+}"
+            ),
+            Self::LookupSwitch { default, pairs } => {
+                write!(f, "lookupswitch {pairs:?} default {default:+}")
+            }
             Self::Return0 => write!(f, "ret0"),
             Self::Return1 => write!(f, "ret1"),
             Self::Return2 => write!(f, "ret2"),
@@ -145,6 +190,7 @@ impl Debug for Instruction {
             Self::InvokeDynamic(num, name, ty) => {
                 write!(f, "invokedynamic #{num} {ty:?} {name}")
             }
+            Self::LoadDynamic(num, name, ty, _) => write!(f, "ldc dynamic #{num} {ty} {name}"),
             Self::New(ty, _) => write!(f, "new {ty}"),
             Self::NewArray1(ty) | Self::NewArray2(ty) => write!(f, "newarray {ty}"),
             Self::NewMultiArray(b, c) => write!(f, "multinewarray[{b}] {c}"),
@@ -158,6 +204,8 @@ impl Debug for Instruction {
             }
             Self::Instanceof(class) => write!(f, "instanceof {class}"),
             Self::CheckedCast(class) => write!(f, "checkedcast {class}"),
+            Self::MonitorEnter => write!(f, "monitorenter"),
+            Self::MonitorExit => write!(f, "monitorexit"),
         }
     }
 }
@@ -199,15 +247,230 @@ pub enum Cmp {
     Ge,
 }
 
+/// the name `javap` gives an opcode, or `None` for the bytes that aren't instructions
+#[must_use]
+pub fn mnemonic(opcode: u8) -> Option<&'static str> {
+    MNEMONICS.get(opcode as usize).copied()
+}
+
+const MNEMONICS: [&str; 202] = [
+    "nop",
+    "aconst_null",
+    "iconst_m1",
+    "iconst_0",
+    "iconst_1",
+    "iconst_2",
+    "iconst_3",
+    "iconst_4",
+    "iconst_5",
+    "lconst_0",
+    "lconst_1",
+    "fconst_0",
+    "fconst_1",
+    "fconst_2",
+    "dconst_0",
+    "dconst_1",
+    "bipush",
+    "sipush",
+    "ldc",
+    "ldc_w",
+    "ldc2_w",
+    "iload",
+    "lload",
+    "fload",
+    "dload",
+    "aload",
+    "iload_0",
+    "iload_1",
+    "iload_2",
+    "iload_3",
+    "lload_0",
+    "lload_1",
+    "lload_2",
+    "lload_3",
+    "fload_0",
+    "fload_1",
+    "fload_2",
+    "fload_3",
+    "dload_0",
+    "dload_1",
+    "dload_2",
+    "dload_3",
+    "aload_0",
+    "aload_1",
+    "aload_2",
+    "aload_3",
+    "iaload",
+    "laload",
+    "faload",
+    "daload",
+    "aaload",
+    "baload",
+    "caload",
+    "saload",
+    "istore",
+    "lstore",
+    "fstore",
+    "dstore",
+    "astore",
+    "istore_0",
+    "istore_1",
+    "istore_2",
+    "istore_3",
+    "lstore_0",
+    "lstore_1",
+    "lstore_2",
+    "lstore_3",
+    "fstore_0",
+    "fstore_1",
+    "fstore_2",
+    "fstore_3",
+    "dstore_0",
+    "dstore_1",
+    "dstore_2",
+    "dstore_3",
+    "astore_0",
+    "astore_1",
+    "astore_2",
+    "astore_3",
+    "iastore",
+    "lastore",
+    "fastore",
+    "dastore",
+    "aastore",
+    "bastore",
+    "castore",
+    "sastore",
+    "pop",
+    "pop2",
+    "dup",
+    "dup_x1",
+    "dup_x2",
+    "dup2",
+    "dup2_x1",
+    "dup2_x2",
+    "swap",
+    "iadd",
+    "ladd",
+    "fadd",
+    "dadd",
+    "isub",
+    "lsub",
+    "fsub",
+    "dsub",
+    "imul",
+    "lmul",
+    "fmul",
+    "dmul",
+    "idiv",
+    "ldiv",
+    "fdiv",
+    "ddiv",
+    "irem",
+    "lrem",
+    "frem",
+    "drem",
+    "ineg",
+    "lneg",
+    "fneg",
+    "dneg",
+    "ishl",
+    "lshl",
+    "ishr",
+    "lshr",
+    "iushr",
+    "lushr",
+    "iand",
+    "land",
+    "ior",
+    "lor",
+    "ixor",
+    "lxor",
+    "iinc",
+    "i2l",
+    "i2f",
+    "i2d",
+    "l2i",
+    "l2f",
+    "l2d",
+    "f2i",
+    "f2l",
+    "f2d",
+    "d2i",
+    "d2l",
+    "d2f",
+    "i2b",
+    "i2c",
+    "i2s",
+    "lcmp",
+    "fcmpl",
+    "fcmpg",
+    "dcmpl",
+    "dcmpg",
+    "ifeq",
+    "ifne",
+    "iflt",
+    "ifge",
+    "ifgt",
+    "ifle",
+    "if_icmpeq",
+    "if_icmpne",
+    "if_icmplt",
+    "if_icmpge",
+    "if_icmpgt",
+    "if_icmple",
+    "if_acmpeq",
+    "if_acmpne",
+    "goto",
+    "jsr",
+    "ret",
+    "tableswitch",
+    "lookupswitch",
+    "ireturn",
+    "lreturn",
+    "freturn",
+    "dreturn",
+    "areturn",
+    "return",
+    "getstatic",
+    "putstatic",
+    "getfield",
+    "putfield",
+    "invokevirtual",
+    "invokespecial",
+    "invokestatic",
+    "invokeinterface",
+    "invokedynamic",
+    "new",
+    "newarray",
+    "anewarray",
+    "arraylength",
+    "athrow",
+    "checkcast",
+    "instanceof",
+    "monitorenter",
+    "monitorexit",
+    "wide",
+    "multianewarray",
+    "ifnull",
+    "ifnonnull",
+    "goto_w",
+    "jsr_w",
+];
+
+/// Parse a method's code into instructions, with jump targets and exception handlers pointing at
+/// instruction indices instead of byte offsets. Also returns each instruction's byte offset and
+/// opcode in the original code, which is how `javap` and other JVMs refer to it
 /// # Panics
 /// # Errors
 pub fn hydrate_code(
     class_area: &SharedClassArea,
     constants: &[Constant],
+    dynamic_constants: &BTreeMap<u16, DynamicConstant>,
     code: Vec<u8>,
     exception_table: &mut [ExceptionTableEntry],
     verbose: bool,
-) -> Result<Vec<Instruction>, String> {
+) -> error::Result<(Vec<Instruction>, Vec<(u16, u8)>)> {
     if verbose {
         for byte in &code {
             print!("{byte:<02X} ");
@@ -216,8 +479,13 @@ pub fn hydrate_code(
     }
     let mut bytes = code.into_iter().enumerate().peekable();
     let mut code = Vec::new();
-    while let Some(&(index, _)) = bytes.peek() {
-        code.push((index, parse_instruction(constants, &mut bytes)?));
+    let mut opcodes = Vec::new();
+    while let Some(&(index, opcode)) = bytes.peek() {
+        opcodes.push((index as u16, opcode));
+        code.push((
+            index,
+            parse_instruction(constants, dynamic_constants, &mut bytes)?,
+        ));
     }
     if verbose {
         println!("{code:?}");
@@ -237,6 +505,34 @@ pub fn hydrate_code(
                     let goto = translate_pc(target).unwrap();
                     Instruction::Goto(goto as i32)
                 }
+                Instruction::TableSwitch {
+                    default,
+                    low,
+                    high,
+                    offsets,
+                } => {
+                    let translate = |offset: i32| {
+                        translate_pc((idx as i32).wrapping_add(offset) as usize).unwrap() as i32
+                    };
+                    Instruction::TableSwitch {
+                        default: translate(default),
+                        low,
+                        high,
+                        offsets: offsets.into_iter().map(translate).collect(),
+                    }
+                }
+                Instruction::LookupSwitch { default, pairs } => {
+                    let translate = |offset: i32| {
+                        translate_pc((idx as i32).wrapping_add(offset) as usize).unwrap() as i32
+                    };
+                    Instruction::LookupSwitch {
+                        default: translate(default),
+                        pairs: pairs
+                            .into_iter()
+                            .map(|(key, offset)| (key, translate(offset)))
+                            .collect(),
+                    }
+                }
                 Instruction::IfCmpZ(cmp, goto) => {
                     let target = (idx as i16).wrapping_add(goto) as usize;
                     let goto = translate_pc(target).unwrap();
@@ -261,7 +557,9 @@ pub fn hydrate_code(
                     Instruction::PutField(Some(idx), class, field, ty)
                 }
                 Instruction::InvokeVirtual(None, a, b, c) => {
-                    let class_ref = class_area.search(&a).expect(&a);
+                    let class_ref = class_area
+                        .search(&a)
+                        .ok_or_else(|| error::Error::class_resolution(&a))?;
                     Instruction::InvokeVirtual(
                         class_ref
                             .vtable
@@ -275,7 +573,8 @@ pub fn hydrate_code(
                 other => other,
             })
         })
-        .collect::<Result<_, String>>()
+        .collect::<error::Result<_>>()
+        .map(|code| (code, opcodes))
 }
 
 fn concrete_field(
@@ -283,27 +582,90 @@ fn concrete_field(
     class: &str,
     field: &str,
     ty: &FieldType,
-) -> Result<usize, String> {
+) -> error::Result<usize> {
     let current = class_area
         .search(class)
-        .ok_or_else(|| format!("Couldn't find class {class}"))?;
+        .ok_or_else(|| error::Error::class_resolution(&class))?;
     let idx = current
         .fields
         .iter()
         .find(|(f, _)| &*f.name == field && &f.descriptor == ty)
-        .ok_or_else(|| format!("Couldn't find field {ty:?} {class}.{field}"))?
+        .ok_or_else(|| {
+            error::Error::LinkageError(format!(
+                "java.lang.NoSuchFieldError: {ty:?} {class}.{field}"
+            ))
+        })?
         .1;
     Ok(idx)
 }
 
+/// skip the 0-3 bytes of padding after a `tableswitch` or `lookupswitch` opcode. The operands are
+/// aligned to a multiple of 4 bytes from the start of the method's code, so this goes by each byte's
+/// offset in the code array rather than by how many bytes the instruction has read
+fn skip_switch_padding(bytes: &mut Peekable<impl Iterator<Item = (usize, u8)>>) {
+    while bytes.peek().is_some_and(|&(idx, _)| idx % 4 != 0) {
+        bytes.next();
+    }
+}
+
+/// read a signed 4-byte operand of a `tableswitch` or `lookupswitch`
+fn next_i32(bytes: &mut Peekable<impl Iterator<Item = (usize, u8)>>) -> error::Result<i32> {
+    let mut operand = [0; 4];
+    for byte in &mut operand {
+        *byte = bytes
+            .next()
+            .ok_or_else(|| error::Error::class_format("Code ended in the middle of a switch"))?
+            .1;
+    }
+    Ok(i32::from_be_bytes(operand))
+}
+
+/// translate the constant loaded by an `ldc` or `ldc_w` into the instruction that pushes it
+fn ldc_constant(
+    constants: &[Constant],
+    dynamic_constants: &BTreeMap<u16, DynamicConstant>,
+    index: u16,
+) -> error::Result<Instruction> {
+    if let Some(dynamic) = dynamic_constants.get(&index) {
+        return if dynamic.field_type.get_size() == 1 {
+            Ok(load_dynamic(dynamic))
+        } else {
+            Err(error::Error::class_format(format!(
+                "Error during ldc; can't load 2-wide {dynamic:?}"
+            )))
+        };
+    }
+    match constants[index as usize - 1].clone() {
+        Constant::Int(i) => Ok(Instruction::Push1(i as u32)),
+        Constant::Float(i) => Ok(Instruction::Push1(i.to_bits())),
+        Constant::String(str) | Constant::StringRef(str) => Ok(Instruction::LoadString(str)),
+        Constant::ClassRef(cls) => Ok(Instruction::LoadClass(cls)),
+        other => Err(error::Error::class_format(format!(
+            "Error during ldc; can't load {other:?}"
+        ))),
+    }
+}
+
+/// the instruction that resolves a dynamically-computed constant the first time it runs
+fn load_dynamic(dynamic: &DynamicConstant) -> Instruction {
+    Instruction::LoadDynamic(
+        dynamic.bootstrap_index,
+        dynamic.name.clone(),
+        dynamic.field_type.clone(),
+        Arc::new(OnceLock::new()),
+    )
+}
+
 #[allow(clippy::too_many_lines)]
 /// # Panics
 /// # Errors
 pub fn parse_instruction(
     constants: &[Constant],
+    dynamic_constants: &BTreeMap<u16, DynamicConstant>,
     bytes: &mut Peekable<impl Iterator<Item = (usize, u8)>>,
-) -> Result<Instruction, String> {
-    match bytes.next().unwrap().1 {
+) -> error::Result<Instruction> {
+    let (index, opcode) = bytes.next().unwrap();
+    match opcode {
         0x0 => Ok(Instruction::Noop),
         0x01 => {
             // aconst_null
@@ -358,20 +720,17 @@ pub fn parse_instruction(
 
             let index = bytes.next().unwrap().1;
 
-            let constant = constants[index as usize - 1].clone();
-
-            match constant {
-                Constant::Int(i) => Ok(Instruction::Push1(i as u32)),
-                Constant::Float(i) => Ok(Instruction::Push1(i.to_bits())),
-                Constant::String(str) | Constant::StringRef(str) => {
-                    Ok(Instruction::LoadString(str))
-                }
-                Constant::ClassRef(cls) => Ok(Instruction::LoadClass(cls)),
-                other => Err(format!("Error during ldc; can't load {other:?}")),
-            }
+            ldc_constant(constants, dynamic_constants, index as u16)
         }
         0x13 => {
-            todo!("ldc_w")
+            // ldc_w
+            // push item from constant pool, with a wide index
+            let upper = bytes.next().unwrap().1;
+            let lower = bytes.next().unwrap().1;
+
+            let index = ((upper as u16) << 8) | lower as u16;
+
+            ldc_constant(constants, dynamic_constants, index)
         }
         0x14 => {
             // ldc2_w
@@ -381,11 +740,22 @@ pub fn parse_instruction(
 
             let index = ((upper as u16) << 8) | lower as u16;
 
+            if let Some(dynamic) = dynamic_constants.get(&index) {
+                return if dynamic.field_type.get_size() == 2 {
+                    Ok(load_dynamic(dynamic))
+                } else {
+                    Err(error::Error::class_format(format!(
+                        "Error during ldc2_w; can't load 1-wide {dynamic:?}"
+                    )))
+                };
+            }
             let constant = constants[index as usize - 1].clone();
             match constant {
                 Constant::Double(d) => Ok(Instruction::push_2(d.to_bits())),
                 Constant::Long(l) => Ok(Instruction::push_2(l as u64)),
-                other => Err(format!("Error during ldc2_w; can't load {other:?}")),
+                other => Err(error::Error::class_format(format!(
+                    "Error during ldc2_w; can't load {other:?}"
+                ))),
             }
         }
         0x18 | 0x16 => {
@@ -851,14 +1221,46 @@ pub fn parse_instruction(
             // jump subroutine
         }
         0xA9 => {
-            todo!("ret")
+            // ret index
             // return from subroutine
+            let index = bytes.next().unwrap().1;
+            Ok(Instruction::Ret(index as usize))
         }
         0xAA => {
-            todo!("tableswitch")
+            // tableswitch <0-3 byte pad> default low high offsets...
+            skip_switch_padding(bytes);
+            let default = next_i32(bytes)?;
+            let low = next_i32(bytes)?;
+            let high = next_i32(bytes)?;
+            if high < low {
+                return Err(error::Error::class_format(format!(
+                    "tableswitch at {index} has high {high} below low {low}"
+                )));
+            }
+            let offsets = (low..=high)
+                .map(|_| next_i32(bytes))
+                .collect::<Result<_, _>>()?;
+            Ok(Instruction::TableSwitch {
+                default,
+                low,
+                high,
+                offsets,
+            })
         }
         0xAB => {
-            todo!("lookupswitch")
+            // lookupswitch <0-3 byte pad> default npairs (match offset)...
+            skip_switch_padding(bytes);
+            let default = next_i32(bytes)?;
+            let npairs = next_i32(bytes)?;
+            if npairs < 0 {
+                return Err(error::Error::class_format(format!(
+                    "lookupswitch at {index} has {npairs} pairs"
+                )));
+            }
+            let pairs = (0..npairs)
+                .map(|_| Ok((next_i32(bytes)?, next_i32(bytes)?)))
+                .collect::<error::Result<_>>()?;
+            Ok(Instruction::LookupSwitch { default, pairs })
         }
         0xAC | 0xAE | 0xB0 => Ok(Instruction::Return1),
         0xAF | 0xAD => Ok(Instruction::Return2),
@@ -880,10 +1282,10 @@ pub fn parse_instruction(
                 field_type,
             } = constants[index as usize - 1].clone()
             else {
-                return Err(format!(
+                return Err(error::Error::class_format(format!(
                     "Error invoking GetStatic at index {index}; {:?}",
                     constants[index as usize - 1]
-                ));
+                )));
             };
             Ok(Instruction::GetStatic(
                 class,
@@ -904,10 +1306,10 @@ pub fn parse_instruction(
                 field_type,
             } = constants[index as usize - 1].clone()
             else {
-                return Err(format!(
+                return Err(error::Error::class_format(format!(
                     "Error invoking PutStatic at index {index}; {:?}",
                     constants[index as usize - 1]
-                ));
+                )));
             };
             Ok(Instruction::PutStatic(
                 class,
@@ -929,10 +1331,10 @@ pub fn parse_instruction(
                 field_type,
             } = constants[index as usize - 1].clone()
             else {
-                return Err(format!(
+                return Err(error::Error::class_format(format!(
                     "Error invoking PutField at index {index}; {:?}",
                     constants[index as usize - 1]
-                ));
+                )));
             };
 
             Ok(Instruction::GetField(None, class, name, field_type))
@@ -950,10 +1352,10 @@ pub fn parse_instruction(
                 field_type,
             } = constants[index as usize - 1].clone()
             else {
-                return Err(format!(
+                return Err(error::Error::class_format(format!(
                     "Error invoking PutField at index {index}; {:?}",
                     constants[index as usize - 1]
-                ));
+                )));
             };
 
             Ok(Instruction::PutField(None, class, name, field_type))
@@ -972,7 +1374,7 @@ pub fn parse_instruction(
                 method_type,
             } = constants[index as usize - 1].clone()
             else {
-                return Err(String::from("Error during InvokeVirtual"));
+                return Err(error::Error::class_format("Error during InvokeVirtual"));
             };
 
             Ok(Instruction::InvokeVirtual(None, class, name, method_type))
@@ -1029,7 +1431,7 @@ pub fn parse_instruction(
             let _count = bytes.next().unwrap().1;
 
             let 0 = bytes.next().unwrap().1 else {
-                return Err(String::from("Expected a zero"));
+                return Err(error::Error::class_format("Expected a zero"));
             };
             let Constant::InterfaceRef {
                 class,
@@ -1037,10 +1439,10 @@ pub fn parse_instruction(
                 interface_type,
             } = constants[index as usize - 1].clone()
             else {
-                return Err(format!(
+                return Err(error::Error::class_format(format!(
                     "Error resolving InvokeInterface - got {:?}",
                     constants[index as usize - 1]
-                ));
+                )));
             };
 
             Ok(Instruction::InvokeInterface(class, name, interface_type))
@@ -1053,10 +1455,10 @@ pub fn parse_instruction(
             let index = u16::from_be_bytes([ib1, ib2]);
 
             let 0 = bytes.next().unwrap().1 else {
-                return Err(String::from("Expected a zero"));
+                return Err(error::Error::class_format("Expected a zero"));
             };
             let 0 = bytes.next().unwrap().1 else {
-                return Err(String::from("Expected a zero"));
+                return Err(error::Error::class_format("Expected a zero"));
             };
 
             let Constant::InvokeDynamic {
@@ -1065,10 +1467,10 @@ pub fn parse_instruction(
                 method_type,
             } = constants[index as usize - 1].clone()
             else {
-                return Err(format!(
+                return Err(error::Error::class_format(format!(
                     "Error running InvokeDynamic - {:?}",
                     constants[index as usize - 1]
-                ));
+                )));
             };
 
             Ok(Instruction::InvokeDynamic(
@@ -1102,7 +1504,9 @@ pub fn parse_instruction(
                 9 => Ok(Instruction::NewArray1(FieldType::Short)),
                 10 => Ok(Instruction::NewArray1(FieldType::Int)),
                 11 => Ok(Instruction::NewArray2(FieldType::Long)),
-                other => Err(format!("Invalid `atype` for `newarray`: {other}")),
+                other => Err(error::Error::class_format(format!(
+                    "Invalid `atype` for `newarray`: {other}"
+                ))),
             }
         }
         0xBD => {
@@ -1112,12 +1516,19 @@ pub fn parse_instruction(
             let ib2 = bytes.next().unwrap().1;
             let index = u16::from_be_bytes([ib1, ib2]);
             let Constant::ClassRef(class) = constants[index as usize - 1].clone() else {
-                return Err(format!(
+                return Err(error::Error::class_format(format!(
                     "Expected a class reference; got {:?}",
                     constants[index as usize - 1]
-                ));
+                )));
             };
-            Ok(Instruction::NewArray1(FieldType::Object(class)))
+            // an array of arrays names its element type by descriptor
+            if class.starts_with('[') {
+                Ok(Instruction::NewArray1(parse_field_type(
+                    &mut class.chars().peekable(),
+                )?))
+            } else {
+                Ok(Instruction::NewArray1(FieldType::Object(class)))
+            }
         }
         0xBE => Ok(Instruction::ArrayLength),
         0xBF => Ok(Instruction::AThrow),
@@ -1129,10 +1540,10 @@ pub fn parse_instruction(
             let index = u16::from_be_bytes([ib1, ib2]);
 
             let Constant::ClassRef(class) = constants[index as usize - 1].clone() else {
-                return Err(format!(
+                return Err(error::Error::class_format(format!(
                     "Expected a class reference; got {:?}",
                     constants[index as usize - 1]
-                ));
+                )));
             };
             Ok(Instruction::CheckedCast(class))
         }
@@ -1144,22 +1555,51 @@ pub fn parse_instruction(
             let index = u16::from_be_bytes([ib1, ib2]);
 
             let Constant::ClassRef(class) = constants[index as usize - 1].clone() else {
-                return Err(format!(
+                return Err(error::Error::class_format(format!(
                     "Expected a class reference; got {:?}",
                     constants[index as usize - 1]
-                ));
+                )));
             };
             Ok(Instruction::Instanceof(class))
         }
-        0xC2 => {
-            todo!("monitorenter")
-        }
-        0xC3 => {
-            todo!("monitorexit")
-        }
+        0xC2 => Ok(Instruction::MonitorEnter),
+        0xC3 => Ok(Instruction::MonitorExit),
         0xC4 => {
-            todo!("wide")
-            // woosh this one's gonna be tough
+            // wide
+            // the next instruction uses a 16-bit local index
+            match bytes.next().unwrap().1 {
+                0x84 => {
+                    // wide iinc
+                    // 16-bit index and signed 16-bit increment; only touches the one int slot
+                    let ib1 = bytes.next().unwrap().1;
+                    let ib2 = bytes.next().unwrap().1;
+                    let index = u16::from_be_bytes([ib1, ib2]) as usize;
+                    let cb1 = bytes.next().unwrap().1;
+                    let cb2 = bytes.next().unwrap().1;
+                    let inc = i16::from_be_bytes([cb1, cb2]) as i32;
+                    Ok(Instruction::IInc(index, inc))
+                }
+                opcode => {
+                    let ib1 = bytes.next().unwrap().1;
+                    let ib2 = bytes.next().unwrap().1;
+                    let index = u16::from_be_bytes([ib1, ib2]) as usize;
+                    match opcode {
+                        // wide dload|lload
+                        0x18 | 0x16 => Ok(Instruction::Load2(index)),
+                        // wide aload|fload|iload
+                        0x19 | 0x17 | 0x15 => Ok(Instruction::Load1(index)),
+                        // wide dstore|lstore
+                        0x39 | 0x37 => Ok(Instruction::Store2(index)),
+                        // wide astore|fstore|istore
+                        0x3A | 0x38 | 0x36 => Ok(Instruction::Store1(index)),
+                        // wide ret
+                        0xA9 => Ok(Instruction::Ret(index)),
+                        other => Err(error::Error::class_format(format!(
+                            "Invalid opcode after wide: 0x{other:x}"
+                        ))),
+                    }
+                }
+            }
         }
         0xC5 => {
             // make a new multi-dimensional array
@@ -1170,17 +1610,17 @@ pub fn parse_instruction(
             // constant index to a type
             let index = (ib1 as u16) << 8 | ib2 as u16;
             let Some(Constant::ClassRef(class_ref)) = constants.get(index as usize - 1) else {
-                return Err(format!(
+                return Err(error::Error::class_format(format!(
                     "Invalid Constant for multianewarray: {:?}",
                     constants.get(index as usize - 1)
-                ));
+                )));
             };
 
             let mut array_type = parse_field_type(&mut class_ref.chars().peekable())?;
             let full_type = array_type.clone();
             for _ in 0..dimensions {
                 let FieldType::Array(inner) = array_type else {
-                    return Err(String::from("Array is too shallow"));
+                    return Err(error::Error::class_format("Array is too shallow"));
                 };
                 array_type = *inner;
             }
@@ -1208,6 +1648,8 @@ pub fn parse_instruction(
             todo!("jsr_w")
             // jump subroutine wide
         }
-        other => Err(format!("Invalid Opcode: 0x{other:x}")),
+        other => Err(error::Error::class_format(format!(
+            "Invalid Opcode: 0x{other:x}"
+        ))),
     }
 }
@@ -0,0 +1,283 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use jvmrs_lib::MethodDescriptor;
+
+use crate::{
+    class::Method,
+    class_loader,
+    data::{Heap, SharedClassArea, SharedHeap, SharedMethodArea},
+};
+
+use super::{
+    error, set_static_classes, start_vm, StackFrame, Thread, VmOptions, DEFAULT_MAX_DEPTH,
+};
+
+/// # Java VM
+/// A set of loaded classes that the host program can call into directly, without going through
+/// `main`. Each call runs on a fresh `Thread` that shares the VM's heap and classes.
+///
+/// Classes are linked as soon as they're loaded, so loading more classes reloads the ones already
+/// there and resets the heap, the same as [`JavaVm::reset`].
+pub struct JavaVm {
+    /// the class files this VM was made from, kept so `reset` can load them again
+    class_files: Vec<Vec<u8>>,
+    /// directories to look in for classes that the class files use but don't include
+    classpath: Vec<PathBuf>,
+    /// the class in the first class file, which is where a program starts by default
+    first_class: Option<Arc<str>>,
+    pub method_area: SharedMethodArea,
+    pub class_area: SharedClassArea,
+    pub heap: SharedHeap,
+    pub options: VmOptions,
+}
+
+impl JavaVm {
+    /// load the native library and each of the given class files
+    /// # Errors
+    /// Returns an error if a class file is malformed or its methods can't be linked
+    pub fn new(
+        class_files: Vec<Vec<u8>>,
+        options: VmOptions,
+        verbose: bool,
+    ) -> error::Result<Self> {
+        Self::with_classpath(class_files, Vec::new(), options, verbose)
+    }
+
+    /// load the native library, each of the given class files, and any class they use from the
+    /// directories and jars in `classpath`
+    /// # Errors
+    /// Returns an error if a class file is malformed or its methods can't be linked
+    pub fn with_classpath(
+        class_files: Vec<Vec<u8>>,
+        classpath: Vec<PathBuf>,
+        options: VmOptions,
+        verbose: bool,
+    ) -> error::Result<Self> {
+        let (method_area, class_area, heap, first_class) =
+            Self::load(&class_files, &classpath, options, verbose)?;
+        Ok(Self {
+            class_files,
+            classpath,
+            first_class,
+            method_area,
+            class_area,
+            heap,
+            options,
+        })
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn load(
+        class_files: &[Vec<u8>],
+        classpath: &[PathBuf],
+        options: VmOptions,
+        verbose: bool,
+    ) -> error::Result<(
+        SharedMethodArea,
+        SharedClassArea,
+        SharedHeap,
+        Option<Arc<str>>,
+    )> {
+        let (mut method_area, mut class_area) = class_loader::load_environment();
+        let mut first_class = None;
+        for bytes in class_files {
+            let class =
+                class_loader::load_class(&mut method_area, &mut bytes.iter().copied(), verbose)?;
+            if verbose {
+                println!("{class:#?}");
+            }
+            first_class.get_or_insert_with(|| class.this.clone());
+            class_area.push(class);
+        }
+        if !classpath.is_empty() {
+            class_loader::load_classpath(
+                &mut method_area,
+                &mut class_area,
+                classpath,
+                [],
+                verbose,
+            )?;
+        }
+        let class_area = class_area.to_shared();
+        let method_area = method_area.to_shared(&class_area, verbose)?;
+        if verbose {
+            println!("{method_area:#?}");
+        }
+        let mut heap = Heap::new(class_area.clone());
+        if options.disable_gc {
+            heap.disable_gc();
+        }
+        let heap = heap.make_shared();
+        set_static_classes(&class_area);
+        Ok((method_area, class_area, heap, first_class))
+    }
+
+    /// throw away the heap and every class's static state by loading the class files again, so the
+    /// next call sees the VM exactly as `new` left it
+    /// # Errors
+    /// Returns an error if the class files can't be loaded again
+    pub fn reset(&mut self, verbose: bool) -> error::Result<()> {
+        (
+            self.method_area,
+            self.class_area,
+            self.heap,
+            self.first_class,
+        ) = Self::load(&self.class_files, &self.classpath, self.options, verbose)?;
+        Ok(())
+    }
+
+    /// the class in the first class file the VM was given, if any
+    #[must_use]
+    pub fn first_class(&self) -> Option<Arc<str>> {
+        self.first_class.clone()
+    }
+
+    /// load one more class file
+    /// # Errors
+    /// Returns an error if the class file is malformed or can't be linked. The VM is left as it was
+    pub fn load_class_bytes(&mut self, bytes: &[u8], verbose: bool) -> error::Result<()> {
+        self.load_more(vec![bytes.to_vec()], verbose)
+    }
+
+    /// load every class file in a directory and its subdirectories, like the output of `javac -d`
+    /// # Errors
+    /// Returns an error if the directory can't be read or a class in it can't be loaded. The VM is
+    /// left as it was
+    pub fn load_dir(&mut self, dir: &Path, verbose: bool) -> error::Result<()> {
+        let mut class_files = Vec::new();
+        read_class_dir(dir, &mut class_files)
+            .map_err(|err| format!("Couldn't read {}: {err}", dir.display()))?;
+        self.load_more(class_files, verbose)
+    }
+
+    /// load a class from the classpath if it isn't loaded already
+    /// # Errors
+    /// Returns an error if the class is on the classpath but can't be loaded. The VM is left as it
+    /// was
+    pub fn load_from_classpath(&mut self, class: &str, verbose: bool) -> error::Result<()> {
+        if self.class_area.search(class).is_some() {
+            return Ok(());
+        }
+        let Some(bytes) = class_loader::find_on_classpath(&self.classpath, class) else {
+            return Ok(());
+        };
+        self.load_more(vec![bytes], verbose)
+    }
+
+    fn load_more(&mut self, class_files: Vec<Vec<u8>>, verbose: bool) -> error::Result<()> {
+        let count = self.class_files.len();
+        self.class_files.extend(class_files);
+        if let Err(err) = self.reset(verbose) {
+            self.class_files.truncate(count);
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    /// Run a class's `main` method with the given arguments, like `java` does. Returns the status
+    /// code the program exits with: the one given to `System.exit`, or 0 if `main` returns
+    /// # Errors
+    /// Returns a `LinkageError` if the class isn't loaded or doesn't have a `main` method. Otherwise
+    /// returns the error that stopped the main thread, like an uncaught exception, which has already
+    /// been reported on stderr
+    pub fn run_main(&self, class: &str, argv: Vec<String>, verbose: bool) -> error::Result<i32> {
+        match start_vm(
+            class,
+            self.method_area.clone(),
+            self.class_area.clone(),
+            self.heap.clone(),
+            argv,
+            self.options,
+            verbose,
+        ) {
+            Ok(()) => Ok(0),
+            Err(error::Error::Exit(code)) => Ok(code),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Run a static method to completion. `args` are the method's local variables, with longs and
+    /// doubles taking two slots. Returns the slots of the return value: none for `void`, two for
+    /// `long` and `double`, and one otherwise.
+    /// # Errors
+    /// Returns an error if the method can't be found, isn't static, or has fewer local slots than
+    /// `args`, or if the call throws
+    pub fn invoke_static(
+        &self,
+        class: &str,
+        name: &str,
+        descriptor: &MethodDescriptor,
+        args: &[u32],
+        verbose: bool,
+    ) -> error::Result<Vec<u32>> {
+        let (class, method) = self
+            .method_area
+            .search(class, name, descriptor)
+            .ok_or_else(|| {
+                error::Error::LinkageError(format!(
+                    "Couldn't find static method {class}.{name}{descriptor:?}"
+                ))
+            })?;
+        if !method.access_flags.is_static() {
+            return Err(error::Error::LinkageError(format!(
+                "{}.{name}{descriptor:?} isn't static",
+                class.this
+            )));
+        }
+        if args.len() > method.max_locals as usize {
+            return Err(error::Error::LinkageError(format!(
+                "{}.{name}{descriptor:?} has {} local slots, but got {} arguments",
+                class.this,
+                method.max_locals,
+                args.len()
+            )));
+        }
+        // the method returns into this frame, which holds a dummy return address for it to pop.
+        // It's native so that exceptions unwind straight through it
+        let mut caller = StackFrame::from_method(Arc::new(Method::default()), class.clone());
+        caller.operand_stack.push(0);
+        let mut thread = Thread {
+            pc_register: 0,
+            stack: vec![caller],
+            stackframe: StackFrame::from_method(method, class.clone()),
+            method_area: self.method_area.clone(),
+            class_area: self.class_area.clone(),
+            heap: self.heap.clone(),
+            trap_overflow: self.options.trap_overflow,
+            verify: self.options.verify,
+            deterministic_hash: self.options.deterministic_hash,
+            trace: self.options.trace,
+            max_depth: self.options.max_depth.unwrap_or(DEFAULT_MAX_DEPTH),
+        };
+        thread.stackframe.locals[..args.len()].copy_from_slice(args);
+        // `<clinit>` returns to the instruction before the pc, which is the start of the method
+        thread.pc_register = 1;
+        if !thread.maybe_initialize_class(&class) {
+            thread.pc_register = 0;
+        }
+        while !thread.stack.is_empty() {
+            thread.tick(verbose)?;
+        }
+        Ok(thread.stackframe.operand_stack)
+    }
+}
+
+/// add the contents of every class file under `dir` to `class_files`, in a stable order
+fn read_class_dir(dir: &Path, class_files: &mut Vec<Vec<u8>>) -> io::Result<()> {
+    let mut entries = fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<io::Result<Vec<_>>>()?;
+    entries.sort();
+    for path in entries {
+        if path.is_dir() {
+            read_class_dir(&path, class_files)?;
+        } else if path.extension().is_some_and(|ext| ext == "class") {
+            class_files.push(fs::read(path)?);
+        }
+    }
+    Ok(())
+}
@@ -1,17 +1,18 @@
 use std::{
-    collections::HashMap,
     hash::{DefaultHasher, Hash, Hasher},
+    io::Read,
     sync::{Arc, Mutex, OnceLock},
+    time::{Instant, SystemTime, UNIX_EPOCH},
 };
 
-use jvmrs_lib::{access, method, FieldType};
+use jvmrs_lib::{access, method, FieldType, MethodDescriptor};
 use rand::{rngs::StdRng, Rng, SeedableRng};
 
 use crate::{
     class::{
         code::{
             native_property, NativeDoubleMethod, NativeNoop, NativeSingleMethod,
-            NativeStringMethod, NativeVoid,
+            NativeStringMethod, NativeVoid, Staged,
         },
         Class, Field,
     },
@@ -22,23 +23,42 @@ use crate::{
 use self::{
     arrays::deep_to_string,
     primitives::make_primitives,
-    string::{native_println_object, native_string_char_at, native_string_value_of},
+    print_stream::PrintTarget,
+    string::{
+        native_string_char_at, native_string_ends_with, native_string_equals,
+        native_string_equals_ignore_case, native_string_hash_code, native_string_index_of,
+        native_string_index_of_char, native_string_intern, native_string_replace_char,
+        native_string_split, native_string_starts_with, native_string_substring,
+        native_string_substring_range, native_string_value_of, native_string_value_of_char_range,
+        native_string_value_of_chars,
+    },
 };
 
 use super::{
-    object::{AnyObj, Array1, Array2, ArrayType, Object, ObjectFinder, Random, StringObj},
+    error,
+    object::{
+        AnyObj, Array1, Array2, ArrayType, ByteStream, Object, ObjectFinder, Random,
+        RandomGaussian, StringObj,
+    },
     Thread,
 };
 
 pub mod arrays;
 pub mod character;
 pub mod collections;
+pub mod collectors;
+pub mod format;
 pub mod function;
+pub mod input;
+pub mod math;
 pub mod primitives;
+pub mod print_stream;
+pub mod record;
 pub mod reflect;
 pub mod stream;
 pub mod string;
 pub mod string_builder;
+pub mod thread;
 pub mod throwable;
 
 pub static mut OBJECT_CLASS: Option<Arc<Class>> = None;
@@ -53,21 +73,33 @@ pub fn get_class(
     class_area: &SharedClassArea,
     obj_class: Arc<str>,
 ) -> Option<u32> {
-    static CLASS_CACHE: Mutex<OnceLock<HashMap<Arc<str>, u32>>> = Mutex::new(OnceLock::new());
-    let mut binding = CLASS_CACHE.lock().unwrap();
-    let class_cache = binding.get_or_init(HashMap::new);
-    if let Some(&ptr) = class_cache.get(&obj_class) {
+    let mut heap = heap.lock().unwrap();
+    if let Some(ptr) = heap.class_object(&obj_class) {
         return Some(ptr);
     }
     let class_class = class_area.search("java/lang/Class")?;
     let mut class_obj = Object::from_class(&class_class);
-    class_obj.native_fields.push(Box::new(class_class));
-    let ptr = heap.lock().unwrap().allocate(class_obj);
-    binding.get_mut().unwrap().insert(obj_class, ptr);
-    drop(binding);
+    // primitive types don't have a class of their own
+    let class = class_area.search(&obj_class).unwrap_or(class_class);
+    class_obj.native_fields.push(Box::new(class));
+    let ptr = heap.allocate(class_obj);
+    heap.cache_class_object(obj_class, ptr);
+    drop(heap);
     Some(ptr)
 }
 
+/// the default `Object.hashCode` of the object at `ptr`
+///
+/// # Panics
+pub fn identity_hash_code(thread: &Thread, ptr: u32) -> u32 {
+    if thread.deterministic_hash {
+        return thread.heap.lock().unwrap().identity_hash(ptr);
+    }
+    let mut hasher = DefaultHasher::new();
+    ptr.hash(&mut hasher);
+    hasher.finish() as u32
+}
+
 #[allow(clippy::too_many_lines)]
 /// # Panics
 pub fn add_native_methods(method_area: &mut WorkingMethodArea, class_area: &mut WorkingClassArea) {
@@ -105,12 +137,19 @@ pub fn add_native_methods(method_area: &mut WorkingMethodArea, class_area: &mut
         access_flags: access!(public native),
         name: "hashCode".into(),
         descriptor: method!(() -> int),
-        code: RawCode::native(NativeSingleMethod(|_: &mut _, [ptr]: [u32; 1], _| {
-            let mut hasher = DefaultHasher::new();
-            ptr.hash(&mut hasher);
-            let value = hasher.finish() as u32;
-            Ok(Some(value))
-        })),
+        code: RawCode::native(NativeSingleMethod(
+            |thread: &mut Thread, [ptr]: [u32; 1], _| Ok(Some(identity_hash_code(thread, ptr))),
+        )),
+        ..Default::default()
+    };
+
+    let object_equals = RawMethod {
+        access_flags: access!(public native),
+        name: "equals".into(),
+        descriptor: method!(((Object(java_lang_object.clone()))) -> boolean),
+        code: RawCode::native(NativeSingleMethod(
+            |_: &mut _, [this, other]: [u32; 2], _| Ok(Some(u32::from(this == other))),
+        )),
         ..Default::default()
     };
 
@@ -120,7 +159,13 @@ pub fn add_native_methods(method_area: &mut WorkingMethodArea, class_area: &mut
         java_lang_object.clone(),
     );
     object.register_methods(
-        [noop_init, object_to_string, object_hash, object_get_class],
+        [
+            noop_init,
+            object_to_string,
+            object_hash,
+            object_equals,
+            object_get_class,
+        ],
         method_area,
     );
 
@@ -180,11 +225,12 @@ pub fn add_native_methods(method_area: &mut WorkingMethodArea, class_area: &mut
     };
     enum_class.register_methods([enum_init, enum_to_string, enum_name], method_area);
 
-    let array = RawClass::new(
+    let mut array = RawClass::new(
         access!(public native),
         "java/lang/Array".into(),
         java_lang_object.clone(),
     );
+    array.register_method(RawMethod::to_string(arrays::object_to_string), method_area);
 
     let arrays_to_string = RawMethod {
         access_flags: access!(public static native),
@@ -218,6 +264,40 @@ pub fn add_native_methods(method_area: &mut WorkingMethodArea, class_area: &mut
         [arrays_to_string, arrays_to_string_obj_arr, deep_to_string],
         method_area,
     );
+    let null_safe_to_string = RawMethod {
+        access_flags: access!(public static native),
+        name: "toString".into(),
+        descriptor: method!(((Object(java_lang_object.clone()))) -> Object(java_lang_string.clone())),
+        code: RawCode::native(Staged(2, NativeSingleMethod(native_string_value_of))),
+        ..Default::default()
+    };
+    let null_safe_to_string_or_default = RawMethod {
+        access_flags: access!(public static native),
+        name: "toString".into(),
+        descriptor: method!(((Object(java_lang_object.clone())), (Object(java_lang_string.clone()))) -> Object(java_lang_string.clone())),
+        code: RawCode::native(Staged(
+            2,
+            NativeSingleMethod(
+                |thread: &mut Thread, [obj_ref, default]: [u32; 2], verbose| {
+                    if obj_ref == NULL {
+                        Ok(Some(default))
+                    } else {
+                        native_string_value_of(thread, [obj_ref], verbose)
+                    }
+                },
+            ),
+        )),
+        ..Default::default()
+    };
+    let mut objects_class = RawClass::new(
+        access!(public native),
+        "java/util/Objects".into(),
+        java_lang_object.clone(),
+    );
+    objects_class.register_methods(
+        [null_safe_to_string, null_safe_to_string_or_default],
+        method_area,
+    );
     let array_methods = make_primitives(method_area, class_area, java_lang_object.clone());
     arrays.methods.extend(
         array_methods
@@ -245,7 +325,21 @@ pub fn add_native_methods(method_area: &mut WorkingMethodArea, class_area: &mut
         access_flags: access!(public native),
         name: "valueOf".into(),
         descriptor: method!(((Object(java_lang_object.clone()))) -> Object(java_lang_string.clone())),
-        code: RawCode::native(NativeSingleMethod(native_string_value_of)),
+        code: RawCode::native(Staged(2, NativeSingleMethod(native_string_value_of))),
+        ..Default::default()
+    };
+    let string_value_of_chars = RawMethod {
+        access_flags: access!(public static native),
+        name: "valueOf".into(),
+        descriptor: method!((([]char)) -> Object(java_lang_string.clone())),
+        code: RawCode::native(NativeStringMethod(native_string_value_of_chars)),
+        ..Default::default()
+    };
+    let string_value_of_char_range = RawMethod {
+        access_flags: access!(public static native),
+        name: "valueOf".into(),
+        descriptor: method!((([]char), int, int) -> Object(java_lang_string.clone())),
+        code: RawCode::native(NativeStringMethod(native_string_value_of_char_range)),
         ..Default::default()
     };
     let string_to_string = RawMethod {
@@ -274,6 +368,25 @@ pub fn add_native_methods(method_area: &mut WorkingMethodArea, class_area: &mut
         )),
         ..Default::default()
     };
+    // the erased `Comparable.compareTo` that generic code like `Comparator.naturalOrder` calls
+    let string_compare_to_object = RawMethod {
+        descriptor: method!(((Object(java_lang_object.clone()))) -> int),
+        ..string_compare_to.clone()
+    };
+    let string_equals = RawMethod {
+        access_flags: access!(public native),
+        name: "equals".into(),
+        descriptor: method!(((Object(java_lang_object.clone()))) -> boolean),
+        code: RawCode::native(NativeSingleMethod(native_string_equals)),
+        ..Default::default()
+    };
+    let string_hash_code = RawMethod {
+        access_flags: access!(public native),
+        name: "hashCode".into(),
+        descriptor: method!(() -> int),
+        code: RawCode::native(NativeSingleMethod(native_string_hash_code)),
+        ..Default::default()
+    };
     let string_contains = RawMethod {
         name: "contains".into(),
         access_flags: access!(public native),
@@ -288,19 +401,188 @@ pub fn add_native_methods(method_area: &mut WorkingMethodArea, class_area: &mut
         )),
         ..Default::default()
     };
+    let substring = RawMethod {
+        access_flags: access!(public native),
+        name: "substring".into(),
+        descriptor: method!((int) -> Object(java_lang_string.clone())),
+        code: RawCode::native(NativeStringMethod(native_string_substring)),
+        ..Default::default()
+    };
+    let substring_range = RawMethod {
+        access_flags: access!(public native),
+        name: "substring".into(),
+        descriptor: method!((int, int) -> Object(java_lang_string.clone())),
+        code: RawCode::native(NativeStringMethod(native_string_substring_range)),
+        ..Default::default()
+    };
+    let index_of = RawMethod {
+        access_flags: access!(public native),
+        name: "indexOf".into(),
+        descriptor: method!(((Object(java_lang_string.clone()))) -> int),
+        code: RawCode::native(NativeSingleMethod(native_string_index_of)),
+        ..Default::default()
+    };
+    let index_of_char = RawMethod {
+        access_flags: access!(public native),
+        name: "indexOf".into(),
+        descriptor: method!((int) -> int),
+        code: RawCode::native(NativeSingleMethod(native_string_index_of_char)),
+        ..Default::default()
+    };
+    let replace_char = RawMethod {
+        access_flags: access!(public native),
+        name: "replace".into(),
+        descriptor: method!((char, char) -> Object(java_lang_string.clone())),
+        code: RawCode::native(NativeStringMethod(native_string_replace_char)),
+        ..Default::default()
+    };
+    let split = RawMethod {
+        access_flags: access!(public native),
+        name: "split".into(),
+        descriptor: MethodDescriptor {
+            parameter_size: 1,
+            parameters: vec![FieldType::Object(java_lang_string.clone())],
+            return_type: Some(FieldType::Array(Box::new(FieldType::Object(
+                java_lang_string.clone(),
+            )))),
+        },
+        code: RawCode::native(NativeSingleMethod(native_string_split)),
+        ..Default::default()
+    };
+    let to_upper_case = RawMethod {
+        access_flags: access!(public native),
+        name: "toUpperCase".into(),
+        descriptor: method!(() -> Object(java_lang_string.clone())),
+        code: RawCode::native(NativeStringMethod(native_property(StringObj::SELF, |s| {
+            s.to_uppercase().into()
+        }))),
+        ..Default::default()
+    };
+    let to_lower_case = RawMethod {
+        access_flags: access!(public native),
+        name: "toLowerCase".into(),
+        descriptor: method!(() -> Object(java_lang_string.clone())),
+        code: RawCode::native(NativeStringMethod(native_property(StringObj::SELF, |s| {
+            s.to_lowercase().into()
+        }))),
+        ..Default::default()
+    };
+    let trim = RawMethod {
+        access_flags: access!(public native),
+        name: "trim".into(),
+        descriptor: method!(() -> Object(java_lang_string.clone())),
+        // `trim` only removes ASCII control characters and spaces
+        code: RawCode::native(NativeStringMethod(native_property(StringObj::SELF, |s| {
+            s.trim_matches(|c| c <= ' ').into()
+        }))),
+        ..Default::default()
+    };
+    let strip = RawMethod {
+        access_flags: access!(public native),
+        name: "strip".into(),
+        descriptor: method!(() -> Object(java_lang_string.clone())),
+        code: RawCode::native(NativeStringMethod(native_property(StringObj::SELF, |s| {
+            s.trim().into()
+        }))),
+        ..Default::default()
+    };
+    let starts_with = RawMethod {
+        access_flags: access!(public native),
+        name: "startsWith".into(),
+        descriptor: method!(((Object(java_lang_string.clone()))) -> boolean),
+        code: RawCode::native(NativeSingleMethod(native_string_starts_with)),
+        ..Default::default()
+    };
+    let ends_with = RawMethod {
+        access_flags: access!(public native),
+        name: "endsWith".into(),
+        descriptor: method!(((Object(java_lang_string.clone()))) -> boolean),
+        code: RawCode::native(NativeSingleMethod(native_string_ends_with)),
+        ..Default::default()
+    };
+    let equals_ignore_case = RawMethod {
+        access_flags: access!(public native),
+        name: "equalsIgnoreCase".into(),
+        descriptor: method!(((Object(java_lang_string.clone()))) -> boolean),
+        code: RawCode::native(NativeSingleMethod(native_string_equals_ignore_case)),
+        ..Default::default()
+    };
+    let is_empty = RawMethod {
+        access_flags: access!(public native),
+        name: "isEmpty".into(),
+        descriptor: method!(() -> boolean),
+        code: RawCode::native(NativeSingleMethod(native_property(StringObj::SELF, |s| {
+            u32::from(s.is_empty())
+        }))),
+        ..Default::default()
+    };
+    // `new String(original)` is a distinct object with the same contents, so it isn't interned
+    let string_init = RawMethod {
+        access_flags: access!(public native),
+        name: "<init>".into(),
+        descriptor: method!(((Object(java_lang_string.clone()))) -> void),
+        code: RawCode::native(NativeVoid(
+            |thread: &mut Thread, [this, original]: [u32; 2], verbose| {
+                if original == NULL {
+                    thread.throw_new("java/lang/NullPointerException", "original", verbose)?;
+                    return Ok(None);
+                }
+                let str =
+                    StringObj::SELF.inspect(&thread.heap, original as usize, |str| str.clone())?;
+                AnyObj
+                    .inspect(&thread.heap, this as usize, |obj| {
+                        obj.native_fields.push(Box::new(str));
+                    })
+                    .map(Option::Some)
+            },
+        )),
+        ..Default::default()
+    };
+    let string_intern = RawMethod {
+        access_flags: access!(public native),
+        name: "intern".into(),
+        descriptor: method!(() -> Object(java_lang_string.clone())),
+        code: RawCode::native(NativeSingleMethod(native_string_intern)),
+        ..Default::default()
+    };
     let mut string = RawClass::new(
         access!(public native),
         java_lang_string.clone(),
         java_lang_object.clone(),
     );
+    string.interfaces.extend([
+        "java/lang/CharSequence".into(),
+        "java/lang/Comparable".into(),
+    ]);
     string.register_methods(
         [
+            string_init,
             string_length,
             char_at,
             string_value_of,
+            string_value_of_chars,
+            string_value_of_char_range,
             string_to_string,
             string_compare_to,
+            string_compare_to_object,
+            string_equals,
+            string_hash_code,
             string_contains,
+            substring,
+            substring_range,
+            index_of,
+            index_of_char,
+            replace_char,
+            split,
+            to_upper_case,
+            to_lower_case,
+            trim,
+            strip,
+            starts_with,
+            ends_with,
+            equals_ignore_case,
+            is_empty,
+            string_intern,
         ],
         method_area,
     );
@@ -312,6 +594,13 @@ pub fn add_native_methods(method_area: &mut WorkingMethodArea, class_area: &mut
         code: RawCode::native(NativeVoid(string_builder::init)),
         ..Default::default()
     };
+    let builder_init_empty = RawMethod {
+        access_flags: access!(public native),
+        name: "<init>".into(),
+        descriptor: method!(() -> void),
+        code: RawCode::native(NativeVoid(string_builder::init_empty)),
+        ..Default::default()
+    };
     let set_char_at = RawMethod {
         access_flags: access!(public native),
         name: "setCharAt".into(),
@@ -325,9 +614,65 @@ pub fn add_native_methods(method_area: &mut WorkingMethodArea, class_area: &mut
         "java/lang/StringBuilder".into(),
         java_lang_object.clone(),
     );
-    string_builder.register_methods([builder_init, set_char_at, to_string], method_area);
+    let append_string = RawMethod {
+        access_flags: access!(public native),
+        name: "append".into(),
+        descriptor: method!(((Object(java_lang_string.clone()))) -> Object(string_builder.this.clone())),
+        code: RawCode::native(Staged(2, NativeSingleMethod(string_builder::append_object))),
+        ..Default::default()
+    };
+    let append_object = RawMethod {
+        descriptor: method!(((Object(java_lang_object.clone()))) -> Object(string_builder.this.clone())),
+        ..append_string.clone()
+    };
+    let append_methods = string_builder::append_methods(&string_builder.this);
+    string_builder.register_methods(
+        [
+            builder_init,
+            builder_init_empty,
+            set_char_at,
+            to_string,
+            append_string,
+            append_object,
+        ]
+        .into_iter()
+        .chain(append_methods),
+        method_area,
+    );
 
-    let random_init = Random::make_init(StdRng::from_entropy);
+    let random_init = RawMethod {
+        access_flags: access!(public native),
+        name: "<init>".into(),
+        descriptor: method!(() -> void),
+        code: RawCode::native(NativeVoid(|thread: &mut Thread, [ptr]: [u32; 1], _| {
+            AnyObj
+                .inspect(&thread.heap, ptr as usize, |obj| {
+                    obj.native_fields.push(Box::new(StdRng::from_entropy()));
+                    obj.native_fields.push(Box::new(None::<f64>));
+                })
+                .map(Option::Some)
+        })),
+        ..Default::default()
+    };
+    // a seeded `Random` repeats its sequence from run to run, though not the one a real JVM gives
+    let random_init_seeded = RawMethod {
+        access_flags: access!(public native),
+        name: "<init>".into(),
+        descriptor: method!((long) -> void),
+        code: RawCode::native(NativeVoid(
+            |thread: &mut Thread, [ptr, seed_hi, seed_lo]: [u32; 3], _| {
+                let seed = (seed_hi as u64) << 32 | seed_lo as u64;
+                AnyObj
+                    .inspect(&thread.heap, ptr as usize, |obj| {
+                        obj.native_fields
+                            .push(Box::new(StdRng::seed_from_u64(seed)));
+                        obj.native_fields.push(Box::new(None::<f64>));
+                    })
+                    .map(Option::Some)
+            },
+        )),
+        ..Default::default()
+    };
     let next_int = RawMethod {
         access_flags: access!(public native),
         name: "nextInt".into(),
@@ -351,107 +696,79 @@ pub fn add_native_methods(method_area: &mut WorkingMethodArea, class_area: &mut
         "java/util/Random".into(),
         java_lang_object.clone(),
     );
-    random.register_methods([random_init, next_int], method_area);
-
-    let println_string = RawMethod {
-        access_flags: access!(public native),
-        name: "println".into(),
-        descriptor: method!(((Object(java_lang_string.clone()))) -> void),
-        code: RawCode::native(NativeVoid(native_println_object)),
-        ..Default::default()
-    };
-    let println_object = RawMethod {
-        access_flags: access!(public native),
-        name: "println".into(),
-        descriptor: method!(((Object(java_lang_object.clone()))) -> void),
-        code: RawCode::native(NativeVoid(native_println_object)),
-        ..Default::default()
-    };
-    let println_char = RawMethod {
-        access_flags: access!(public native),
-        name: "println".into(),
-        descriptor: method!((char) -> void),
-        code: RawCode::native(NativeVoid(|_: &mut _, [_, c]: [u32; 2], _| {
-            let char = char::from_u32(c).ok_or_else(|| String::from("Invalid Character code"))?;
-            println!("{char}");
-            Ok(Some(()))
-        })),
-        ..Default::default()
-    };
-    let println_bool = RawMethod {
+    let next_gaussian = RawMethod {
         access_flags: access!(public native),
-        name: "println".into(),
-        descriptor: method!((boolean) -> void),
-        code: RawCode::native(NativeVoid(|_: &mut _, [_, b]: [u32; 2], _| {
-            let bool = b != 0;
-            println!("{bool}");
-            Ok(Some(()))
-        })),
-        ..Default::default()
-    };
-    let println_int = RawMethod {
-        access_flags: access!(public native),
-        name: "println".into(),
-        descriptor: method!((int) -> void),
-        code: RawCode::native(NativeVoid(|_: &mut _, [_, i]: [u32; 2], _| {
-            let int = i as i32;
-            println!("{int}");
-            Ok(Some(()))
-        })),
-        ..Default::default()
-    };
-    let println_long = RawMethod {
-        access_flags: access!(public native),
-        name: "println".into(),
-        descriptor: method!((long) -> void),
-        code: RawCode::native(NativeVoid(|_: &mut _, [_, left, right]: [u32; 3], _| {
-            let long = ((left as u64) << 32 | (right as u64)) as i64;
-            println!("{long}");
-            Ok(Some(()))
-        })),
-        ..Default::default()
-    };
-    let println_float = RawMethod {
-        access_flags: access!(public native),
-        name: "println".into(),
-        descriptor: method!((float) -> void),
-        code: RawCode::native(NativeVoid(|_: &mut _, [_, f]: [u32; 2], _| {
-            let float = f32::from_bits(f);
-            println!("{float}");
-            Ok(Some(()))
-        })),
+        name: "nextGaussian".into(),
+        descriptor: method!(() -> double),
+        code: RawCode::native(NativeDoubleMethod(
+            |thread: &mut Thread, [obj_ref]: [u32; 1], _| {
+                if let Some(next) =
+                    RandomGaussian::inspect(&thread.heap, obj_ref as usize, Option::take)?
+                {
+                    return Ok(Some(next.to_bits()));
+                }
+                // the polar method makes two values at a time; keep the second for the next call
+                let (value, next) = Random::inspect(&thread.heap, obj_ref as usize, |rng| loop {
+                    let v1: f64 = rng.gen_range(-1.0..1.0);
+                    let v2: f64 = rng.gen_range(-1.0..1.0);
+                    let s = v1.mul_add(v1, v2 * v2);
+                    if s < 1.0 && s != 0.0 {
+                        let multiplier = (-2.0 * s.ln() / s).sqrt();
+                        break (v1 * multiplier, v2 * multiplier);
+                    }
+                })?;
+                RandomGaussian::inspect(&thread.heap, obj_ref as usize, |cached| {
+                    *cached = Some(next);
+                })?;
+                Ok(Some(value.to_bits()))
+            },
+        )),
         ..Default::default()
     };
-    let println_empty = RawMethod {
+    random.register_methods(
+        [random_init, random_init_seeded, next_int, next_gaussian],
+        method_area,
+    );
+
+    let input_stream_read = RawMethod {
         access_flags: access!(public native),
-        name: "println".into(),
-        descriptor: method!(() -> void),
-        code: RawCode::native(NativeVoid(|_: &mut _, [_]: [u32; 1], _| {
-            println!();
-            Ok(Some(()))
+        name: "read".into(),
+        descriptor: method!(() -> int),
+        code: RawCode::native(NativeSingleMethod(|_: &mut _, [_]: [u32; 1], _| {
+            let byte = input::read_byte().map_err(|err| err.to_string())?;
+            Ok(Some(byte.map_or(u32::MAX, u32::from)))
         })),
         ..Default::default()
     };
-    let mut printstream = RawClass::new(
+    let mut input_stream = RawClass::new(
         access!(public native),
-        "java/io/PrintStream".into(),
+        "java/io/InputStream".into(),
         java_lang_object.clone(),
     );
-    printstream.register_methods(
-        [
-            println_string,
-            println_object,
-            println_empty,
-            println_float,
-            println_int,
-            println_bool,
-            println_char,
-            println_long,
-        ],
-        method_area,
-    );
+    input_stream.register_method(input_stream_read, method_area);
 
-    // let system_out = heap.allocate(Object::from_class(class_area, &printstream));
+    let mut byte_array_input_stream = RawClass::new(
+        access!(public native),
+        "java/io/ByteArrayInputStream".into(),
+        input_stream.this.clone(),
+    );
+    let byte_stream_read = RawMethod {
+        access_flags: access!(public native),
+        name: "read".into(),
+        descriptor: method!(() -> int),
+        code: RawCode::native(NativeSingleMethod(native_property(
+            ByteStream::SELF,
+            |stream| {
+                let mut byte = [0];
+                match stream.read(&mut byte) {
+                    Ok(1) => u32::from(byte[0]),
+                    _ => u32::MAX,
+                }
+            },
+        ))),
+        ..Default::default()
+    };
+    byte_array_input_stream.register_method(byte_stream_read, method_area);
 
     let mut system = RawClass::new(
         access!(public native),
@@ -464,11 +781,17 @@ pub fn add_native_methods(method_area: &mut WorkingMethodArea, class_area: &mut
         descriptor: method!(() -> void),
         code: RawCode::native(NativeVoid(|thread: &mut Thread, []: [u32; 0], verbose| {
             let system_class = thread.class_area.search("java/lang/System").unwrap();
-            let out_ref = thread.heap.lock().unwrap().allocate(Object::from_class(
-                &thread.class_area.search("java/io/PrintStream").unwrap(),
-            ));
+            let out_ref = PrintTarget::Stdout.make(thread);
             system_class.static_data.lock().unwrap()[0] = out_ref;
             thread.rember(out_ref, verbose);
+            let in_ref = thread.heap.lock().unwrap().allocate(Object::from_class(
+                &thread.class_area.search("java/io/InputStream").unwrap(),
+            ));
+            system_class.static_data.lock().unwrap()[1] = in_ref;
+            thread.rember(in_ref, verbose);
+            let err_ref = PrintTarget::Stderr.make(thread);
+            system_class.static_data.lock().unwrap()[2] = err_ref;
+            thread.rember(err_ref, verbose);
             Ok(Some(()))
         })),
         ..Default::default()
@@ -487,6 +810,30 @@ pub fn add_native_methods(method_area: &mut WorkingMethodArea, class_area: &mut
         },
         0,
     ));
+    system.static_data.push(NULL);
+    system.statics.push((
+        Field {
+            access_flags: access!(public native),
+            name: "in".into(),
+            descriptor: FieldType::Object("java/io/InputStream".into()),
+            attributes: Vec::new(),
+            signature: None,
+            constant_value: None,
+        },
+        1,
+    ));
+    system.static_data.push(NULL);
+    system.statics.push((
+        Field {
+            access_flags: access!(public native),
+            name: "err".into(),
+            descriptor: FieldType::Object("java/io/PrintStream".into()),
+            attributes: Vec::new(),
+            signature: None,
+            constant_value: None,
+        },
+        2,
+    ));
 
     let arraycopy = RawMethod {
         access_flags: access!(public native),
@@ -531,29 +878,50 @@ pub fn add_native_methods(method_area: &mut WorkingMethodArea, class_area: &mut
         )),
         ..Default::default()
     };
-    system.register_method(arraycopy, method_area);
-
-    let sqrt_double = RawMethod {
+    let exit = RawMethod {
         access_flags: access!(public static native),
-        name: "sqrt".into(),
-        descriptor: method!((double) -> double),
-        code: RawCode::native(NativeDoubleMethod(
-            |_: &mut _, [left, right]: [u32; 2], _| {
-                Ok(Some(
-                    f64::from_bits((left as u64) << 32 | (right as u64))
-                        .sqrt()
-                        .to_bits(),
-                ))
-            },
-        )),
+        name: "exit".into(),
+        descriptor: method!((int) -> void),
+        code: RawCode::native(NativeVoid(|_: &mut _, [code]: [u32; 1], _| {
+            Err(error::Error::Exit(code as i32))
+        })),
         ..Default::default()
     };
-    let mut math = RawClass::new(
-        access!(public native),
-        "java/lang/Math".into(),
-        java_lang_object.clone(),
+    let current_time_millis = RawMethod {
+        access_flags: access!(public static native),
+        name: "currentTimeMillis".into(),
+        descriptor: method!(() -> long),
+        code: RawCode::native(NativeDoubleMethod(|_: &mut _, []: [u32; 0], _| {
+            let since_epoch = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_err(|err| err.to_string())?;
+            Ok(Some(since_epoch.as_millis() as u64))
+        })),
+        ..Default::default()
+    };
+    let nano_time = RawMethod {
+        access_flags: access!(public static native),
+        name: "nanoTime".into(),
+        descriptor: method!(() -> long),
+        code: RawCode::native(NativeDoubleMethod(|_: &mut _, []: [u32; 0], _| {
+            // like java, the origin is arbitrary; only differences between calls mean anything
+            static ORIGIN: OnceLock<Instant> = OnceLock::new();
+            Ok(Some(
+                ORIGIN.get_or_init(Instant::now).elapsed().as_nanos() as u64
+            ))
+        })),
+        ..Default::default()
+    };
+    system.register_methods(
+        [
+            arraycopy,
+            exit,
+            current_time_millis,
+            nano_time,
+            input::system_console(),
+        ],
+        method_area,
     );
-    math.register_method(sqrt_double, method_area);
 
     throwable::add_native_methods(
         &java_lang_object,
@@ -580,6 +948,27 @@ pub fn add_native_methods(method_area: &mut WorkingMethodArea, class_area: &mut
         &java_lang_string,
     );
     stream::add_native_methods(method_area, class_area, &java_lang_object);
+    collectors::add_native_methods(method_area, class_area, &java_lang_object);
+    record::add_native_methods(method_area, class_area, &java_lang_object);
+    math::add_native_methods(method_area, class_area, &java_lang_object);
+    print_stream::add_native_methods(
+        method_area,
+        class_area,
+        &java_lang_object,
+        &java_lang_string,
+    );
+    input::add_native_methods(
+        method_area,
+        class_area,
+        &java_lang_object,
+        &java_lang_string,
+    );
+    thread::add_native_methods(
+        method_area,
+        class_area,
+        &java_lang_object,
+        &java_lang_string,
+    );
 
     arrays.register_methods(array_methods, method_area);
     class_area.extend([
@@ -587,12 +976,13 @@ pub fn add_native_methods(method_area: &mut WorkingMethodArea, class_area: &mut
         enum_class,
         array,
         arrays,
+        objects_class,
         string,
         string_builder,
         random,
         system,
-        printstream,
-        math,
+        input_stream,
+        byte_array_input_stream,
     ]);
     drop((java_lang_object, java_lang_string));
 }
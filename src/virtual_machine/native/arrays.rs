@@ -11,6 +11,37 @@ use crate::{
     },
 };
 
+use super::{identity_hash_code, primitives::JavaFloat};
+
+/// the JVM descriptor of a field type, written the way `Class.getName` spells array classes
+fn binary_name(field_type: &FieldType) -> String {
+    match field_type {
+        FieldType::Byte => String::from("B"),
+        FieldType::Char => String::from("C"),
+        FieldType::Double => String::from("D"),
+        FieldType::Float => String::from("F"),
+        FieldType::Int => String::from("I"),
+        FieldType::Long => String::from("J"),
+        FieldType::Short => String::from("S"),
+        FieldType::Boolean => String::from("Z"),
+        FieldType::Object(class) => format!("L{};", class.replace('/', ".")),
+        FieldType::Array(inner) => format!("[{}", binary_name(inner)),
+    }
+}
+
+/// `Object.toString` for arrays, ie `[I@1b6d3586`
+pub fn object_to_string(
+    thread: &mut Thread,
+    [arr_ref]: [u32; 1],
+    _verbose: bool,
+) -> NativeReturn<Arc<str>> {
+    let field_type = ArrayType::SELF.inspect(&thread.heap, arr_ref as usize, |a| a.clone())?;
+    let hash = identity_hash_code(thread, arr_ref);
+    Ok(Some(
+        format!("[{}@{hash:x}", binary_name(&field_type)).into(),
+    ))
+}
+
 #[allow(clippy::only_used_in_recursion)]
 pub fn deep_to_string(
     thread: &mut Thread,
@@ -91,9 +122,14 @@ pub fn to_string(
                 arr_ref,
                 match field_type {
                     FieldType::Double => |arr: ArrayFields<'_, u64>| {
-                        format!("{:?}", unsafe {
-                            &*std::ptr::addr_of!(arr.contents).cast::<Vec<f64>>()
-                        })
+                        format!(
+                            "[{}]",
+                            arr.contents
+                                .iter()
+                                .map(|bits| JavaFloat(f64::from_bits(*bits)).to_string())
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        )
                         .into()
                     },
                     FieldType::Long => |arr: ArrayFields<'_, u64>| {
@@ -119,9 +155,14 @@ pub fn to_string(
                         .into()
                     },
                     FieldType::Float => |arr: ArrayFields<'_, u32>| {
-                        format!("{:?}", unsafe {
-                            &*std::ptr::addr_of!(arr.contents).cast::<Vec<f32>>()
-                        })
+                        format!(
+                            "[{}]",
+                            arr.contents
+                                .iter()
+                                .map(|bits| JavaFloat(f32::from_bits(*bits)).to_string())
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        )
                         .into()
                     },
                     _ => |arr: ArrayFields<'_, u32>| {
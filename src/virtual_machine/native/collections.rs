@@ -1,19 +1,27 @@
-use std::sync::Arc;
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+};
 
 use jvmrs_lib::{access, field, method};
+use rand::{seq::SliceRandom, Rng};
 
 use crate::{
     class::{
-        code::{native_property, NativeDoubleMethod, NativeSingleMethod, NativeVoid},
+        code::{
+            native_property, NativeDoubleMethod, NativeReturn, NativeSingleMethod,
+            NativeStringMethod, NativeVoid, Staged,
+        },
         Field,
     },
     class_loader::{RawClass, RawCode, RawMethod},
-    data::{WorkingClassArea, WorkingMethodArea, NULL},
+    data::{BuildNonHasher, WorkingClassArea, WorkingMethodArea, NULL},
     virtual_machine::{
-        native::function::Optional,
+        error,
+        native::function::{make_lambda_override, Optional},
         object::{
-            AnyObj, ArrayListObj, HashMapObj, HashSetObj, Object, ObjectFinder, StringBuilder,
-            StringObj,
+            AnyObj, ArrayListObj, HashMapObj, HashSetObj, LinkedListObj, Object, ObjectFinder,
+            Random, StringBuilder, StringObj,
         },
         Thread,
     },
@@ -32,6 +40,8 @@ pub fn add_native_collections(
         java_lang_object.clone(),
     );
 
+    hash_map.interfaces.push("java/util/Map".into());
+
     let hash_map_init = HashMapObj::default_init();
     let hash_map_size = RawMethod {
         access_flags: access!(public native),
@@ -39,40 +49,42 @@ pub fn add_native_collections(
         descriptor: method!(() -> int),
         code: RawCode::native(NativeSingleMethod(native_property(
             HashMapObj::SELF,
-            |map| map.len() as u32,
+            |map| map.values().map(Vec::len).sum::<usize>() as u32,
         ))),
         ..Default::default()
     };
     let hash_map_put = RawMethod {
         access_flags: access!(public native),
         name: "put".into(),
-        descriptor: method!(((Object(java_lang_object.clone())), (Object(java_lang_object.clone())))->void),
-        code: RawCode::native(NativeVoid(
-            |thread: &mut Thread, [this, key, value]: [u32; 3], verbose: bool| {
-                if thread.pc_register == 0 {
-                    // get the hash code for the object
-                    let (class, method) = AnyObj.inspect(&thread.heap, key as usize, |obj| {
-                        obj.resolve_method(
-                            &thread.method_area,
-                            &thread.class_area,
-                            "getHashCode",
-                            &method!(() -> int),
-                            verbose,
-                        )
+        descriptor: method!(((Object(java_lang_object.clone())), (Object(java_lang_object.clone()))) -> Object(java_lang_object.clone())),
+        code: RawCode::native(Staged(
+            4,
+            NativeSingleMethod(
+                |thread: &mut Thread, [this, key, value, hash, _]: [u32; 5], verbose| {
+                    let Some(found) = find_key(thread, key, 3, map_key(this), verbose)? else {
+                        return Ok(None);
+                    };
+                    // a key that's already present keeps its original key object, like Java
+                    let old = HashMapObj::inspect(&thread.heap, this as usize, |map| {
+                        let bucket = map.entry(hash).or_default();
+                        match found {
+                            Some(index) => Some(core::mem::replace(&mut bucket[index].1, value)),
+                            None => {
+                                bucket.push((key, value));
+                                None
+                            }
+                        }
                     })?;
-                    thread.stackframe.operand_stack.push(1);
-                    thread.invoke_method(method, class);
-                    thread.stackframe.locals[0] = key;
-                    Ok(None)
-                } else {
-                    let hash_code = thread.stackframe.operand_stack.pop().unwrap();
-                    // add the object to the hash map
-                    HashMapObj::inspect(&thread.heap, this as usize, |map| {
-                        map.insert(hash_code, value)
-                    })
-                    .map(|_| Some(()))
-                }
-            },
+                    thread.rember(value, verbose);
+                    let Some(old) = old else {
+                        thread.rember(key, verbose);
+                        return Ok(Some(NULL));
+                    };
+                    // the map's reference to the old value lasts until the caller has it
+                    thread.stackframe.garbage.push(old);
+                    Ok(Some(old))
+                },
+            ),
         )),
         ..Default::default()
     };
@@ -80,38 +92,118 @@ pub fn add_native_collections(
         access_flags: access!(public native),
         name: "get".into(),
         descriptor: method!(((Object(java_lang_object.clone()))) -> Object(java_lang_object.clone())),
+        code: RawCode::native(Staged(
+            4,
+            NativeSingleMethod(
+                |thread: &mut Thread, [this, key, hash, _]: [u32; 4], verbose| {
+                    hash_map_lookup(thread, this, key, hash, verbose)
+                        .map(|found| found.map(|found| found.unwrap_or(NULL)))
+                },
+            ),
+        )),
+        ..Default::default()
+    };
+    let hash_map_get_or_default = RawMethod {
+        access_flags: access!(public native),
+        name: "getOrDefault".into(),
+        descriptor: method!(((Object(java_lang_object.clone())), (Object(java_lang_object.clone()))) -> Object(java_lang_object.clone())),
+        code: RawCode::native(Staged(
+            4,
+            NativeSingleMethod(
+                |thread: &mut Thread, [this, key, default, hash, _]: [u32; 5], verbose| {
+                    let Some(found) = find_key(thread, key, 3, map_key(this), verbose)? else {
+                        return Ok(None);
+                    };
+                    let Some(index) = found else {
+                        return Ok(Some(default));
+                    };
+                    HashMapObj::inspect(&thread.heap, this as usize, |map| map[&hash][index].1)
+                        .map(Option::Some)
+                },
+            ),
+        )),
+        ..Default::default()
+    };
+    let hash_map_contains_key = RawMethod {
+        access_flags: access!(public native),
+        name: "containsKey".into(),
+        descriptor: method!(((Object(java_lang_object.clone()))) -> boolean),
+        code: RawCode::native(Staged(
+            4,
+            NativeSingleMethod(
+                |thread: &mut Thread, [this, key, _, _]: [u32; 4], verbose| {
+                    find_key(thread, key, 2, map_key(this), verbose)
+                        .map(|found| found.map(|found| u32::from(found.is_some())))
+                },
+            ),
+        )),
+        ..Default::default()
+    };
+    let hash_map_remove = RawMethod {
+        access_flags: access!(public native),
+        name: "remove".into(),
+        descriptor: method!(((Object(java_lang_object.clone()))) -> Object(java_lang_object.clone())),
+        code: RawCode::native(Staged(
+            4,
+            NativeSingleMethod(
+                |thread: &mut Thread, [this, key, hash, _]: [u32; 4], verbose| {
+                    let Some(found) = find_key(thread, key, 2, map_key(this), verbose)? else {
+                        return Ok(None);
+                    };
+                    let Some(index) = found else {
+                        return Ok(Some(NULL));
+                    };
+                    let (old_key, old_value) =
+                        HashMapObj::inspect(&thread.heap, this as usize, |map| {
+                            let bucket = map.get_mut(&hash).unwrap();
+                            let entry = bucket.remove(index);
+                            if bucket.is_empty() {
+                                map.remove(&hash);
+                            }
+                            entry
+                        })?;
+                    thread.forgor(old_key, verbose);
+                    // the map's reference to the value lasts until the caller has it
+                    thread.stackframe.garbage.push(old_value);
+                    Ok(Some(old_value))
+                },
+            ),
+        )),
+        ..Default::default()
+    };
+    let hash_map_key_set = RawMethod {
+        access_flags: access!(public native),
+        name: "keySet".into(),
+        descriptor: method!(() -> Object("java/util/Set".into())),
         code: RawCode::native(NativeSingleMethod(
-            |thread: &mut Thread, [this, key]: [u32; 2], verbose| {
-                if thread.pc_register == 0 {
-                    // get the hash code for the object
-                    let (class, method) = AnyObj.inspect(&thread.heap, key as usize, |obj| {
-                        obj.resolve_method(
-                            &thread.method_area,
-                            &thread.class_area,
-                            "getHashCode",
-                            &method!(() -> int),
-                            verbose,
-                        )
-                    })?;
-                    thread.stackframe.operand_stack.push(1);
-                    thread.invoke_method(method, class);
-                    thread.stackframe.locals[0] = key;
-                    Ok(None)
-                } else {
-                    let hash_code = thread.stackframe.operand_stack.pop().unwrap();
-                    // add the object to the hash map
-                    HashMapObj::inspect(&thread.heap, this as usize, |map| {
-                        map.get(&hash_code).copied()
-                    })
-                    .map(|opt| opt.unwrap_or(NULL))
-                    .map(Option::Some)
+            |thread: &mut Thread, [this]: [u32; 1], verbose| {
+                let keys = HashMapObj::inspect(&thread.heap, this as usize, |map| {
+                    map.iter()
+                        .map(|(&hash, bucket)| (hash, bucket.iter().map(|&(key, _)| key).collect()))
+                        .collect::<HashMap<u32, Vec<u32>, BuildNonHasher>>()
+                })?;
+                for &key in keys.values().flatten() {
+                    thread.rember(key, verbose);
                 }
+                let mut key_set =
+                    Object::from_class(&thread.class_area.search("java/util/HashSet").unwrap());
+                key_set.native_fields.push(Box::new(keys));
+                Ok(Some(thread.heap.lock().unwrap().allocate(key_set)))
             },
         )),
         ..Default::default()
     };
     hash_map.register_methods(
-        [hash_map_init, hash_map_put, hash_map_get, hash_map_size],
+        [
+            hash_map_init,
+            hash_map_put,
+            hash_map_get,
+            hash_map_get_or_default,
+            hash_map_contains_key,
+            hash_map_remove,
+            hash_map_key_set,
+            hash_map_size,
+        ],
         method_area,
     );
 
@@ -120,6 +212,7 @@ pub fn add_native_collections(
         "java/util/HashSet".into(),
         java_lang_object.clone(),
     );
+    hash_set.interfaces.push("java/util/Set".into());
 
     let hash_set_init = HashSetObj::default_init();
     let hash_set_size = RawMethod {
@@ -128,40 +221,30 @@ pub fn add_native_collections(
         descriptor: method!(() -> int),
         code: RawCode::native(NativeSingleMethod(native_property(
             HashSetObj::SELF,
-            |set| set.len() as u32,
+            |set| set.values().map(Vec::len).sum::<usize>() as u32,
         ))),
         ..Default::default()
     };
-    let hash_set_insert = RawMethod {
+    let hash_set_add = RawMethod {
         access_flags: access!(public native),
-        name: "insert".into(),
-        descriptor: method!(((Object(java_lang_object.clone())))->void),
-        code: RawCode::native(NativeVoid(
-            |thread: &mut Thread, [this, key]: [u32; 2], verbose: bool| {
-                if thread.pc_register == 0 {
-                    // get the hash code for the object
-                    let (class, method) = AnyObj.inspect(&thread.heap, key as usize, |obj| {
-                        obj.resolve_method(
-                            &thread.method_area,
-                            &thread.class_area,
-                            "getHashCode",
-                            &method!(() -> int),
-                            verbose,
-                        )
-                    })?;
-                    thread.stackframe.operand_stack.push(1);
-                    thread.invoke_method(method, class);
-                    thread.stackframe.locals[0] = key;
-                    Ok(None)
-                } else {
-                    let hash_code = thread.stackframe.operand_stack.pop().unwrap();
-                    // add the object to the hash map
-                    HashSetObj::inspect(&thread.heap, this as usize, |set| {
-                        set.insert(hash_code);
-                    })
-                    .map(Option::Some)
-                }
-            },
+        name: "add".into(),
+        descriptor: method!(((Object(java_lang_object.clone()))) -> boolean),
+        code: RawCode::native(Staged(
+            4,
+            NativeSingleMethod(
+                |thread: &mut Thread, [this, key, hash, _]: [u32; 4], verbose| {
+                    let Some(found) = find_key(thread, key, 2, set_key(this), verbose)? else {
+                        return Ok(None);
+                    };
+                    if found.is_none() {
+                        HashSetObj::inspect(&thread.heap, this as usize, |set| {
+                            set.entry(hash).or_default().push(key);
+                        })?;
+                        thread.rember(key, verbose);
+                    }
+                    Ok(Some(u32::from(found.is_none())))
+                },
+            ),
         )),
         ..Default::default()
     };
@@ -169,32 +252,14 @@ pub fn add_native_collections(
         access_flags: access!(public native),
         name: "contains".into(),
         descriptor: method!(((Object(java_lang_object.clone()))) -> boolean),
-        code: RawCode::native(NativeSingleMethod(
-            |thread: &mut Thread, [this, key]: [u32; 2], verbose| {
-                if thread.pc_register == 0 {
-                    // get the hash code for the object
-                    let (class, method) = AnyObj.inspect(&thread.heap, key as usize, |obj| {
-                        obj.resolve_method(
-                            &thread.method_area,
-                            &thread.class_area,
-                            "getHashCode",
-                            &method!(() -> int),
-                            verbose,
-                        )
-                    })?;
-                    thread.stackframe.operand_stack.push(1);
-                    thread.invoke_method(method, class);
-                    thread.stackframe.locals[0] = key;
-                    Ok(None)
-                } else {
-                    let hash_code = thread.stackframe.operand_stack.pop().unwrap();
-                    // check if the set contains the element
-                    HashSetObj::inspect(&thread.heap, this as usize, |map| {
-                        u32::from(map.contains(&hash_code))
-                    })
-                    .map(Option::Some)
-                }
-            },
+        code: RawCode::native(Staged(
+            4,
+            NativeSingleMethod(
+                |thread: &mut Thread, [this, key, _, _]: [u32; 4], verbose| {
+                    find_key(thread, key, 2, set_key(this), verbose)
+                        .map(|found| found.map(|found| u32::from(found.is_some())))
+                },
+            ),
         )),
         ..Default::default()
     };
@@ -203,7 +268,7 @@ pub fn add_native_collections(
         [
             hash_set_init,
             hash_set_contains,
-            hash_set_insert,
+            hash_set_add,
             hash_set_size,
         ],
         method_area,
@@ -214,17 +279,20 @@ pub fn add_native_collections(
         "java/util/ArrayList".into(),
         java_lang_object.clone(),
     );
+    array_list.interfaces.push("java/util/List".into());
+    array_list.interfaces.push("java/util/RandomAccess".into());
     let arrlist_init = ArrayListObj::default_init();
     let arrlist_append = RawMethod {
         access_flags: access!(public native),
         name: "append".into(),
         descriptor: method!(((Object(java_lang_object.clone()))) -> void),
         code: RawCode::native(NativeVoid(
-            |thread: &mut Thread, [this, ptr]: [u32; 2], _| {
+            |thread: &mut Thread, [this, ptr]: [u32; 2], verbose| {
                 ArrayListObj::inspect(&thread.heap, this as usize, |arrlist| {
                     arrlist.push(ptr);
-                })
-                .map(Option::Some)
+                })?;
+                thread.rember(ptr, verbose);
+                Ok(Some(()))
             },
         )),
         ..Default::default()
@@ -234,11 +302,12 @@ pub fn add_native_collections(
         name: "add".into(),
         descriptor: method!(((Object(java_lang_object.clone()))) -> boolean),
         code: RawCode::native(NativeSingleMethod(
-            |thread: &mut Thread, [this, ptr]: [u32; 2], _| {
+            |thread: &mut Thread, [this, ptr]: [u32; 2], verbose| {
                 ArrayListObj::inspect(&thread.heap, this as usize, |arrlist| {
                     arrlist.push(ptr);
-                })
-                .map(|()| Some(1))
+                })?;
+                thread.rember(ptr, verbose);
+                Ok(Some(1))
             },
         )),
         ..Default::default()
@@ -257,37 +326,41 @@ pub fn add_native_collections(
         access_flags: access!(public native),
         name: "sort".into(),
         descriptor: method!(((Object("java/util/Comparator".into()))) -> void),
-        code: RawCode::native(NativeVoid(
-            |thread: &mut Thread,
-             [this, cmp, partition, length, target_ptr, index]: [u32; 6],
-             verbose: bool| {
-                if verbose {
-                    println!("Partition: {partition}, Length: {length}, Index: {index}");
-                }
-                let pc = thread.pc_register;
-                thread.pc_register += 1;
-                match pc {
-                    0 => {
-                        thread.stackframe.locals[2] = 1;
-                        let length =
-                            ArrayListObj::inspect(&thread.heap, this as usize, |v| v.len())? as u32;
-                        thread.stackframe.locals[3] = length;
-                        Ok(None)
+        code: RawCode::native(Staged(
+            5,
+            NativeVoid(
+                |thread: &mut Thread,
+                 [this, cmp, partition, length, target_ptr, index]: [u32; 6],
+                 verbose: bool| {
+                    if verbose {
+                        println!("Partition: {partition}, Length: {length}, Index: {index}");
                     }
-                    1 => {
-                        let target_ptr =
-                            ArrayListObj::inspect(&thread.heap, this as usize, |vec| {
-                                vec.get(partition as usize).copied().unwrap()
-                            })?;
-                        thread.stackframe.locals[4] = target_ptr;
-                        thread.stackframe.locals[5] = partition;
-                        Ok(None)
-                    }
-                    2 => {
-                        let next_ptr = ArrayListObj::inspect(&thread.heap, this as usize, |vec| {
-                            vec.get(index as usize - 1).copied().unwrap()
-                        })?;
-                        let (resolved_class, resolved_method) =
+                    let pc = thread.pc_register;
+                    thread.pc_register += 1;
+                    match pc {
+                        0 => {
+                            thread.stackframe.locals[2] = 1;
+                            let length =
+                                ArrayListObj::inspect(&thread.heap, this as usize, |v| v.len())?
+                                    as u32;
+                            thread.stackframe.locals[3] = length;
+                            Ok(None)
+                        }
+                        1 => {
+                            let target_ptr =
+                                ArrayListObj::inspect(&thread.heap, this as usize, |vec| {
+                                    vec.get(partition as usize).copied().unwrap()
+                                })?;
+                            thread.stackframe.locals[4] = target_ptr;
+                            thread.stackframe.locals[5] = partition;
+                            Ok(None)
+                        }
+                        2 => {
+                            let next_ptr =
+                                ArrayListObj::inspect(&thread.heap, this as usize, |vec| {
+                                    vec.get(index as usize - 1).copied().unwrap()
+                                })?;
+                            let (resolved_class, resolved_method) =
                             AnyObj.inspect(&thread.heap, cmp as usize, |obj| {
                                 obj.resolve_method(
                                     &thread.method_area,
@@ -296,134 +369,72 @@ pub fn add_native_collections(
                                     &method!(((Object("java/lang/Object".into())), (Object("java/lang/Object".into()))) -> int),
                                     verbose,
                                 )
-                            })?;
-                        thread.stackframe.operand_stack.push(3);
-                        thread.invoke_method(resolved_method, resolved_class);
-                        thread.stackframe.locals[0] = cmp;
-                        thread.stackframe.locals[1] = target_ptr;
-                        thread.stackframe.locals[2] = next_ptr;
-                        Ok(None)
-                    }
-                    3 => {
-                        let cmp = thread.stackframe.operand_stack.pop().unwrap() as i32;
-                        if cmp >= 0 {
-                            thread.pc_register = 4;
-                        } else {
-                            // shift the value
-                            ArrayListObj::inspect(&thread.heap, this as usize, |vec| {
-                                vec[index as usize] = vec[index as usize - 1];
-                            })?;
-                            // start the next loop if it's not over
-                            if partition > 0 {
-                                // start the next loop
+                            })??;
+                            thread.stackframe.operand_stack.push(3);
+                            thread.invoke_method(resolved_method, resolved_class);
+                            thread.stackframe.locals[0] = cmp;
+                            thread.stackframe.locals[1] = target_ptr;
+                            thread.stackframe.locals[2] = next_ptr;
+                            Ok(None)
+                        }
+                        3 => {
+                            let cmp = thread.stackframe.operand_stack.pop().unwrap() as i32;
+                            if cmp >= 0 {
                                 thread.pc_register = 4;
-                                thread.stackframe.locals[5] -= 1;
+                            } else {
+                                // shift the value
+                                ArrayListObj::inspect(&thread.heap, this as usize, |vec| {
+                                    vec[index as usize] = vec[index as usize - 1];
+                                })?;
+                                // start the next loop if it's not over
+                                if partition > 0 {
+                                    // start the next loop
+                                    thread.pc_register = 4;
+                                    thread.stackframe.locals[5] -= 1;
+                                }
                             }
-                        }
-                        Ok(None)
-                    }
-                    4 => {
-                        // exit the loop and simulate the end
-                        ArrayListObj::inspect(&thread.heap, this as usize, |vec| {
-                            vec[index as usize] = target_ptr;
-                        })?;
-                        // start the next outer loop or exit the function
-                        if partition + 1 >= length {
-                            // exit the function
-                            Ok(Some(()))
-                        } else {
-                            // start the next outer loop
-                            thread.pc_register = 1;
-                            // increment partition
-                            thread.stackframe.locals[2] += 1;
                             Ok(None)
                         }
+                        4 => {
+                            // exit the loop and simulate the end
+                            ArrayListObj::inspect(&thread.heap, this as usize, |vec| {
+                                vec[index as usize] = target_ptr;
+                            })?;
+                            // start the next outer loop or exit the function
+                            if partition + 1 >= length {
+                                // exit the function
+                                Ok(Some(()))
+                            } else {
+                                // start the next outer loop
+                                thread.pc_register = 1;
+                                // increment partition
+                                thread.stackframe.locals[2] += 1;
+                                Ok(None)
+                            }
+                        }
+                        _ => Err("Impossible pc reached".to_string().into()),
                     }
-                    _ => Err("Impossible pc reached".to_string().into()),
-                }
-                /*
-                def insertion_sort_wo_swap(a_list):
-                    # 0
-                    for partition in range(1, len(a_list)):
-                        # 1
-                        target = a_list[partition]
-                        for index in range(partition, -1, -1):
-                            # 2
-                            if target >= a_list[index - 1]:
-                            # 3
-                                break
-                            a_list[index] = a_list[index - 1]
-                        # 4
-                        a_list[index] = target
-                */
-            },
+                    /*
+                    def insertion_sort_wo_swap(a_list):
+                        # 0
+                        for partition in range(1, len(a_list)):
+                            # 1
+                            target = a_list[partition]
+                            for index in range(partition, -1, -1):
+                                # 2
+                                if target >= a_list[index - 1]:
+                                # 3
+                                    break
+                                a_list[index] = a_list[index - 1]
+                            # 4
+                            a_list[index] = target
+                    */
+                },
+            ),
         )),
         ..Default::default()
     };
-    let java_lang_string = java_lang_string.clone();
-    let arrlist_to_string = RawMethod::to_string(
-        move |thread: &mut Thread, [this, builder, index, length]: [u32; 4], verbose: bool| {
-            let pc = thread.pc_register;
-            thread.pc_register += 1;
-            match pc {
-                0 => {
-                    let builder = StringBuilder::new("[".to_string(), &thread.class_area);
-                    let builder_ref = thread.heap.lock().unwrap().allocate(builder);
-                    thread.rember_temp(builder_ref, verbose);
-                    let length =
-                        ArrayListObj::inspect(&thread.heap, this as usize, |vec| vec.len() as u32)?;
-                    thread.stackframe.locals[1] = builder_ref;
-                    thread.stackframe.locals[2] = 0;
-                    thread.stackframe.locals[3] = length;
-                    Ok(None)
-                }
-                1 => {
-                    let next_obj = ArrayListObj::inspect(&thread.heap, this as usize, |vec| {
-                        vec[index as usize]
-                    })?;
-                    let (resolved_class, resolved_method) =
-                        AnyObj.inspect(&thread.heap, next_obj as usize, |obj| {
-                            obj.resolve_method(
-                                &thread.method_area,
-                                &thread.class_area,
-                                "toString",
-                                &method!(() -> Object(java_lang_string.clone())),
-                                verbose,
-                            )
-                        })?;
-                    thread.stackframe.operand_stack.push(2);
-                    thread.invoke_method(resolved_method, resolved_class);
-                    thread.stackframe.locals[0] = next_obj;
-                    Ok(None)
-                }
-                2 => {
-                    let str_ptr = thread.stackframe.operand_stack.pop().unwrap();
-                    let string =
-                        StringObj::inspect(&thread.heap, str_ptr as usize, |arc| arc.clone())?;
-                    StringBuilder::inspect(&thread.heap, builder as usize, |str| {
-                        if index == 0 {
-                            str.push_str(&string);
-                        } else {
-                            str.push_str(&format!(", {string}"));
-                        }
-                    })?;
-                    thread.stackframe.locals[2] += 1;
-                    if thread.stackframe.locals[2] >= length {
-                        let str =
-                            StringBuilder::inspect(&thread.heap, builder as usize, |builder| {
-                                builder.push(']');
-                                Arc::<str>::from(&**builder)
-                            })?;
-                        Ok(Some(str))
-                    } else {
-                        thread.pc_register = 1;
-                        Ok(None)
-                    }
-                }
-                _ => Err("Impossible PC reached".to_string().into()),
-            }
-        },
-    );
+    let arrlist_to_string = list_to_string(java_lang_string.clone());
 
     let mut array_stream = RawClass::new(
         access!(public native),
@@ -511,7 +522,7 @@ pub fn add_native_collections(
         access_flags: access!(public native),
         descriptor: method!(() -> Object("java/util/stream/Stream".into())),
         code: RawCode::native(NativeSingleMethod(
-            |thread: &mut Thread, [this]: [u32; 1], _verbose| {
+            |thread: &mut Thread, [this]: [u32; 1], verbose| {
                 let array_stream = thread
                     .class_area
                     .search("java/util/ArrayList$Stream")
@@ -521,6 +532,7 @@ pub fn add_native_collections(
                 stream.fields[2] = ArrayListObj::inspect(&thread.heap, this as usize, |arrls| {
                     arrls.len() as u32 - 1
                 })?;
+                thread.rember(this, verbose);
                 let stream_idx = thread.heap.lock().unwrap().allocate(stream);
                 Ok(Some(stream_idx))
             },
@@ -536,24 +548,1066 @@ pub fn add_native_collections(
             arrlist_sort,
             arrlist_to_string,
             arrlist_stream,
-        ],
+        ]
+        .into_iter()
+        .chain(arrlist_methods(java_lang_object)),
         method_area,
     );
 
+    let mut linked_list = RawClass::new(
+        access!(public native),
+        "java/util/LinkedList".into(),
+        java_lang_object.clone(),
+    );
+    linked_list.interfaces.push("java/util/List".into());
+    linked_list.interfaces.push("java/util/Deque".into());
+    linked_list.register_methods(linked_list_methods(java_lang_object), method_area);
+    linked_list.register_method(list_to_string(java_lang_string.clone()), method_area);
+
+    let linked_list_iterator = list_iterator(&mut linked_list, java_lang_object, method_area);
+
     let mut comparator = RawClass::new(
         access!(public abstract native),
         "java/util/Comparator".into(),
         java_lang_object.clone(),
     );
+    comparator.register_methods(comparator_methods(java_lang_object), method_area);
+
+    let mut collections = RawClass::new(
+        access!(public native),
+        "java/util/Collections".into(),
+        java_lang_object.clone(),
+    );
+    let shuffle = RawMethod {
+        name: "shuffle".into(),
+        access_flags: access!(public static native),
+        descriptor: method!(((Object("java/util/List".into()))) -> void),
+        code: RawCode::native(NativeVoid(
+            |thread: &mut Thread, [list]: [u32; 1], _verbose| {
+                shuffle_list(thread, list, &mut rand::thread_rng()).map(Option::Some)
+            },
+        )),
+        ..Default::default()
+    };
+    let shuffle_random = RawMethod {
+        name: "shuffle".into(),
+        access_flags: access!(public static native),
+        descriptor: method!(((Object("java/util/List".into())), (Object("java/util/Random".into()))) -> void),
+        code: RawCode::native(NativeVoid(
+            |thread: &mut Thread, [list, random]: [u32; 2], _verbose| {
+                Random::inspect(&thread.heap, random as usize, |rng| {
+                    shuffle_list(thread, list, rng)
+                })?
+                .map(Option::Some)
+            },
+        )),
+        ..Default::default()
+    };
+    let frequency = RawMethod {
+        name: "frequency".into(),
+        access_flags: access!(public static native),
+        descriptor: method!(((Object("java/util/Collection".into())), (Object(java_lang_object.clone()))) -> int),
+        code: RawCode::native(Staged(
+            3,
+            NativeSingleMethod(
+                |thread: &mut Thread, [list, obj, index, count]: [u32; 4], verbose| {
+                    match thread.pc_register {
+                        0 => {
+                            thread.stackframe.locals[2] = 0;
+                            thread.stackframe.locals[3] = 0;
+                            thread.pc_register = 1;
+                            Ok(None)
+                        }
+                        1 => {
+                            let Some(element) = list_element(thread, list, index as usize)? else {
+                                return Ok(Some(count));
+                            };
+                            if obj == NULL {
+                                // null only matches null
+                                if element == NULL {
+                                    thread.stackframe.locals[3] += 1;
+                                }
+                                thread.stackframe.locals[2] += 1;
+                                return Ok(None);
+                            }
+                            // push a fake return address
+                            thread.stackframe.operand_stack.push(2);
+                            thread.resolve_and_invoke(
+                                obj,
+                                "equals",
+                                &method!(((Object("java/lang/Object".into()))) -> boolean),
+                                verbose,
+                            )?;
+                            thread.stackframe.locals[0] = obj;
+                            thread.stackframe.locals[1] = element;
+                            Ok(None)
+                        }
+                        2 => {
+                            if thread.stackframe.operand_stack.pop().unwrap() != 0 {
+                                thread.stackframe.locals[3] += 1;
+                            }
+                            thread.stackframe.locals[2] += 1;
+                            thread.pc_register = 1;
+                            Ok(None)
+                        }
+                        _ => Err("Impossible pc reached".to_string().into()),
+                    }
+                },
+            ),
+        )),
+        ..Default::default()
+    };
+    collections.register_methods([shuffle, shuffle_random, frequency], method_area);
+
+    class_area.extend([
+        hash_map,
+        hash_set,
+        array_list,
+        linked_list,
+        linked_list_iterator,
+        comparator,
+        array_stream,
+        collections,
+    ]);
+    let interfaces: [(&str, &[&str]); 9] = [
+        ("java/lang/Iterable", &[]),
+        ("java/util/Collection", &["java/lang/Iterable"]),
+        ("java/util/List", &["java/util/Collection"]),
+        ("java/util/Queue", &["java/util/Collection"]),
+        ("java/util/Deque", &["java/util/Queue"]),
+        ("java/util/Set", &["java/util/Collection"]),
+        ("java/util/Map", &[]),
+        ("java/util/RandomAccess", &[]),
+        ("java/util/Iterator", &[]),
+    ];
+    class_area.extend(
+        interfaces.map(|(name, interfaces)| marker_interface(name, interfaces, java_lang_object)),
+    );
+}
+
+/// `toString` for `ArrayList` and `LinkedList`. Locals are `[this, builder, index, length]`; stage 1
+/// calls `toString` on the element at `index` and stage 2 appends the result
+fn list_to_string(java_lang_string: Arc<str>) -> RawMethod {
+    let to_string_descriptor = method!(() -> Object(java_lang_string.clone()));
+    RawMethod {
+        access_flags: access!(public native),
+        name: "toString".into(),
+        descriptor: to_string_descriptor.clone(),
+        code: RawCode::native(Staged(
+            3,
+            NativeStringMethod(
+                move |thread: &mut Thread,
+                      [this, builder, index, length]: [u32; 4],
+                      verbose: bool| {
+                    let pc = thread.pc_register;
+                    thread.pc_register += 1;
+                    match pc {
+                        0 => {
+                            let length = list_len(thread, this)? as u32;
+                            if length == 0 {
+                                return Ok(Some("[]".into()));
+                            }
+                            let builder = StringBuilder::new("[".to_string(), &thread.class_area);
+                            let builder_ref = thread.heap.lock().unwrap().allocate(builder);
+                            thread.rember_temp(builder_ref, verbose);
+                            thread.stackframe.locals[1] = builder_ref;
+                            thread.stackframe.locals[2] = 0;
+                            thread.stackframe.locals[3] = length;
+                            Ok(None)
+                        }
+                        1 => {
+                            let next_obj =
+                                list_element(thread, this, index as usize)?.unwrap_or(NULL);
+                            if next_obj == NULL {
+                                // there's no `toString` to call, so hand stage 2 the string directly
+                                let null_str =
+                                    thread.heap.lock().unwrap().allocate_str("null".into());
+                                thread.rember_temp(null_str, verbose);
+                                thread.stackframe.operand_stack.push(null_str);
+                                return Ok(None);
+                            }
+                            let (resolved_class, resolved_method) =
+                                AnyObj.inspect(&thread.heap, next_obj as usize, |obj| {
+                                    obj.resolve_method(
+                                        &thread.method_area,
+                                        &thread.class_area,
+                                        "toString",
+                                        &to_string_descriptor,
+                                        verbose,
+                                    )
+                                })??;
+                            thread.stackframe.operand_stack.push(2);
+                            thread.invoke_method(resolved_method, resolved_class);
+                            thread.stackframe.locals[0] = next_obj;
+                            Ok(None)
+                        }
+                        2 => {
+                            let str_ptr = thread.stackframe.operand_stack.pop().unwrap();
+                            let string =
+                                StringObj::inspect(&thread.heap, str_ptr as usize, |arc| {
+                                    arc.clone()
+                                })?;
+                            StringBuilder::inspect(&thread.heap, builder as usize, |str| {
+                                if index == 0 {
+                                    str.push_str(&string);
+                                } else {
+                                    str.push_str(&format!(", {string}"));
+                                }
+                            })?;
+                            thread.stackframe.locals[2] += 1;
+                            if thread.stackframe.locals[2] >= length {
+                                let str = StringBuilder::inspect(
+                                    &thread.heap,
+                                    builder as usize,
+                                    |builder| {
+                                        builder.push(']');
+                                        Arc::<str>::from(&**builder)
+                                    },
+                                )?;
+                                Ok(Some(str))
+                            } else {
+                                thread.pc_register = 1;
+                                Ok(None)
+                            }
+                        }
+                        _ => Err("Impossible PC reached".to_string().into()),
+                    }
+                },
+            ),
+        )),
+        ..Default::default()
+    }
+}
+
+/// the length of a native `ArrayList` or `LinkedList`
+fn list_len(thread: &Thread, list: u32) -> error::Result<usize> {
+    AnyObj.inspect(&thread.heap, list as usize, |obj| {
+        ArrayListObj::SELF
+            .extract(obj, |vec| vec.len())
+            .or_else(|_| LinkedListObj::SELF.extract(obj, |list| list.len()))
+    })?
+}
+
+/// the element at `index` of a native `ArrayList` or `LinkedList`, if it's in bounds
+fn list_element(thread: &Thread, list: u32, index: usize) -> error::Result<Option<u32>> {
+    AnyObj.inspect(&thread.heap, list as usize, |obj| {
+        ArrayListObj::SELF
+            .extract(obj, |vec| vec.get(index).copied())
+            .or_else(|_| LinkedListObj::SELF.extract(obj, |list| list.get(index).copied()))
+    })?
+}
+
+/// shuffle a native `ArrayList` or `LinkedList` in place
+fn shuffle_list(thread: &Thread, list: u32, rng: &mut impl Rng) -> error::Result<()> {
+    AnyObj.inspect(&thread.heap, list as usize, |obj| {
+        ArrayListObj::SELF
+            .extract(obj, |vec| vec.shuffle(rng))
+            .or_else(|_| {
+                LinkedListObj::SELF.extract(obj, |list| list.make_contiguous().shuffle(rng))
+            })
+    })?
+}
+
+/// an interface without any methods of its own. It's only here so casts and `instanceof` can see
+/// which interfaces the collections implement, including the ones they inherit
+fn marker_interface(name: &str, interfaces: &[&str], java_lang_object: &Arc<str>) -> RawClass {
+    let mut interface = RawClass::new(
+        access!(public abstract native),
+        name.into(),
+        java_lang_object.clone(),
+    );
+    interface
+        .interfaces
+        .extend(interfaces.iter().map(|&i| Arc::from(i)));
+    interface
+}
+
+/// Find `key` in a hash collection. Stage 0 calls `key.hashCode()`, stage 1 saves the hash to
+/// `locals[slot]`, and stages 2 and 3 walk that hash's bucket, using `locals[slot + 1]` as the index
+/// and calling `key.equals` on each entry until one matches. `bucket_key` gets the key at an index
+/// of a bucket, if there is one. Returns the index of the matching entry, or `None` if the bucket
+/// doesn't hold the key
+fn find_key(
+    thread: &mut Thread,
+    key: u32,
+    slot: usize,
+    bucket_key: impl Fn(&Thread, u32, usize) -> error::Result<Option<u32>>,
+    verbose: bool,
+) -> NativeReturn<Option<usize>> {
+    match thread.pc_register {
+        0 => {
+            if key == NULL {
+                // null hashes to 0 without a call, like Java's `HashMap`
+                thread.stackframe.operand_stack.push(0);
+                thread.pc_register = 1;
+                return Ok(None);
+            }
+            thread.stackframe.operand_stack.push(1);
+            thread.resolve_and_invoke(key, "hashCode", &method!(() -> int), verbose)?;
+            thread.stackframe.locals[0] = key;
+            Ok(None)
+        }
+        1 => {
+            thread.stackframe.locals[slot] = thread.stackframe.operand_stack.pop().unwrap();
+            thread.stackframe.locals[slot + 1] = 0;
+            thread.pc_register = 2;
+            Ok(None)
+        }
+        2 => {
+            let hash = thread.stackframe.locals[slot];
+            let index = thread.stackframe.locals[slot + 1] as usize;
+            let Some(other) = bucket_key(thread, hash, index)? else {
+                return Ok(Some(None));
+            };
+            if other == key {
+                return Ok(Some(Some(index)));
+            }
+            if key == NULL {
+                // null only matches null
+                thread.stackframe.locals[slot + 1] += 1;
+                return Ok(None);
+            }
+            // push a fake return address
+            thread.stackframe.operand_stack.push(3);
+            thread.resolve_and_invoke(
+                key,
+                "equals",
+                &method!(((Object("java/lang/Object".into()))) -> boolean),
+                verbose,
+            )?;
+            thread.stackframe.locals[0] = key;
+            thread.stackframe.locals[1] = other;
+            Ok(None)
+        }
+        3 => {
+            let index = thread.stackframe.locals[slot + 1];
+            if thread.stackframe.operand_stack.pop().unwrap() != 0 {
+                return Ok(Some(Some(index as usize)));
+            }
+            thread.stackframe.locals[slot + 1] = index + 1;
+            thread.pc_register = 2;
+            Ok(None)
+        }
+        _ => Err("Impossible pc reached".to_string().into()),
+    }
+}
+
+/// look up keys in the buckets of the map at `this`, for `find_key`
+fn map_key(this: u32) -> impl Fn(&Thread, u32, usize) -> error::Result<Option<u32>> {
+    move |thread, hash, index| {
+        HashMapObj::inspect(&thread.heap, this as usize, |map| {
+            map.get(&hash)
+                .and_then(|bucket| bucket.get(index))
+                .map(|&(key, _)| key)
+        })
+    }
+}
+
+/// look up elements in the buckets of the set at `this`, for `find_key`
+fn set_key(this: u32) -> impl Fn(&Thread, u32, usize) -> error::Result<Option<u32>> {
+    move |thread, hash, index| {
+        HashSetObj::inspect(&thread.heap, this as usize, |set| {
+            set.get(&hash).and_then(|bucket| bucket.get(index)).copied()
+        })
+    }
+}
+
+/// the value that the map at `this` holds for `key`. Locals from `hash` on are used by `find_key`
+fn hash_map_lookup(
+    thread: &mut Thread,
+    this: u32,
+    key: u32,
+    hash: u32,
+    verbose: bool,
+) -> NativeReturn<Option<u32>> {
+    let Some(found) = find_key(thread, key, 2, map_key(this), verbose)? else {
+        return Ok(None);
+    };
+    let Some(index) = found else {
+        return Ok(Some(None));
+    };
+    HashMapObj::inspect(&thread.heap, this as usize, |map| Some(map[&hash][index].1))
+        .map(Option::Some)
+}
+
+/// check that `index` is in bounds for the list at `this`, throwing an `IndexOutOfBoundsException`
+/// if it isn't
+fn list_check_index(
+    thread: &mut Thread,
+    this: u32,
+    index: u32,
+    verbose: bool,
+) -> NativeReturn<usize> {
+    let length = list_len(thread, this)?;
+    if (index as usize) < length {
+        return Ok(Some(index as usize));
+    }
+    thread.throw_new(
+        "java/lang/IndexOutOfBoundsException",
+        &format!("Index {} out of bounds for length {length}", index as i32),
+        verbose,
+    )?;
+    Ok(None)
+}
+
+/// `ArrayList.indexOf(Object)`. Locals are `[this, target, index]`; stage 1 checks the element at
+/// `index` and stage 2 receives the result of `target.equals(element)`
+fn arrlist_index_of(
+    thread: &mut Thread,
+    [this, target, index]: [u32; 3],
+    verbose: bool,
+) -> NativeReturn<u32> {
+    match thread.pc_register {
+        0 => {
+            thread.stackframe.locals[2] = 0;
+            thread.pc_register = 1;
+            Ok(None)
+        }
+        1 => {
+            let Some(element) = ArrayListObj::inspect(&thread.heap, this as usize, |vec| {
+                vec.get(index as usize).copied()
+            })?
+            else {
+                return Ok(Some(-1i32 as u32));
+            };
+            if target == NULL {
+                // null only matches null
+                if element == NULL {
+                    return Ok(Some(index));
+                }
+                thread.stackframe.locals[2] += 1;
+                return Ok(None);
+            }
+            // push a fake return address
+            thread.stackframe.operand_stack.push(2);
+            thread.resolve_and_invoke(
+                target,
+                "equals",
+                &method!(((Object("java/lang/Object".into()))) -> boolean),
+                verbose,
+            )?;
+            thread.stackframe.locals[0] = target;
+            thread.stackframe.locals[1] = element;
+            Ok(None)
+        }
+        2 => {
+            if thread.stackframe.operand_stack.pop().unwrap() != 0 {
+                return Ok(Some(index));
+            }
+            thread.stackframe.locals[2] += 1;
+            thread.pc_register = 1;
+            Ok(None)
+        }
+        _ => Err("Impossible pc reached".to_string().into()),
+    }
+}
+
+/// `ArrayList.get`, `set`, `remove(int)`, `indexOf`, `contains`, `isEmpty`, and `clear`
+#[allow(clippy::too_many_lines)]
+fn arrlist_methods(java_lang_object: &Arc<str>) -> Vec<RawMethod> {
+    let get = RawMethod {
+        access_flags: access!(public native),
+        name: "get".into(),
+        descriptor: method!((int) -> Object(java_lang_object.clone())),
+        code: RawCode::native(NativeSingleMethod(
+            |thread: &mut Thread, [this, index]: [u32; 2], verbose| {
+                let Some(index) = list_check_index(thread, this, index, verbose)? else {
+                    return Ok(None);
+                };
+                ArrayListObj::inspect(&thread.heap, this as usize, |vec| vec[index])
+                    .map(Option::Some)
+            },
+        )),
+        ..Default::default()
+    };
+    let set = RawMethod {
+        access_flags: access!(public native),
+        name: "set".into(),
+        descriptor: method!((int, (Object(java_lang_object.clone()))) -> Object(java_lang_object.clone())),
+        code: RawCode::native(NativeSingleMethod(
+            |thread: &mut Thread, [this, index, ptr]: [u32; 3], verbose| {
+                let Some(index) = list_check_index(thread, this, index, verbose)? else {
+                    return Ok(None);
+                };
+                let old = ArrayListObj::inspect(&thread.heap, this as usize, |vec| {
+                    core::mem::replace(&mut vec[index], ptr)
+                })?;
+                thread.rember(ptr, verbose);
+                // the list's reference to the old element lasts until the caller has it
+                thread.stackframe.garbage.push(old);
+                Ok(Some(old))
+            },
+        )),
+        ..Default::default()
+    };
+    let remove = RawMethod {
+        access_flags: access!(public native),
+        name: "remove".into(),
+        descriptor: method!((int) -> Object(java_lang_object.clone())),
+        code: RawCode::native(NativeSingleMethod(
+            |thread: &mut Thread, [this, index]: [u32; 2], verbose| {
+                let Some(index) = list_check_index(thread, this, index, verbose)? else {
+                    return Ok(None);
+                };
+                let removed =
+                    ArrayListObj::inspect(&thread.heap, this as usize, |vec| vec.remove(index))?;
+                // the list's reference to the removed element lasts until the caller has it
+                thread.stackframe.garbage.push(removed);
+                Ok(Some(removed))
+            },
+        )),
+        ..Default::default()
+    };
+    let index_of = RawMethod {
+        access_flags: access!(public native),
+        name: "indexOf".into(),
+        descriptor: method!(((Object(java_lang_object.clone()))) -> int),
+        code: RawCode::native(Staged(3, NativeSingleMethod(arrlist_index_of))),
+        ..Default::default()
+    };
+    let contains = RawMethod {
+        access_flags: access!(public native),
+        name: "contains".into(),
+        descriptor: method!(((Object(java_lang_object.clone()))) -> boolean),
+        code: RawCode::native(Staged(
+            3,
+            NativeSingleMethod(|thread: &mut Thread, locals: [u32; 3], verbose| {
+                arrlist_index_of(thread, locals, verbose)
+                    .map(|index| index.map(|index| u32::from(index as i32 >= 0)))
+            }),
+        )),
+        ..Default::default()
+    };
+    let is_empty = RawMethod {
+        access_flags: access!(public native),
+        name: "isEmpty".into(),
+        descriptor: method!(() -> boolean),
+        code: RawCode::native(NativeSingleMethod(native_property(
+            ArrayListObj::SELF,
+            |arrls| u32::from(arrls.is_empty()),
+        ))),
+        ..Default::default()
+    };
+    let clear = RawMethod {
+        access_flags: access!(public native),
+        name: "clear".into(),
+        descriptor: method!(() -> void),
+        code: RawCode::native(NativeVoid(
+            |thread: &mut Thread, [this]: [u32; 1], verbose| {
+                let elements = ArrayListObj::inspect(&thread.heap, this as usize, core::mem::take)?;
+                for element in elements {
+                    thread.forgor(element, verbose);
+                }
+                Ok(Some(()))
+            },
+        )),
+        ..Default::default()
+    };
+    vec![get, set, remove, index_of, contains, is_empty, clear]
+}
+
+/// `LinkedList.add`, `addFirst`, `addLast`, `removeFirst`, `removeLast`, `peek`, `poll`, `push`,
+/// `pop`, `get`, `size`, and `isEmpty`
+#[allow(clippy::too_many_lines)]
+fn linked_list_methods(java_lang_object: &Arc<str>) -> Vec<RawMethod> {
+    let add = RawMethod {
+        access_flags: access!(public native),
+        name: "add".into(),
+        descriptor: method!(((Object(java_lang_object.clone()))) -> boolean),
+        code: RawCode::native(NativeSingleMethod(
+            |thread: &mut Thread, [this, ptr]: [u32; 2], verbose| {
+                LinkedListObj::inspect(&thread.heap, this as usize, |list| list.push_back(ptr))?;
+                thread.rember(ptr, verbose);
+                Ok(Some(1))
+            },
+        )),
+        ..Default::default()
+    };
+    let add_first = RawMethod {
+        access_flags: access!(public native),
+        name: "addFirst".into(),
+        descriptor: method!(((Object(java_lang_object.clone()))) -> void),
+        code: RawCode::native(NativeVoid(
+            |thread: &mut Thread, [this, ptr]: [u32; 2], verbose| {
+                LinkedListObj::inspect(&thread.heap, this as usize, |list| list.push_front(ptr))?;
+                thread.rember(ptr, verbose);
+                Ok(Some(()))
+            },
+        )),
+        ..Default::default()
+    };
+    let add_last = RawMethod {
+        name: "addLast".into(),
+        code: RawCode::native(NativeVoid(
+            |thread: &mut Thread, [this, ptr]: [u32; 2], verbose| {
+                LinkedListObj::inspect(&thread.heap, this as usize, |list| list.push_back(ptr))?;
+                thread.rember(ptr, verbose);
+                Ok(Some(()))
+            },
+        )),
+        ..add_first.clone()
+    };
+    let push = RawMethod {
+        name: "push".into(),
+        ..add_first.clone()
+    };
+    let remove_first = RawMethod {
+        access_flags: access!(public native),
+        name: "removeFirst".into(),
+        descriptor: method!(() -> Object(java_lang_object.clone())),
+        code: RawCode::native(NativeSingleMethod(
+            |thread: &mut Thread, [this]: [u32; 1], verbose| {
+                linked_list_remove(thread, this, true, verbose)
+            },
+        )),
+        ..Default::default()
+    };
+    let remove_last = RawMethod {
+        name: "removeLast".into(),
+        code: RawCode::native(NativeSingleMethod(
+            |thread: &mut Thread, [this]: [u32; 1], verbose| {
+                linked_list_remove(thread, this, false, verbose)
+            },
+        )),
+        ..remove_first.clone()
+    };
+    let pop = RawMethod {
+        name: "pop".into(),
+        ..remove_first.clone()
+    };
+    let peek = RawMethod {
+        access_flags: access!(public native),
+        name: "peek".into(),
+        descriptor: method!(() -> Object(java_lang_object.clone())),
+        code: RawCode::native(NativeSingleMethod(native_property(
+            LinkedListObj::SELF,
+            |list| list.front().copied().unwrap_or(NULL),
+        ))),
+        ..Default::default()
+    };
+    let poll = RawMethod {
+        access_flags: access!(public native),
+        name: "poll".into(),
+        descriptor: method!(() -> Object(java_lang_object.clone())),
+        code: RawCode::native(NativeSingleMethod(
+            |thread: &mut Thread, [this]: [u32; 1], _| {
+                let removed =
+                    LinkedListObj::inspect(&thread.heap, this as usize, VecDeque::pop_front)?
+                        .unwrap_or(NULL);
+                // the list's reference to the removed element lasts until the caller has it
+                thread.stackframe.garbage.push(removed);
+                Ok(Some(removed))
+            },
+        )),
+        ..Default::default()
+    };
+    let size = RawMethod {
+        access_flags: access!(public native),
+        name: "size".into(),
+        descriptor: method!(() -> int),
+        code: RawCode::native(NativeSingleMethod(native_property(
+            LinkedListObj::SELF,
+            |list| list.len() as u32,
+        ))),
+        ..Default::default()
+    };
+    let is_empty = RawMethod {
+        access_flags: access!(public native),
+        name: "isEmpty".into(),
+        descriptor: method!(() -> boolean),
+        code: RawCode::native(NativeSingleMethod(native_property(
+            LinkedListObj::SELF,
+            |list| u32::from(list.is_empty()),
+        ))),
+        ..Default::default()
+    };
+    let get = RawMethod {
+        access_flags: access!(public native),
+        name: "get".into(),
+        descriptor: method!((int) -> Object(java_lang_object.clone())),
+        code: RawCode::native(NativeSingleMethod(
+            |thread: &mut Thread, [this, index]: [u32; 2], verbose| {
+                let Some(index) = list_check_index(thread, this, index, verbose)? else {
+                    return Ok(None);
+                };
+                LinkedListObj::inspect(&thread.heap, this as usize, |list| list[index])
+                    .map(Option::Some)
+            },
+        )),
+        ..Default::default()
+    };
+    vec![
+        LinkedListObj::default_init(),
+        add,
+        add_first,
+        add_last,
+        push,
+        remove_first,
+        remove_last,
+        pop,
+        peek,
+        poll,
+        get,
+        size,
+        is_empty,
+    ]
+}
+
+/// Register `list.iterator()` and make the class of the iterator it returns, which holds the list
+/// and the index of the next element
+fn list_iterator(
+    list: &mut RawClass,
+    java_lang_object: &Arc<str>,
+    method_area: &mut WorkingMethodArea,
+) -> RawClass {
+    let mut iterator = RawClass::new(
+        access!(public native),
+        format!("{}$Itr", list.this).into(),
+        java_lang_object.clone(),
+    );
+    iterator.interfaces.push("java/util/Iterator".into());
+    iterator.fields.extend([
+        (
+            Field {
+                name: "list".into(),
+                access_flags: access!(private),
+                descriptor: field!(Object(list.this.clone())),
+                ..Default::default()
+            },
+            0,
+        ),
+        (
+            Field {
+                name: "index".into(),
+                access_flags: access!(private),
+                descriptor: field!(int),
+                ..Default::default()
+            },
+            1,
+        ),
+    ]);
+    iterator.field_size += 2;
+    let has_next = RawMethod {
+        access_flags: access!(public native),
+        name: "hasNext".into(),
+        descriptor: method!(() -> boolean),
+        code: RawCode::native(NativeSingleMethod(
+            |thread: &mut Thread, [this]: [u32; 1], _| {
+                let [list, index] = AnyObj.inspect(&thread.heap, this as usize, |obj| {
+                    [obj.fields[0], obj.fields[1]]
+                })?;
+                Ok(Some(u32::from((index as usize) < list_len(thread, list)?)))
+            },
+        )),
+        ..Default::default()
+    };
+    let next = RawMethod {
+        access_flags: access!(public native),
+        name: "next".into(),
+        descriptor: method!(() -> Object(java_lang_object.clone())),
+        code: RawCode::native(NativeSingleMethod(
+            |thread: &mut Thread, [this]: [u32; 1], verbose| {
+                let [list, index] = AnyObj.inspect(&thread.heap, this as usize, |obj| {
+                    [obj.fields[0], obj.fields[1]]
+                })?;
+                let Some(element) = list_element(thread, list, index as usize)? else {
+                    thread.throw_new("java/util/NoSuchElementException", "", verbose)?;
+                    return Ok(None);
+                };
+                AnyObj.inspect(&thread.heap, this as usize, |obj| obj.fields[1] += 1)?;
+                Ok(Some(element))
+            },
+        )),
+        ..Default::default()
+    };
+    iterator.register_methods([has_next, next], method_area);
+
+    let iterator_class = iterator.this.clone();
+    let make_iterator = RawMethod {
+        access_flags: access!(public native),
+        name: "iterator".into(),
+        descriptor: method!(() -> Object("java/util/Iterator".into())),
+        code: RawCode::native(NativeSingleMethod(
+            move |thread: &mut Thread, [this]: [u32; 1], verbose| {
+                let class = thread
+                    .class_area
+                    .search(&iterator_class)
+                    .ok_or_else(|| error::Error::class_resolution(&iterator_class))?;
+                let mut iterator = Object::from_class(&class);
+                iterator.fields[0] = this;
+                thread.rember(this, verbose);
+                Ok(Some(thread.heap.lock().unwrap().allocate(iterator)))
+            },
+        )),
+        ..Default::default()
+    };
+    list.register_method(make_iterator, method_area);
+    iterator
+}
+
+/// remove the first or last element of the linked list at `this`, throwing a
+/// `NoSuchElementException` if it's empty
+fn linked_list_remove(
+    thread: &mut Thread,
+    this: u32,
+    first: bool,
+    verbose: bool,
+) -> NativeReturn<u32> {
+    let removed = LinkedListObj::inspect(&thread.heap, this as usize, |list| {
+        if first {
+            list.pop_front()
+        } else {
+            list.pop_back()
+        }
+    })?;
+    let Some(removed) = removed else {
+        thread.throw_new("java/util/NoSuchElementException", "", verbose)?;
+        return Ok(None);
+    };
+    // the list's reference to the removed element lasts until the caller has it
+    thread.stackframe.garbage.push(removed);
+    Ok(Some(removed))
+}
+
+/// `Comparator.compare`, `naturalOrder`, `reverseOrder`, `comparing`, and `thenComparing`. Each
+/// combinator returns a lambda over one of the `$` helpers, with its arguments as captures
+#[allow(clippy::too_many_lines)]
+fn comparator_methods(java_lang_object: &Arc<str>) -> Vec<RawMethod> {
+    let comparator: Arc<str> = "java/util/Comparator".into();
     let compare = RawMethod {
         name: "compare".into(),
-        access_flags: access!(public native),
-        descriptor: method!(((Object(java_lang_object.clone())), (Object(java_lang_object.clone()))) -> boolean),
+        access_flags: access!(public abstract),
+        descriptor: method!(((Object(java_lang_object.clone())), (Object(java_lang_object.clone()))) -> int),
         code: RawCode::Abstract,
         ..Default::default()
     };
+    let natural_lambda = RawMethod {
+        name: "$natural".into(),
+        access_flags: access!(public static native),
+        descriptor: compare.descriptor.clone(),
+        code: RawCode::native(Staged(
+            2,
+            NativeSingleMethod(|thread: &mut Thread, [lhs, rhs]: [u32; 2], verbose| {
+                compare_natural(thread, lhs, rhs, verbose)
+            }),
+        )),
+        ..Default::default()
+    };
+    let reverse_lambda = RawMethod {
+        name: "$reverse".into(),
+        access_flags: access!(public static native),
+        descriptor: compare.descriptor.clone(),
+        code: RawCode::native(Staged(
+            2,
+            NativeSingleMethod(|thread: &mut Thread, [lhs, rhs]: [u32; 2], verbose| {
+                compare_natural(thread, rhs, lhs, verbose)
+            }),
+        )),
+        ..Default::default()
+    };
+    let comparing_lambda = {
+        let apply_signature =
+            method!(((Object(java_lang_object.clone()))) -> Object(java_lang_object.clone()));
+        let compare_to_signature = method!(((Object(java_lang_object.clone()))) -> int);
+        RawMethod {
+            name: "$comparing".into(),
+            access_flags: access!(public static native),
+            descriptor: method!(((Object("java/util/function/Function".into())), (Object(java_lang_object.clone())), (Object(java_lang_object.clone()))) -> int),
+            code: RawCode::native(Staged(
+                4,
+                NativeSingleMethod(
+                    move |thread: &mut Thread,
+                          [key_extractor, lhs, rhs, lhs_key]: [u32; 4],
+                          verbose| {
+                        match thread.pc_register {
+                            0 => {
+                                // extract the first key
+                                thread.stackframe.operand_stack.push(1);
+                                thread.resolve_and_invoke(
+                                    key_extractor,
+                                    "apply",
+                                    &apply_signature,
+                                    verbose,
+                                )?;
+                                thread.stackframe.locals[0] = key_extractor;
+                                thread.stackframe.locals[1] = lhs;
+                                Ok(None)
+                            }
+                            1 => {
+                                // save the first key and extract the second
+                                let lhs_key = thread.stackframe.operand_stack.pop().unwrap();
+                                thread.stackframe.locals[3] = lhs_key;
+                                thread.stackframe.operand_stack.push(2);
+                                thread.resolve_and_invoke(
+                                    key_extractor,
+                                    "apply",
+                                    &apply_signature,
+                                    verbose,
+                                )?;
+                                thread.stackframe.locals[0] = key_extractor;
+                                thread.stackframe.locals[1] = rhs;
+                                Ok(None)
+                            }
+                            2 => {
+                                // compare the keys
+                                let rhs_key = thread.stackframe.operand_stack.pop().unwrap();
+                                thread.stackframe.operand_stack.push(3);
+                                thread.resolve_and_invoke(
+                                    lhs_key,
+                                    "compareTo",
+                                    &compare_to_signature,
+                                    verbose,
+                                )?;
+                                thread.stackframe.locals[0] = lhs_key;
+                                thread.stackframe.locals[1] = rhs_key;
+                                Ok(None)
+                            }
+                            3 => Ok(Some(thread.stackframe.operand_stack.pop().unwrap())),
+                            pc => Err(format!("Invalid PC: {pc}").into()),
+                        }
+                    },
+                ),
+            )),
+            ..Default::default()
+        }
+    };
+    let then_lambda = {
+        let compare_signature = compare.descriptor.clone();
+        RawMethod {
+            name: "$then".into(),
+            access_flags: access!(public static native),
+            descriptor: method!(((Object(comparator.clone())), (Object(comparator.clone())), (Object(java_lang_object.clone())), (Object(java_lang_object.clone()))) -> int),
+            code: RawCode::native(Staged(
+                3,
+                NativeSingleMethod(
+                    move |thread: &mut Thread, [first, second, lhs, rhs]: [u32; 4], verbose| {
+                        match thread.pc_register {
+                            0 => {
+                                thread.stackframe.operand_stack.push(1);
+                                thread.resolve_and_invoke(
+                                    first,
+                                    "compare",
+                                    &compare_signature,
+                                    verbose,
+                                )?;
+                                thread.stackframe.locals[0] = first;
+                                thread.stackframe.locals[1] = lhs;
+                                thread.stackframe.locals[2] = rhs;
+                                Ok(None)
+                            }
+                            1 => {
+                                // only break ties with the second comparator
+                                let ret = thread.stackframe.operand_stack.pop().unwrap();
+                                if ret != 0 {
+                                    return Ok(Some(ret));
+                                }
+                                thread.stackframe.operand_stack.push(2);
+                                thread.resolve_and_invoke(
+                                    second,
+                                    "compare",
+                                    &compare_signature,
+                                    verbose,
+                                )?;
+                                thread.stackframe.locals[0] = second;
+                                thread.stackframe.locals[1] = lhs;
+                                thread.stackframe.locals[2] = rhs;
+                                Ok(None)
+                            }
+                            2 => Ok(Some(thread.stackframe.operand_stack.pop().unwrap())),
+                            pc => Err(format!("Invalid PC: {pc}").into()),
+                        }
+                    },
+                ),
+            )),
+            ..Default::default()
+        }
+    };
+    let natural_order = RawMethod {
+        name: "naturalOrder".into(),
+        access_flags: access!(public static native),
+        descriptor: method!(() -> Object(comparator.clone())),
+        code: RawCode::native(make_lambda_override::<0>(
+            &compare.name,
+            &compare.descriptor,
+            &comparator,
+            &natural_lambda.name,
+            &natural_lambda.descriptor,
+            &comparator,
+        )),
+        ..Default::default()
+    };
+    let reverse_order = RawMethod {
+        name: "reverseOrder".into(),
+        access_flags: access!(public static native),
+        descriptor: method!(() -> Object(comparator.clone())),
+        code: RawCode::native(make_lambda_override::<0>(
+            &compare.name,
+            &compare.descriptor,
+            &comparator,
+            &reverse_lambda.name,
+            &reverse_lambda.descriptor,
+            &comparator,
+        )),
+        ..Default::default()
+    };
+    let comparing = RawMethod {
+        name: "comparing".into(),
+        access_flags: access!(public static native),
+        descriptor: method!(((Object("java/util/function/Function".into()))) -> Object(comparator.clone())),
+        code: RawCode::native(make_lambda_override::<1>(
+            &compare.name,
+            &compare.descriptor,
+            &comparator,
+            &comparing_lambda.name,
+            &comparing_lambda.descriptor,
+            &comparator,
+        )),
+        ..Default::default()
+    };
+    let then_comparing = RawMethod {
+        name: "thenComparing".into(),
+        access_flags: access!(public native),
+        descriptor: method!(((Object(comparator.clone()))) -> Object(comparator.clone())),
+        code: RawCode::native(make_lambda_override::<2>(
+            &compare.name,
+            &compare.descriptor,
+            &comparator,
+            &then_lambda.name,
+            &then_lambda.descriptor,
+            &comparator,
+        )),
+        ..Default::default()
+    };
+    vec![
+        compare,
+        natural_order,
+        reverse_order,
+        comparing,
+        then_comparing,
+        natural_lambda,
+        reverse_lambda,
+        comparing_lambda,
+        then_lambda,
+    ]
+}
 
-    comparator.register_method(compare, method_area);
-
-    class_area.extend([hash_map, hash_set, array_list, comparator, array_stream]);
+/// `lhs.compareTo(rhs)`, through the erased `Comparable.compareTo(Object)`. Stage 0 makes the call
+/// and stage 1 returns its result
+fn compare_natural(thread: &mut Thread, lhs: u32, rhs: u32, verbose: bool) -> NativeReturn<u32> {
+    match thread.pc_register {
+        0 => {
+            thread.stackframe.operand_stack.push(1);
+            thread.resolve_and_invoke(
+                lhs,
+                "compareTo",
+                &method!(((Object("java/lang/Object".into()))) -> int),
+                verbose,
+            )?;
+            thread.stackframe.locals[0] = lhs;
+            thread.stackframe.locals[1] = rhs;
+            Ok(None)
+        }
+        1 => Ok(Some(thread.stackframe.operand_stack.pop().unwrap())),
+        pc => Err(format!("Invalid PC: {pc}").into()),
+    }
 }
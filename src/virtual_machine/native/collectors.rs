@@ -0,0 +1,458 @@
+use std::{collections::HashMap, sync::Arc};
+
+use jvmrs_lib::{access, method, MethodDescriptor};
+
+use crate::{
+    class::code::{NativeSingleMethod, Staged},
+    class_loader::{RawClass, RawCode, RawMethod},
+    data::{BuildNonHasher, WorkingClassArea, WorkingMethodArea, NULL},
+    virtual_machine::{
+        error,
+        object::{ArrayListObj, CollectorObj, Object, ObjectFinder, StringBuilder, StringObj},
+        Thread,
+    },
+};
+
+use super::function::Optional;
+
+/// # Collector Kind
+/// The reduction that a `java/util/stream/Collector` made by `Collectors` performs. Function
+/// pointers are kept alive for as long as the collector is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CollectorKind {
+    /// `Collectors.toMap(keyMapper, valueMapper)`
+    ToMap { key_mapper: u32, value_mapper: u32 },
+    /// `Collectors.counting()`
+    Counting,
+    /// `Collectors.summingInt(mapper)`
+    SummingInt { mapper: u32 },
+    /// `Collectors.toList()`
+    ToList,
+    /// `Collectors.joining()` or `Collectors.joining(delimiter)`; `delimiter` is the `String` form of
+    /// the `CharSequence`, or null for none
+    Joining { delimiter: u32 },
+}
+
+impl CollectorKind {
+    /// allocate a `Collector` object that performs this reduction
+    fn make(self, thread: &Thread, verbose: bool) -> u32 {
+        match self {
+            Self::ToMap {
+                key_mapper,
+                value_mapper,
+            } => {
+                thread.rember(key_mapper, verbose);
+                thread.rember(value_mapper, verbose);
+            }
+            Self::SummingInt { mapper } => thread.rember(mapper, verbose),
+            Self::Joining { delimiter } => thread.rember(delimiter, verbose),
+            Self::Counting | Self::ToList => {}
+        }
+        let mut collector = Object::from_class(
+            &thread
+                .class_area
+                .search("java/util/stream/Collector")
+                .unwrap(),
+        );
+        collector.native_fields.push(Box::new(self));
+        thread.heap.lock().unwrap().allocate(collector)
+    }
+}
+
+pub(super) fn add_native_methods(
+    method_area: &mut WorkingMethodArea,
+    class_area: &mut WorkingClassArea,
+    java_lang_object: &Arc<str>,
+) {
+    let collector = RawClass::new(
+        access!(public native),
+        "java/util/stream/Collector".into(),
+        java_lang_object.clone(),
+    );
+    let mut collectors = RawClass::new(
+        access!(public native),
+        "java/util/stream/Collectors".into(),
+        java_lang_object.clone(),
+    );
+    let to_map = RawMethod {
+        name: "toMap".into(),
+        access_flags: access!(public static native),
+        descriptor: method!(((Object("java/util/function/Function".into())), (Object("java/util/function/Function".into()))) -> Object(collector.this.clone())),
+        code: RawCode::native(NativeSingleMethod(
+            |thread: &mut Thread, [key_mapper, value_mapper]: [u32; 2], verbose| {
+                Ok(Some(
+                    CollectorKind::ToMap {
+                        key_mapper,
+                        value_mapper,
+                    }
+                    .make(thread, verbose),
+                ))
+            },
+        )),
+        ..Default::default()
+    };
+    let counting = RawMethod {
+        name: "counting".into(),
+        access_flags: access!(public static native),
+        descriptor: method!(() -> Object(collector.this.clone())),
+        code: RawCode::native(NativeSingleMethod(
+            |thread: &mut Thread, []: [u32; 0], verbose| {
+                Ok(Some(CollectorKind::Counting.make(thread, verbose)))
+            },
+        )),
+        ..Default::default()
+    };
+    let summing_int = RawMethod {
+        name: "summingInt".into(),
+        access_flags: access!(public static native),
+        descriptor: method!(((Object("java/util/function/ToIntFunction".into()))) -> Object(collector.this.clone())),
+        code: RawCode::native(NativeSingleMethod(
+            |thread: &mut Thread, [mapper]: [u32; 1], verbose| {
+                Ok(Some(
+                    CollectorKind::SummingInt { mapper }.make(thread, verbose),
+                ))
+            },
+        )),
+        ..Default::default()
+    };
+    let to_list = RawMethod {
+        name: "toList".into(),
+        access_flags: access!(public static native),
+        descriptor: method!(() -> Object(collector.this.clone())),
+        code: RawCode::native(NativeSingleMethod(
+            |thread: &mut Thread, []: [u32; 0], verbose| {
+                Ok(Some(CollectorKind::ToList.make(thread, verbose)))
+            },
+        )),
+        ..Default::default()
+    };
+    let joining = RawMethod {
+        name: "joining".into(),
+        access_flags: access!(public static native),
+        descriptor: method!(() -> Object(collector.this.clone())),
+        code: RawCode::native(NativeSingleMethod(
+            |thread: &mut Thread, []: [u32; 0], verbose| {
+                Ok(Some(
+                    CollectorKind::Joining { delimiter: NULL }.make(thread, verbose),
+                ))
+            },
+        )),
+        ..Default::default()
+    };
+    // the delimiter can be any `CharSequence`, so stage 0 calls its `toString` and stage 1 makes the
+    // collector from the result
+    let joining_delimiter = RawMethod {
+        name: "joining".into(),
+        access_flags: access!(public static native),
+        descriptor: method!(((Object("java/lang/CharSequence".into()))) -> Object(collector.this.clone())),
+        code: RawCode::native(Staged(
+            2,
+            NativeSingleMethod(|thread: &mut Thread, [delimiter]: [u32; 1], verbose| {
+                if thread.pc_register == 0 {
+                    if delimiter == NULL {
+                        thread.throw_new("java/lang/NullPointerException", "delimiter", verbose)?;
+                        return Ok(None);
+                    }
+                    thread.stackframe.operand_stack.push(1);
+                    thread.resolve_and_invoke(
+                        delimiter,
+                        "toString",
+                        &method!(() -> Object("java/lang/String".into())),
+                        verbose,
+                    )?;
+                    thread.stackframe.locals[0] = delimiter;
+                    return Ok(None);
+                }
+                let delimiter = thread.stackframe.operand_stack.pop().unwrap();
+                Ok(Some(
+                    CollectorKind::Joining { delimiter }.make(thread, verbose),
+                ))
+            }),
+        )),
+        ..Default::default()
+    };
+    collectors.register_methods(
+        [
+            to_map,
+            counting,
+            summing_int,
+            to_list,
+            joining,
+            joining_delimiter,
+        ],
+        method_area,
+    );
+
+    class_area.extend([collector, collectors]);
+}
+
+/// `Stream.collect(Collector)`
+///
+/// locals are `[this, collector, accumulator, element, key, value, previous]`. Stage 1 receives the
+/// next element; stages 2 and 3 receive the results of the collector's functions; stage 4 receives
+/// the value `HashMap.put` replaced. A replaced value is a duplicate key, so stages 5 to 7 receive
+/// the `toString`s of the key and both values for the `IllegalStateException`. When joining, `key`
+/// counts the elements joined so far and stage 2 receives the element's `toString`, or `"null"` for
+/// a null element.
+#[allow(clippy::too_many_lines)]
+pub(super) fn stream_collect(java_lang_object: &Arc<str>) -> RawMethod {
+    let next_descriptor = method!(() -> Object(java_lang_object.clone()));
+    let apply_descriptor =
+        method!(((Object(java_lang_object.clone()))) -> Object(java_lang_object.clone()));
+    let apply_as_int_descriptor = method!(((Object(java_lang_object.clone()))) -> int);
+    let put_descriptor = method!(((Object(java_lang_object.clone())), (Object(java_lang_object.clone()))) -> Object(java_lang_object.clone()));
+    let to_string_descriptor = method!(() -> Object("java/lang/String".into()));
+    RawMethod {
+        name: "collect".into(),
+        access_flags: access!(public native),
+        descriptor: method!(((Object("java/util/stream/Collector".into()))) -> Object(java_lang_object.clone())),
+        code: RawCode::native(Staged(
+            8,
+            NativeSingleMethod(
+                move |thread: &mut Thread,
+                      [this, collector, accumulator, element, key, value, previous]: [u32; 7],
+                      verbose| {
+                    let kind =
+                        CollectorObj::inspect(&thread.heap, collector as usize, |kind| *kind)?;
+                    let invoke = |thread: &mut Thread,
+                                  stage: u32,
+                                  obj: u32,
+                                  name: &str,
+                                  descriptor: &MethodDescriptor,
+                                  args: &[u32]|
+                     -> error::Result<()> {
+                        thread.stackframe.operand_stack.push(stage);
+                        thread.resolve_and_invoke(obj, name, descriptor, verbose)?;
+                        thread.stackframe.locals[0] = obj;
+                        thread.stackframe.locals[1..=args.len()].copy_from_slice(args);
+                        Ok(())
+                    };
+                    // `String.valueOf(obj)`, resuming at `stage`
+                    let to_string =
+                        |thread: &mut Thread, stage: usize, obj: u32| -> error::Result<()> {
+                            if obj == NULL {
+                                let null_str =
+                                    thread.heap.lock().unwrap().allocate_str("null".into());
+                                thread.rember_temp(null_str, verbose);
+                                thread.stackframe.operand_stack.push(null_str);
+                                thread.pc_register = stage;
+                                return Ok(());
+                            }
+                            invoke(
+                                thread,
+                                stage as u32,
+                                obj,
+                                "toString",
+                                &to_string_descriptor,
+                                &[],
+                            )
+                        };
+                    match thread.pc_register {
+                        0 => {
+                            thread.stackframe.locals[2] = match kind {
+                                CollectorKind::ToMap { .. } => {
+                                    let mut map = Object::from_class(
+                                        &thread.class_area.search("java/util/HashMap").unwrap(),
+                                    );
+                                    let entries: HashMap<u32, Vec<(u32, u32)>, BuildNonHasher> =
+                                        HashMap::default();
+                                    map.native_fields.push(Box::new(entries));
+                                    let map = thread.heap.lock().unwrap().allocate(map);
+                                    thread.rember_temp(map, verbose);
+                                    map
+                                }
+                                CollectorKind::ToList => {
+                                    let mut list = Object::from_class(
+                                        &thread.class_area.search("java/util/ArrayList").unwrap(),
+                                    );
+                                    list.native_fields.push(Box::<Vec<u32>>::default());
+                                    let list = thread.heap.lock().unwrap().allocate(list);
+                                    thread.rember_temp(list, verbose);
+                                    list
+                                }
+                                CollectorKind::Joining { .. } => {
+                                    let builder =
+                                        StringBuilder::new(String::new(), &thread.class_area);
+                                    let builder = thread.heap.lock().unwrap().allocate(builder);
+                                    thread.rember_temp(builder, verbose);
+                                    builder
+                                }
+                                CollectorKind::Counting | CollectorKind::SummingInt { .. } => 0,
+                            };
+                            invoke(thread, 1, this, "$next", &next_descriptor, &[])?;
+                            Ok(None)
+                        }
+                        1 => {
+                            let next = thread.stackframe.operand_stack.pop().unwrap();
+                            let Some(next) =
+                                Optional.inspect(&thread.heap, next as usize, |o| *o)?
+                            else {
+                                return Ok(Some(match kind {
+                                    CollectorKind::ToMap { .. } | CollectorKind::ToList => {
+                                        accumulator
+                                    }
+                                    CollectorKind::Counting => {
+                                        box_value(thread, "java/lang/Long", &[0, accumulator])
+                                    }
+                                    CollectorKind::SummingInt { .. } => {
+                                        box_value(thread, "java/lang/Integer", &[accumulator])
+                                    }
+                                    CollectorKind::Joining { .. } => {
+                                        let joined = StringBuilder::inspect(
+                                            &thread.heap,
+                                            accumulator as usize,
+                                            |builder| builder.clone(),
+                                        )?;
+                                        thread.heap.lock().unwrap().allocate_str(joined.into())
+                                    }
+                                }));
+                            };
+                            thread.stackframe.locals[3] = next;
+                            match kind {
+                                CollectorKind::ToMap { key_mapper, .. } => {
+                                    invoke(
+                                        thread,
+                                        2,
+                                        key_mapper,
+                                        "apply",
+                                        &apply_descriptor,
+                                        &[next],
+                                    )?;
+                                }
+                                CollectorKind::Counting => {
+                                    thread.stackframe.locals[2] = accumulator + 1;
+                                    invoke(thread, 1, this, "$next", &next_descriptor, &[])?;
+                                }
+                                CollectorKind::Joining { .. } => to_string(thread, 2, next)?,
+                                CollectorKind::ToList => {
+                                    let list = accumulator as usize;
+                                    ArrayListObj::inspect(&thread.heap, list, |list| {
+                                        list.push(next)
+                                    })?;
+                                    thread.rember(next, verbose);
+                                    invoke(thread, 1, this, "$next", &next_descriptor, &[])?;
+                                }
+                                CollectorKind::SummingInt { mapper } => {
+                                    invoke(
+                                        thread,
+                                        2,
+                                        mapper,
+                                        "applyAsInt",
+                                        &apply_as_int_descriptor,
+                                        &[next],
+                                    )?;
+                                }
+                            }
+                            Ok(None)
+                        }
+                        2 => {
+                            let result = thread.stackframe.operand_stack.pop().unwrap();
+                            match kind {
+                                CollectorKind::ToMap { value_mapper, .. } => {
+                                    thread.stackframe.locals[4] = result;
+                                    invoke(
+                                        thread,
+                                        3,
+                                        value_mapper,
+                                        "apply",
+                                        &apply_descriptor,
+                                        &[element],
+                                    )?;
+                                }
+                                CollectorKind::SummingInt { .. } => {
+                                    thread.stackframe.locals[2] = accumulator.wrapping_add(result);
+                                    invoke(thread, 1, this, "$next", &next_descriptor, &[])?;
+                                }
+                                CollectorKind::Joining { delimiter } => {
+                                    let mut joined = String::new();
+                                    if key > 0 && delimiter != NULL {
+                                        joined += &StringObj::inspect(
+                                            &thread.heap,
+                                            delimiter as usize,
+                                            |delimiter| delimiter.clone(),
+                                        )?;
+                                    }
+                                    joined += &StringObj::inspect(
+                                        &thread.heap,
+                                        result as usize,
+                                        |str| str.clone(),
+                                    )?;
+                                    StringBuilder::inspect(
+                                        &thread.heap,
+                                        accumulator as usize,
+                                        |builder| builder.push_str(&joined),
+                                    )?;
+                                    thread.stackframe.locals[4] = key + 1;
+                                    invoke(thread, 1, this, "$next", &next_descriptor, &[])?;
+                                }
+                                CollectorKind::Counting | CollectorKind::ToList => unreachable!(),
+                            }
+                            Ok(None)
+                        }
+                        3 => {
+                            let value = thread.stackframe.operand_stack.pop().unwrap();
+                            thread.stackframe.locals[5] = value;
+                            invoke(
+                                thread,
+                                4,
+                                accumulator,
+                                "put",
+                                &put_descriptor,
+                                &[key, value],
+                            )?;
+                            Ok(None)
+                        }
+                        4 => {
+                            let previous = thread.stackframe.operand_stack.pop().unwrap();
+                            if previous == NULL {
+                                invoke(thread, 1, this, "$next", &next_descriptor, &[])?;
+                            } else {
+                                thread.stackframe.locals[6] = previous;
+                                to_string(thread, 5, key)?;
+                            }
+                            Ok(None)
+                        }
+                        5 => {
+                            // the key has been turned into a string, so its slot can hold that instead
+                            thread.stackframe.locals[4] =
+                                thread.stackframe.operand_stack.pop().unwrap();
+                            to_string(thread, 6, previous)?;
+                            Ok(None)
+                        }
+                        6 => {
+                            thread.stackframe.locals[6] =
+                                thread.stackframe.operand_stack.pop().unwrap();
+                            to_string(thread, 7, value)?;
+                            Ok(None)
+                        }
+                        7 => {
+                            let value_str = thread.stackframe.operand_stack.pop().unwrap();
+                            let [key, previous, value] = [key, previous, value_str].map(|str| {
+                                StringObj::inspect(&thread.heap, str as usize, |str| str.clone())
+                            });
+                            let message = format!(
+                                "Duplicate key {} (attempted merging values {} and {})",
+                                key?, previous?, value?
+                            );
+                            thread.throw_new(
+                                "java/lang/IllegalStateException",
+                                &message,
+                                verbose,
+                            )?;
+                            Ok(None)
+                        }
+                        _ => unreachable!(),
+                    }
+                },
+            ),
+        )),
+        ..Default::default()
+    }
+}
+
+/// allocate an instance of a primitive wrapper class holding `value`
+fn box_value(thread: &Thread, class: &str, value: &[u32]) -> u32 {
+    let mut boxed = Object::from_class(&thread.class_area.search(class).unwrap());
+    boxed.fields[..value.len()].copy_from_slice(value);
+    thread.heap.lock().unwrap().allocate(boxed)
+}
@@ -0,0 +1,424 @@
+use std::{iter::Peekable, sync::Arc};
+
+use jvmrs_lib::{FieldType, MethodDescriptor};
+
+use crate::{
+    class::code::NativeReturn,
+    data::NULL,
+    virtual_machine::{
+        error,
+        object::{AnyObj, Array1, ObjectFinder, StringBuilder, StringObj},
+        Thread,
+    },
+};
+
+/// # Format Spec
+/// One `%[flags][width][.precision]conversion` specifier from a java format string
+struct FormatSpec {
+    left_justify: bool,
+    zero_pad: bool,
+    /// `+`: always write the sign of a number
+    plus: bool,
+    /// `,`: separate the integer digits of a number into groups of three
+    group: bool,
+    width: Option<usize>,
+    precision: Option<usize>,
+    conversion: char,
+    /// number of bytes the specifier takes up in the format string
+    len: usize,
+}
+
+/// # Format Argument
+/// An element of the `Object[]` passed to `printf`, unboxed
+enum FormatArg {
+    Null,
+    /// a boxed `byte`, `short`, `int`, or `long`, and its width in bits
+    Int(i64, u32),
+    Float(f32),
+    Double(f64),
+    Char(char),
+    Bool(bool),
+    String(Arc<str>),
+    /// any other object; holds its class name
+    Other(Arc<str>),
+}
+
+/// # Converted
+/// The result of applying a specifier to its argument
+enum Converted {
+    Text(String),
+    /// the argument has to be converted by calling its `toString`
+    ToString,
+    /// throw an exception of the given class with the given message
+    Throw(&'static str, String),
+}
+
+impl FormatSpec {
+    /// parse the specifier at the start of `text`, which begins with `%`
+    fn parse(text: &str) -> Option<Self> {
+        let mut chars = text.char_indices().skip(1).peekable();
+        let mut left_justify = false;
+        let mut zero_pad = false;
+        let mut plus = false;
+        let mut group = false;
+        while let Some(&(_, flag @ ('-' | '0' | '+' | ','))) = chars.peek() {
+            match flag {
+                '-' => left_justify = true,
+                '0' => zero_pad = true,
+                '+' => plus = true,
+                _ => group = true,
+            }
+            chars.next();
+        }
+        let width = Self::number(&mut chars);
+        let precision = if chars.next_if(|&(_, c)| c == '.').is_some() {
+            Some(Self::number(&mut chars).unwrap_or(0))
+        } else {
+            None
+        };
+        let (idx, conversion) = chars.next()?;
+        Some(Self {
+            left_justify,
+            zero_pad,
+            plus,
+            group,
+            width,
+            precision,
+            conversion,
+            len: idx + conversion.len_utf8(),
+        })
+    }
+
+    fn number(chars: &mut Peekable<impl Iterator<Item = (usize, char)>>) -> Option<usize> {
+        let mut number = None;
+        while let Some((_, digit)) = chars.next_if(|(_, c)| c.is_ascii_digit()) {
+            number = Some(number.unwrap_or(0) * 10 + digit as usize - '0' as usize);
+        }
+        number
+    }
+
+    /// pad `text` out to this specifier's width
+    fn pad(&self, text: String) -> String {
+        let len = text.chars().count();
+        let Some(padding) = self.width.and_then(|width| width.checked_sub(len)) else {
+            return text;
+        };
+        if self.left_justify {
+            format!("{text}{}", " ".repeat(padding))
+        } else if self.zero_pad {
+            let (sign, digits) = text.split_at(usize::from(text.starts_with(['-', '+'])));
+            format!("{sign}{}{digits}", "0".repeat(padding))
+        } else {
+            format!("{}{text}", " ".repeat(padding))
+        }
+    }
+
+    /// apply a `%s` or `%S` specifier to the string form of its argument
+    fn finish_string(&self, text: &str) -> String {
+        let text = self.precision.map_or_else(
+            || text.to_string(),
+            |precision| text.chars().take(precision).collect(),
+        );
+        if self.conversion == 'S' {
+            self.pad(text.to_uppercase())
+        } else {
+            self.pad(text)
+        }
+    }
+
+    /// apply the `+` and `,` flags to the text of a `%d` or `%f` number
+    fn sign(&self, text: &str) -> String {
+        let (sign, magnitude) = text
+            .strip_prefix('-')
+            .map_or(("", text), |magnitude| ("-", magnitude));
+        let sign = if sign.is_empty() && self.plus && magnitude != "NaN" {
+            "+"
+        } else {
+            sign
+        };
+        if !self.group || !magnitude.starts_with(|c: char| c.is_ascii_digit()) {
+            return format!("{sign}{magnitude}");
+        }
+        let (int, frac) = magnitude.split_at(magnitude.find('.').unwrap_or(magnitude.len()));
+        let mut grouped = String::from(sign);
+        for (idx, digit) in int.chars().enumerate() {
+            if idx > 0 && (int.len() - idx) % 3 == 0 {
+                grouped.push(',');
+            }
+            grouped.push(digit);
+        }
+        grouped.push_str(frac);
+        grouped
+    }
+
+    fn convert(&self, arg: FormatArg) -> Converted {
+        if (self.plus || self.group) && !matches!(self.conversion, 'd' | 'f' | 'n' | '%') {
+            return Converted::Throw(
+                "java/util/FormatFlagsConversionMismatchException",
+                format!(
+                    "Conversion = {}, Flags = {}",
+                    self.conversion,
+                    if self.plus { '+' } else { ',' }
+                ),
+            );
+        }
+        let text = match (self.conversion, arg) {
+            ('n', _) => return Converted::Text(String::from("\n")),
+            ('%', _) => return Converted::Text(self.pad(String::from("%"))),
+            ('s' | 'S', FormatArg::Null) => return Converted::Text(self.finish_string("null")),
+            ('s' | 'S', FormatArg::String(str)) => {
+                return Converted::Text(self.finish_string(&str))
+            }
+            ('s' | 'S', _) => return Converted::ToString,
+            ('d' | 'x' | 'X' | 'f' | 'c', FormatArg::Null) => String::from("null"),
+            ('d', FormatArg::Int(int, _)) => self.sign(&int.to_string()),
+            ('x', FormatArg::Int(int, bits)) => {
+                format!("{:x}", int as u64 & (u64::MAX >> (64 - bits)))
+            }
+            ('X', FormatArg::Int(int, bits)) => {
+                format!("{:X}", int as u64 & (u64::MAX >> (64 - bits)))
+            }
+            // `Formatter` prints a `float` through its `double` value, so `%.10f` of `0.1f` is
+            // `0.1000000015`
+            ('f', FormatArg::Float(float)) => {
+                self.sign(&fixed(f64::from(float), self.precision.unwrap_or(6)))
+            }
+            ('f', FormatArg::Double(double)) => {
+                self.sign(&fixed(double, self.precision.unwrap_or(6)))
+            }
+            ('c', FormatArg::Char(char)) => char.to_string(),
+            ('c', FormatArg::Int(int, bits)) if bits <= 32 => match char::from_u32(int as u32) {
+                Some(char) => char.to_string(),
+                None => {
+                    return Converted::Throw(
+                        "java/util/IllegalFormatCodePointException",
+                        format!("Code point = {int:#x}"),
+                    )
+                }
+            },
+            ('b', FormatArg::Null) => String::from("false"),
+            ('b', FormatArg::Bool(bool)) => bool.to_string(),
+            ('b', _) => String::from("true"),
+            ('d' | 'x' | 'X' | 'f' | 'c', arg) => {
+                let class = match arg {
+                    FormatArg::Int(_, 8) => "java.lang.Byte",
+                    FormatArg::Int(_, 16) => "java.lang.Short",
+                    FormatArg::Int(_, 32) => "java.lang.Integer",
+                    FormatArg::Int(..) => "java.lang.Long",
+                    FormatArg::Float(_) => "java.lang.Float",
+                    FormatArg::Double(_) => "java.lang.Double",
+                    FormatArg::Char(_) => "java.lang.Character",
+                    FormatArg::Bool(_) => "java.lang.Boolean",
+                    FormatArg::String(_) => "java.lang.String",
+                    FormatArg::Other(class) => {
+                        return Converted::Throw(
+                            "java/util/IllegalFormatConversionException",
+                            format!("{} != {}", self.conversion, class.replace('/', ".")),
+                        )
+                    }
+                    FormatArg::Null => unreachable!(),
+                };
+                return Converted::Throw(
+                    "java/util/IllegalFormatConversionException",
+                    format!("{} != {class}", self.conversion),
+                );
+            }
+            (conversion, _) => {
+                return Converted::Throw(
+                    "java/util/UnknownFormatConversionException",
+                    format!("Conversion = '{conversion}'"),
+                )
+            }
+        };
+        Converted::Text(self.pad(text))
+    }
+}
+
+/// format `value` with `precision` digits after the decimal point, rounding half-up from its
+/// shortest representation the way `java.util.Formatter` does
+fn fixed(value: f64, precision: usize) -> String {
+    if value.is_nan() {
+        return String::from("NaN");
+    }
+    if value.is_infinite() {
+        return String::from(if value < 0.0 { "-Infinity" } else { "Infinity" });
+    }
+    let sign = if value.is_sign_negative() { "-" } else { "" };
+    // shortest round-trip digits and the exponent of the first one
+    let scientific = format!("{:e}", value.abs());
+    let (mantissa, exponent) = scientific.split_once('e').unwrap();
+    let mut digits: Vec<u8> = mantissa.bytes().filter(u8::is_ascii_digit).collect();
+    let mut point = exponent.parse::<isize>().unwrap() + 1;
+    if point < 1 {
+        let leading = (1 - point) as usize;
+        digits.splice(0..0, std::iter::repeat(b'0').take(leading));
+        point = 1;
+    }
+    let point = point as usize;
+    let keep = point + precision;
+    if digits.len() <= keep {
+        digits.resize(keep, b'0');
+    } else {
+        let round_up = digits[keep] >= b'5';
+        digits.truncate(keep);
+        if round_up {
+            let mut carry = true;
+            for digit in digits.iter_mut().rev() {
+                if *digit == b'9' {
+                    *digit = b'0';
+                } else {
+                    *digit += 1;
+                    carry = false;
+                    break;
+                }
+            }
+            if carry {
+                digits.insert(0, b'1');
+                return assemble(sign, &digits, point + 1);
+            }
+        }
+    }
+    assemble(sign, &digits, point)
+}
+
+fn assemble(sign: &str, digits: &[u8], point: usize) -> String {
+    let (int, frac) = digits.split_at(point);
+    let int = String::from_utf8_lossy(int);
+    if frac.is_empty() {
+        format!("{sign}{int}")
+    } else {
+        format!("{sign}{int}.{}", String::from_utf8_lossy(frac))
+    }
+}
+
+/// read a `printf` argument, unboxing primitive wrappers
+fn read_arg(thread: &Thread, ptr: u32) -> error::Result<FormatArg> {
+    if ptr == NULL {
+        return Ok(FormatArg::Null);
+    }
+    let (class, fields) = AnyObj.inspect(&thread.heap, ptr as usize, |obj| {
+        (obj.class.clone(), obj.fields.clone())
+    })?;
+    let long = || ((u64::from(fields[0]) << 32) | u64::from(fields[1])) as i64;
+    Ok(match &*class {
+        "java/lang/Byte" => FormatArg::Int(i64::from(fields[0] as i32), 8),
+        "java/lang/Short" => FormatArg::Int(i64::from(fields[0] as i32), 16),
+        "java/lang/Integer" => FormatArg::Int(i64::from(fields[0] as i32), 32),
+        "java/lang/Long" => FormatArg::Int(long(), 64),
+        "java/lang/Float" => FormatArg::Float(f32::from_bits(fields[0])),
+        "java/lang/Double" => FormatArg::Double(f64::from_bits(long() as u64)),
+        "java/lang/Character" => FormatArg::Char(
+            char::from_u32(fields[0]).ok_or_else(|| String::from("Invalid Character code"))?,
+        ),
+        "java/lang/Boolean" => FormatArg::Bool(fields[0] != 0),
+        "java/lang/String" => {
+            FormatArg::String(StringObj::SELF.inspect(&thread.heap, ptr as usize, |s| s.clone())?)
+        }
+        _ => FormatArg::Other(class),
+    })
+}
+
+/// apply the java format string at `format` to the `Object[]` at `args`
+///
+/// locals are `[this, format, args, builder, position, arg_index]`. Stage 0 allocates the builder
+/// that holds the output so far; stage 1 receives the result of an argument's `toString`, for the
+/// specifier at byte `position` of the format string.
+pub(super) fn format(
+    thread: &mut Thread,
+    [_, format, args, _, position, arg_index]: [u32; 6],
+    verbose: bool,
+) -> NativeReturn<Arc<str>> {
+    let text = StringObj::SELF.inspect(&thread.heap, format as usize, |s| s.clone())?;
+    let args = if args == NULL {
+        Vec::new()
+    } else {
+        Array1.inspect(&thread.heap, args as usize, |arr| arr.contents.to_vec())?
+    };
+    let mut position = position as usize;
+    let mut arg_index = arg_index as usize;
+    let mut output = String::new();
+    if thread.pc_register == 0 {
+        let builder = StringBuilder::new(String::new(), &thread.class_area);
+        let builder = thread.heap.lock().unwrap().allocate(builder);
+        thread.rember_temp(builder, verbose);
+        thread.stackframe.locals[3] = builder;
+        position = 0;
+        arg_index = 0;
+    } else {
+        let ret = thread.stackframe.operand_stack.pop().unwrap();
+        let str = StringObj::SELF.inspect(&thread.heap, ret as usize, |s| s.clone())?;
+        let spec = FormatSpec::parse(&text[position..]).unwrap();
+        output.push_str(&spec.finish_string(&str));
+        position += spec.len;
+    }
+    let builder = thread.stackframe.locals[3];
+    loop {
+        let rest = &text[position..];
+        let Some(offset) = rest.find('%') else {
+            output.push_str(rest);
+            break;
+        };
+        output.push_str(&rest[..offset]);
+        position += offset;
+        let Some(spec) = FormatSpec::parse(&text[position..]) else {
+            thread.throw_new(
+                "java/util/UnknownFormatConversionException",
+                "Conversion = '%'",
+                verbose,
+            )?;
+            return Ok(None);
+        };
+        let arg = if matches!(spec.conversion, 'n' | '%') {
+            NULL
+        } else if let Some(&arg) = args.get(arg_index) {
+            arg_index += 1;
+            arg
+        } else {
+            thread.throw_new(
+                "java/util/MissingFormatArgumentException",
+                &format!(
+                    "Format specifier '{}'",
+                    &text[position..position + spec.len]
+                ),
+                verbose,
+            )?;
+            return Ok(None);
+        };
+        match spec.convert(read_arg(thread, arg)?) {
+            Converted::Text(converted) => {
+                output.push_str(&converted);
+                position += spec.len;
+            }
+            Converted::ToString => {
+                StringBuilder::inspect(&thread.heap, builder as usize, |builder| {
+                    builder.push_str(&output);
+                })?;
+                thread.stackframe.locals[4] = position as u32;
+                thread.stackframe.locals[5] = arg_index as u32;
+                // push a fake return address
+                thread.stackframe.operand_stack.push(1);
+                thread.resolve_and_invoke(
+                    arg,
+                    "toString",
+                    &MethodDescriptor {
+                        parameter_size: 0,
+                        parameters: Vec::new(),
+                        return_type: Some(FieldType::Object("java/lang/String".into())),
+                    },
+                    verbose,
+                )?;
+                thread.stackframe.locals[0] = arg;
+                return Ok(None);
+            }
+            Converted::Throw(class, message) => {
+                thread.throw_new(class, &message, verbose)?;
+                return Ok(None);
+            }
+        }
+    }
+    let result = StringBuilder::inspect(&thread.heap, builder as usize, |builder| {
+        builder.push_str(&output);
+        Arc::from(&**builder)
+    })?;
+    Ok(Some(result))
+}
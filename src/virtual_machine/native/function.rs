@@ -4,7 +4,7 @@ use jvmrs_lib::{access, field, method, MethodDescriptor, MethodHandle};
 
 use crate::{
     class::{
-        code::{NativeMethod, NativeSingleMethod},
+        code::{NativeMethod, NativeReturn, NativeSingleMethod, NativeStringMethod, Staged},
         Field,
     },
     class_loader::{RawClass, RawCode, RawMethod},
@@ -27,6 +27,16 @@ impl Optional {
         let idx = thread.heap.lock().unwrap().allocate(opt);
         idx
     }
+
+    /// the shared empty `Optional` from `$EMPTY`, or `None` if the class has to be initialized first
+    pub fn empty(thread: &mut Thread) -> Option<u32> {
+        let optional = thread.class_area.search("java/util/Optional").unwrap();
+        if thread.maybe_initialize_class(&optional) {
+            return None;
+        }
+        let empty = optional.static_data.lock().unwrap()[0];
+        Some(empty)
+    }
 }
 
 impl ObjectFinder for Optional {
@@ -114,46 +124,49 @@ pub(super) fn add_native_methods(
             name: "$compose".into(),
             access_flags: access!(public static native),
             descriptor: method!(((Object(function.this.clone())), (Object(function.this.clone())), (Object(java_lang_object.clone()))) -> Object(java_lang_object.clone())),
-            code: RawCode::native(NativeSingleMethod(
-                move |thread: &mut Thread,
-                      [first_fn_ptr, second_fn_ptr, arg]: [u32; 3],
-                      verbose| {
-                    match thread.pc_register {
-                        0 => {
-                            // push the return address
-                            thread.stackframe.operand_stack.push(1);
-                            thread.resolve_and_invoke(
-                                first_fn_ptr,
-                                "apply",
-                                &apply_signature,
-                                verbose,
-                            )?;
-                            thread.stackframe.locals[0] = first_fn_ptr;
-                            thread.stackframe.locals[1] = arg;
-                            Ok(None)
-                        }
-                        1 => {
-                            // get the first value returned
-                            let first_return = thread.stackframe.operand_stack.pop().unwrap();
-                            // push the return address
-                            thread.stackframe.operand_stack.push(2);
-                            thread.resolve_and_invoke(
-                                second_fn_ptr,
-                                "apply",
-                                &apply_signature,
-                                verbose,
-                            )?;
-                            thread.stackframe.locals[0] = second_fn_ptr;
-                            thread.stackframe.locals[1] = first_return;
-                            Ok(None)
-                        }
-                        2 => {
-                            // return the result
-                            Ok(Some(thread.stackframe.operand_stack.pop().unwrap()))
+            code: RawCode::native(Staged(
+                3,
+                NativeSingleMethod(
+                    move |thread: &mut Thread,
+                          [first_fn_ptr, second_fn_ptr, arg]: [u32; 3],
+                          verbose| {
+                        match thread.pc_register {
+                            0 => {
+                                // push the return address
+                                thread.stackframe.operand_stack.push(1);
+                                thread.resolve_and_invoke(
+                                    first_fn_ptr,
+                                    "apply",
+                                    &apply_signature,
+                                    verbose,
+                                )?;
+                                thread.stackframe.locals[0] = first_fn_ptr;
+                                thread.stackframe.locals[1] = arg;
+                                Ok(None)
+                            }
+                            1 => {
+                                // get the first value returned
+                                let first_return = thread.stackframe.operand_stack.pop().unwrap();
+                                // push the return address
+                                thread.stackframe.operand_stack.push(2);
+                                thread.resolve_and_invoke(
+                                    second_fn_ptr,
+                                    "apply",
+                                    &apply_signature,
+                                    verbose,
+                                )?;
+                                thread.stackframe.locals[0] = second_fn_ptr;
+                                thread.stackframe.locals[1] = first_return;
+                                Ok(None)
+                            }
+                            2 => {
+                                // return the result
+                                Ok(Some(thread.stackframe.operand_stack.pop().unwrap()))
+                            }
+                            pc => Err(format!("Invalid PC: {pc}").into()),
                         }
-                        pc => Err(format!("Invalid PC: {pc}").into()),
-                    }
-                },
+                    },
+                ),
             )),
             ..Default::default()
         }
@@ -290,24 +303,32 @@ pub(super) fn add_native_methods(
             name: "$negative".into(),
             access_flags: access!(public static),
             descriptor: method!(((Object(predicate.this.clone())), (Object(java_lang_object.clone()))) -> boolean),
-            code: RawCode::native(NativeSingleMethod(
-                move |thread: &mut Thread, [predicate, object]: [u32; 2], verbose| match thread
-                    .pc_register
-                {
-                    0 => {
-                        thread.stackframe.operand_stack.push(1);
-                        thread.resolve_and_invoke(predicate, "test", &test_signature, verbose)?;
-                        thread.stackframe.locals[0] = predicate;
-                        thread.stackframe.locals[1] = object;
-                        Ok(None)
-                    }
-                    1 => {
-                        // reverse the output
-                        let value = thread.stackframe.operand_stack.pop().unwrap();
-                        Ok(Some(u32::from(value == 0)))
-                    }
-                    _ => unreachable!(),
-                },
+            code: RawCode::native(Staged(
+                2,
+                NativeSingleMethod(
+                    move |thread: &mut Thread, [predicate, object]: [u32; 2], verbose| match thread
+                        .pc_register
+                    {
+                        0 => {
+                            thread.stackframe.operand_stack.push(1);
+                            thread.resolve_and_invoke(
+                                predicate,
+                                "test",
+                                &test_signature,
+                                verbose,
+                            )?;
+                            thread.stackframe.locals[0] = predicate;
+                            thread.stackframe.locals[1] = object;
+                            Ok(None)
+                        }
+                        1 => {
+                            // reverse the output
+                            let value = thread.stackframe.operand_stack.pop().unwrap();
+                            Ok(Some(u32::from(value == 0)))
+                        }
+                        _ => unreachable!(),
+                    },
+                ),
             )),
             ..Default::default()
         }
@@ -375,31 +396,44 @@ pub(super) fn add_native_methods(
             name: "$and".into(),
             access_flags: access!(public static native),
             descriptor: method!(((Object(predicate.this.clone())), (Object(predicate.this.clone())), (Object(java_lang_object.clone()))) -> boolean),
-            code: RawCode::native(NativeSingleMethod(
-                move |thread: &mut Thread, [first, second, operand]: [u32; 3], verbose| match thread
-                    .pc_register
-                {
-                    0 => {
-                        thread.stackframe.operand_stack.push(1);
-                        thread.resolve_and_invoke(first, "test", &test_signature, verbose)?;
-                        thread.stackframe.locals[0] = first;
-                        thread.stackframe.locals[1] = operand;
-                        Ok(None)
-                    }
-                    1 => {
-                        let ret = thread.stackframe.operand_stack.pop().unwrap();
-                        if ret == 0 {
-                            return Ok(Some(0));
+            code: RawCode::native(Staged(
+                3,
+                NativeSingleMethod(
+                    move |thread: &mut Thread, [first, second, operand]: [u32; 3], verbose| {
+                        match thread.pc_register {
+                            0 => {
+                                thread.stackframe.operand_stack.push(1);
+                                thread.resolve_and_invoke(
+                                    first,
+                                    "test",
+                                    &test_signature,
+                                    verbose,
+                                )?;
+                                thread.stackframe.locals[0] = first;
+                                thread.stackframe.locals[1] = operand;
+                                Ok(None)
+                            }
+                            1 => {
+                                let ret = thread.stackframe.operand_stack.pop().unwrap();
+                                if ret == 0 {
+                                    return Ok(Some(0));
+                                }
+                                thread.stackframe.operand_stack.push(2);
+                                thread.resolve_and_invoke(
+                                    second,
+                                    "test",
+                                    &test_signature,
+                                    verbose,
+                                )?;
+                                thread.stackframe.locals[0] = second;
+                                thread.stackframe.locals[1] = operand;
+                                Ok(None)
+                            }
+                            2 => Ok(Some(thread.stackframe.operand_stack.pop().unwrap())),
+                            _ => unreachable!(),
                         }
-                        thread.stackframe.operand_stack.push(2);
-                        thread.resolve_and_invoke(second, "test", &test_signature, verbose)?;
-                        thread.stackframe.locals[0] = second;
-                        thread.stackframe.locals[1] = operand;
-                        Ok(None)
-                    }
-                    2 => Ok(Some(thread.stackframe.operand_stack.pop().unwrap())),
-                    _ => unreachable!(),
-                },
+                    },
+                ),
             )),
             ..Default::default()
         }
@@ -410,31 +444,44 @@ pub(super) fn add_native_methods(
             name: "$or".into(),
             access_flags: access!(public static native),
             descriptor: method!(((Object(predicate.this.clone())), (Object(predicate.this.clone())), (Object(java_lang_object.clone()))) -> boolean),
-            code: RawCode::native(NativeSingleMethod(
-                move |thread: &mut Thread, [first, second, operand]: [u32; 3], verbose| match thread
-                    .pc_register
-                {
-                    0 => {
-                        thread.stackframe.operand_stack.push(1);
-                        thread.resolve_and_invoke(first, "test", &test_signature, verbose)?;
-                        thread.stackframe.locals[0] = first;
-                        thread.stackframe.locals[1] = operand;
-                        Ok(None)
-                    }
-                    1 => {
-                        let ret = thread.stackframe.operand_stack.pop().unwrap();
-                        if ret == 1 {
-                            return Ok(Some(1));
+            code: RawCode::native(Staged(
+                3,
+                NativeSingleMethod(
+                    move |thread: &mut Thread, [first, second, operand]: [u32; 3], verbose| {
+                        match thread.pc_register {
+                            0 => {
+                                thread.stackframe.operand_stack.push(1);
+                                thread.resolve_and_invoke(
+                                    first,
+                                    "test",
+                                    &test_signature,
+                                    verbose,
+                                )?;
+                                thread.stackframe.locals[0] = first;
+                                thread.stackframe.locals[1] = operand;
+                                Ok(None)
+                            }
+                            1 => {
+                                let ret = thread.stackframe.operand_stack.pop().unwrap();
+                                if ret == 1 {
+                                    return Ok(Some(1));
+                                }
+                                thread.stackframe.operand_stack.push(2);
+                                thread.resolve_and_invoke(
+                                    second,
+                                    "test",
+                                    &test_signature,
+                                    verbose,
+                                )?;
+                                thread.stackframe.locals[0] = second;
+                                thread.stackframe.locals[1] = operand;
+                                Ok(None)
+                            }
+                            2 => Ok(Some(thread.stackframe.operand_stack.pop().unwrap())),
+                            _ => unreachable!(),
                         }
-                        thread.stackframe.operand_stack.push(2);
-                        thread.resolve_and_invoke(second, "test", &test_signature, verbose)?;
-                        thread.stackframe.locals[0] = second;
-                        thread.stackframe.locals[1] = operand;
-                        Ok(None)
-                    }
-                    2 => Ok(Some(thread.stackframe.operand_stack.pop().unwrap())),
-                    _ => unreachable!(),
-                },
+                    },
+                ),
             )),
             ..Default::default()
         }
@@ -564,40 +611,51 @@ pub(super) fn add_native_methods(
         name: "equals".into(),
         access_flags: access!(public native),
         descriptor: method!(((Object(java_lang_object.clone()))) -> boolean),
-        code: RawCode::native(NativeSingleMethod(
-            move |thread: &mut Thread, [this, other]: [u32; 2], verbose| match thread.pc_register {
-                0 => {
-                    let Some(other_inner) = AnyObj.inspect(&thread.heap, other as usize, |o| {
-                        if o.isinstance(&thread.class_area, "java/util/Optional", verbose) {
-                            Some(o.fields[0])
-                        } else {
-                            None
+        code: RawCode::native(Staged(
+            2,
+            NativeSingleMethod(
+                move |thread: &mut Thread, [this, other]: [u32; 2], verbose| match thread
+                    .pc_register
+                {
+                    0 => {
+                        let Some(other_inner) =
+                            AnyObj.inspect(&thread.heap, other as usize, |o| {
+                                if o.isinstance(&thread.class_area, "java/util/Optional", verbose) {
+                                    Some(o.fields[0])
+                                } else {
+                                    None
+                                }
+                            })?
+                        else {
+                            // if it's not an Optional, return false
+                            return Ok(Some(0));
+                        };
+                        let this_inner =
+                            AnyObj.inspect(&thread.heap, this as usize, |o| o.fields[0])?;
+                        // if they're both None, they're equal
+                        if this_inner == u32::MAX && other_inner == u32::MAX {
+                            return Ok(Some(1));
                         }
-                    })?
-                    else {
-                        // if it's not an Optional, return false
-                        return Ok(Some(0));
-                    };
-                    let this_inner =
-                        AnyObj.inspect(&thread.heap, this as usize, |o| o.fields[0])?;
-                    // if they're both None, they're equal
-                    if this_inner == u32::MAX && other_inner == u32::MAX {
-                        return Ok(Some(1));
-                    }
-                    // if one's None but the other isn't, they're not equal
-                    if this_inner == u32::MAX || other_inner == u32::MAX {
-                        return Ok(Some(0));
+                        // if one's None but the other isn't, they're not equal
+                        if this_inner == u32::MAX || other_inner == u32::MAX {
+                            return Ok(Some(0));
+                        }
+                        thread.stackframe.operand_stack.push(1);
+                        thread.resolve_and_invoke(
+                            this_inner,
+                            "equals",
+                            &equals_descriptor,
+                            verbose,
+                        )?;
+                        thread.stackframe.locals[0] = this_inner;
+                        thread.stackframe.locals[1] = other_inner;
+                        Ok(None)
                     }
-                    thread.stackframe.operand_stack.push(1);
-                    thread.resolve_and_invoke(this_inner, "equals", &equals_descriptor, verbose)?;
-                    thread.stackframe.locals[0] = this_inner;
-                    thread.stackframe.locals[1] = other_inner;
-                    Ok(None)
-                }
-                // re-return the value from Object.equals
-                1 => Ok(Some(thread.stackframe.operand_stack.pop().unwrap())),
-                _ => unreachable!(),
-            },
+                    // re-return the value from Object.equals
+                    1 => Ok(Some(thread.stackframe.operand_stack.pop().unwrap())),
+                    _ => unreachable!(),
+                },
+            ),
         )),
         ..Default::default()
     };
@@ -607,57 +665,176 @@ pub(super) fn add_native_methods(
             name: "filter".into(),
             access_flags: access!(public native),
             descriptor: method!(((Object(predicate.this.clone()))) -> Object(optional.this.clone())),
-            code: RawCode::native(NativeSingleMethod(
-                move |thread: &mut Thread, [this, predicate]: [u32; 2], verbose| match thread
-                    .pc_register
-                {
-                    0 => {
-                        let this_value =
-                            AnyObj.inspect(&thread.heap, this as usize, |obj| obj.fields[0])?;
+            code: RawCode::native(Staged(
+                2,
+                NativeSingleMethod(
+                    move |thread: &mut Thread, [this, predicate]: [u32; 2], verbose| match thread
+                        .pc_register
+                    {
+                        0 => {
+                            let this_value =
+                                AnyObj.inspect(&thread.heap, this as usize, |obj| obj.fields[0])?;
 
-                        if this_value == u32::MAX {
-                            let optional = thread.class_area.search("java/util/Optional").unwrap();
-                            if thread.maybe_initialize_class(&optional) {
-                                return Ok(None);
+                            if this_value == u32::MAX {
+                                let optional =
+                                    thread.class_area.search("java/util/Optional").unwrap();
+                                if thread.maybe_initialize_class(&optional) {
+                                    return Ok(None);
+                                }
+                                let null = optional.static_data.lock().unwrap()[0];
+                                return Ok(Some(null));
                             }
-                            let null = optional.static_data.lock().unwrap()[0];
-                            return Ok(Some(null));
-                        }
 
-                        thread.stackframe.operand_stack.push(1);
-                        thread.resolve_and_invoke(predicate, "test", &test_signature, verbose)?;
-                        thread.stackframe.locals[0] = predicate;
-                        thread.stackframe.locals[1] = this_value;
-                        Ok(None)
-                    }
-                    1 => {
-                        let result = *thread.stackframe.operand_stack.last().unwrap();
+                            thread.stackframe.operand_stack.push(1);
+                            thread.resolve_and_invoke(
+                                predicate,
+                                "test",
+                                &test_signature,
+                                verbose,
+                            )?;
+                            thread.stackframe.locals[0] = predicate;
+                            thread.stackframe.locals[1] = this_value;
+                            Ok(None)
+                        }
+                        1 => {
+                            let result = *thread.stackframe.operand_stack.last().unwrap();
 
-                        if result == 0 {
-                            let optional = thread.class_area.search("java/util/Optional").unwrap();
-                            if thread.maybe_initialize_class(&optional) {
-                                return Ok(None);
+                            if result == 0 {
+                                let optional =
+                                    thread.class_area.search("java/util/Optional").unwrap();
+                                if thread.maybe_initialize_class(&optional) {
+                                    return Ok(None);
+                                }
+                                let null = optional.static_data.lock().unwrap()[0];
+                                return Ok(Some(null));
                             }
-                            let null = optional.static_data.lock().unwrap()[0];
-                            return Ok(Some(null));
+                            Ok(Some(this))
                         }
-                        Ok(Some(this))
-                    }
-                    _ => unreachable!(),
-                },
+                        _ => unreachable!(),
+                    },
+                ),
+            )),
+            ..Default::default()
+        }
+    };
+    let opt_flat_map = {
+        let apply_descriptor =
+            method!(((Object(java_lang_object.clone()))) -> Object(java_lang_object.clone()));
+        RawMethod {
+            name: "flatMap".into(),
+            access_flags: access!(public native),
+            descriptor: method!(((Object(function.this.clone()))) -> Object(optional.this.clone())),
+            code: RawCode::native(Staged(
+                2,
+                NativeSingleMethod(
+                    move |thread: &mut Thread, [this, mapper]: [u32; 2], verbose| match thread
+                        .pc_register
+                    {
+                        0 => {
+                            let this_value =
+                                AnyObj.inspect(&thread.heap, this as usize, |obj| obj.fields[0])?;
+                            // an empty Optional stays empty without calling the mapper
+                            if this_value == u32::MAX {
+                                return Ok(Some(this));
+                            }
+                            thread.stackframe.operand_stack.push(1);
+                            thread.resolve_and_invoke(
+                                mapper,
+                                "apply",
+                                &apply_descriptor,
+                                verbose,
+                            )?;
+                            thread.stackframe.locals[0] = mapper;
+                            thread.stackframe.locals[1] = this_value;
+                            Ok(None)
+                        }
+                        // the mapper already returned an Optional, so don't wrap it again
+                        1 => Ok(Some(thread.stackframe.operand_stack.pop().unwrap())),
+                        _ => unreachable!(),
+                    },
+                ),
+            )),
+            ..Default::default()
+        }
+    };
+    let opt_get = RawMethod {
+        name: "get".into(),
+        access_flags: access!(public native),
+        descriptor: method!(() -> Object(java_lang_object.clone())),
+        code: RawCode::native(NativeSingleMethod(opt_or_else_throw)),
+        ..Default::default()
+    };
+    let opt_or_else_throw = RawMethod {
+        name: "orElseThrow".into(),
+        ..opt_get.clone()
+    };
+    let opt_or_else = RawMethod {
+        name: "orElse".into(),
+        access_flags: access!(public native),
+        descriptor: method!(((Object(java_lang_object.clone()))) -> Object(java_lang_object.clone())),
+        code: RawCode::native(NativeSingleMethod(
+            |thread: &mut Thread, [this, other]: [u32; 2], _verbose| {
+                Optional
+                    .inspect(&thread.heap, this as usize, |value| value.unwrap_or(other))
+                    .map(Option::Some)
+            },
+        )),
+        ..Default::default()
+    };
+    let opt_map = {
+        let apply_descriptor =
+            method!(((Object(java_lang_object.clone()))) -> Object(java_lang_object.clone()));
+        RawMethod {
+            name: "map".into(),
+            access_flags: access!(public native),
+            descriptor: method!(((Object(function.this.clone()))) -> Object(optional.this.clone())),
+            code: RawCode::native(Staged(
+                2,
+                NativeSingleMethod(
+                    move |thread: &mut Thread, [this, mapper]: [u32; 2], verbose| match thread
+                        .pc_register
+                    {
+                        0 => {
+                            let this_value =
+                                AnyObj.inspect(&thread.heap, this as usize, |obj| obj.fields[0])?;
+                            // an empty Optional stays empty without calling the mapper
+                            if this_value == u32::MAX {
+                                return Ok(Optional::empty(thread));
+                            }
+                            thread.stackframe.operand_stack.push(1);
+                            thread.resolve_and_invoke(
+                                mapper,
+                                "apply",
+                                &apply_descriptor,
+                                verbose,
+                            )?;
+                            thread.stackframe.locals[0] = mapper;
+                            thread.stackframe.locals[1] = this_value;
+                            Ok(None)
+                        }
+                        1 => {
+                            let result = thread.stackframe.operand_stack.pop().unwrap();
+                            // a null result makes an empty Optional, like `Optional.ofNullable`
+                            if result == 0 {
+                                return Ok(Optional::empty(thread));
+                            }
+                            Ok(Some(Optional::make(thread, result, verbose)))
+                        }
+                        _ => unreachable!(),
+                    },
+                ),
             )),
             ..Default::default()
         }
     };
-    // TODO: flatMap
-    // TODO: get
     let hash_code_descriptor = method!(() -> int);
     let opt_hash_code = RawMethod {
         name: "hashCode".into(),
         access_flags: access!(public native),
         descriptor: method!(() -> int),
-        code: RawCode::native(NativeSingleMethod(
-            move |thread: &mut Thread, [this]: [u32; 1], verbose| {
+        code: RawCode::native(Staged(
+            2,
+            NativeSingleMethod(move |thread: &mut Thread, [this]: [u32; 1], verbose| {
                 match thread.pc_register {
                     0 => {
                         let value =
@@ -680,10 +857,41 @@ pub(super) fn add_native_methods(
                     1 => Ok(Some(thread.stackframe.operand_stack.pop().unwrap())),
                     _ => unreachable!(),
                 }
-            },
+            }),
         )),
         ..Default::default()
     };
+    let opt_or_supplier = {
+        let get_descriptor = method!(() -> Object(java_lang_object.clone()));
+        RawMethod {
+            name: "or".into(),
+            access_flags: access!(public native),
+            descriptor: method!(((Object("java/util/function/Supplier".into()))) -> Object(optional.this.clone())),
+            code: RawCode::native(Staged(
+                2,
+                NativeSingleMethod(
+                    move |thread: &mut Thread, [this, supplier]: [u32; 2], verbose| match thread
+                        .pc_register
+                    {
+                        0 => {
+                            let this_value =
+                                AnyObj.inspect(&thread.heap, this as usize, |obj| obj.fields[0])?;
+                            if this_value != u32::MAX {
+                                return Ok(Some(this));
+                            }
+                            thread.stackframe.operand_stack.push(1);
+                            thread.resolve_and_invoke(supplier, "get", &get_descriptor, verbose)?;
+                            thread.stackframe.locals[0] = supplier;
+                            Ok(None)
+                        }
+                        1 => Ok(Some(thread.stackframe.operand_stack.pop().unwrap())),
+                        _ => unreachable!(),
+                    },
+                ),
+            )),
+            ..Default::default()
+        }
+    };
     // TODO: ifPresent
     // TODO: ifPresentOrElse
     let is_empty = RawMethod {
@@ -714,40 +922,46 @@ pub(super) fn add_native_methods(
         )),
         ..Default::default()
     };
-    // TODO: map
-    // TODO: orElse
     // TODO: orElseGet
-    // TODO: orElseThrow
     // TODO: stream
     let to_string_descriptor = method!(() -> Object(java_lang_string.clone()));
-    let opt_to_string =
-        RawMethod::to_string(move |thread: &mut Thread, [this]: [u32; 1], verbose| {
-            match thread.pc_register {
-                0 => {
-                    let value = AnyObj.inspect(&thread.heap, this as usize, |obj| obj.fields[0])?;
-                    if value == u32::MAX {
-                        Ok(Some("None".into()))
-                    } else {
-                        // push return address
-                        thread.stackframe.operand_stack.push(1);
-                        thread.resolve_and_invoke(
-                            value,
-                            "toString",
-                            &to_string_descriptor,
-                            verbose,
-                        )?;
-                        thread.stackframe.locals[0] = value;
-                        Ok(None)
+    let opt_to_string = RawMethod {
+        access_flags: access!(public native),
+        name: "toString".into(),
+        descriptor: to_string_descriptor.clone(),
+        code: RawCode::native(Staged(
+            2,
+            NativeStringMethod(move |thread: &mut Thread, [this]: [u32; 1], verbose| {
+                match thread.pc_register {
+                    0 => {
+                        let value =
+                            AnyObj.inspect(&thread.heap, this as usize, |obj| obj.fields[0])?;
+                        if value == u32::MAX {
+                            Ok(Some("None".into()))
+                        } else {
+                            // push return address
+                            thread.stackframe.operand_stack.push(1);
+                            thread.resolve_and_invoke(
+                                value,
+                                "toString",
+                                &to_string_descriptor,
+                                verbose,
+                            )?;
+                            thread.stackframe.locals[0] = value;
+                            Ok(None)
+                        }
                     }
+                    1 => StringObj::inspect(
+                        &thread.heap,
+                        thread.stackframe.operand_stack.pop().unwrap() as usize,
+                        |s| Some(format!("Some({s})").into()),
+                    ),
+                    _ => unreachable!(),
                 }
-                1 => StringObj::inspect(
-                    &thread.heap,
-                    thread.stackframe.operand_stack.pop().unwrap() as usize,
-                    |s| Some(format!("Some({s})").into()),
-                ),
-                _ => unreachable!(),
-            }
-        });
+            }),
+        )),
+        ..Default::default()
+    };
     optional.register_methods(
         [
             opt_empty,
@@ -760,6 +974,12 @@ pub(super) fn add_native_methods(
             opt_equals,
             opt_clinit,
             opt_filter,
+            opt_flat_map,
+            opt_or_supplier,
+            opt_get,
+            opt_or_else,
+            opt_or_else_throw,
+            opt_map,
         ],
         method_area,
     );
@@ -779,5 +999,85 @@ pub(super) fn add_native_methods(
     };
     consumer.register_method(consumer_accept, method_area);
 
-    class_area.extend([function, optional, predicate, consumer]);
+    let mut supplier = RawClass::new(
+        access!(public native abstract),
+        "java/util/function/Supplier".into(),
+        java_lang_object.clone(),
+    );
+
+    let supplier_get = RawMethod {
+        name: "get".into(),
+        access_flags: access!(public abstract native),
+        descriptor: method!(() -> Object(java_lang_object.clone())),
+        code: RawCode::Abstract,
+        ..Default::default()
+    };
+    supplier.register_method(supplier_get, method_area);
+
+    class_area.extend([function, optional, predicate, consumer, supplier]);
+
+    // the primitive specializations take and return `int` directly instead of a boxed `Integer`
+    for (name, method_name, descriptor) in [
+        (
+            "java/util/function/IntFunction",
+            "apply",
+            method!((int) -> Object(java_lang_object.clone())),
+        ),
+        (
+            "java/util/function/ToIntFunction",
+            "applyAsInt",
+            method!(((Object(java_lang_object.clone()))) -> int),
+        ),
+        (
+            "java/util/function/IntPredicate",
+            "test",
+            method!((int) -> boolean),
+        ),
+        (
+            "java/util/function/IntConsumer",
+            "accept",
+            method!((int) -> void),
+        ),
+        (
+            "java/util/function/IntUnaryOperator",
+            "applyAsInt",
+            method!((int) -> int),
+        ),
+        (
+            "java/util/function/IntBinaryOperator",
+            "applyAsInt",
+            method!((int, int) -> int),
+        ),
+    ] {
+        let mut interface = RawClass::new(
+            access!(public native abstract),
+            name.into(),
+            java_lang_object.clone(),
+        );
+        interface.register_method(
+            RawMethod {
+                name: method_name.into(),
+                access_flags: access!(public abstract native),
+                descriptor,
+                code: RawCode::Abstract,
+                ..Default::default()
+            },
+            method_area,
+        );
+        class_area.push(interface);
+    }
+}
+
+/// `Optional.get` and `Optional.orElseThrow`, which throw a `NoSuchElementException` when the
+/// Optional is empty
+fn opt_or_else_throw(thread: &mut Thread, [this]: [u32; 1], verbose: bool) -> NativeReturn<u32> {
+    if let Some(value) = Optional.inspect(&thread.heap, this as usize, |value| *value)? {
+        return Ok(Some(value));
+    }
+    thread.throw_new(
+        "java/util/NoSuchElementException",
+        "No value present",
+        verbose,
+    )?;
+    Ok(None)
 }
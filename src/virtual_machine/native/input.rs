@@ -0,0 +1,363 @@
+use std::{
+    io::{self, BufRead, BufReader, IsTerminal, Read, Write},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, MutexGuard,
+    },
+};
+
+use jvmrs_lib::{access, method, FieldType, MethodDescriptor};
+
+use crate::{
+    class::code::{
+        NativeDoubleMethod, NativeReturn, NativeSingleMethod, NativeStringMethod, NativeVoid,
+    },
+    class_loader::{RawClass, RawCode, RawMethod},
+    data::{WorkingClassArea, WorkingMethodArea, NULL},
+    virtual_machine::{
+        object::{Array1, Object},
+        Thread,
+    },
+};
+
+/// where the java program's standard input comes from; defaults to the terminal
+static STDIN: Mutex<Option<Source>> = Mutex::new(None);
+/// whether standard input has been redirected with `set_stdin`
+static REDIRECTED: AtomicBool = AtomicBool::new(false);
+
+/// lock standard input, first flushing stdout so that a prompt without a newline shows up before
+/// the program waits for input
+fn lock_stdin() -> io::Result<MutexGuard<'static, Option<Source>>> {
+    io::stdout().flush()?;
+    Ok(STDIN.lock().unwrap())
+}
+
+/// standard input, along with anything that was read from it and then put back
+struct Source {
+    reader: Box<dyn BufRead + Send>,
+    /// bytes to read before going back to `reader`, like a token that didn't parse
+    unread: Vec<u8>,
+}
+
+impl Source {
+    fn new(reader: Box<dyn BufRead + Send>) -> Self {
+        Self {
+            reader,
+            unread: Vec::new(),
+        }
+    }
+
+    fn stdin() -> Self {
+        Self::new(Box::new(BufReader::new(io::stdin())))
+    }
+}
+
+impl Read for Source {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let len = available.len().min(buf.len());
+        buf[..len].copy_from_slice(&available[..len]);
+        self.consume(len);
+        Ok(len)
+    }
+}
+
+impl BufRead for Source {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.unread.is_empty() {
+            self.reader.fill_buf()
+        } else {
+            Ok(&self.unread)
+        }
+    }
+
+    fn consume(&mut self, amt: usize) {
+        if self.unread.is_empty() {
+            self.reader.consume(amt);
+        } else {
+            self.unread.drain(..amt);
+        }
+    }
+}
+
+/// Feed the java program's standard input from the given reader instead of the terminal
+/// # Panics
+pub fn set_stdin(reader: impl BufRead + Send + 'static) {
+    *STDIN.lock().unwrap() = Some(Source::new(Box::new(reader)));
+    REDIRECTED.store(true, Ordering::Relaxed);
+}
+
+/// Whether the java program's standard input is an interactive terminal
+pub fn is_terminal() -> bool {
+    !REDIRECTED.load(Ordering::Relaxed) && io::stdin().is_terminal()
+}
+
+/// Read a single byte of standard input, returning `None` at the end of input
+/// # Errors
+/// # Panics
+pub fn read_byte() -> io::Result<Option<u8>> {
+    let mut binding = lock_stdin()?;
+    let source = binding.get_or_insert_with(Source::stdin);
+    let byte = source.fill_buf()?.first().copied();
+    if byte.is_some() {
+        source.consume(1);
+    }
+    drop(binding);
+    Ok(byte)
+}
+
+/// Read a line of standard input without its line terminator, returning `None` at the end of input
+/// # Errors
+/// # Panics
+pub fn read_line() -> io::Result<Option<String>> {
+    let mut binding = lock_stdin()?;
+    let source = binding.get_or_insert_with(Source::stdin);
+    let mut line = String::new();
+    if source.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    drop(binding);
+    if line.ends_with('\n') {
+        line.pop();
+        if line.ends_with('\r') {
+            line.pop();
+        }
+    }
+    Ok(Some(line))
+}
+
+/// Read the next whitespace-separated token of standard input and parse it with `parse`, returning
+/// `None` if only whitespace is left. Whitespace after the token stays in the input, so a following
+/// `read_line` gets the rest of the line. A token that doesn't parse is returned as the error and
+/// put back, along with the whitespace before it, so the input is left as it was
+/// # Errors
+/// # Panics
+pub fn read_token<T>(
+    parse: impl FnOnce(&str) -> Option<T>,
+) -> io::Result<Option<Result<T, String>>> {
+    let mut binding = lock_stdin()?;
+    let source = binding.get_or_insert_with(Source::stdin);
+    let mut consumed = Vec::new();
+    let mut token_start = 0;
+    while let Some(&byte) = source.fill_buf()?.first() {
+        if byte.is_ascii_whitespace() && consumed.len() > token_start {
+            break;
+        }
+        consumed.push(byte);
+        source.consume(1);
+        if byte.is_ascii_whitespace() {
+            token_start = consumed.len();
+        }
+    }
+    if consumed.len() == token_start {
+        return Ok(None);
+    }
+    let token = String::from_utf8_lossy(&consumed[token_start..]).into_owned();
+    if let Some(value) = parse(&token) {
+        return Ok(Some(Ok(value)));
+    }
+    source.unread.splice(0..0, consumed);
+    drop(binding);
+    Ok(Some(Err(token)))
+}
+
+/// Whether there's any standard input left to read
+/// # Errors
+/// # Panics
+pub fn has_input() -> io::Result<bool> {
+    let mut binding = lock_stdin()?;
+    let source = binding.get_or_insert_with(Source::stdin);
+    let has_input = !source.fill_buf()?.is_empty();
+    drop(binding);
+    Ok(has_input)
+}
+
+/// read a token and parse it with `parse`, throwing a `NoSuchElementException` at the end of input
+/// or an `InputMismatchException` if it doesn't parse. A token that doesn't parse stays in the input,
+/// like it does for `Scanner`
+fn next_parsed<T>(
+    thread: &mut Thread,
+    parse: impl FnOnce(&str) -> Option<T>,
+    verbose: bool,
+) -> NativeReturn<T> {
+    let Some(token) = read_token(parse).map_err(|err| err.to_string())? else {
+        thread.throw_new("java/util/NoSuchElementException", "", verbose)?;
+        return Ok(None);
+    };
+    let token = match token {
+        Ok(value) => return Ok(Some(value)),
+        Err(token) => token,
+    };
+    thread.throw_new(
+        "java/util/InputMismatchException",
+        &format!("For input string: \"{token}\""),
+        verbose,
+    )?;
+    Ok(None)
+}
+
+#[allow(clippy::too_many_lines)]
+pub(super) fn add_native_methods(
+    method_area: &mut WorkingMethodArea,
+    class_area: &mut WorkingClassArea,
+    java_lang_object: &Arc<str>,
+    java_lang_string: &Arc<str>,
+) {
+    let mut console = RawClass::new(
+        access!(public native),
+        "java/io/Console".into(),
+        java_lang_object.clone(),
+    );
+    let console_read_line = RawMethod {
+        name: "readLine".into(),
+        access_flags: access!(public native),
+        descriptor: method!(() -> Object(java_lang_string.clone())),
+        code: RawCode::native(NativeSingleMethod(
+            |thread: &mut Thread, [_]: [u32; 1], _| {
+                let line = read_line().map_err(|err| err.to_string())?;
+                Ok(Some(line.map_or(NULL, |line| {
+                    thread.heap.lock().unwrap().allocate_str(line.into())
+                })))
+            },
+        )),
+        ..Default::default()
+    };
+    // the password is still echoed; turning that off would need a platform-specific terminal API
+    let read_password = RawMethod {
+        name: "readPassword".into(),
+        access_flags: access!(public native),
+        descriptor: MethodDescriptor {
+            parameter_size: 0,
+            parameters: Vec::new(),
+            return_type: Some(FieldType::Array(Box::new(FieldType::Char))),
+        },
+        code: RawCode::native(NativeSingleMethod(
+            |thread: &mut Thread, [_]: [u32; 1], _| {
+                let line = read_line().map_err(|err| err.to_string())?;
+                Ok(Some(line.map_or(NULL, |line| {
+                    let chars = line.encode_utf16().map(u32::from).collect();
+                    thread
+                        .heap
+                        .lock()
+                        .unwrap()
+                        .allocate(Array1::from_vec(chars, FieldType::Char))
+                })))
+            },
+        )),
+        ..Default::default()
+    };
+    console.register_methods([console_read_line, read_password], method_area);
+
+    // every scanner reads from the same standard input, so scanners don't keep any state
+    let mut scanner = RawClass::new(
+        access!(public native),
+        "java/util/Scanner".into(),
+        java_lang_object.clone(),
+    );
+    let scanner_init = RawMethod {
+        name: "<init>".into(),
+        access_flags: access!(public native),
+        descriptor: method!(((Object("java/io/InputStream".into()))) -> void),
+        code: RawCode::native(NativeVoid(|_: &mut _, [_, _]: [u32; 2], _| Ok(Some(())))),
+        ..Default::default()
+    };
+    let next_line = RawMethod {
+        name: "nextLine".into(),
+        access_flags: access!(public native),
+        descriptor: method!(() -> Object(java_lang_string.clone())),
+        code: RawCode::native(NativeStringMethod(
+            |thread: &mut Thread, [_]: [u32; 1], verbose| {
+                if let Some(line) = read_line().map_err(|err| err.to_string())? {
+                    return Ok(Some(line.into()));
+                }
+                thread.throw_new("java/util/NoSuchElementException", "No line found", verbose)?;
+                Ok(None)
+            },
+        )),
+        ..Default::default()
+    };
+    let next = RawMethod {
+        name: "next".into(),
+        access_flags: access!(public native),
+        descriptor: method!(() -> Object(java_lang_string.clone())),
+        code: RawCode::native(NativeStringMethod(
+            |thread: &mut Thread, [_]: [u32; 1], verbose| {
+                next_parsed(thread, |token| Some(token.into()), verbose)
+            },
+        )),
+        ..Default::default()
+    };
+    let next_int = RawMethod {
+        name: "nextInt".into(),
+        access_flags: access!(public native),
+        descriptor: method!(() -> int),
+        code: RawCode::native(NativeSingleMethod(
+            |thread: &mut Thread, [_]: [u32; 1], verbose| {
+                next_parsed(
+                    thread,
+                    |token| token.parse::<i32>().ok().map(|i| i as u32),
+                    verbose,
+                )
+            },
+        )),
+        ..Default::default()
+    };
+    let next_double = RawMethod {
+        name: "nextDouble".into(),
+        access_flags: access!(public native),
+        descriptor: method!(() -> double),
+        code: RawCode::native(NativeDoubleMethod(
+            |thread: &mut Thread, [_]: [u32; 1], verbose| {
+                next_parsed(
+                    thread,
+                    |token| token.parse::<f64>().ok().map(f64::to_bits),
+                    verbose,
+                )
+            },
+        )),
+        ..Default::default()
+    };
+    let has_next_line = RawMethod {
+        name: "hasNextLine".into(),
+        access_flags: access!(public native),
+        descriptor: method!(() -> boolean),
+        code: RawCode::native(NativeSingleMethod(|_: &mut _, [_]: [u32; 1], _| {
+            let has_input = has_input().map_err(|err| err.to_string())?;
+            Ok(Some(u32::from(has_input)))
+        })),
+        ..Default::default()
+    };
+    scanner.register_methods(
+        [
+            scanner_init,
+            next_line,
+            next,
+            next_int,
+            next_double,
+            has_next_line,
+        ],
+        method_area,
+    );
+
+    class_area.extend([console, scanner]);
+}
+
+/// `System.console()`, which is null unless standard input is a terminal
+pub(super) fn system_console() -> RawMethod {
+    RawMethod {
+        name: "console".into(),
+        access_flags: access!(public static native),
+        descriptor: method!(() -> Object("java/io/Console".into())),
+        code: RawCode::native(NativeSingleMethod(
+            |thread: &mut Thread, []: [u32; 0], _| {
+                if !is_terminal() {
+                    return Ok(Some(NULL));
+                }
+                let console =
+                    Object::from_class(&thread.class_area.search("java/io/Console").unwrap());
+                Ok(Some(thread.heap.lock().unwrap().allocate(console)))
+            },
+        )),
+        ..Default::default()
+    }
+}
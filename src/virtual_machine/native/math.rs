@@ -0,0 +1,294 @@
+use std::{
+    f64::consts::{E, PI},
+    sync::Arc,
+};
+
+use jvmrs_lib::{access, method, FieldType};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{
+    class::{
+        code::{NativeDoubleMethod, NativeSingleMethod},
+        Field,
+    },
+    class_loader::{RawClass, RawCode, RawMethod},
+    data::{WorkingClassArea, WorkingMethodArea, NULL},
+    virtual_machine::{
+        object::{Object, Random},
+        Thread,
+    },
+};
+
+#[allow(clippy::too_many_lines)]
+pub(super) fn add_native_methods(
+    method_area: &mut WorkingMethodArea,
+    class_area: &mut WorkingClassArea,
+    java_lang_object: &Arc<str>,
+) {
+    let mut math = RawClass::new(
+        access!(public native),
+        "java/lang/Math".into(),
+        java_lang_object.clone(),
+    );
+    for (name, value) in [("PI", PI), ("E", E)] {
+        math.statics.push((
+            Field {
+                access_flags: access!(public static final native),
+                name: name.into(),
+                descriptor: FieldType::Double,
+                ..Default::default()
+            },
+            math.static_data.len(),
+        ));
+        let bits = value.to_bits();
+        math.static_data.extend([(bits >> 32) as u32, bits as u32]);
+    }
+
+    let abs_int = RawMethod {
+        access_flags: access!(public static native),
+        name: "abs".into(),
+        descriptor: method!((int) -> int),
+        code: RawCode::native(NativeSingleMethod(|_: &mut _, [int]: [u32; 1], _| {
+            Ok(Some((int as i32).wrapping_abs() as u32))
+        })),
+        ..Default::default()
+    };
+    let abs_long = RawMethod {
+        access_flags: access!(public static native),
+        name: "abs".into(),
+        descriptor: method!((long) -> long),
+        code: RawCode::native(NativeDoubleMethod(
+            |_: &mut _, [left, right]: [u32; 2], _| {
+                let long = ((left as u64) << 32 | (right as u64)) as i64;
+                Ok(Some(long.wrapping_abs() as u64))
+            },
+        )),
+        ..Default::default()
+    };
+    let abs_float = RawMethod {
+        access_flags: access!(public static native),
+        name: "abs".into(),
+        descriptor: method!((float) -> float),
+        code: RawCode::native(NativeSingleMethod(|_: &mut _, [float]: [u32; 1], _| {
+            Ok(Some(f32::from_bits(float).abs().to_bits()))
+        })),
+        ..Default::default()
+    };
+    let round_float = RawMethod {
+        access_flags: access!(public static native),
+        name: "round".into(),
+        descriptor: method!((float) -> int),
+        code: RawCode::native(NativeSingleMethod(|_: &mut _, [float]: [u32; 1], _| {
+            let float = f32::from_bits(float);
+            let floor = float.floor();
+            let rounded = if float - floor >= 0.5 {
+                floor + 1.0
+            } else {
+                floor
+            };
+            Ok(Some(rounded as i32 as u32))
+        })),
+        ..Default::default()
+    };
+    let round_double = RawMethod {
+        access_flags: access!(public static native),
+        name: "round".into(),
+        descriptor: method!((double) -> long),
+        code: RawCode::native(NativeDoubleMethod(
+            |_: &mut _, [left, right]: [u32; 2], _| {
+                let double = f64::from_bits((left as u64) << 32 | (right as u64));
+                let floor = double.floor();
+                let rounded = if double - floor >= 0.5 {
+                    floor + 1.0
+                } else {
+                    floor
+                };
+                Ok(Some(rounded as i64 as u64))
+            },
+        )),
+        ..Default::default()
+    };
+    // the `java/util/Random` behind `Math.random`, made on its first call
+    let random_slot = math.static_data.len();
+    math.statics.push((
+        Field {
+            access_flags: access!(private static native),
+            name: "$random".into(),
+            descriptor: FieldType::Object("java/util/Random".into()),
+            ..Default::default()
+        },
+        random_slot,
+    ));
+    math.static_data.push(NULL);
+    let random = RawMethod {
+        access_flags: access!(public static native),
+        name: "random".into(),
+        descriptor: method!(() -> double),
+        code: RawCode::native(NativeDoubleMethod(
+            move |thread: &mut Thread, []: [u32; 0], verbose| {
+                let math = thread.class_area.search("java/lang/Math").unwrap();
+                let mut generator = math.static_data.lock().unwrap()[random_slot];
+                if generator == NULL {
+                    let mut random =
+                        Object::from_class(&thread.class_area.search("java/util/Random").unwrap());
+                    random.native_fields.push(Box::new(StdRng::from_entropy()));
+                    random.native_fields.push(Box::new(None::<f64>));
+                    generator = thread.heap.lock().unwrap().allocate(random);
+                    thread.rember(generator, verbose);
+                    math.static_data.lock().unwrap()[random_slot] = generator;
+                }
+                Random::inspect(&thread.heap, generator as usize, |random| {
+                    random.gen::<f64>().to_bits()
+                })
+                .map(Option::Some)
+            },
+        )),
+        ..Default::default()
+    };
+
+    math.register_methods(
+        [
+            abs_int,
+            abs_long,
+            abs_float,
+            math_int_binary("max", i32::max),
+            math_int_binary("min", i32::min),
+            math_long_binary("max", i64::max),
+            math_long_binary("min", i64::min),
+            math_float_binary("max", java_max),
+            math_float_binary("min", java_min),
+            math_binary("max", java_max),
+            math_binary("min", java_min),
+            round_float,
+            round_double,
+            random,
+            math_unary("abs", f64::abs),
+            math_unary("sqrt", f64::sqrt),
+            math_unary("floor", f64::floor),
+            math_unary("ceil", f64::ceil),
+            math_unary("cbrt", f64::cbrt),
+            math_unary("log10", f64::log10),
+            math_unary("toRadians", f64::to_radians),
+            math_unary("toDegrees", f64::to_degrees),
+            math_unary("expm1", f64::exp_m1),
+            math_unary("log1p", f64::ln_1p),
+            math_binary("pow", java_pow),
+            math_binary("hypot", f64::hypot),
+            math_binary("atan2", f64::atan2),
+        ],
+        method_area,
+    );
+    class_area.extend([math]);
+}
+
+/// `Math.max`, which unlike `f64::max` propagates NaN and ranks `0.0` above `-0.0`
+fn java_max(lhs: f64, rhs: f64) -> f64 {
+    if lhs.is_nan() || rhs.is_nan() {
+        f64::NAN
+    } else if lhs.total_cmp(&rhs).is_ge() {
+        lhs
+    } else {
+        rhs
+    }
+}
+
+/// `Math.min`, which unlike `f64::min` propagates NaN and ranks `-0.0` below `0.0`
+fn java_min(lhs: f64, rhs: f64) -> f64 {
+    if lhs.is_nan() || rhs.is_nan() {
+        f64::NAN
+    } else if lhs.total_cmp(&rhs).is_le() {
+        lhs
+    } else {
+        rhs
+    }
+}
+
+/// `Math.pow`, which is NaN for a NaN exponent and for `±1` to an infinite power
+fn java_pow(base: f64, exponent: f64) -> f64 {
+    if exponent.is_nan() || (base.abs().total_cmp(&1.0).is_eq() && exponent.is_infinite()) {
+        f64::NAN
+    } else {
+        base.powf(exponent)
+    }
+}
+
+/// make a static `java/lang/Math` method taking and returning a double
+fn math_unary(name: &str, func: fn(f64) -> f64) -> RawMethod {
+    RawMethod {
+        access_flags: access!(public static native),
+        name: name.into(),
+        descriptor: method!((double) -> double),
+        code: RawCode::native(NativeDoubleMethod(
+            move |_: &mut _, [left, right]: [u32; 2], _| {
+                let value = f64::from_bits((left as u64) << 32 | (right as u64));
+                Ok(Some(func(value).to_bits()))
+            },
+        )),
+        ..Default::default()
+    }
+}
+
+/// make a static `java/lang/Math` method taking two doubles and returning a double
+fn math_binary(name: &str, func: fn(f64, f64) -> f64) -> RawMethod {
+    RawMethod {
+        access_flags: access!(public static native),
+        name: name.into(),
+        descriptor: method!((double, double) -> double),
+        code: RawCode::native(NativeDoubleMethod(
+            move |_: &mut _, [a_left, a_right, b_left, b_right]: [u32; 4], _| {
+                let lhs = f64::from_bits((a_left as u64) << 32 | (a_right as u64));
+                let rhs = f64::from_bits((b_left as u64) << 32 | (b_right as u64));
+                Ok(Some(func(lhs, rhs).to_bits()))
+            },
+        )),
+        ..Default::default()
+    }
+}
+
+/// make a static `java/lang/Math` method taking two floats and returning a float. `func` works
+/// on doubles, which hold every float exactly.
+fn math_float_binary(name: &str, func: fn(f64, f64) -> f64) -> RawMethod {
+    RawMethod {
+        access_flags: access!(public static native),
+        name: name.into(),
+        descriptor: method!((float, float) -> float),
+        code: RawCode::native(NativeSingleMethod(
+            move |_: &mut _, [lhs, rhs]: [u32; 2], _| {
+                let lhs = f64::from(f32::from_bits(lhs));
+                let rhs = f64::from(f32::from_bits(rhs));
+                Ok(Some((func(lhs, rhs) as f32).to_bits()))
+            },
+        )),
+        ..Default::default()
+    }
+}
+
+/// make a static `java/lang/Math` method taking two ints and returning an int
+fn math_int_binary(name: &str, func: fn(i32, i32) -> i32) -> RawMethod {
+    RawMethod {
+        access_flags: access!(public static native),
+        name: name.into(),
+        descriptor: method!((int, int) -> int),
+        code: RawCode::native(NativeSingleMethod(
+            move |_: &mut _, [lhs, rhs]: [u32; 2], _| Ok(Some(func(lhs as i32, rhs as i32) as u32)),
+        )),
+        ..Default::default()
+    }
+}
+
+/// make a static `java/lang/Math` method taking two longs and returning a long
+fn math_long_binary(name: &str, func: fn(i64, i64) -> i64) -> RawMethod {
+    RawMethod {
+        access_flags: access!(public static native),
+        name: name.into(),
+        descriptor: method!((long, long) -> long),
+        code: RawCode::native(NativeDoubleMethod(
+            move |_: &mut _, [a_left, a_right, b_left, b_right]: [u32; 4], _| {
+                let lhs = ((a_left as u64) << 32 | (a_right as u64)) as i64;
+                let rhs = ((b_left as u64) << 32 | (b_right as u64)) as i64;
+                Ok(Some(func(lhs, rhs) as u64))
+            },
+        )),
+        ..Default::default()
+    }
+}
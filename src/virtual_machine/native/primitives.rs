@@ -1,25 +1,74 @@
-use std::{fmt::Display, sync::Arc};
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display, LowerExp},
+    sync::Arc,
+};
 
 use jvmrs_lib::{access, method, FieldType, MethodDescriptor};
 
 use crate::{
     class::{
         code::{
-            native_property, NativeDoubleMethod, NativeMethod, NativeSingleMethod, NativeTodo,
-            NativeVoid,
+            native_property, NativeDoubleMethod, NativeReturn, NativeSingleMethod,
+            NativeStringMethod, NativeTodo, NativeVoid,
         },
         Field,
     },
     class_loader::{RawClass, RawCode, RawMethod},
     data::{WorkingClassArea, WorkingMethodArea},
     virtual_machine::{
-        native::character::Char,
+        native::{character::Char, reflect::add_primitive_type},
         object::{AnyObj, Object, ObjectFinder, StringObj},
-        thread::stacking::Stackable,
         Thread,
     },
 };
 
+/// # Java Float
+/// Displays a `float` or `double` the way `Double.toString` does since Java 19: `-0.0`, `1.0`,
+/// `1.0E10`, and `4.9E-324` instead of Rust's `-0`, `1`, `10000000000`, and `5e-324`.
+#[derive(Clone, Copy, Debug)]
+pub struct JavaFloat<T>(pub T);
+
+impl<T: Display + LowerExp + Into<f64> + Copy> Display for JavaFloat<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value: f64 = self.0.into();
+        if value.is_nan() {
+            return write!(f, "NaN");
+        }
+        if value.is_sign_negative() {
+            write!(f, "-")?;
+        }
+        if value.is_infinite() {
+            return write!(f, "Infinity");
+        }
+        if value == 0.0 {
+            return write!(f, "0.0");
+        }
+        // both of rust's formats use the fewest digits that read back as the same value
+        if (1e-3..1e7).contains(&value.abs()) {
+            let decimal = self.0.to_string();
+            let decimal = decimal.trim_start_matches('-');
+            write!(f, "{decimal}")?;
+            if !decimal.contains('.') {
+                write!(f, ".0")?;
+            }
+            Ok(())
+        } else {
+            // java always shows two digits, and picks the closest ones rather than padding
+            // `5e-324` to `5.0E-324`
+            let mut scientific = format!("{:e}", self.0);
+            if !scientific.contains('.') {
+                scientific = format!("{:.1e}", self.0);
+            }
+            let scientific = scientific.trim_start_matches('-');
+            let (mantissa, exponent) = scientific.split_once('e').unwrap_or((scientific, "0"));
+            write!(f, "{mantissa}")?;
+            write!(f, "E{exponent}")
+        }
+    }
+}
+
+#[allow(clippy::too_many_lines)]
 pub(super) fn make_primitives(
     method_area: &mut WorkingMethodArea,
     class_area: &mut WorkingClassArea,
@@ -34,9 +83,10 @@ pub(super) fn make_primitives(
             "java/lang/Byte".into(),
             "byte",
             |i, _| i as u8,
-            NativeSingleMethod(native_property(StringObj::SELF, |s| {
-                s.parse::<u8>().unwrap() as u32
-            })),
+            |s| s.parse::<i8>().ok().map(|i| i as i32 as u32 as u64),
+            |lhs, rhs| lhs as u32 as i32 - rhs as u32 as i32,
+            None,
+            Vec::new(),
         ),
         make_primitive_class::<i16>(
             method_area,
@@ -46,9 +96,10 @@ pub(super) fn make_primitives(
             "java/lang/Short".into(),
             "short",
             |i, _| i as i16,
-            NativeSingleMethod(native_property(StringObj::SELF, |s| {
-                s.parse::<i16>().unwrap() as u32
-            })),
+            |s| s.parse::<i16>().ok().map(|i| i as i32 as u32 as u64),
+            |lhs, rhs| lhs as u32 as i32 - rhs as u32 as i32,
+            None,
+            Vec::new(),
         ),
         make_primitive_class::<i32>(
             method_area,
@@ -58,9 +109,10 @@ pub(super) fn make_primitives(
             "java/lang/Integer".into(),
             "int",
             |i, _| i as i32,
-            NativeSingleMethod(native_property(StringObj::SELF, |s| {
-                s.parse::<i32>().unwrap() as u32
-            })),
+            |s| s.parse::<i32>().ok().map(|i| i as u32 as u64),
+            |lhs, rhs| (lhs as u32 as i32).cmp(&(rhs as u32 as i32)) as i32,
+            Some((i32::MIN as u32 as u64, i32::MAX as u64)),
+            integer_methods(),
         ),
         make_primitive_class::<i64>(
             method_area,
@@ -70,33 +122,39 @@ pub(super) fn make_primitives(
             "java/lang/Long".into(),
             "long",
             |u, l| (((u as u64) << 32) | (l as u64)) as i64,
-            NativeDoubleMethod(native_property(StringObj::SELF, |s| {
-                s.parse::<i64>().unwrap() as u64
-            })),
+            |s| s.parse::<i64>().ok().map(|l| l as u64),
+            |lhs, rhs| (lhs as i64).cmp(&(rhs as i64)) as i32,
+            Some((i64::MIN as u64, i64::MAX as u64)),
+            long_methods(),
         ),
-        make_primitive_class::<f32>(
+        make_primitive_class::<JavaFloat<f32>>(
             method_area,
             class_area,
             object_class.clone(),
             FieldType::Float,
             "java/lang/Float".into(),
             "float",
-            |i, _| f32::from_bits(i),
-            NativeSingleMethod(native_property(StringObj::SELF, |s| {
-                s.parse::<f32>().unwrap().to_bits()
-            })),
+            |i, _| JavaFloat(f32::from_bits(i)),
+            |s| s.trim().parse::<f32>().ok().map(|f| f.to_bits() as u64),
+            |lhs, rhs| {
+                compare_floats(f32::from_bits(lhs as u32), f32::from_bits(rhs as u32)) as i32
+            },
+            // Java's MIN_VALUE is the smallest positive subnormal
+            Some((1, f32::MAX.to_bits() as u64)),
+            float_bits_methods(),
         ),
-        make_primitive_class::<f64>(
+        make_primitive_class::<JavaFloat<f64>>(
             method_area,
             class_area,
             object_class.clone(),
             FieldType::Double,
             "java/lang/Double".into(),
             "double",
-            |u, l| f64::from_bits(((u as u64) << 32) | (l as u64)),
-            NativeDoubleMethod(native_property(StringObj::SELF, |s| {
-                s.parse::<f64>().unwrap().to_bits()
-            })),
+            |u, l| JavaFloat(f64::from_bits(((u as u64) << 32) | (l as u64))),
+            |s| s.trim().parse::<f64>().ok().map(f64::to_bits),
+            |lhs, rhs| compare_floats(f64::from_bits(lhs), f64::from_bits(rhs)) as i32,
+            Some((1, f64::MAX.to_bits())),
+            double_bits_methods(),
         ),
         make_primitive_class::<bool>(
             method_area,
@@ -106,28 +164,375 @@ pub(super) fn make_primitives(
             "java/lang/Boolean".into(),
             "boolean",
             |i, _| i != 0,
-            NativeSingleMethod(native_property(StringObj::SELF, |s| {
-                s.parse::<bool>().unwrap() as u32
-            })),
+            |s| Some(u64::from(s.eq_ignore_ascii_case("true"))),
+            |lhs, rhs| lhs.cmp(&rhs) as i32,
+            None,
+            Vec::new(),
         ),
         make_primitive_class::<Char>(
             method_area,
             class_area,
             object_class,
-            FieldType::Float,
+            FieldType::Char,
             "java/lang/Character".into(),
             "char",
             |i, _| Char(i as u16),
-            NativeSingleMethod(native_property(StringObj::SELF, |s| {
-                s.parse::<char>().unwrap() as u32
-            })),
+            |s| s.parse::<char>().ok().map(|c| c as u64),
+            |lhs, rhs| lhs as i32 - rhs as i32,
+            None,
+            character_methods(),
         ),
     ]
 }
 
+/// the bit pattern that every NaN float collapses to in `Float.floatToIntBits`
+const CANONICAL_NAN_F32: u32 = 0x7fc0_0000;
+/// the bit pattern that every NaN double collapses to in `Double.doubleToLongBits`
+const CANONICAL_NAN_F64: u64 = 0x7ff8_0000_0000_0000;
+
+/// `Integer.toString(int)`, `Integer.compare`, `Integer.decode`, and `Integer.parseUnsignedInt`
+fn integer_methods() -> Vec<RawMethod> {
+    let to_string = RawMethod {
+        access_flags: access!(public static native),
+        name: "toString".into(),
+        descriptor: method!((int) -> Object("java/lang/String".into())),
+        code: RawCode::native(NativeStringMethod(|_: &mut Thread, [int]: [u32; 1], _| {
+            Ok(Some(format!("{}", int as i32).into()))
+        })),
+        ..Default::default()
+    };
+    let compare = RawMethod {
+        access_flags: access!(public static native),
+        name: "compare".into(),
+        descriptor: method!((int, int) -> int),
+        code: RawCode::native(NativeSingleMethod(
+            |_: &mut Thread, [lhs, rhs]: [u32; 2], _| {
+                Ok(Some((lhs as i32).cmp(&(rhs as i32)) as i32 as u32))
+            },
+        )),
+        ..Default::default()
+    };
+    let decode = RawMethod {
+        access_flags: access!(public static native),
+        name: "decode".into(),
+        descriptor: method!(((Object("java/lang/String".into()))) -> Object("java/lang/Integer".into())),
+        code: RawCode::native(NativeSingleMethod(
+            |thread: &mut Thread, [str_ref]: [u32; 1], verbose| {
+                let parse_fn = |s: &str| decode(s, 32).map(|int| int as u32 as u64);
+                let Some(value) = parse_or_throw(thread, str_ref, parse_fn, verbose)? else {
+                    return Ok(None);
+                };
+                let mut integer =
+                    Object::from_class(&thread.class_area.search("java/lang/Integer").unwrap());
+                integer.fields[0] = value as u32;
+                Ok(Some(thread.heap.lock().unwrap().allocate(integer)))
+            },
+        )),
+        ..Default::default()
+    };
+    let parse_unsigned_int = RawMethod {
+        access_flags: access!(public static native),
+        name: "parseUnsignedInt".into(),
+        descriptor: method!(((Object("java/lang/String".into()))) -> int),
+        code: RawCode::native(NativeSingleMethod(
+            |thread: &mut Thread, [str_ref]: [u32; 1], verbose| {
+                let parse_fn = |s: &str| s.parse::<u32>().ok().map(u64::from);
+                parse_or_throw(thread, str_ref, parse_fn, verbose)
+                    .map(|value| value.map(|value| value as u32))
+            },
+        )),
+        ..Default::default()
+    };
+    vec![to_string, compare, decode, parse_unsigned_int]
+}
+
+/// `Long.toString(long)`, `Long.compare`, `Long.decode`, and `Long.parseUnsignedLong`
+fn long_methods() -> Vec<RawMethod> {
+    let to_string = RawMethod {
+        access_flags: access!(public static native),
+        name: "toString".into(),
+        descriptor: method!((long) -> Object("java/lang/String".into())),
+        code: RawCode::native(NativeStringMethod(
+            |_: &mut Thread, [upper, lower]: [u32; 2], _| {
+                Ok(Some(
+                    format!("{}", ((upper as u64) << 32 | lower as u64) as i64).into(),
+                ))
+            },
+        )),
+        ..Default::default()
+    };
+    let compare = RawMethod {
+        access_flags: access!(public static native),
+        name: "compare".into(),
+        descriptor: method!((long, long) -> int),
+        code: RawCode::native(NativeSingleMethod(
+            |_: &mut Thread, [lhs_upper, lhs_lower, rhs_upper, rhs_lower]: [u32; 4], _| {
+                let lhs = ((lhs_upper as u64) << 32 | lhs_lower as u64) as i64;
+                let rhs = ((rhs_upper as u64) << 32 | rhs_lower as u64) as i64;
+                Ok(Some(lhs.cmp(&rhs) as i32 as u32))
+            },
+        )),
+        ..Default::default()
+    };
+    let decode = RawMethod {
+        access_flags: access!(public static native),
+        name: "decode".into(),
+        descriptor: method!(((Object("java/lang/String".into()))) -> Object("java/lang/Long".into())),
+        code: RawCode::native(NativeSingleMethod(
+            |thread: &mut Thread, [str_ref]: [u32; 1], verbose| {
+                let parse_fn = |s: &str| decode(s, 64).map(|long| long as u64);
+                let Some(value) = parse_or_throw(thread, str_ref, parse_fn, verbose)? else {
+                    return Ok(None);
+                };
+                let mut long =
+                    Object::from_class(&thread.class_area.search("java/lang/Long").unwrap());
+                long.fields[0] = (value >> 32) as u32;
+                long.fields[1] = value as u32;
+                Ok(Some(thread.heap.lock().unwrap().allocate(long)))
+            },
+        )),
+        ..Default::default()
+    };
+    let parse_unsigned_long = RawMethod {
+        access_flags: access!(public static native),
+        name: "parseUnsignedLong".into(),
+        descriptor: method!(((Object("java/lang/String".into()))) -> long),
+        code: RawCode::native(NativeDoubleMethod(
+            |thread: &mut Thread, [str_ref]: [u32; 1], verbose| {
+                parse_or_throw(thread, str_ref, |s| s.parse::<u64>().ok(), verbose)
+            },
+        )),
+        ..Default::default()
+    };
+    vec![to_string, compare, decode, parse_unsigned_long]
+}
+
+/// `Character.isDigit`, `isLetter`, `isLetterOrDigit`, `isAlphabetic`, `isUpperCase`, `isLowerCase`,
+/// and `digit(char, int)`
+fn character_methods() -> Vec<RawMethod> {
+    let digit = RawMethod {
+        access_flags: access!(public static native),
+        name: "digit".into(),
+        descriptor: method!((char, int) -> int),
+        code: RawCode::native(NativeSingleMethod(
+            |_: &mut Thread, [c, radix]: [u32; 2], _| {
+                Ok(Some(Char(c as u16).digit(radix).unwrap_or(u32::MAX)))
+            },
+        )),
+        ..Default::default()
+    };
+    // `is_numeric` and `is_alphabetic` would also accept `Ⅻ`, `½`, and combining vowel signs
+    let predicates: [(&str, fn(char) -> bool); 6] = [
+        ("isDigit", |c| Char(c as u16).decimal_digit().is_some()),
+        ("isLetter", |c| Char(c as u16).is_letter()),
+        ("isLetterOrDigit", |c| {
+            Char(c as u16).is_letter() || Char(c as u16).decimal_digit().is_some()
+        }),
+        ("isAlphabetic", char::is_alphabetic),
+        ("isUpperCase", char::is_uppercase),
+        ("isLowerCase", char::is_lowercase),
+    ];
+    predicates
+        .into_iter()
+        .map(|(name, predicate)| RawMethod {
+            access_flags: access!(public static native),
+            name: name.into(),
+            descriptor: method!((char) -> boolean),
+            code: RawCode::native(NativeSingleMethod(
+                move |_: &mut Thread, [c]: [u32; 1], _| {
+                    Ok(Some(u32::from(char::from_u32(c).is_some_and(predicate))))
+                },
+            )),
+            ..Default::default()
+        })
+        .chain([digit])
+        .collect()
+}
+
+/// `Integer.decode` and `Long.decode`: a decimal, `0x`/`0X`/`#` hexadecimal, or `0` octal number
+/// with an optional sign, which has to fit in a signed integer of `bits` bits
+fn decode(str: &str, bits: u32) -> Option<i64> {
+    let (negative, digits) = match str.as_bytes().first()? {
+        b'-' => (true, &str[1..]),
+        b'+' => (false, &str[1..]),
+        _ => (false, str),
+    };
+    let hex = digits
+        .strip_prefix("0x")
+        .or_else(|| digits.strip_prefix("0X"))
+        .or_else(|| digits.strip_prefix('#'));
+    let (radix, digits) = match hex {
+        Some(hex) => (16, hex),
+        None if digits.len() > 1 && digits.starts_with('0') => (8, &digits[1..]),
+        None => (10, digits),
+    };
+    // the sign only goes before the radix prefix
+    if digits.starts_with(['-', '+']) {
+        return None;
+    }
+    let magnitude = i128::from(u64::from_str_radix(digits, radix).ok()?);
+    let value = if negative { -magnitude } else { magnitude };
+    let limit = 1i128 << (bits - 1);
+    (-limit..limit).contains(&value).then_some(value as i64)
+}
+
+/// order two floating-point values the way `Float.compare` and `Double.compare` do: `-0.0` is less
+/// than `0.0`, and NaN is equal to itself and greater than everything else
+fn compare_floats<F: Into<f64>>(lhs: F, rhs: F) -> u32 {
+    let (lhs, rhs) = (lhs.into(), rhs.into());
+    let ordering = match (lhs.is_nan(), rhs.is_nan()) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Greater,
+        (false, true) => Ordering::Less,
+        (false, false) => lhs.total_cmp(&rhs),
+    };
+    ordering as i32 as u32
+}
+
+/// `Float.floatToIntBits`, `floatToRawIntBits`, `intBitsToFloat`, `toString(float)`, and `compare`.
+/// Floats are already stored as their bits, so only the canonical conversion does any work
+fn float_bits_methods() -> Vec<RawMethod> {
+    let float_to_int_bits = RawMethod {
+        access_flags: access!(public static native),
+        name: "floatToIntBits".into(),
+        descriptor: method!((float) -> int),
+        code: RawCode::native(NativeSingleMethod(|_: &mut Thread, [bits]: [u32; 1], _| {
+            Ok(Some(if f32::from_bits(bits).is_nan() {
+                CANONICAL_NAN_F32
+            } else {
+                bits
+            }))
+        })),
+        ..Default::default()
+    };
+    let float_to_raw_int_bits = RawMethod {
+        access_flags: access!(public static native),
+        name: "floatToRawIntBits".into(),
+        descriptor: method!((float) -> int),
+        code: RawCode::native(NativeSingleMethod(|_: &mut Thread, [bits]: [u32; 1], _| {
+            Ok(Some(bits))
+        })),
+        ..Default::default()
+    };
+    let int_bits_to_float = RawMethod {
+        access_flags: access!(public static native),
+        name: "intBitsToFloat".into(),
+        descriptor: method!((int) -> float),
+        code: RawCode::native(NativeSingleMethod(|_: &mut Thread, [bits]: [u32; 1], _| {
+            Ok(Some(bits))
+        })),
+        ..Default::default()
+    };
+    let to_string = RawMethod {
+        access_flags: access!(public static native),
+        name: "toString".into(),
+        descriptor: method!((float) -> Object("java/lang/String".into())),
+        code: RawCode::native(NativeStringMethod(|_: &mut Thread, [bits]: [u32; 1], _| {
+            Ok(Some(JavaFloat(f32::from_bits(bits)).to_string().into()))
+        })),
+        ..Default::default()
+    };
+    let compare = RawMethod {
+        access_flags: access!(public static native),
+        name: "compare".into(),
+        descriptor: method!((float, float) -> int),
+        code: RawCode::native(NativeSingleMethod(
+            |_: &mut Thread, [lhs, rhs]: [u32; 2], _| {
+                Ok(Some(compare_floats(
+                    f32::from_bits(lhs),
+                    f32::from_bits(rhs),
+                )))
+            },
+        )),
+        ..Default::default()
+    };
+    vec![
+        float_to_int_bits,
+        float_to_raw_int_bits,
+        int_bits_to_float,
+        to_string,
+        compare,
+    ]
+}
+
+/// `Double.doubleToLongBits`, `doubleToRawLongBits`, `longBitsToDouble`, `toString(double)`, and
+/// `compare`
+fn double_bits_methods() -> Vec<RawMethod> {
+    let double_to_long_bits = RawMethod {
+        access_flags: access!(public static native),
+        name: "doubleToLongBits".into(),
+        descriptor: method!((double) -> long),
+        code: RawCode::native(NativeDoubleMethod(
+            |_: &mut Thread, [upper, lower]: [u32; 2], _| {
+                let bits = (upper as u64) << 32 | lower as u64;
+                Ok(Some(if f64::from_bits(bits).is_nan() {
+                    CANONICAL_NAN_F64
+                } else {
+                    bits
+                }))
+            },
+        )),
+        ..Default::default()
+    };
+    let double_to_raw_long_bits = RawMethod {
+        access_flags: access!(public static native),
+        name: "doubleToRawLongBits".into(),
+        descriptor: method!((double) -> long),
+        code: RawCode::native(NativeDoubleMethod(
+            |_: &mut Thread, [upper, lower]: [u32; 2], _| {
+                Ok(Some((upper as u64) << 32 | lower as u64))
+            },
+        )),
+        ..Default::default()
+    };
+    let long_bits_to_double = RawMethod {
+        access_flags: access!(public static native),
+        name: "longBitsToDouble".into(),
+        descriptor: method!((long) -> double),
+        code: RawCode::native(NativeDoubleMethod(
+            |_: &mut Thread, [upper, lower]: [u32; 2], _| {
+                Ok(Some((upper as u64) << 32 | lower as u64))
+            },
+        )),
+        ..Default::default()
+    };
+    let to_string = RawMethod {
+        access_flags: access!(public static native),
+        name: "toString".into(),
+        descriptor: method!((double) -> Object("java/lang/String".into())),
+        code: RawCode::native(NativeStringMethod(
+            |_: &mut Thread, [upper, lower]: [u32; 2], _| {
+                let value = f64::from_bits((upper as u64) << 32 | lower as u64);
+                Ok(Some(JavaFloat(value).to_string().into()))
+            },
+        )),
+        ..Default::default()
+    };
+    let compare = RawMethod {
+        access_flags: access!(public static native),
+        name: "compare".into(),
+        descriptor: method!((double, double) -> int),
+        code: RawCode::native(NativeSingleMethod(
+            |_: &mut Thread, [lhs_upper, lhs_lower, rhs_upper, rhs_lower]: [u32; 4], _| {
+                let lhs = f64::from_bits((lhs_upper as u64) << 32 | lhs_lower as u64);
+                let rhs = f64::from_bits((rhs_upper as u64) << 32 | rhs_lower as u64);
+                Ok(Some(compare_floats(lhs, rhs)))
+            },
+        )),
+        ..Default::default()
+    };
+    vec![
+        double_to_long_bits,
+        double_to_raw_long_bits,
+        long_bits_to_double,
+        to_string,
+        compare,
+    ]
+}
+
 #[allow(clippy::too_many_lines)]
 #[allow(clippy::too_many_arguments)]
-fn make_primitive_class<T: Stackable<u32> + Display + 'static>(
+fn make_primitive_class<T: Display + 'static>(
     method_area: &mut WorkingMethodArea,
     class_area: &mut WorkingClassArea,
     object_class: Arc<str>,
@@ -135,12 +540,15 @@ fn make_primitive_class<T: Stackable<u32> + Display + 'static>(
     primitive_class: Arc<str>,
     primitive_name: &str,
     from_parameter: fn(u32, u32) -> T,
-    parse_fn: impl NativeMethod + 'static,
+    parse_fn: fn(&str) -> Option<u64>,
+    compare: fn(u64, u64) -> i32,
+    limits: Option<(u64, u64)>,
+    extra_methods: Vec<RawMethod>,
 ) -> RawMethod {
     let mut class = RawClass::new(
         access!(public native),
         primitive_class.clone(),
-        object_class,
+        object_class.clone(),
     );
     let primitive_size = primitive.get_size();
     class.fields.push((
@@ -215,7 +623,7 @@ fn make_primitive_class<T: Stackable<u32> + Display + 'static>(
         access_flags: access!(public native),
         name: format!("{primitive_name}Value").into(),
         descriptor: MethodDescriptor {
-            parameter_size: primitive_size,
+            parameter_size: 0,
             parameters: Vec::new(),
             return_type: Some(primitive.clone()),
         },
@@ -230,6 +638,31 @@ fn make_primitive_class<T: Stackable<u32> + Display + 'static>(
         },
         ..Default::default()
     };
+    if let Some((min, max)) = limits {
+        for (name, value) in [("MIN_VALUE", min), ("MAX_VALUE", max)] {
+            class.statics.push((
+                Field {
+                    access_flags: access!(public static native),
+                    name: name.into(),
+                    descriptor: primitive.clone(),
+                    constant_value: None,
+                    signature: None,
+                    attributes: Vec::new(),
+                },
+                class.static_data.len(),
+            ));
+            if primitive_size == 2 {
+                class
+                    .static_data
+                    .extend([(value >> 32) as u32, value as u32]);
+            } else {
+                class.static_data.push(value as u32);
+            }
+        }
+    }
+
+    add_primitive_type(&mut class, primitive_name, method_area);
+
     let parse = RawMethod {
         access_flags: access!(public static native),
         name: format!(
@@ -243,14 +676,53 @@ fn make_primitive_class<T: Stackable<u32> + Display + 'static>(
             parameters: vec![FieldType::Object("java/lang/String".into())],
             return_type: Some(primitive),
         },
-        code: RawCode::native(parse_fn),
+        code: if primitive_size == 2 {
+            RawCode::native(NativeDoubleMethod(
+                move |thread: &mut Thread, [str_ref]: [u32; 1], verbose| {
+                    parse_or_throw(thread, str_ref, parse_fn, verbose)
+                },
+            ))
+        } else {
+            RawCode::native(NativeSingleMethod(
+                move |thread: &mut Thread, [str_ref]: [u32; 1], verbose| {
+                    parse_or_throw(thread, str_ref, parse_fn, verbose)
+                        .map(|value| value.map(|value| value as u32))
+                },
+            ))
+        },
         ..Default::default()
     };
 
+    // `compareTo` takes its own class, and the erased `Comparable.compareTo(Object)` bridges to it
+    let compare_to = [primitive_class.clone(), object_class].map(|other_class| RawMethod {
+        access_flags: access!(public native),
+        name: "compareTo".into(),
+        descriptor: MethodDescriptor {
+            parameter_size: 1,
+            parameters: vec![FieldType::Object(other_class)],
+            return_type: Some(FieldType::Int),
+        },
+        code: RawCode::native(NativeSingleMethod(
+            move |thread: &mut Thread, [this, other]: [u32; 2], _| {
+                let [lhs, rhs] = [this, other].map(|ptr| {
+                    AnyObj.inspect(&thread.heap, ptr as usize, |obj| {
+                        obj.fields[..primitive_size]
+                            .iter()
+                            .fold(0, |value, &field| value << 32 | field as u64)
+                    })
+                });
+                Ok(Some(compare(lhs?, rhs?) as u32))
+            },
+        )),
+        ..Default::default()
+    });
+
     class.register_methods(
         [value_of, init, to_string, primitive_value, parse],
         method_area,
     );
+    class.register_methods(compare_to, method_area);
+    class.register_methods(extra_methods, method_area);
 
     class_area.push(class);
 
@@ -264,3 +736,22 @@ fn make_primitive_class<T: Stackable<u32> + Display + 'static>(
         ..Default::default()
     }
 }
+
+/// parse a string with `parse_fn`, throwing a `NumberFormatException` if it isn't valid
+fn parse_or_throw(
+    thread: &mut Thread,
+    str_ref: u32,
+    parse_fn: fn(&str) -> Option<u64>,
+    verbose: bool,
+) -> NativeReturn<u64> {
+    let str = StringObj::inspect(&thread.heap, str_ref as usize, |str| str.clone())?;
+    if let Some(value) = parse_fn(&str) {
+        return Ok(Some(value));
+    }
+    thread.throw_new(
+        "java/lang/NumberFormatException",
+        &format!("For input string: \"{str}\""),
+        verbose,
+    )?;
+    Ok(None)
+}
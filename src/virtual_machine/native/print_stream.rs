@@ -0,0 +1,282 @@
+use std::{
+    io::{self, Write},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use jvmrs_lib::{access, method, FieldType, MethodDescriptor};
+
+use crate::{
+    class::code::{NativeReturn, NativeSingleMethod, NativeVoid, Staged},
+    class_loader::{RawClass, RawCode, RawMethod},
+    data::{WorkingClassArea, WorkingMethodArea, NULL},
+    virtual_machine::{
+        object::{AnyObj, Object, ObjectFinder, PrintStreamObj, StringObj},
+        Thread,
+    },
+};
+
+use super::{format::format, primitives::JavaFloat};
+
+/// whether `System.out` waits for the end of a line to flush, rather than flushing every write
+static LINE_BUFFERED: AtomicBool = AtomicBool::new(false);
+
+/// Only flush `System.out` when a line ends, instead of after every `print`. This is faster for
+/// programs that print a lot, but a partial line can show up after verbose output that came later.
+pub fn set_line_buffered(line_buffered: bool) {
+    LINE_BUFFERED.store(line_buffered, Ordering::Relaxed);
+}
+
+/// # Print Target
+/// The stream that a `java/io/PrintStream` writes to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PrintTarget {
+    /// `System.out`
+    Stdout,
+    /// `System.err`
+    Stderr,
+}
+
+impl PrintTarget {
+    /// allocate a `PrintStream` that writes to this target
+    /// # Panics
+    pub fn make(self, thread: &Thread) -> u32 {
+        let mut stream =
+            Object::from_class(&thread.class_area.search("java/io/PrintStream").unwrap());
+        stream.native_fields.push(Box::new(self));
+        thread.heap.lock().unwrap().allocate(stream)
+    }
+}
+
+/// write `text` to the stream at `this`. Every write goes through the process-wide stdout or stderr
+/// handle and is flushed straight away, so output interleaves the way it would in java.
+fn print(thread: &Thread, this: u32, text: &str, newline: bool) -> NativeReturn<()> {
+    fn write_to(mut out: impl Write, text: &str, newline: bool, flush: bool) -> io::Result<()> {
+        out.write_all(text.as_bytes())?;
+        if newline {
+            out.write_all(b"\n")?;
+        }
+        if flush {
+            out.flush()?;
+        }
+        Ok(())
+    }
+    let target = PrintStreamObj::inspect(&thread.heap, this as usize, |target| *target)?;
+    match target {
+        // stdout is line-buffered already, so it only needs an explicit flush for partial lines
+        PrintTarget::Stdout => write_to(
+            io::stdout().lock(),
+            text,
+            newline,
+            !LINE_BUFFERED.load(Ordering::Relaxed),
+        ),
+        PrintTarget::Stderr => write_to(io::stderr().lock(), text, newline, true),
+    }
+    .map_err(|err| err.to_string())?;
+    Ok(Some(()))
+}
+
+/// `print(Object)` and `println(Object)`; stage 1 receives the result of the object's `toString`
+fn print_object(
+    thread: &mut Thread,
+    [this, arg]: [u32; 2],
+    newline: bool,
+    verbose: bool,
+) -> NativeReturn<()> {
+    if arg == NULL {
+        return print(thread, this, "null", newline);
+    }
+    if thread.pc_register == 0 {
+        let (to_string_class, to_string_method) =
+            AnyObj.inspect(&thread.heap, arg as usize, |obj| {
+                obj.resolve_method(
+                    &thread.method_area,
+                    &thread.class_area,
+                    "toString",
+                    &MethodDescriptor {
+                        parameter_size: 0,
+                        parameters: Vec::new(),
+                        return_type: Some(FieldType::Object("java/lang/String".into())),
+                    },
+                    verbose,
+                )
+            })??;
+        if verbose {
+            println!(
+                "Resolved java/lang/Object.toString to {}.{}",
+                to_string_class.this, to_string_method.name
+            );
+        }
+        // push a fake return address
+        thread.stackframe.operand_stack.push(1);
+        thread.invoke_method(to_string_method, to_string_class);
+        thread.stackframe.locals[0] = arg;
+        Ok(None)
+    } else {
+        let ret = thread.stackframe.operand_stack.pop().unwrap();
+        let str = StringObj::SELF.inspect(&thread.heap, ret as usize, |a| a.clone())?;
+        print(thread, this, &str, newline)
+    }
+}
+
+/// `printf(String, Object[])` and `format(String, Object[])`; see [`format`] for the stages
+fn printf(thread: &mut Thread, locals: [u32; 6], verbose: bool) -> NativeReturn<u32> {
+    let Some(text) = format(thread, locals, verbose)? else {
+        return Ok(None);
+    };
+    print(thread, locals[0], &text, false)?;
+    Ok(Some(locals[0]))
+}
+
+/// make a `print` or `println` method for each printable type
+fn print_methods(
+    name: &str,
+    newline: bool,
+    java_lang_object: &Arc<str>,
+    java_lang_string: &Arc<str>,
+) -> Vec<RawMethod> {
+    let print_method = |descriptor: MethodDescriptor, code: RawCode| RawMethod {
+        access_flags: access!(public native),
+        name: name.into(),
+        descriptor,
+        code,
+        ..Default::default()
+    };
+    let print_object = move |thread: &mut Thread, args: [u32; 2], verbose| {
+        print_object(thread, args, newline, verbose)
+    };
+    vec![
+        print_method(
+            method!(((Object(java_lang_string.clone()))) -> void),
+            RawCode::native(Staged(2, NativeVoid(print_object))),
+        ),
+        print_method(
+            method!(((Object(java_lang_object.clone()))) -> void),
+            RawCode::native(Staged(2, NativeVoid(print_object))),
+        ),
+        print_method(
+            method!((char) -> void),
+            RawCode::native(NativeVoid(
+                move |thread: &mut Thread, [this, c]: [u32; 2], _| {
+                    let char =
+                        char::from_u32(c).ok_or_else(|| String::from("Invalid Character code"))?;
+                    print(thread, this, &char.to_string(), newline)
+                },
+            )),
+        ),
+        print_method(
+            method!((boolean) -> void),
+            RawCode::native(NativeVoid(
+                move |thread: &mut Thread, [this, b]: [u32; 2], _| {
+                    print(thread, this, &(b != 0).to_string(), newline)
+                },
+            )),
+        ),
+        print_method(
+            method!((int) -> void),
+            RawCode::native(NativeVoid(
+                move |thread: &mut Thread, [this, i]: [u32; 2], _| {
+                    print(thread, this, &(i as i32).to_string(), newline)
+                },
+            )),
+        ),
+        print_method(
+            method!((long) -> void),
+            RawCode::native(NativeVoid(
+                move |thread: &mut Thread, [this, left, right]: [u32; 3], _| {
+                    let long = ((left as u64) << 32 | (right as u64)) as i64;
+                    print(thread, this, &long.to_string(), newline)
+                },
+            )),
+        ),
+        print_method(
+            method!((float) -> void),
+            RawCode::native(NativeVoid(
+                move |thread: &mut Thread, [this, f]: [u32; 2], _| {
+                    let float = JavaFloat(f32::from_bits(f));
+                    print(thread, this, &float.to_string(), newline)
+                },
+            )),
+        ),
+        print_method(
+            method!((double) -> void),
+            RawCode::native(NativeVoid(
+                move |thread: &mut Thread, [this, left, right]: [u32; 3], _| {
+                    let double = JavaFloat(f64::from_bits((left as u64) << 32 | (right as u64)));
+                    print(thread, this, &double.to_string(), newline)
+                },
+            )),
+        ),
+    ]
+}
+
+pub(super) fn add_native_methods(
+    method_area: &mut WorkingMethodArea,
+    class_area: &mut WorkingClassArea,
+    java_lang_object: &Arc<str>,
+    java_lang_string: &Arc<str>,
+) {
+    let mut print_stream = RawClass::new(
+        access!(public native),
+        "java/io/PrintStream".into(),
+        java_lang_object.clone(),
+    );
+    let println_empty = RawMethod {
+        access_flags: access!(public native),
+        name: "println".into(),
+        descriptor: method!(() -> void),
+        code: RawCode::native(NativeVoid(|thread: &mut Thread, [this]: [u32; 1], _| {
+            print(thread, this, "", true)
+        })),
+        ..Default::default()
+    };
+    let flush = RawMethod {
+        access_flags: access!(public native),
+        name: "flush".into(),
+        descriptor: method!(() -> void),
+        code: RawCode::native(NativeVoid(|thread: &mut Thread, [this]: [u32; 1], _| {
+            let target = PrintStreamObj::inspect(&thread.heap, this as usize, |target| *target)?;
+            match target {
+                PrintTarget::Stdout => io::stdout().flush(),
+                PrintTarget::Stderr => io::stderr().flush(),
+            }
+            .map_err(|err| err.to_string())?;
+            Ok(Some(()))
+        })),
+        ..Default::default()
+    };
+    let printf = RawMethod {
+        access_flags: access!(public native),
+        name: "printf".into(),
+        descriptor: MethodDescriptor {
+            parameter_size: 2,
+            parameters: vec![
+                FieldType::Object(java_lang_string.clone()),
+                FieldType::Array(Box::new(FieldType::Object(java_lang_object.clone()))),
+            ],
+            return_type: Some(FieldType::Object(print_stream.this.clone())),
+        },
+        code: RawCode::native(Staged(2, NativeSingleMethod(printf))),
+        ..Default::default()
+    };
+    let format = RawMethod {
+        name: "format".into(),
+        ..printf.clone()
+    };
+    print_stream.register_methods(
+        print_methods("print", false, java_lang_object, java_lang_string)
+            .into_iter()
+            .chain(print_methods(
+                "println",
+                true,
+                java_lang_object,
+                java_lang_string,
+            ))
+            .chain([println_empty, printf, format, flush]),
+        method_area,
+    );
+
+    class_area.extend([print_stream]);
+}
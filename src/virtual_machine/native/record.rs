@@ -0,0 +1,316 @@
+use std::{fmt::Write, sync::Arc};
+
+use jvmrs_lib::{access, method, FieldType};
+
+use crate::{
+    class::{
+        code::{NativeMethod, NativeNoop},
+        Code, Method,
+    },
+    class_loader::{RawClass, RawCode, RawMethod},
+    data::{WorkingClassArea, WorkingMethodArea, NULL},
+    virtual_machine::{
+        error,
+        object::{AnyObj, ObjectFinder, StringBuilder, StringObj},
+        Thread,
+    },
+};
+
+use super::primitives::JavaFloat;
+
+pub fn add_native_methods(
+    method_area: &mut WorkingMethodArea,
+    class_area: &mut WorkingClassArea,
+    java_lang_object: &Arc<str>,
+) {
+    let mut record = RawClass::new(
+        access!(public abstract native),
+        "java/lang/Record".into(),
+        java_lang_object.clone(),
+    );
+    let record_init = RawMethod {
+        access_flags: access!(public native),
+        name: "<init>".into(),
+        descriptor: method!(() -> void),
+        code: RawCode::native(NativeNoop),
+        ..Default::default()
+    };
+    record.register_method(record_init, method_area);
+    class_area.extend([record]);
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RecordMethodKind {
+    ToString,
+    HashCode,
+    Equals,
+}
+
+/// # Record Method
+/// A `toString`, `hashCode`, or `equals` implementation made by `java/lang/runtime/ObjectMethods` for a record class.
+#[derive(Clone, Debug)]
+pub struct RecordMethod {
+    pub kind: RecordMethodKind,
+    /// the record class
+    pub class: Arc<str>,
+    /// name and type of each component, in declaration order
+    pub components: Vec<(Arc<str>, FieldType)>,
+}
+
+impl RecordMethod {
+    /// the number of locals taken up by the method's parameters
+    const fn params(&self) -> usize {
+        match self.kind {
+            RecordMethodKind::Equals => 2,
+            RecordMethodKind::ToString | RecordMethodKind::HashCode => 1,
+        }
+    }
+
+    #[must_use]
+    pub fn as_method(&self) -> Method {
+        let (name, descriptor) = match self.kind {
+            RecordMethodKind::ToString => (
+                "toString",
+                method!(((Object(self.class.clone()))) -> Object("java/lang/String".into())),
+            ),
+            RecordMethodKind::HashCode => {
+                ("hashCode", method!(((Object(self.class.clone()))) -> int))
+            }
+            RecordMethodKind::Equals => (
+                "equals",
+                method!(((Object(self.class.clone())), (Object("java/lang/Object".into()))) -> boolean),
+            ),
+        };
+        Method {
+            // parameters, then the component index and the accumulated result
+            max_locals: self.params() as u16 + 2,
+            access_flags: access!(public static),
+            name: name.into(),
+            code: Code::native(self.clone()),
+            descriptor,
+            ..Default::default()
+        }
+    }
+
+    /// read the value of a component from a record object
+    fn component(&self, thread: &Thread, record: u32, index: usize) -> error::Result<u64> {
+        let (name, field_type) = &self.components[index];
+        let class = thread
+            .class_area
+            .search(&self.class)
+            .ok_or_else(|| error::Error::class_resolution(&self.class))?;
+        let &(_, field_index) = class
+            .fields
+            .iter()
+            .find(|(field, _)| &field.name == name)
+            .ok_or_else(|| {
+                error::Error::LinkageError(format!(
+                    "Couldn't find component `{name}` on record `{}`",
+                    self.class
+                ))
+            })?;
+        AnyObj.inspect(&thread.heap, record as usize, |obj| {
+            if field_type.get_size() == 1 {
+                obj.fields[field_index] as u64
+            } else {
+                (obj.fields[field_index] as u64) << 32 | obj.fields[field_index + 1] as u64
+            }
+        })
+    }
+
+    fn finish(thread: &mut Thread, value: u32, verbose: bool) {
+        thread.stackframe.operand_stack.push(value);
+        thread.return_one(verbose);
+    }
+}
+
+impl NativeMethod for RecordMethod {
+    fn args(&self) -> u16 {
+        self.params() as u16
+    }
+
+    /// stage 0 sets up, stage 1 visits the next component, and stage 2 receives the result of a
+    /// call on a reference component
+    fn stages(&self) -> usize {
+        3
+    }
+
+    #[allow(clippy::too_many_lines)]
+    fn run(&self, thread: &mut Thread, verbose: bool) -> error::Result<()> {
+        let params = self.params();
+        let this = thread.stackframe.locals[0];
+        let index = thread.stackframe.locals[params] as usize;
+        let accumulator = thread.stackframe.locals[params + 1];
+        match thread.pc_register {
+            0 => {
+                thread.pc_register = 1;
+                thread.stackframe.locals[params] = 0;
+                match self.kind {
+                    RecordMethodKind::ToString => {
+                        let simple_name = self
+                            .class
+                            .rsplit(['/', '$'])
+                            .next()
+                            .unwrap_or(&self.class)
+                            .to_string();
+                        let builder = StringBuilder::new(simple_name + "[", &thread.class_area);
+                        let builder_ref = thread.heap.lock().unwrap().allocate(builder);
+                        thread.rember_temp(builder_ref, verbose);
+                        thread.stackframe.locals[params + 1] = builder_ref;
+                    }
+                    RecordMethodKind::HashCode => {
+                        thread.stackframe.locals[params + 1] = 0;
+                    }
+                    RecordMethodKind::Equals => {
+                        let other = thread.stackframe.locals[1];
+                        if other == this {
+                            Self::finish(thread, 1, verbose);
+                        } else if other == NULL
+                            || AnyObj.inspect(&thread.heap, other as usize, |obj| {
+                                obj.class != self.class
+                            })?
+                        {
+                            Self::finish(thread, 0, verbose);
+                        }
+                    }
+                }
+                Ok(())
+            }
+            1 => {
+                if index >= self.components.len() {
+                    let result = match self.kind {
+                        RecordMethodKind::ToString => {
+                            let str = StringBuilder::inspect(
+                                &thread.heap,
+                                accumulator as usize,
+                                |builder| {
+                                    builder.push(']');
+                                    Arc::<str>::from(&**builder)
+                                },
+                            )?;
+                            thread.heap.lock().unwrap().allocate_str(str)
+                        }
+                        RecordMethodKind::HashCode => accumulator,
+                        RecordMethodKind::Equals => 1,
+                    };
+                    Self::finish(thread, result, verbose);
+                    return Ok(());
+                }
+                let (name, field_type) = &self.components[index];
+                let value = self.component(thread, this, index)?;
+                if field_type.is_reference() && value as u32 != NULL {
+                    let (method, descriptor) = match self.kind {
+                        RecordMethodKind::ToString => {
+                            ("toString", method!(() -> Object("java/lang/String".into())))
+                        }
+                        RecordMethodKind::HashCode => ("hashCode", method!(() -> int)),
+                        RecordMethodKind::Equals => (
+                            "equals",
+                            method!(((Object("java/lang/Object".into()))) -> boolean),
+                        ),
+                    };
+                    let other_value = if self.kind == RecordMethodKind::Equals {
+                        self.component(thread, thread.stackframe.locals[1], index)? as u32
+                    } else {
+                        NULL
+                    };
+                    // push a fake return address
+                    thread.stackframe.operand_stack.push(2);
+                    thread.resolve_and_invoke(value as u32, method, &descriptor, verbose)?;
+                    thread.stackframe.locals[0] = value as u32;
+                    if self.kind == RecordMethodKind::Equals {
+                        thread.stackframe.locals[1] = other_value;
+                    }
+                    return Ok(());
+                }
+                match self.kind {
+                    RecordMethodKind::ToString => {
+                        let mut str = String::new();
+                        if index > 0 {
+                            str.push_str(", ");
+                        }
+                        write!(str, "{name}=").map_err(|err| format!("{err:?}"))?;
+                        match field_type {
+                            FieldType::Boolean => write!(str, "{}", value != 0),
+                            FieldType::Char => write!(
+                                str,
+                                "{}",
+                                char::from_u32(value as u32).unwrap_or(char::REPLACEMENT_CHARACTER)
+                            ),
+                            FieldType::Float => {
+                                write!(str, "{}", JavaFloat(f32::from_bits(value as u32)))
+                            }
+                            FieldType::Double => {
+                                write!(str, "{}", JavaFloat(f64::from_bits(value)))
+                            }
+                            FieldType::Long => write!(str, "{}", value as i64),
+                            FieldType::Object(_) | FieldType::Array(_) => write!(str, "null"),
+                            _ => write!(str, "{}", value as u32 as i32),
+                        }
+                        .map_err(|err| format!("{err:?}"))?;
+                        StringBuilder::inspect(&thread.heap, accumulator as usize, |builder| {
+                            builder.push_str(&str);
+                        })?;
+                    }
+                    RecordMethodKind::HashCode => {
+                        let hash = match field_type {
+                            FieldType::Boolean => {
+                                if value == 0 {
+                                    1237
+                                } else {
+                                    1231
+                                }
+                            }
+                            FieldType::Long | FieldType::Double => (value ^ (value >> 32)) as u32,
+                            _ => value as u32,
+                        };
+                        thread.stackframe.locals[params + 1] =
+                            accumulator.wrapping_mul(31).wrapping_add(hash);
+                    }
+                    RecordMethodKind::Equals => {
+                        let other_value =
+                            self.component(thread, thread.stackframe.locals[1], index)?;
+                        if value != other_value {
+                            Self::finish(thread, 0, verbose);
+                            return Ok(());
+                        }
+                    }
+                }
+                thread.stackframe.locals[params] += 1;
+                Ok(())
+            }
+            2 => {
+                let result = thread.stackframe.operand_stack.pop().unwrap();
+                match self.kind {
+                    RecordMethodKind::ToString => {
+                        let (name, _) = &self.components[index];
+                        let string =
+                            StringObj::inspect(&thread.heap, result as usize, |str| str.clone())?;
+                        StringBuilder::inspect(&thread.heap, accumulator as usize, |builder| {
+                            if index > 0 {
+                                builder.push_str(", ");
+                            }
+                            builder.push_str(name);
+                            builder.push('=');
+                            builder.push_str(&string);
+                        })?;
+                    }
+                    RecordMethodKind::HashCode => {
+                        thread.stackframe.locals[params + 1] =
+                            accumulator.wrapping_mul(31).wrapping_add(result);
+                    }
+                    RecordMethodKind::Equals => {
+                        if result == 0 {
+                            Self::finish(thread, 0, verbose);
+                            return Ok(());
+                        }
+                    }
+                }
+                thread.stackframe.locals[params] += 1;
+                thread.pc_register = 1;
+                Ok(())
+            }
+            other => Err(format!("Invalid pc in Record Method: {other}").into()),
+        }
+    }
+}
@@ -1,23 +1,93 @@
-use std::sync::Arc;
+use std::{
+    fs,
+    io::Cursor,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
 
-use jvmrs_lib::{access, method};
+use jvmrs_lib::{access, method, FieldType, MethodDescriptor};
 
 use crate::{
-    class::code::{native_property, NativeStringMethod},
-    class_loader::{RawClass, RawCode, RawMethod},
-    data::{WorkingClassArea, WorkingMethodArea},
-    virtual_machine::object::ClassObj,
+    class::{
+        code::{native_property, NativeSingleMethod, NativeStringMethod, NativeVoid},
+        Field,
+    },
+    class_loader::{find_resource, RawClass, RawCode, RawMethod},
+    data::{WorkingClassArea, WorkingMethodArea, NULL},
+    virtual_machine::{
+        error,
+        object::{Array1, ByteStream, ClassObj, MethodObj, Object, StringObj},
+        Thread,
+    },
 };
 
+use super::get_class;
+
+/// the directory that `Class.getResourceAsStream` looks in; defaults to the working directory
+static RESOURCE_ROOT: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// the jars and directories that `Class.getResourceAsStream` looks in when the resource root doesn't
+/// have a resource
+static RESOURCE_CLASSPATH: Mutex<Vec<PathBuf>> = Mutex::new(Vec::new());
+
+/// Load resources for `Class.getResourceAsStream` from the given directory instead of the working
+/// directory
+/// # Panics
+/// Panics if a thread panicked while loading a resource
+pub fn set_resource_root(root: PathBuf) {
+    *RESOURCE_ROOT.lock().unwrap() = Some(root);
+}
+
+/// Look for resources for `Class.getResourceAsStream` in these jars and directories, in order,
+/// when the resource root doesn't have them
+/// # Panics
+/// Panics if a thread panicked while loading a resource
+pub fn set_resource_classpath(classpath: Vec<PathBuf>) {
+    *RESOURCE_CLASSPATH.lock().unwrap() = classpath;
+}
+
+/// resolve a resource name the way Java does: an absolute name starts at the root, and any other
+/// name is relative to the class's package. A name whose `..`s climb out of the root isn't a
+/// resource
+fn resource_name(class: &str, name: &str) -> Option<String> {
+    let name = match (name.strip_prefix('/'), class.rsplit_once('/')) {
+        (Some(absolute), _) => absolute.to_string(),
+        (None, Some((package, _))) => format!("{package}/{name}"),
+        (None, None) => name.to_string(),
+    };
+    let mut parts = Vec::new();
+    for part in name.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop()?;
+            }
+            part => parts.push(part),
+        }
+    }
+    Some(parts.join("/"))
+}
+
+/// read a resource from the resource root, or else from the resource classpath
+fn read_resource(name: &str) -> Option<Vec<u8>> {
+    let root = RESOURCE_ROOT.lock().unwrap().clone().unwrap_or_default();
+    fs::read(root.join(name))
+        .ok()
+        .or_else(|| find_resource(&RESOURCE_CLASSPATH.lock().unwrap(), name))
+}
+
+#[allow(clippy::too_many_lines)]
 pub fn add_native_methods(
     method_area: &mut WorkingMethodArea,
     class_area: &mut WorkingClassArea,
     java_lang_object: &Arc<str>,
     java_lang_string: &Arc<str>,
 ) {
+    let java_lang_class: Arc<str> = Arc::from("java/lang/Class");
+    let java_lang_reflect_method: Arc<str> = Arc::from("java/lang/reflect/Method");
     let mut class_class = RawClass::new(
         access!(public native),
-        "java/lang/Class".into(),
+        java_lang_class.clone(),
         java_lang_object.clone(),
     );
     let class_name = RawMethod {
@@ -25,11 +95,150 @@ pub fn add_native_methods(
         access_flags: access!(public native),
         descriptor: method!(() -> Object(java_lang_string.clone())),
         code: RawCode::native(NativeStringMethod(native_property(ClassObj::SELF, |cls| {
-            cls.this.clone()
+            cls.this.replace('/', ".").into()
         }))),
         ..Default::default()
     };
-    class_class.register_method(class_name, method_area);
+    let get_resource_as_stream = RawMethod {
+        name: "getResourceAsStream".into(),
+        access_flags: access!(public native),
+        descriptor: method!(((Object(java_lang_string.clone()))) -> Object("java/io/InputStream".into())),
+        code: RawCode::native(NativeSingleMethod(
+            |thread: &mut Thread, [this, name]: [u32; 2], _| {
+                let class = ClassObj::inspect(&thread.heap, this as usize, |cls| cls.this.clone())?;
+                let name = StringObj::inspect(&thread.heap, name as usize, |str| str.clone())?;
+                // a missing resource is null rather than an exception
+                let Some(bytes) =
+                    resource_name(&class, &name).and_then(|name| read_resource(&name))
+                else {
+                    return Ok(Some(NULL));
+                };
+                let mut stream = Object::from_class(
+                    &thread
+                        .class_area
+                        .search("java/io/ByteArrayInputStream")
+                        .unwrap(),
+                );
+                stream.native_fields.push(Box::new(Cursor::new(bytes)));
+                Ok(Some(thread.heap.lock().unwrap().allocate(stream)))
+            },
+        )),
+        ..Default::default()
+    };
+    class_class.register_methods([class_name, get_resource_as_stream], method_area);
+
+    let mut method_class = RawClass::new(
+        access!(public native),
+        java_lang_reflect_method,
+        java_lang_object.clone(),
+    );
+    let method_name = RawMethod {
+        name: "getName".into(),
+        access_flags: access!(public native),
+        descriptor: method!(() -> Object(java_lang_string.clone())),
+        code: RawCode::native(NativeStringMethod(native_property(
+            MethodObj::SELF,
+            |method| method.name.clone(),
+        ))),
+        ..Default::default()
+    };
+    let get_exception_types = RawMethod {
+        name: "getExceptionTypes".into(),
+        access_flags: access!(public native),
+        descriptor: array_return(FieldType::Object(java_lang_class)),
+        code: RawCode::native(NativeSingleMethod(
+            |thread: &mut Thread, [this]: [u32; 1], _| {
+                let exceptions = MethodObj::inspect(&thread.heap, this as usize, |method| {
+                    method.exceptions.clone()
+                })?;
+                let classes = exceptions
+                    .into_iter()
+                    .map(|exception| {
+                        let ptr = get_class(&thread.heap, &thread.class_area, exception)
+                            .ok_or_else(|| error::Error::class_resolution(&"java/lang/Class"))?;
+                        thread.heap.lock().unwrap().inc_ref(ptr);
+                        Ok(ptr)
+                    })
+                    .collect::<error::Result<Vec<_>>>()?;
+                let array = thread.heap.lock().unwrap().allocate(Array1::from_vec(
+                    classes,
+                    FieldType::Object("java/lang/Class".into()),
+                ));
+                Ok(Some(array))
+            },
+        )),
+        ..Default::default()
+    };
+    method_class.register_methods([method_name, get_exception_types], method_area);
+
+    // `int.class` and friends are `Class` objects too, but there's nothing to load for them
+    let primitive_classes = [
+        "boolean", "byte", "char", "short", "int", "long", "float", "double", "void",
+    ]
+    .map(|name| {
+        RawClass::new(
+            access!(public abstract native),
+            name.into(),
+            java_lang_object.clone(),
+        )
+    });
+
+    let mut void = RawClass::new(
+        access!(public native),
+        "java/lang/Void".into(),
+        java_lang_object.clone(),
+    );
+    add_primitive_type(&mut void, "void", method_area);
+
+    class_area.extend([class_class, method_class, void]);
+    class_area.extend(primitive_classes);
+}
+
+/// add the `TYPE` static that holds the `Class` object of a primitive type to its wrapper class,
+/// along with a `<clinit>` to set it
+pub(super) fn add_primitive_type(
+    wrapper: &mut RawClass,
+    primitive_name: &str,
+    method_area: &mut WorkingMethodArea,
+) {
+    let type_index = wrapper.static_data.len();
+    wrapper.static_data.push(NULL);
+    wrapper.statics.push((
+        Field {
+            access_flags: access!(public static native),
+            name: "TYPE".into(),
+            descriptor: FieldType::Object("java/lang/Class".into()),
+            ..Default::default()
+        },
+        type_index,
+    ));
+    let wrapper_name = wrapper.this.clone();
+    let primitive_name: Arc<str> = Arc::from(primitive_name);
+    let clinit = RawMethod {
+        name: "<clinit>".into(),
+        access_flags: access!(public static native),
+        descriptor: method!(() -> void),
+        code: RawCode::native(NativeVoid(
+            move |thread: &mut Thread, []: [u32; 0], verbose| {
+                let class_ptr = get_class(&thread.heap, &thread.class_area, primitive_name.clone())
+                    .ok_or_else(|| error::Error::class_resolution(&"java/lang/Class"))?;
+                // class objects are cached, so they have to live forever
+                thread.rember(class_ptr, verbose);
+                let wrapper = thread.class_area.search(&wrapper_name).unwrap();
+                wrapper.static_data.lock().unwrap()[type_index] = class_ptr;
+                Ok(Some(()))
+            },
+        )),
+        ..Default::default()
+    };
+    wrapper.register_method(clinit, method_area);
+}
 
-    class_area.extend([class_class]);
+/// a descriptor for a method that takes no arguments and returns an array
+fn array_return(element: FieldType) -> MethodDescriptor {
+    MethodDescriptor {
+        parameter_size: 0,
+        parameters: Vec::new(),
+        return_type: Some(FieldType::Array(Box::new(element))),
+    }
 }
@@ -3,13 +3,16 @@ use std::sync::Arc;
 use jvmrs_lib::{access, method};
 
 use crate::{
-    class::code::{NativeSingleMethod, NativeVoid},
+    class::code::{NativeSingleMethod, NativeVoid, Staged},
     class_loader::{RawClass, RawCode, RawMethod},
     data::{WorkingClassArea, WorkingMethodArea},
     virtual_machine::{object::ObjectFinder, Thread},
 };
 
-use super::function::{make_lambda_override, Optional};
+use super::{
+    collectors::stream_collect,
+    function::{make_lambda_override, Optional},
+};
 
 #[allow(clippy::too_many_lines)]
 pub(super) fn add_native_methods(
@@ -37,39 +40,48 @@ pub(super) fn add_native_methods(
             name: "allMatch".into(),
             access_flags: access!(public native),
             descriptor: method!(((Object("java/util/function/Predicate".into()))) -> boolean),
-            code: RawCode::native(NativeSingleMethod(
-                move |thread: &mut Thread, [this, predicate]: [u32; 2], verbose| match thread
-                    .pc_register
-                {
-                    0 => {
-                        thread.stackframe.operand_stack.push(1);
-                        thread.resolve_and_invoke(this, "$next", &next_descriptor, verbose)?;
-                        thread.stackframe.locals[0] = this;
-                        Ok(None)
-                    }
-                    1 => {
-                        let ret_opt = thread.stackframe.operand_stack.pop().unwrap();
-                        let Some(ret) = Optional.inspect(&thread.heap, ret_opt as usize, |o| *o)?
-                        else {
-                            return Ok(Some(1));
-                        };
-                        thread.stackframe.operand_stack.push(2);
-                        thread.resolve_and_invoke(predicate, "test", &test_descriptor, verbose)?;
-                        thread.stackframe.locals[0] = predicate;
-                        thread.stackframe.locals[1] = ret;
-                        Ok(None)
-                    }
-                    2 => {
-                        let predicate_ret = thread.stackframe.operand_stack.pop().unwrap();
-                        if predicate_ret == 0 {
-                            Ok(Some(0))
-                        } else {
-                            thread.pc_register = 0;
+            code: RawCode::native(Staged(
+                3,
+                NativeSingleMethod(
+                    move |thread: &mut Thread, [this, predicate]: [u32; 2], verbose| match thread
+                        .pc_register
+                    {
+                        0 => {
+                            thread.stackframe.operand_stack.push(1);
+                            thread.resolve_and_invoke(this, "$next", &next_descriptor, verbose)?;
+                            thread.stackframe.locals[0] = this;
                             Ok(None)
                         }
-                    }
-                    _ => unreachable!(),
-                },
+                        1 => {
+                            let ret_opt = thread.stackframe.operand_stack.pop().unwrap();
+                            let Some(ret) =
+                                Optional.inspect(&thread.heap, ret_opt as usize, |o| *o)?
+                            else {
+                                return Ok(Some(1));
+                            };
+                            thread.stackframe.operand_stack.push(2);
+                            thread.resolve_and_invoke(
+                                predicate,
+                                "test",
+                                &test_descriptor,
+                                verbose,
+                            )?;
+                            thread.stackframe.locals[0] = predicate;
+                            thread.stackframe.locals[1] = ret;
+                            Ok(None)
+                        }
+                        2 => {
+                            let predicate_ret = thread.stackframe.operand_stack.pop().unwrap();
+                            if predicate_ret == 0 {
+                                Ok(Some(0))
+                            } else {
+                                thread.pc_register = 0;
+                                Ok(None)
+                            }
+                        }
+                        _ => unreachable!(),
+                    },
+                ),
             )),
             ..Default::default()
         }
@@ -82,44 +94,53 @@ pub(super) fn add_native_methods(
             name: "anyMatch".into(),
             access_flags: access!(public native),
             descriptor: method!(((Object("java/util/function/Predicate".into()))) -> boolean),
-            code: RawCode::native(NativeSingleMethod(
-                move |thread: &mut Thread, [this, predicate]: [u32; 2], verbose| match thread
-                    .pc_register
-                {
-                    0 => {
-                        thread.stackframe.operand_stack.push(1);
-                        thread.resolve_and_invoke(this, "$next", &next_descriptor, verbose)?;
-                        thread.stackframe.locals[0] = this;
-                        Ok(None)
-                    }
-                    1 => {
-                        let ret_opt = thread.stackframe.operand_stack.pop().unwrap();
-                        let Some(ret) = Optional.inspect(&thread.heap, ret_opt as usize, |o| *o)?
-                        else {
-                            return Ok(Some(0));
-                        };
-                        thread.stackframe.operand_stack.push(2);
-                        thread.resolve_and_invoke(predicate, "test", &test_descriptor, verbose)?;
-                        thread.stackframe.locals[0] = predicate;
-                        thread.stackframe.locals[1] = ret;
-                        Ok(None)
-                    }
-                    2 => {
-                        let predicate_ret = thread.stackframe.operand_stack.pop().unwrap();
-                        if predicate_ret == 0 {
-                            thread.pc_register = 0;
+            code: RawCode::native(Staged(
+                3,
+                NativeSingleMethod(
+                    move |thread: &mut Thread, [this, predicate]: [u32; 2], verbose| match thread
+                        .pc_register
+                    {
+                        0 => {
+                            thread.stackframe.operand_stack.push(1);
+                            thread.resolve_and_invoke(this, "$next", &next_descriptor, verbose)?;
+                            thread.stackframe.locals[0] = this;
                             Ok(None)
-                        } else {
-                            Ok(Some(1))
                         }
-                    }
-                    _ => unreachable!(),
-                },
+                        1 => {
+                            let ret_opt = thread.stackframe.operand_stack.pop().unwrap();
+                            let Some(ret) =
+                                Optional.inspect(&thread.heap, ret_opt as usize, |o| *o)?
+                            else {
+                                return Ok(Some(0));
+                            };
+                            thread.stackframe.operand_stack.push(2);
+                            thread.resolve_and_invoke(
+                                predicate,
+                                "test",
+                                &test_descriptor,
+                                verbose,
+                            )?;
+                            thread.stackframe.locals[0] = predicate;
+                            thread.stackframe.locals[1] = ret;
+                            Ok(None)
+                        }
+                        2 => {
+                            let predicate_ret = thread.stackframe.operand_stack.pop().unwrap();
+                            if predicate_ret == 0 {
+                                thread.pc_register = 0;
+                                Ok(None)
+                            } else {
+                                Ok(Some(1))
+                            }
+                        }
+                        _ => unreachable!(),
+                    },
+                ),
             )),
             ..Default::default()
         }
     };
-    // TODO: collect
+    let collect = stream_collect(java_lang_object);
     // TODO: concat
     // TODO: count
     // TODO: distinct
@@ -145,50 +166,63 @@ pub(super) fn add_native_methods(
             name: "$filter".into(),
             access_flags: access!(private native static),
             descriptor: method!(((Object(stream.this.clone())), (Object("java/util/function/Predicate".into()))) -> Object("java/util/Optional".into())),
-            code: RawCode::native(NativeSingleMethod(
-                move |thread: &mut Thread, [stream, predicate]: [u32; 2], verbose| match thread
-                    .pc_register
-                {
-                    0 => {
-                        thread.stackframe.operand_stack.push(1);
-                        thread.resolve_and_invoke(stream, "$next", &next_descriptor, verbose)?;
-                        thread.stackframe.locals[0] = stream;
-                        Ok(None)
-                    }
-                    1 => {
-                        let returned_opt = thread.stackframe.operand_stack.pop().unwrap();
-                        let Some(returned_obj) =
-                            Optional.inspect(&thread.heap, returned_opt as usize, |o| *o)?
-                        else {
-                            if verbose {
-                                println!("Stream end - returning None");
+            code: RawCode::native(Staged(
+                3,
+                NativeSingleMethod(
+                    move |thread: &mut Thread, [stream, predicate]: [u32; 2], verbose| match thread
+                        .pc_register
+                    {
+                        0 => {
+                            thread.stackframe.operand_stack.push(1);
+                            thread.resolve_and_invoke(
+                                stream,
+                                "$next",
+                                &next_descriptor,
+                                verbose,
+                            )?;
+                            thread.stackframe.locals[0] = stream;
+                            Ok(None)
+                        }
+                        1 => {
+                            let returned_opt = thread.stackframe.operand_stack.pop().unwrap();
+                            let Some(returned_obj) =
+                                Optional.inspect(&thread.heap, returned_opt as usize, |o| *o)?
+                            else {
+                                if verbose {
+                                    println!("Stream end - returning None");
+                                }
+                                return Ok(Some(returned_opt));
+                            };
+                            thread.stackframe.operand_stack.push(returned_opt);
+                            thread.stackframe.operand_stack.push(2);
+                            thread.resolve_and_invoke(
+                                predicate,
+                                "test",
+                                &test_descriptor,
+                                verbose,
+                            )?;
+                            thread.stackframe.locals[0] = predicate;
+                            thread.stackframe.locals[1] = returned_obj;
+                            Ok(None)
+                        }
+                        2 => {
+                            let test_result = thread.stackframe.operand_stack.pop().unwrap();
+                            if test_result == 0 {
+                                if verbose {
+                                    println!("Predicate failed - continuing to next option");
+                                }
+                                thread.stackframe.operand_stack.pop();
+                                thread.pc_register = 0;
+                                return Ok(None);
                             }
-                            return Ok(Some(returned_opt));
-                        };
-                        thread.stackframe.operand_stack.push(returned_opt);
-                        thread.stackframe.operand_stack.push(2);
-                        thread.resolve_and_invoke(predicate, "test", &test_descriptor, verbose)?;
-                        thread.stackframe.locals[0] = predicate;
-                        thread.stackframe.locals[1] = returned_obj;
-                        Ok(None)
-                    }
-                    2 => {
-                        let test_result = thread.stackframe.operand_stack.pop().unwrap();
-                        if test_result == 0 {
                             if verbose {
-                                println!("Predicate failed - continuing to next option");
+                                println!("Predicate succeeded - returning the tested object");
                             }
-                            thread.stackframe.operand_stack.pop();
-                            thread.pc_register = 0;
-                            return Ok(None);
-                        }
-                        if verbose {
-                            println!("Predicate succeeded - returning the tested object");
+                            Ok(Some(thread.stackframe.operand_stack.pop().unwrap()))
                         }
-                        Ok(Some(thread.stackframe.operand_stack.pop().unwrap()))
-                    }
-                    _ => unreachable!(),
-                },
+                        _ => unreachable!(),
+                    },
+                ),
             )),
             ..Default::default()
         }
@@ -223,37 +257,40 @@ pub(super) fn add_native_methods(
             name: "forEach".into(),
             access_flags: access!(public native),
             descriptor: method!(((Object("java/util/function/Consumer".into()))) -> void),
-            code: RawCode::native(NativeVoid(
-                move |thread: &mut Thread, [this, consumer]: [u32; 2], verbose| match thread
-                    .pc_register
-                {
-                    0 => {
-                        thread.stackframe.operand_stack.push(1);
-                        thread.resolve_and_invoke(this, "$next", &next_signature, verbose)?;
-                        thread.stackframe.locals[0] = this;
-                        Ok(None)
-                    }
-                    1 => {
-                        let next_value = thread.stackframe.operand_stack.pop().unwrap();
-                        let next_value =
-                            Optional.inspect(&thread.heap, next_value as usize, |o| *o)?;
-                        let Some(next_value) = next_value else {
-                            return Ok(Some(()));
-                        };
-                        // invoke the consumer
-                        thread.stackframe.operand_stack.push(0);
-                        thread.resolve_and_invoke(
-                            consumer,
-                            "accept",
-                            &accept_signature,
-                            verbose,
-                        )?;
-                        thread.stackframe.locals[0] = consumer;
-                        thread.stackframe.locals[1] = next_value;
-                        Ok(None)
-                    }
-                    _ => unreachable!(),
-                },
+            code: RawCode::native(Staged(
+                2,
+                NativeVoid(
+                    move |thread: &mut Thread, [this, consumer]: [u32; 2], verbose| match thread
+                        .pc_register
+                    {
+                        0 => {
+                            thread.stackframe.operand_stack.push(1);
+                            thread.resolve_and_invoke(this, "$next", &next_signature, verbose)?;
+                            thread.stackframe.locals[0] = this;
+                            Ok(None)
+                        }
+                        1 => {
+                            let next_value = thread.stackframe.operand_stack.pop().unwrap();
+                            let next_value =
+                                Optional.inspect(&thread.heap, next_value as usize, |o| *o)?;
+                            let Some(next_value) = next_value else {
+                                return Ok(Some(()));
+                            };
+                            // invoke the consumer
+                            thread.stackframe.operand_stack.push(0);
+                            thread.resolve_and_invoke(
+                                consumer,
+                                "accept",
+                                &accept_signature,
+                                verbose,
+                            )?;
+                            thread.stackframe.locals[0] = consumer;
+                            thread.stackframe.locals[1] = next_value;
+                            Ok(None)
+                        }
+                        _ => unreachable!(),
+                    },
+                ),
             )),
             ..Default::default()
         }
@@ -265,17 +302,20 @@ pub(super) fn add_native_methods(
             name: "$generate".into(),
             access_flags: access!(public static native),
             descriptor: method!(((Object("java/util/function/Supplier".into()))) -> Object(java_lang_object.clone())),
-            code: RawCode::native(NativeSingleMethod(
-                move |thread: &mut Thread, [supplier]: [u32; 1], verbose| match thread.pc_register {
-                    0 => {
-                        thread.stackframe.operand_stack.push(1);
-                        thread.resolve_and_invoke(supplier, "get", &get_descriptor, verbose)?;
-                        thread.stackframe.locals[0] = supplier;
-                        Ok(None)
+            code: RawCode::native(Staged(
+                2,
+                NativeSingleMethod(move |thread: &mut Thread, [supplier]: [u32; 1], verbose| {
+                    match thread.pc_register {
+                        0 => {
+                            thread.stackframe.operand_stack.push(1);
+                            thread.resolve_and_invoke(supplier, "get", &get_descriptor, verbose)?;
+                            thread.stackframe.locals[0] = supplier;
+                            Ok(None)
+                        }
+                        1 => Ok(Some(thread.stackframe.operand_stack.pop().unwrap())),
+                        _ => unreachable!(),
                     }
-                    1 => Ok(Some(thread.stackframe.operand_stack.pop().unwrap())),
-                    _ => unreachable!(),
-                },
+                }),
             )),
             ..Default::default()
         }
@@ -307,35 +347,39 @@ pub(super) fn add_native_methods(
             name: "$map".into(),
             access_flags: access!(public static native),
             descriptor: method!(((Object(stream.this.clone())), (Object("java/util/function/Function".into()))) -> Object(java_lang_object.clone())),
-            code: RawCode::native(NativeSingleMethod(
-                move |thread: &mut Thread, [this, func]: [u32; 2], verbose| match thread.pc_register
-                {
-                    0 => {
-                        thread.stackframe.operand_stack.push(1);
-                        thread.resolve_and_invoke(this, "$next", &next_descriptor, verbose)?;
-                        thread.stackframe.locals[0] = this;
-                        Ok(None)
-                    }
-                    1 => {
-                        let next_idx = thread.stackframe.operand_stack.pop().unwrap();
-                        let Some(next_obj) =
-                            Optional.inspect(&thread.heap, next_idx as usize, |o| *o)?
-                        else {
-                            return Ok(Some(next_idx));
-                        };
-                        thread.stackframe.operand_stack.push(2);
-                        thread.resolve_and_invoke(func, "apply", &apply_descriptor, verbose)?;
-                        thread.stackframe.locals[0] = func;
-                        thread.stackframe.locals[1] = next_obj;
-                        Ok(None)
-                    }
-                    2 => {
-                        let func_ret = thread.stackframe.operand_stack.pop().unwrap();
-                        let opt_ret = Optional::make(thread, func_ret, verbose);
-                        Ok(Some(opt_ret))
-                    }
-                    _ => unreachable!(),
-                },
+            code: RawCode::native(Staged(
+                3,
+                NativeSingleMethod(
+                    move |thread: &mut Thread, [this, func]: [u32; 2], verbose| match thread
+                        .pc_register
+                    {
+                        0 => {
+                            thread.stackframe.operand_stack.push(1);
+                            thread.resolve_and_invoke(this, "$next", &next_descriptor, verbose)?;
+                            thread.stackframe.locals[0] = this;
+                            Ok(None)
+                        }
+                        1 => {
+                            let next_idx = thread.stackframe.operand_stack.pop().unwrap();
+                            let Some(next_obj) =
+                                Optional.inspect(&thread.heap, next_idx as usize, |o| *o)?
+                            else {
+                                return Ok(Some(next_idx));
+                            };
+                            thread.stackframe.operand_stack.push(2);
+                            thread.resolve_and_invoke(func, "apply", &apply_descriptor, verbose)?;
+                            thread.stackframe.locals[0] = func;
+                            thread.stackframe.locals[1] = next_obj;
+                            Ok(None)
+                        }
+                        2 => {
+                            let func_ret = thread.stackframe.operand_stack.pop().unwrap();
+                            let opt_ret = Optional::make(thread, func_ret, verbose);
+                            Ok(Some(opt_ret))
+                        }
+                        _ => unreachable!(),
+                    },
+                ),
             )),
             ..Default::default()
         }
@@ -364,39 +408,48 @@ pub(super) fn add_native_methods(
             name: "noneMatch".into(),
             access_flags: access!(public native),
             descriptor: method!(((Object("java/util/function/Predicate".into()))) -> boolean),
-            code: RawCode::native(NativeSingleMethod(
-                move |thread: &mut Thread, [this, predicate]: [u32; 2], verbose| match thread
-                    .pc_register
-                {
-                    0 => {
-                        thread.stackframe.operand_stack.push(1);
-                        thread.resolve_and_invoke(this, "$next", &next_descriptor, verbose)?;
-                        thread.stackframe.locals[0] = this;
-                        Ok(None)
-                    }
-                    1 => {
-                        let ret_opt = thread.stackframe.operand_stack.pop().unwrap();
-                        let Some(ret) = Optional.inspect(&thread.heap, ret_opt as usize, |o| *o)?
-                        else {
-                            return Ok(Some(1));
-                        };
-                        thread.stackframe.operand_stack.push(2);
-                        thread.resolve_and_invoke(predicate, "test", &test_descriptor, verbose)?;
-                        thread.stackframe.locals[0] = predicate;
-                        thread.stackframe.locals[1] = ret;
-                        Ok(None)
-                    }
-                    2 => {
-                        let predicate_ret = thread.stackframe.operand_stack.pop().unwrap();
-                        if predicate_ret == 0 {
-                            thread.pc_register = 0;
+            code: RawCode::native(Staged(
+                3,
+                NativeSingleMethod(
+                    move |thread: &mut Thread, [this, predicate]: [u32; 2], verbose| match thread
+                        .pc_register
+                    {
+                        0 => {
+                            thread.stackframe.operand_stack.push(1);
+                            thread.resolve_and_invoke(this, "$next", &next_descriptor, verbose)?;
+                            thread.stackframe.locals[0] = this;
                             Ok(None)
-                        } else {
-                            Ok(Some(0))
                         }
-                    }
-                    _ => unreachable!(),
-                },
+                        1 => {
+                            let ret_opt = thread.stackframe.operand_stack.pop().unwrap();
+                            let Some(ret) =
+                                Optional.inspect(&thread.heap, ret_opt as usize, |o| *o)?
+                            else {
+                                return Ok(Some(1));
+                            };
+                            thread.stackframe.operand_stack.push(2);
+                            thread.resolve_and_invoke(
+                                predicate,
+                                "test",
+                                &test_descriptor,
+                                verbose,
+                            )?;
+                            thread.stackframe.locals[0] = predicate;
+                            thread.stackframe.locals[1] = ret;
+                            Ok(None)
+                        }
+                        2 => {
+                            let predicate_ret = thread.stackframe.operand_stack.pop().unwrap();
+                            if predicate_ret == 0 {
+                                thread.pc_register = 0;
+                                Ok(None)
+                            } else {
+                                Ok(Some(0))
+                            }
+                        }
+                        _ => unreachable!(),
+                    },
+                ),
             )),
             ..Default::default()
         }
@@ -414,6 +467,7 @@ pub(super) fn add_native_methods(
         [
             all_match,
             any_match,
+            collect,
             stream_next,
             for_each,
             filter,
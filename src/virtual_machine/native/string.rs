@@ -1,10 +1,12 @@
+use std::sync::Arc;
+
 use jvmrs_lib::{FieldType, MethodDescriptor};
 
 use crate::{
     class::code::NativeReturn,
     data::NULL,
     virtual_machine::{
-        object::{AnyObj, ObjectFinder, StringObj},
+        object::{AnyObj, Array1, ObjectFinder, StringObj},
         Thread,
     },
 };
@@ -31,7 +33,7 @@ pub fn native_string_value_of(
                     },
                     verbose,
                 )
-            })?;
+            })??;
         // push a fake return address
         thread.stackframe.operand_stack.push(1);
         thread.invoke_method(to_string_method, to_string_class);
@@ -42,44 +44,44 @@ pub fn native_string_value_of(
     }
 }
 
-pub fn native_println_object(
+pub fn native_string_value_of_chars(
+    thread: &mut Thread,
+    [arr_ref]: [u32; 1],
+    _verbose: bool,
+) -> NativeReturn<Arc<str>> {
+    Array1
+        .inspect(&thread.heap, arr_ref as usize, |arr| {
+            decode_chars(arr.contents).into()
+        })
+        .map(Option::Some)
+}
+
+pub fn native_string_value_of_char_range(
     thread: &mut Thread,
-    [_, arg]: [u32; 2],
+    [arr_ref, offset, count]: [u32; 3],
     verbose: bool,
-) -> NativeReturn<()> {
-    // println!("{stackframe:?}");
-    if thread.pc_register == 0 {
-        let (to_string_class, to_string_method) =
-            AnyObj.inspect(&thread.heap, arg as usize, |obj| {
-                obj.resolve_method(
-                    &thread.method_area,
-                    &thread.class_area,
-                    "toString",
-                    &MethodDescriptor {
-                        parameter_size: 0,
-                        parameters: Vec::new(),
-                        return_type: Some(FieldType::Object("java/lang/String".into())),
-                    },
-                    verbose,
-                )
-            })?;
-        if verbose {
-            println!(
-                "Resolved java/lang/Object.toString to {}.{}",
-                to_string_class.this, to_string_method.name
-            );
-        }
-        // push a fake return address
-        thread.stackframe.operand_stack.push(1);
-        thread.invoke_method(to_string_method, to_string_class);
-        thread.stackframe.locals[0] = arg;
-        Ok(None)
-    } else {
-        let ret = thread.stackframe.operand_stack.pop().unwrap();
-        let str = StringObj::SELF.inspect(&thread.heap, ret as usize, |a| a.clone())?;
-        println!("{str}");
-        Ok(Some(()))
+) -> NativeReturn<Arc<str>> {
+    let chars = Array1.inspect(&thread.heap, arr_ref as usize, |arr| arr.contents.to_vec())?;
+    let len = chars.len();
+    let (offset, count) = (offset as i32, count as i32);
+    if offset < 0 || count < 0 || offset as usize + count as usize > len {
+        thread.throw_new(
+            "java/lang/StringIndexOutOfBoundsException",
+            &format!("offset {offset}, count {count}, length {len}"),
+            verbose,
+        )?;
+        return Ok(None);
     }
+    Ok(Some(
+        decode_chars(&chars[offset as usize..(offset + count) as usize]).into(),
+    ))
+}
+
+/// decode the UTF-16 contents of a `char[]`
+fn decode_chars(chars: &[u32]) -> String {
+    char::decode_utf16(chars.iter().map(|&c| c as u16))
+        .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+        .collect()
 }
 
 pub fn native_string_char_at(
@@ -94,3 +96,226 @@ pub fn native_string_char_at(
         })
         .map(Option::Some)
 }
+
+/// `String.substring(int)`
+pub fn native_string_substring(
+    thread: &mut Thread,
+    [string_ref, begin]: [u32; 2],
+    verbose: bool,
+) -> NativeReturn<Arc<str>> {
+    let len = StringObj::SELF.inspect(&thread.heap, string_ref as usize, |str| {
+        str.chars().count() as u32
+    })?;
+    native_string_substring_range(thread, [string_ref, begin, len], verbose)
+}
+
+/// `String.substring(int, int)`; indices count chars, like `charAt`
+pub fn native_string_substring_range(
+    thread: &mut Thread,
+    [string_ref, begin, end]: [u32; 3],
+    verbose: bool,
+) -> NativeReturn<Arc<str>> {
+    let str = StringObj::SELF.inspect(&thread.heap, string_ref as usize, |str| str.clone())?;
+    let len = str.chars().count();
+    let (begin, end) = (begin as i32, end as i32);
+    if begin < 0 || begin > end || end as usize > len {
+        thread.throw_new(
+            "java/lang/StringIndexOutOfBoundsException",
+            &format!("begin {begin}, end {end}, length {len}"),
+            verbose,
+        )?;
+        return Ok(None);
+    }
+    Ok(Some(
+        str.chars()
+            .skip(begin as usize)
+            .take((end - begin) as usize)
+            .collect::<String>()
+            .into(),
+    ))
+}
+
+/// convert the byte index of a match into a char index, or -1 if there wasn't one
+fn char_index(str: &str, byte_idx: Option<usize>) -> u32 {
+    byte_idx.map_or(-1, |idx| str[..idx].chars().count() as i32) as u32
+}
+
+/// `String.indexOf(String)`
+pub fn native_string_index_of(
+    thread: &mut Thread,
+    [string_ref, target]: [u32; 2],
+    _verbose: bool,
+) -> NativeReturn<u32> {
+    let target = StringObj::SELF.inspect(&thread.heap, target as usize, |str| str.clone())?;
+    StringObj::SELF
+        .inspect(&thread.heap, string_ref as usize, |str| {
+            char_index(str, str.find(&*target))
+        })
+        .map(Option::Some)
+}
+
+/// `String.indexOf(int)`
+pub fn native_string_index_of_char(
+    thread: &mut Thread,
+    [string_ref, ch]: [u32; 2],
+    _verbose: bool,
+) -> NativeReturn<u32> {
+    let Some(ch) = char::from_u32(ch) else {
+        return Ok(Some(-1i32 as u32));
+    };
+    StringObj::SELF
+        .inspect(&thread.heap, string_ref as usize, |str| {
+            char_index(str, str.find(ch))
+        })
+        .map(Option::Some)
+}
+
+/// `String.replace(char, char)`
+pub fn native_string_replace_char(
+    thread: &mut Thread,
+    [string_ref, old, new]: [u32; 3],
+    _verbose: bool,
+) -> NativeReturn<Arc<str>> {
+    let old = char::from_u32(old);
+    let new = char::from_u32(new).unwrap_or(char::REPLACEMENT_CHARACTER);
+    StringObj::SELF
+        .inspect(&thread.heap, string_ref as usize, |str| {
+            str.chars()
+                .map(|c| if Some(c) == old { new } else { c })
+                .collect::<String>()
+                .into()
+        })
+        .map(Option::Some)
+}
+
+/// `String.split(String)`. The argument is treated as a literal delimiter rather than a regex.
+/// Like java, trailing empty strings are left out of the result.
+pub fn native_string_split(
+    thread: &mut Thread,
+    [string_ref, delimiter]: [u32; 2],
+    _verbose: bool,
+) -> NativeReturn<u32> {
+    let str = StringObj::SELF.inspect(&thread.heap, string_ref as usize, |str| str.clone())?;
+    let delimiter = StringObj::SELF.inspect(&thread.heap, delimiter as usize, |str| str.clone())?;
+    let mut pieces: Vec<&str> = if delimiter.is_empty() {
+        str.char_indices()
+            .map(|(idx, c)| &str[idx..idx + c.len_utf8()])
+            .collect()
+    } else if str.contains(&*delimiter) {
+        str.split(&*delimiter).collect()
+    } else {
+        vec![&str]
+    };
+    if pieces.len() > 1 {
+        while pieces.last().is_some_and(|piece| piece.is_empty()) {
+            pieces.pop();
+        }
+    }
+    let mut heap = thread.heap.lock().unwrap();
+    let piece_refs = pieces
+        .into_iter()
+        .map(|piece| {
+            let piece_ref = heap.allocate_str(piece.into());
+            heap.inc_ref(piece_ref);
+            piece_ref
+        })
+        .collect();
+    Ok(Some(heap.allocate(Array1::from_vec(
+        piece_refs,
+        FieldType::Object("java/lang/String".into()),
+    ))))
+}
+
+/// test a string against another with `predicate`
+fn string_predicate(
+    thread: &Thread,
+    this: u32,
+    other: u32,
+    predicate: impl Fn(&str, &str) -> bool,
+) -> NativeReturn<u32> {
+    let this = StringObj::SELF.inspect(&thread.heap, this as usize, |str| str.clone())?;
+    let other = StringObj::SELF.inspect(&thread.heap, other as usize, |str| str.clone())?;
+    Ok(Some(u32::from(predicate(&this, &other))))
+}
+
+/// `String.startsWith(String)`
+pub fn native_string_starts_with(
+    thread: &mut Thread,
+    [this, prefix]: [u32; 2],
+    _verbose: bool,
+) -> NativeReturn<u32> {
+    string_predicate(thread, this, prefix, |this, prefix| {
+        this.starts_with(prefix)
+    })
+}
+
+/// `String.endsWith(String)`
+pub fn native_string_ends_with(
+    thread: &mut Thread,
+    [this, suffix]: [u32; 2],
+    _verbose: bool,
+) -> NativeReturn<u32> {
+    string_predicate(thread, this, suffix, |this, suffix| this.ends_with(suffix))
+}
+
+/// `String.equalsIgnoreCase(String)`; chars match if they're equal after converting both to
+/// upper or lower case
+pub fn native_string_equals_ignore_case(
+    thread: &mut Thread,
+    [this, other]: [u32; 2],
+    _verbose: bool,
+) -> NativeReturn<u32> {
+    if other == NULL {
+        return Ok(Some(0));
+    }
+    string_predicate(thread, this, other, |this, other| {
+        this.chars().count() == other.chars().count()
+            && this.chars().zip(other.chars()).all(|(a, b)| {
+                a == b
+                    || a.to_uppercase().eq(b.to_uppercase())
+                    || a.to_lowercase().eq(b.to_lowercase())
+            })
+    })
+}
+
+/// `String.equals(Object)`
+pub fn native_string_equals(
+    thread: &mut Thread,
+    [this, other]: [u32; 2],
+    verbose: bool,
+) -> NativeReturn<u32> {
+    if this == other {
+        return Ok(Some(1));
+    }
+    if other == NULL
+        || !AnyObj.inspect(&thread.heap, other as usize, |obj| {
+            obj.isinstance(&thread.class_area, "java/lang/String", verbose)
+        })?
+    {
+        return Ok(Some(0));
+    }
+    string_predicate(thread, this, other, |this, other| this == other)
+}
+
+/// `String.hashCode()`, using java's formula over UTF-16 code units so hashes match a real JVM
+pub fn native_string_hash_code(
+    thread: &mut Thread,
+    [this]: [u32; 1],
+    _verbose: bool,
+) -> NativeReturn<u32> {
+    StringObj::SELF
+        .inspect(&thread.heap, this as usize, |str| {
+            str.encode_utf16()
+                .fold(0u32, |hash, c| hash.wrapping_mul(31).wrapping_add(c as u32))
+        })
+        .map(Option::Some)
+}
+
+pub fn native_string_intern(
+    thread: &mut Thread,
+    [string_ref]: [u32; 1],
+    _verbose: bool,
+) -> NativeReturn<u32> {
+    let str = StringObj::SELF.inspect(&thread.heap, string_ref as usize, |str| str.clone())?;
+    Ok(Some(thread.heap.lock().unwrap().intern(string_ref, str)))
+}
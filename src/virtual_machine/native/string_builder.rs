@@ -1,13 +1,19 @@
 use std::sync::Arc;
 
+use jvmrs_lib::{access, method, FieldType, MethodDescriptor};
+
 use crate::{
-    class::code::NativeReturn,
+    class::code::{NativeReturn, NativeSingleMethod},
+    class_loader::{RawCode, RawMethod},
+    data::NULL,
     virtual_machine::{
         object::{AnyObj, ObjectFinder, StringBuilder, StringObj},
         Thread,
     },
 };
 
+use super::primitives::JavaFloat;
+
 #[allow(clippy::significant_drop_tightening)]
 pub fn init(thread: &mut Thread, [obj_ref, str_ref]: [u32; 2], _verbose: bool) -> NativeReturn<()> {
     let init_string = StringObj::SELF.inspect(&thread.heap, str_ref as usize, |init_string| {
@@ -60,3 +66,117 @@ pub fn to_string(
         );
     Ok(Some(string))
 }
+
+/// `new StringBuilder()`
+pub fn init_empty(thread: &mut Thread, [obj_ref]: [u32; 1], _verbose: bool) -> NativeReturn<()> {
+    AnyObj
+        .inspect(&thread.heap, obj_ref as usize, |heap_obj| {
+            heap_obj.native_fields.push(Box::new(String::new()));
+        })
+        .map(Option::Some)
+}
+
+/// add `text` to the end of the builder at `this` and return the builder
+fn push_str(thread: &Thread, this: u32, text: &str) -> NativeReturn<u32> {
+    StringBuilder::inspect(&thread.heap, this as usize, |builder| {
+        builder.push_str(text);
+    })?;
+    Ok(Some(this))
+}
+
+/// `append(Object)` and `append(String)`; stage 1 receives the result of the object's `toString`
+pub fn append_object(
+    thread: &mut Thread,
+    [this, arg]: [u32; 2],
+    verbose: bool,
+) -> NativeReturn<u32> {
+    if arg == NULL {
+        return push_str(thread, this, "null");
+    }
+    if thread.pc_register == 0 {
+        // push a fake return address
+        thread.stackframe.operand_stack.push(1);
+        thread.resolve_and_invoke(
+            arg,
+            "toString",
+            &MethodDescriptor {
+                parameter_size: 0,
+                parameters: Vec::new(),
+                return_type: Some(FieldType::Object("java/lang/String".into())),
+            },
+            verbose,
+        )?;
+        thread.stackframe.locals[0] = arg;
+        Ok(None)
+    } else {
+        let ret = thread.stackframe.operand_stack.pop().unwrap();
+        let str = StringObj::SELF.inspect(&thread.heap, ret as usize, |a| a.clone())?;
+        push_str(thread, this, &str)
+    }
+}
+
+/// make an `append` method for each primitive type. Each overload has its own descriptor, so
+/// `append(int)`, `append(long)`, `append(float)` and `append(double)` resolve separately.
+pub(super) fn append_methods(this: &Arc<str>) -> Vec<RawMethod> {
+    let append_method = |descriptor: MethodDescriptor, code: RawCode| RawMethod {
+        access_flags: access!(public native),
+        name: "append".into(),
+        descriptor,
+        code,
+        ..Default::default()
+    };
+    vec![
+        append_method(
+            method!((char) -> Object(this.clone())),
+            RawCode::native(NativeSingleMethod(
+                |thread: &mut Thread, [this, c]: [u32; 2], _| {
+                    let char =
+                        char::from_u32(c).ok_or_else(|| String::from("Invalid Character code"))?;
+                    push_str(thread, this, &char.to_string())
+                },
+            )),
+        ),
+        append_method(
+            method!((boolean) -> Object(this.clone())),
+            RawCode::native(NativeSingleMethod(
+                |thread: &mut Thread, [this, b]: [u32; 2], _| {
+                    push_str(thread, this, &(b != 0).to_string())
+                },
+            )),
+        ),
+        append_method(
+            method!((int) -> Object(this.clone())),
+            RawCode::native(NativeSingleMethod(
+                |thread: &mut Thread, [this, i]: [u32; 2], _| {
+                    push_str(thread, this, &(i as i32).to_string())
+                },
+            )),
+        ),
+        append_method(
+            method!((long) -> Object(this.clone())),
+            RawCode::native(NativeSingleMethod(
+                |thread: &mut Thread, [this, left, right]: [u32; 3], _| {
+                    let long = ((left as u64) << 32 | (right as u64)) as i64;
+                    push_str(thread, this, &long.to_string())
+                },
+            )),
+        ),
+        append_method(
+            method!((float) -> Object(this.clone())),
+            RawCode::native(NativeSingleMethod(
+                |thread: &mut Thread, [this, f]: [u32; 2], _| {
+                    push_str(thread, this, &JavaFloat(f32::from_bits(f)).to_string())
+                },
+            )),
+        ),
+        append_method(
+            method!((double) -> Object(this.clone())),
+            RawCode::native(NativeSingleMethod(
+                |thread: &mut Thread, [this, left, right]: [u32; 3], _| {
+                    let double = JavaFloat(f64::from_bits((left as u64) << 32 | (right as u64)));
+                    push_str(thread, this, &double.to_string())
+                },
+            )),
+        ),
+    ]
+}
@@ -0,0 +1,153 @@
+use std::sync::Arc;
+
+use jvmrs_lib::{access, method, FieldType};
+
+use crate::{
+    class::{
+        code::{
+            native_property, NativeDoubleMethod, NativeSingleMethod, NativeStringMethod, NativeVoid,
+        },
+        Field,
+    },
+    class_loader::{RawClass, RawCode, RawMethod},
+    data::{WorkingClassArea, WorkingMethodArea, NULL},
+    virtual_machine::{
+        object::{Object, StringObj, ThreadObj},
+        Thread,
+    },
+};
+
+/// # Java Thread
+/// The native state of a `java/lang/Thread`. Programs only ever run on the main thread, so the only
+/// instance is the one made in `Thread.<clinit>`.
+#[derive(Clone, Debug)]
+pub struct JavaThread {
+    pub name: Arc<str>,
+    pub id: u64,
+}
+
+pub fn add_native_methods(
+    method_area: &mut WorkingMethodArea,
+    class_area: &mut WorkingClassArea,
+    java_lang_object: &Arc<str>,
+    java_lang_string: &Arc<str>,
+) {
+    let java_lang_thread: Arc<str> = Arc::from("java/lang/Thread");
+    let mut thread_class = RawClass::new(
+        access!(public native),
+        java_lang_thread.clone(),
+        java_lang_object.clone(),
+    );
+    let thread_clinit = RawMethod {
+        name: "<clinit>".into(),
+        access_flags: access!(public static native),
+        descriptor: method!(() -> void),
+        code: RawCode::native(NativeVoid(|thread: &mut Thread, []: [u32; 0], verbose| {
+            let thread_class = thread.class_area.search("java/lang/Thread").unwrap();
+            let mut main_thread = Object::from_class(&thread_class);
+            main_thread.native_fields.push(Box::new(JavaThread {
+                name: "main".into(),
+                id: 1,
+            }));
+            let main_ref = thread.heap.lock().unwrap().allocate(main_thread);
+            thread_class.static_data.lock().unwrap()[0] = main_ref;
+            thread.rember(main_ref, verbose);
+            Ok(Some(()))
+        })),
+        ..Default::default()
+    };
+    let current_thread = RawMethod {
+        name: "currentThread".into(),
+        access_flags: access!(public static native),
+        descriptor: method!(() -> Object(java_lang_thread.clone())),
+        code: RawCode::native(NativeSingleMethod(
+            |thread: &mut Thread, []: [u32; 0], _| {
+                let thread_class = thread.class_area.search("java/lang/Thread").unwrap();
+                let main_ref = thread_class.static_data.lock().unwrap()[0];
+                Ok(Some(main_ref))
+            },
+        )),
+        ..Default::default()
+    };
+    let get_name = RawMethod {
+        name: "getName".into(),
+        access_flags: access!(public native),
+        descriptor: method!(() -> Object(java_lang_string.clone())),
+        code: RawCode::native(NativeStringMethod(native_property(
+            ThreadObj::SELF,
+            |thread| thread.name.clone(),
+        ))),
+        ..Default::default()
+    };
+    let set_name = RawMethod {
+        name: "setName".into(),
+        access_flags: access!(public native),
+        descriptor: method!(((Object(java_lang_string.clone()))) -> void),
+        code: RawCode::native(NativeVoid(
+            |thread: &mut Thread, [this, name]: [u32; 2], verbose| {
+                if name == NULL {
+                    thread.throw_null_pointer_exception("name cannot be null", verbose)?;
+                    return Ok(None);
+                }
+                let name = StringObj::inspect(&thread.heap, name as usize, |str| str.clone())?;
+                ThreadObj::inspect(&thread.heap, this as usize, |thread| {
+                    thread.name = name;
+                })
+                .map(Option::Some)
+            },
+        )),
+        ..Default::default()
+    };
+    let get_id = RawMethod {
+        name: "getId".into(),
+        access_flags: access!(public native),
+        descriptor: method!(() -> long),
+        code: RawCode::native(NativeDoubleMethod(native_property(
+            ThreadObj::SELF,
+            |thread| thread.id,
+        ))),
+        ..Default::default()
+    };
+    let holds_lock = RawMethod {
+        name: "holdsLock".into(),
+        access_flags: access!(public static native),
+        descriptor: method!(((Object(java_lang_object.clone()))) -> boolean),
+        code: RawCode::native(NativeSingleMethod(
+            |thread: &mut Thread, [obj]: [u32; 1], verbose| {
+                if obj == NULL {
+                    thread.throw_new("java/lang/NullPointerException", "obj", verbose)?;
+                    return Ok(None);
+                }
+                let held = thread.heap.lock().unwrap().holds_monitor(obj);
+                Ok(Some(u32::from(held)))
+            },
+        )),
+        ..Default::default()
+    };
+    thread_class.register_methods(
+        [
+            thread_clinit,
+            current_thread,
+            get_name,
+            set_name,
+            get_id,
+            holds_lock,
+        ],
+        method_area,
+    );
+
+    thread_class.static_data.push(NULL);
+    thread_class.statics.push((
+        Field {
+            access_flags: access!(private static native),
+            name: "main".into(),
+            descriptor: FieldType::Object(java_lang_thread),
+            attributes: Vec::new(),
+            signature: None,
+            constant_value: None,
+        },
+        0,
+    ));
+
+    class_area.extend([thread_class]);
+}
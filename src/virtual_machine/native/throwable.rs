@@ -3,11 +3,37 @@ use std::sync::Arc;
 use jvmrs_lib::{access, method, FieldType};
 
 use crate::{
-    class::{code::NativeNoop, Field},
+    class::{
+        code::{NativeNoop, NativeSingleMethod, NativeVoid},
+        Field,
+    },
     class_loader::{RawClass, RawCode, RawMethod},
-    data::{WorkingClassArea, WorkingMethodArea},
+    data::{SharedHeap, WorkingClassArea, WorkingMethodArea, NULL},
+    virtual_machine::{
+        error,
+        object::{AnyObj, ObjectFinder, StringObj},
+        Thread,
+    },
 };
 
+/// `Throwable.toString`: the exception's class name, followed by its message if it has one
+/// # Errors
+/// Returns an error if `ptr` isn't an object
+pub fn describe_throwable(heap: &SharedHeap, ptr: u32) -> error::Result<String> {
+    let (class, message) = AnyObj.inspect(heap, ptr as usize, |obj| {
+        (
+            obj.class.clone(),
+            obj.fields.first().copied().unwrap_or(NULL),
+        )
+    })?;
+    let class = class.replace('/', ".");
+    if message == NULL {
+        return Ok(class);
+    }
+    let message = StringObj::inspect(heap, message as usize, |message| message.to_string())?;
+    Ok(format!("{class}: {message}"))
+}
+
 #[allow(clippy::too_many_lines)]
 pub fn add_native_methods(
     java_lang_object: &Arc<str>,
@@ -28,6 +54,7 @@ pub fn add_native_methods(
         "java/lang/Throwable".into(),
         java_lang_object.clone(),
     );
+    throwable.field_size = 2;
     throwable.fields.extend([
         (
             Field {
@@ -49,7 +76,45 @@ pub fn add_native_methods(
         ),
     ]);
 
-    throwable.register_method(noop_init.clone(), method_area);
+    // `Throwable(String message)`, which subclasses that take a message pass on to
+    let message_init = RawMethod {
+        access_flags: access!(public native),
+        name: "<init>".into(),
+        descriptor: method!(((Object(java_lang_string.clone()))) -> void),
+        code: RawCode::native(NativeVoid(
+            |thread: &mut Thread, [this, message]: [u32; 2], verbose| {
+                thread.rember(message, verbose);
+                AnyObj.inspect(&thread.heap, this as usize, |obj| {
+                    obj.fields[0] = message;
+                })?;
+                Ok(Some(()))
+            },
+        )),
+        ..Default::default()
+    };
+    let get_message = RawMethod {
+        access_flags: access!(public native),
+        name: "getMessage".into(),
+        descriptor: method!(() -> Object(java_lang_string.clone())),
+        code: RawCode::native(NativeSingleMethod(
+            |thread: &mut Thread, [this]: [u32; 1], _verbose| {
+                AnyObj.inspect(&thread.heap, this as usize, |obj| Some(obj.fields[0]))
+            },
+        )),
+        ..Default::default()
+    };
+    let throwable_to_string = RawMethod::to_string(|thread: &mut Thread, [this]: [u32; 1], _| {
+        Ok(Some(describe_throwable(&thread.heap, this)?.into()))
+    });
+    throwable.register_methods(
+        [
+            noop_init.clone(),
+            message_init.clone(),
+            get_message,
+            throwable_to_string,
+        ],
+        method_area,
+    );
 
     let mut exception = RawClass::new(
         access!(public native),
@@ -57,7 +122,7 @@ pub fn add_native_methods(
         throwable.this.clone(),
     );
 
-    exception.register_method(noop_init.clone(), method_area);
+    exception.register_methods([noop_init.clone(), message_init.clone()], method_area);
 
     let mut runtime_exception = RawClass::new(
         access!(public native),
@@ -65,21 +130,89 @@ pub fn add_native_methods(
         exception.this.clone(),
     );
 
-    runtime_exception.register_method(noop_init.clone(), method_area);
+    runtime_exception.register_methods([noop_init.clone(), message_init.clone()], method_area);
 
     let mut illegal_argument_exception = RawClass::new(
         access!(public native),
         "java/lang/IllegalArgumentException".into(),
         runtime_exception.this.clone(),
     );
-    let illegal_argument_exception_to_string = RawMethod::to_string(|_: &mut _, _: [_; 0], _| {
-        Ok(Some("java.lang.IllegalArgumentException".into()))
+
+    illegal_argument_exception
+        .register_methods([noop_init.clone(), message_init.clone()], method_area);
+
+    let mut illegal_format_exception = RawClass::new(
+        access!(public native),
+        "java/util/IllegalFormatException".into(),
+        illegal_argument_exception.this.clone(),
+    );
+    illegal_format_exception
+        .register_methods([noop_init.clone(), message_init.clone()], method_area);
+
+    let format_exceptions = [
+        "UnknownFormatConversionException",
+        "MissingFormatArgumentException",
+        "IllegalFormatConversionException",
+        "IllegalFormatCodePointException",
+        "FormatFlagsConversionMismatchException",
+    ]
+    .map(|name| {
+        let mut exception = RawClass::new(
+            access!(public native),
+            format!("java/util/{name}").into(),
+            illegal_format_exception.this.clone(),
+        );
+        exception.register_methods([noop_init.clone(), message_init.clone()], method_area);
+        exception
     });
 
-    illegal_argument_exception.register_methods(
-        [noop_init.clone(), illegal_argument_exception_to_string],
-        method_area,
+    let mut number_format_exception = RawClass::new(
+        access!(public native),
+        "java/lang/NumberFormatException".into(),
+        illegal_argument_exception.this.clone(),
+    );
+    number_format_exception
+        .register_methods([noop_init.clone(), message_init.clone()], method_area);
+
+    let mut index_out_of_bounds_exception = RawClass::new(
+        access!(public native),
+        "java/lang/IndexOutOfBoundsException".into(),
+        runtime_exception.this.clone(),
+    );
+    index_out_of_bounds_exception
+        .register_methods([noop_init.clone(), message_init.clone()], method_area);
+
+    let mut string_index_out_of_bounds_exception = RawClass::new(
+        access!(public native),
+        "java/lang/StringIndexOutOfBoundsException".into(),
+        index_out_of_bounds_exception.this.clone(),
+    );
+    string_index_out_of_bounds_exception
+        .register_methods([noop_init.clone(), message_init.clone()], method_area);
+
+    let mut array_index_out_of_bounds_exception = RawClass::new(
+        access!(public native),
+        "java/lang/ArrayIndexOutOfBoundsException".into(),
+        index_out_of_bounds_exception.this.clone(),
+    );
+    array_index_out_of_bounds_exception
+        .register_methods([noop_init.clone(), message_init.clone()], method_area);
+
+    let mut no_such_element_exception = RawClass::new(
+        access!(public native),
+        "java/util/NoSuchElementException".into(),
+        runtime_exception.this.clone(),
     );
+    no_such_element_exception
+        .register_methods([noop_init.clone(), message_init.clone()], method_area);
+
+    let mut input_mismatch_exception = RawClass::new(
+        access!(public native),
+        "java/util/InputMismatchException".into(),
+        no_such_element_exception.this.clone(),
+    );
+    input_mismatch_exception
+        .register_methods([noop_init.clone(), message_init.clone()], method_area);
 
     let mut arithmetic_exception = RawClass::new(
         access!(public native),
@@ -87,16 +220,95 @@ pub fn add_native_methods(
         runtime_exception.this.clone(),
     );
 
-    let arith_to_string = RawMethod::to_string(|_: &mut _, _: [_; 0], _| {
-        Ok(Some("java.lang.ArithmeticException".into()))
-    });
-    arithmetic_exception.register_methods([arith_to_string, noop_init], method_area);
+    arithmetic_exception.register_methods([noop_init.clone(), message_init.clone()], method_area);
+
+    let mut illegal_monitor_state_exception = RawClass::new(
+        access!(public native),
+        "java/lang/IllegalMonitorStateException".into(),
+        runtime_exception.this.clone(),
+    );
+    illegal_monitor_state_exception
+        .register_methods([noop_init.clone(), message_init.clone()], method_area);
+
+    let mut illegal_state_exception = RawClass::new(
+        access!(public native),
+        "java/lang/IllegalStateException".into(),
+        runtime_exception.this.clone(),
+    );
+    illegal_state_exception
+        .register_methods([noop_init.clone(), message_init.clone()], method_area);
+
+    let mut class_cast_exception = RawClass::new(
+        access!(public native),
+        "java/lang/ClassCastException".into(),
+        runtime_exception.this.clone(),
+    );
+    class_cast_exception.register_methods([noop_init.clone(), message_init.clone()], method_area);
+
+    let mut null_pointer_exception = RawClass::new(
+        access!(public native),
+        "java/lang/NullPointerException".into(),
+        runtime_exception.this.clone(),
+    );
+    null_pointer_exception.register_methods([noop_init.clone(), message_init.clone()], method_area);
+
+    let mut error = RawClass::new(
+        access!(public native),
+        "java/lang/Error".into(),
+        throwable.this.clone(),
+    );
+    error.register_methods([noop_init.clone(), message_init.clone()], method_area);
+
+    let mut linkage_error = RawClass::new(
+        access!(public native),
+        "java/lang/LinkageError".into(),
+        error.this.clone(),
+    );
+    linkage_error.register_methods([noop_init.clone(), message_init.clone()], method_area);
+
+    let mut verify_error = RawClass::new(
+        access!(public native),
+        "java/lang/VerifyError".into(),
+        linkage_error.this.clone(),
+    );
+    verify_error.register_methods([noop_init.clone(), message_init.clone()], method_area);
+
+    let mut virtual_machine_error = RawClass::new(
+        access!(public abstract native),
+        "java/lang/VirtualMachineError".into(),
+        error.this.clone(),
+    );
+    virtual_machine_error.register_methods([noop_init.clone(), message_init.clone()], method_area);
+
+    let mut stack_overflow_error = RawClass::new(
+        access!(public native),
+        "java/lang/StackOverflowError".into(),
+        virtual_machine_error.this.clone(),
+    );
+    stack_overflow_error.register_methods([noop_init, message_init], method_area);
 
     class_area.extend([
         throwable,
         exception,
         runtime_exception,
         illegal_argument_exception,
+        number_format_exception,
+        index_out_of_bounds_exception,
+        string_index_out_of_bounds_exception,
+        array_index_out_of_bounds_exception,
+        no_such_element_exception,
+        input_mismatch_exception,
         arithmetic_exception,
+        null_pointer_exception,
+        class_cast_exception,
+        illegal_monitor_state_exception,
+        illegal_state_exception,
+        error,
+        linkage_error,
+        verify_error,
+        virtual_machine_error,
+        stack_overflow_error,
+        illegal_format_exception,
     ]);
+    class_area.extend(format_exceptions);
 }
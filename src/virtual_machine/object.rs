@@ -1,6 +1,7 @@
 use std::{
     any::Any,
-    collections::{HashMap, HashSet},
+    collections::{HashMap, VecDeque},
+    io::Cursor,
     marker::PhantomData,
     sync::{Arc, Mutex, OnceLock},
 };
@@ -13,11 +14,15 @@ use crate::{
         code::{NativeMethod, NativeVoid},
         Class, Code, Method,
     },
-    class_loader::{RawCode, RawMethod},
+    class_loader::{parse_field_type, RawCode, RawMethod},
     data::{BuildNonHasher, Heap, SharedClassArea, SharedMethodArea, NULL},
 };
 
-use super::{error, native, Thread};
+use super::{
+    error, native,
+    native::{collectors::CollectorKind, print_stream::PrintTarget, thread::JavaThread},
+    Thread,
+};
 
 #[derive(Debug)]
 pub struct Instance {
@@ -57,8 +62,11 @@ impl Object {
         }
     }
 
-    /// # Panics
-    #[must_use]
+    /// find the implementation of a method that a call on this object runs, checking the object's
+    /// class, then its superclasses, then the interfaces they implement
+    /// # Errors
+    /// Returns a `LinkageError` if nothing implements the method, or a `ClassResolution` error if
+    /// the object's class or one of its superclasses isn't loaded
     pub fn resolve_method(
         &mut self,
         method_area: &SharedMethodArea,
@@ -66,11 +74,13 @@ impl Object {
         method: &str,
         descriptor: &MethodDescriptor,
         verbose: bool,
-    ) -> (Arc<Class>, Arc<Method>) {
+    ) -> error::Result<(Arc<Class>, Arc<Method>)> {
         if verbose {
             println!("Resolving {descriptor:?} {method}");
         }
-        let mut current_class = class_area.search(&self.class).unwrap();
+        let mut current_class = class_area
+            .search(&self.class)
+            .ok_or_else(|| error::Error::class_resolution(&self.class))?;
         if let Ok(Some(lambda_override)) = LambdaObject::SELF.extract(self, |lambda_override| {
             if &*lambda_override.method_name == method
                 && &lambda_override.method_descriptor == descriptor
@@ -86,12 +96,16 @@ impl Object {
                     lambda_override.code.as_native().unwrap()
                 );
             }
-            return (current_class, Arc::new(lambda_override));
+            return Ok((current_class, Arc::new(lambda_override)));
         }
         let mut class_list = vec![current_class.clone()];
         loop {
+            // a lambda's class is the interface it implements, which may redeclare an `Object`
+            // method like `toString` abstractly, so keep looking up to `java/lang/Object`
             if let Some(values) = method_area.search(&current_class.this, method, descriptor) {
-                return values;
+                if !matches!(values.1.code, Code::Abstract) {
+                    return Ok(values);
+                }
             }
             if verbose {
                 println!("{}.{method} not found", current_class.this);
@@ -102,14 +116,16 @@ impl Object {
                 }
                 break;
             }
-            current_class = class_area.search(&current_class.super_class).unwrap();
+            current_class = class_area
+                .search(&current_class.super_class)
+                .ok_or_else(|| error::Error::class_resolution(&current_class.super_class))?;
             class_list.push(current_class.clone());
         }
         for class in class_list {
             for interface in &class.interfaces {
                 if let Some(values) = method_area.search(interface, method, descriptor) {
                     if !matches!(values.1.code, Code::Abstract) {
-                        return values;
+                        return Ok(values);
                     }
                 }
                 if verbose {
@@ -117,7 +133,68 @@ impl Object {
                 }
             }
         }
-        panic!("Failed to find any implementation")
+        Err(error::Error::LinkageError(format!(
+            "java.lang.AbstractMethodError: {} doesn't implement {method}{descriptor:?}",
+            self.class
+        )))
+    }
+
+    /// values in the object's native fields that might point at other objects without holding a
+    /// reference to them, like a lambda's captures
+    #[must_use]
+    pub fn native_pointers(&self) -> Vec<u32> {
+        let mut pointers = Vec::new();
+        for field in &self.native_fields {
+            if let Some(lambda) = field.downcast_ref::<LambdaOverride>() {
+                pointers.extend(&lambda.captures);
+            }
+        }
+        pointers
+    }
+
+    /// values in the object's native fields that hold a reference to another object, like the
+    /// elements of a native collection or a collector's functions. They're released when this
+    /// object is deallocated
+    #[must_use]
+    pub fn counted_native_pointers(&self) -> Vec<u32> {
+        // an array's contents are a `Vec<u32>` too, but they're only references if its type says so
+        if self
+            .native_fields
+            .first()
+            .is_some_and(|field| field.is::<FieldType>())
+        {
+            return Vec::new();
+        }
+        let mut pointers = Vec::new();
+        for field in &self.native_fields {
+            if let Some(list) = field.downcast_ref::<Vec<u32>>() {
+                pointers.extend(list);
+            } else if let Some(list) = field.downcast_ref::<VecDeque<u32>>() {
+                pointers.extend(list);
+            } else if let Some(map) =
+                field.downcast_ref::<HashMap<u32, Vec<(u32, u32)>, BuildNonHasher>>()
+            {
+                pointers.extend(
+                    map.values()
+                        .flatten()
+                        .flat_map(|&(key, value)| [key, value]),
+                );
+            } else if let Some(set) = field.downcast_ref::<HashMap<u32, Vec<u32>, BuildNonHasher>>()
+            {
+                pointers.extend(set.values().flatten());
+            } else if let Some(collector) = field.downcast_ref::<CollectorKind>() {
+                match *collector {
+                    CollectorKind::ToMap {
+                        key_mapper,
+                        value_mapper,
+                    } => pointers.extend([key_mapper, value_mapper]),
+                    CollectorKind::SummingInt { mapper } => pointers.push(mapper),
+                    CollectorKind::Joining { delimiter } => pointers.push(delimiter),
+                    CollectorKind::Counting | CollectorKind::ToList => {}
+                }
+            }
+        }
+        pointers
     }
 
     #[must_use]
@@ -128,31 +205,97 @@ impl Object {
     #[must_use]
     /// # Panics
     pub fn isinstance(&self, class_area: &SharedClassArea, class: &str, verbose: bool) -> bool {
-        let mut current = class_area.search(class).unwrap();
-        if verbose {
-            println!("Checking if {} is an instance of {}", current.this, class);
+        // arrays keep their element type in their first native field, and array classes are named
+        // by their descriptor
+        let element_type = self
+            .native_fields
+            .first()
+            .and_then(|field| field.downcast_ref::<FieldType>());
+        match (element_type, class.starts_with('[')) {
+            (Some(element_type), true) => parse_field_type(&mut class.chars().peekable())
+                .is_ok_and(|target| {
+                    is_field_type_assignable(
+                        class_area,
+                        &FieldType::Array(Box::new(element_type.clone())),
+                        &target,
+                        verbose,
+                    )
+                }),
+            (None, true) => false,
+            (_, false) => is_assignable(class_area, &self.class, class, verbose),
         }
-        while &*current.this != "java/lang/Object" {
-            if &*current.this == class {
-                return true;
-            }
-            for i in &current.interfaces {
-                if verbose {
-                    println!("Checking interface {i}");
-                }
-                if &**i == class {
-                    return true;
-                }
-            }
-            if verbose {
-                println!("Checking {}", current.super_class);
-            }
-            current = class_area.search(&current.super_class).unwrap();
+    }
+}
+
+/// check whether a value of type `from` can be used where `to` is expected. Arrays of references
+/// are covariant, but arrays of primitives only fit arrays of the same primitive
+fn is_field_type_assignable(
+    class_area: &SharedClassArea,
+    from: &FieldType,
+    to: &FieldType,
+    verbose: bool,
+) -> bool {
+    match (from, to) {
+        (FieldType::Array(from), FieldType::Array(to)) => {
+            is_field_type_assignable(class_area, from, to, verbose)
+        }
+        (FieldType::Object(from), FieldType::Object(to)) => {
+            is_assignable(class_area, from, to, verbose)
         }
-        false
+        (FieldType::Array(_), FieldType::Object(to)) => matches!(
+            &**to,
+            "java/lang/Object" | "java/lang/Cloneable" | "java/io/Serializable"
+        ),
+        (FieldType::Object(_), FieldType::Array(_)) => false,
+        (from, to) => std::mem::discriminant(from) == std::mem::discriminant(to),
     }
 }
 
+/// check whether a value of class `from` can be used where `to` is expected, because `to` is the
+/// same class, one of its superclasses, or an interface it implements
+#[must_use]
+pub fn is_assignable(class_area: &SharedClassArea, from: &str, to: &str, verbose: bool) -> bool {
+    let Some(mut current) = class_area.search(from) else {
+        return false;
+    };
+    if verbose {
+        println!("Checking if {} is an instance of {}", current.this, to);
+    }
+    if to == "java/lang/Object" {
+        return true;
+    }
+    while &*current.this != "java/lang/Object" {
+        if &*current.this == to {
+            return true;
+        }
+        if implements(class_area, &current, to, verbose) {
+            return true;
+        }
+        if verbose {
+            println!("Checking {}", current.super_class);
+        }
+        // a superclass that was never loaded can't lead to `to`
+        let Some(super_class) = class_area.search(&current.super_class) else {
+            return false;
+        };
+        current = super_class;
+    }
+    false
+}
+
+/// check whether any of a class's interfaces is `interface` or extends it
+fn implements(class_area: &SharedClassArea, class: &Class, interface: &str, verbose: bool) -> bool {
+    class.interfaces.iter().any(|i| {
+        if verbose {
+            println!("Checking interface {i}");
+        }
+        &**i == interface
+            || class_area
+                .search(i)
+                .is_some_and(|i| implements(class_area, &i, interface, verbose))
+    })
+}
+
 pub trait ObjectFinder {
     type Target<'a>;
 
@@ -289,11 +432,22 @@ impl ObjectFinder for AnyObj {
 
 pub type StringBuilder = NativeFieldObj<String>;
 pub type ArrayType = NativeFieldObj<FieldType>;
-pub type HashMapObj = NativeFieldObj<HashMap<u32, u32, BuildNonHasher>>;
-pub type HashSetObj = NativeFieldObj<HashSet<u32, BuildNonHasher>>;
+/// maps each hash code to the keys with that hash and their values
+pub type HashMapObj = NativeFieldObj<HashMap<u32, Vec<(u32, u32)>, BuildNonHasher>>;
+/// maps each hash code to the elements with that hash
+pub type HashSetObj = NativeFieldObj<HashMap<u32, Vec<u32>, BuildNonHasher>>;
 pub type ArrayListObj = NativeFieldObj<Vec<u32>>;
+pub type LinkedListObj = NativeFieldObj<VecDeque<u32>>;
 pub type ClassObj = NativeFieldObj<Arc<Class>>;
+pub type MethodObj = NativeFieldObj<Arc<Method>>;
 pub type Random = NativeFieldObj<StdRng>;
+/// the second value from the last `Random.nextGaussian` call, if it hasn't been returned yet
+pub type RandomGaussian = NativeFieldObj<Option<f64>, 1>;
+pub type ThreadObj = NativeFieldObj<JavaThread>;
+pub type CollectorObj = NativeFieldObj<CollectorKind>;
+pub type PrintStreamObj = NativeFieldObj<PrintTarget>;
+/// the bytes of a `ByteArrayInputStream` and how far it's read
+pub type ByteStream = NativeFieldObj<Cursor<Vec<u8>>>;
 
 impl StringBuilder {
     /// # Panics
@@ -327,74 +481,178 @@ impl NativeMethod for LambdaOverride {
         self.method_descriptor.parameter_size as u16 + 1
     }
 
+    /// stage 0 calls the method handle and stage 1 returns what it returned
+    fn stages(&self) -> usize {
+        2
+    }
+
+    #[allow(clippy::too_many_lines)]
     fn run(&self, thread: &mut Thread, verbose: bool) -> error::Result<()> {
-        match &self.invoke {
-            MethodHandle::InvokeStatic {
-                class: invoke_class,
-                name: invoke_name,
-                method_type: invoke_type,
-            } => {
-                match thread.pc_register {
-                    0 => {
-                        let (class_ref, method_ref) = thread
+        match thread.pc_register {
+            0 => {
+                let (MethodHandle::InvokeStatic {
+                    class: invoke_class,
+                    name: invoke_name,
+                    method_type: invoke_type,
+                }
+                | MethodHandle::InvokeSpecial {
+                    class: invoke_class,
+                    name: invoke_name,
+                    method_type: invoke_type,
+                }
+                | MethodHandle::InvokeVirtual {
+                    class: invoke_class,
+                    name: invoke_name,
+                    method_type: invoke_type,
+                }
+                | MethodHandle::InvokeInterface {
+                    class: invoke_class,
+                    name: invoke_name,
+                    method_type: invoke_type,
+                }
+                | MethodHandle::NewInvokeSpecial {
+                    class: invoke_class,
+                    name: invoke_name,
+                    method_type: invoke_type,
+                }) = &self.invoke
+                else {
+                    return Err(format!("Unimplemented Lambda Override: {:?}", self.invoke).into());
+                };
+                // an instance method's receiver is the first capture, or the first argument if
+                // the method reference is unbound
+                let mut combined_args: Vec<u32> = self
+                    .captures
+                    .iter()
+                    .copied()
+                    .chain(
+                        thread
+                            .stackframe
+                            .locals
+                            .iter()
+                            .skip(1)
+                            .take(self.method_descriptor.parameter_size)
+                            .copied(),
+                    )
+                    .collect();
+                let (class_ref, method_ref) = if let MethodHandle::InvokeVirtual { .. }
+                | MethodHandle::InvokeInterface { .. } =
+                    &self.invoke
+                {
+                    if combined_args[0] == NULL {
+                        return thread.throw_null_pointer_exception(
+                            &format!(
+                                "Cannot invoke \"{}.{invoke_name}()\"",
+                                invoke_class.replace('/', ".")
+                            ),
+                            verbose,
+                        );
+                    }
+                    AnyObj.inspect(&thread.heap, combined_args[0] as usize, |obj| {
+                        obj.resolve_method(
+                            &thread.method_area,
+                            &thread.class_area,
+                            invoke_name,
+                            invoke_type,
+                            verbose,
+                        )
+                    })??
+                } else {
+                    thread
                         .method_area
                         .search(invoke_class, invoke_name, invoke_type)
                         .ok_or_else(|| {
-                            format!("Error during Lambda Override InvokeStatic; {invoke_class}.{invoke_name}: {invoke_type:?}")
-                        })?;
+                            format!("Error during Lambda Override {}; {invoke_class}.{invoke_name}: {invoke_type:?}", self.invoke)
+                        })?
+                };
 
-                        if thread.maybe_initialize_class(&class_ref) {
-                            return Ok(());
-                        }
+                if thread.maybe_initialize_class(&class_ref) {
+                    return Ok(());
+                }
 
-                        if verbose {
-                            println!(
-                                "Lambda Override: Invoking Static Method {} on {}",
-                                method_ref.name, class_ref.this,
-                            );
-                        }
-                        let combined_args: Vec<u32> = self
-                            .captures
-                            .iter()
-                            .copied()
-                            .chain(
-                                thread
-                                    .stackframe
-                                    .locals
-                                    .iter()
-                                    .skip(1)
-                                    .take(self.method_descriptor.parameter_size)
-                                    .copied(),
-                            )
-                            .collect();
-                        // push the return address
-                        thread.stackframe.operand_stack.push(1);
-                        thread.invoke_method(method_ref, class_ref);
-                        thread
-                            .stackframe
-                            .locals
-                            .iter_mut()
-                            .zip(combined_args)
-                            .for_each(|(l, a)| *l = a);
-                        if verbose {
-                            println!("new locals: {:?}", thread.stackframe.locals);
+                if let MethodHandle::NewInvokeSpecial { .. } = &self.invoke {
+                    // `Type::new`; construct the object and leave it under the return address
+                    let new_obj = thread
+                        .heap
+                        .lock()
+                        .unwrap()
+                        .allocate(Object::from_class(&class_ref));
+                    thread.stackframe.operand_stack.push(new_obj);
+                    combined_args.insert(0, new_obj);
+                }
+
+                if verbose {
+                    println!(
+                        "Lambda Override: Invoking Method {} on {}",
+                        method_ref.name, class_ref.this,
+                    );
+                }
+                // push the return address
+                thread.stackframe.operand_stack.push(1);
+                thread.invoke_method(method_ref, class_ref);
+                thread
+                    .stackframe
+                    .locals
+                    .iter_mut()
+                    .zip(combined_args)
+                    .for_each(|(l, a)| *l = a);
+                if verbose {
+                    println!("new locals: {:?}", thread.stackframe.locals);
+                }
+            }
+            1 => match &self.method_descriptor.return_type {
+                None => thread.return_void()?,
+                Some(t) if t.is_reference() => {
+                    // a method like `String::length` returns a primitive that the interface expects boxed
+                    if let Some(primitive) = self.invoke_return_type() {
+                        if let Some(wrapper) = wrapper_class(primitive) {
+                            let stack = &mut thread.stackframe.operand_stack;
+                            let value = stack.split_off(stack.len() - primitive.get_size());
+                            let mut boxed =
+                                Object::from_class(&thread.class_area.search(wrapper).unwrap());
+                            boxed.fields[..value.len()].copy_from_slice(&value);
+                            let boxed = thread.heap.lock().unwrap().allocate(boxed);
+                            thread.stackframe.operand_stack.push(boxed);
                         }
                     }
-                    1 => match &self.method_descriptor.return_type {
-                        None => thread.return_void()?,
-                        Some(t) if t.get_size() == 1 => thread.return_one(verbose),
-                        _ => thread.return_two(verbose),
-                    },
-                    o => return Err(format!("Invalid opcode in Lambda Override: {o}").into()),
+                    thread.return_one(verbose);
                 }
-                Ok(())
-            }
-            other => Err(format!("Unimplemented Lambda Override: {other:?}").into()),
+                Some(t) if t.get_size() == 1 => thread.return_one(verbose),
+                _ => thread.return_two(verbose),
+            },
+            o => return Err(format!("Invalid opcode in Lambda Override: {o}").into()),
         }
+        Ok(())
+    }
+}
+
+/// the class that boxes values of a primitive type
+const fn wrapper_class(primitive: &FieldType) -> Option<&'static str> {
+    match primitive {
+        FieldType::Byte => Some("java/lang/Byte"),
+        FieldType::Char => Some("java/lang/Character"),
+        FieldType::Double => Some("java/lang/Double"),
+        FieldType::Float => Some("java/lang/Float"),
+        FieldType::Int => Some("java/lang/Integer"),
+        FieldType::Long => Some("java/lang/Long"),
+        FieldType::Short => Some("java/lang/Short"),
+        FieldType::Boolean => Some("java/lang/Boolean"),
+        FieldType::Object(_) | FieldType::Array(_) => None,
     }
 }
 
 impl LambdaOverride {
+    /// the type returned by the method handle, which may differ from the interface's return type.
+    /// Constructors return the object they made
+    const fn invoke_return_type(&self) -> Option<&FieldType> {
+        match &self.invoke {
+            MethodHandle::InvokeStatic { method_type, .. }
+            | MethodHandle::InvokeSpecial { method_type, .. }
+            | MethodHandle::InvokeVirtual { method_type, .. }
+            | MethodHandle::InvokeInterface { method_type, .. } => method_type.return_type.as_ref(),
+            _ => None,
+        }
+    }
+
     #[must_use]
     pub fn as_method(&self) -> Method {
         Method {
@@ -0,0 +1,297 @@
+use std::io::{self, BufRead, Write};
+
+use jvmrs_lib::{method, FieldType};
+
+use super::{
+    native::primitives::JavaFloat,
+    object::{is_assignable, StringObj},
+    JValue, JavaVm,
+};
+
+/// A literal argument typed at the prompt
+#[derive(Debug)]
+enum Literal {
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    Boolean(bool),
+    Char(char),
+    String(String),
+    Null,
+}
+
+/// Read `Class.method(args)` calls from `input`, one per line, and write each result to `output`.
+/// Arguments are java literals: numbers with an optional `L`, `f`, or `d` suffix, `true`, `false`,
+/// `'c'`, `"strings"`, and `null`. The loop ends at the end of the input or on `/exit`
+/// # Errors
+/// Returns an error if reading the input or writing the output fails
+pub fn repl(
+    vm: &JavaVm,
+    mut input: impl BufRead,
+    mut output: impl Write,
+    verbose: bool,
+) -> io::Result<()> {
+    let mut line = String::new();
+    loop {
+        write!(output, "> ")?;
+        output.flush()?;
+        line.clear();
+        if input.read_line(&mut line)? == 0 {
+            writeln!(output)?;
+            return Ok(());
+        }
+        let line = line.trim().trim_end_matches(';');
+        if line.is_empty() {
+            continue;
+        }
+        if line == "/exit" {
+            return Ok(());
+        }
+        match eval(vm, line, verbose) {
+            Ok(Some(result)) => writeln!(output, "{result}")?,
+            Ok(None) => {}
+            Err(err) => writeln!(output, "Error: {err}")?,
+        }
+    }
+}
+
+/// run one call and describe its result, or `None` if the method is `void`
+fn eval(vm: &JavaVm, line: &str, verbose: bool) -> Result<Option<String>, String> {
+    let (callee, args) = line
+        .strip_suffix(')')
+        .and_then(|call| call.split_once('('))
+        .ok_or_else(|| String::from("Expected a call like `Class.method(args)`"))?;
+    let (class, name) = callee
+        .rsplit_once('.')
+        .ok_or_else(|| format!("Expected a class name before `{callee}`"))?;
+    let class = class.trim().replace('.', "/");
+    let name = name.trim();
+    let args = split_args(args)?
+        .iter()
+        .map(|arg| parse_literal(arg))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let class_ref = vm
+        .class_area
+        .search(&class)
+        .ok_or_else(|| format!("Class `{class}` isn't loaded"))?;
+    let descriptor = class_ref
+        .vtable
+        .iter()
+        .map(|entry| &entry.name)
+        .filter(|method| *method.class == *class && *method.name == *name)
+        .filter(|method| {
+            vm.method_area
+                .search(&method.class, &method.name, &method.descriptor)
+                .is_some_and(|(_, method)| method.access_flags.is_static())
+        })
+        .map(|method| &method.descriptor)
+        .find(|descriptor| {
+            descriptor.parameters.len() == args.len()
+                && descriptor
+                    .parameters
+                    .iter()
+                    .zip(&args)
+                    .all(|(ty, arg)| fits(vm, arg, ty))
+        })
+        .ok_or_else(|| format!("No static method {class}.{name} takes those arguments"))?
+        .clone();
+
+    let mut slots = Vec::with_capacity(descriptor.parameter_size);
+    for (ty, arg) in descriptor.parameters.iter().zip(args) {
+        slots.extend(to_slots(vm, ty, arg));
+    }
+    let result = vm
+        .invoke_static(&class, name, &descriptor, &slots, verbose)
+        .map_err(|err| err.to_string())?;
+    let Some(return_type) = &descriptor.return_type else {
+        return Ok(None);
+    };
+    describe(
+        vm,
+        return_type,
+        JValue::from_slots(return_type, &result),
+        verbose,
+    )
+    .map(Some)
+}
+
+/// split an argument list on the commas that aren't inside a string or char literal
+fn split_args(args: &str) -> Result<Vec<&str>, String> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut quote = None;
+    let mut escaped = false;
+    for (idx, c) in args.char_indices() {
+        match (quote, c) {
+            (Some(_), _) if escaped => escaped = false,
+            (Some(_), '\\') => escaped = true,
+            (Some(q), c) if c == q => quote = None,
+            (None, '"' | '\'') => quote = Some(c),
+            (None, ',') => {
+                parts.push(args[start..idx].trim());
+                start = idx + 1;
+            }
+            _ => {}
+        }
+    }
+    if quote.is_some() {
+        return Err(String::from("Unterminated literal"));
+    }
+    let last = args[start..].trim();
+    if !last.is_empty() || !parts.is_empty() {
+        parts.push(last);
+    }
+    Ok(parts)
+}
+
+fn parse_literal(arg: &str) -> Result<Literal, String> {
+    let invalid = || format!("`{arg}` isn't a literal");
+    if let Some(str) = arg.strip_prefix('"').and_then(|arg| arg.strip_suffix('"')) {
+        return Ok(Literal::String(unescape(str)));
+    }
+    if let Some(c) = arg
+        .strip_prefix('\'')
+        .and_then(|arg| arg.strip_suffix('\''))
+    {
+        let mut chars = unescape(c).chars();
+        return match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(Literal::Char(c)),
+            _ => Err(invalid()),
+        };
+    }
+    match arg {
+        "true" => return Ok(Literal::Boolean(true)),
+        "false" => return Ok(Literal::Boolean(false)),
+        "null" => return Ok(Literal::Null),
+        _ => {}
+    }
+    if let Some(long) = arg.strip_suffix(['L', 'l']) {
+        return long.parse().map(Literal::Long).map_err(|_| invalid());
+    }
+    if let Some(float) = arg.strip_suffix(['F', 'f']) {
+        return float.parse().map(Literal::Float).map_err(|_| invalid());
+    }
+    if let Some(double) = arg.strip_suffix(['D', 'd']) {
+        return double.parse().map(Literal::Double).map_err(|_| invalid());
+    }
+    if arg.contains(['.', 'e', 'E']) {
+        return arg.parse().map(Literal::Double).map_err(|_| invalid());
+    }
+    arg.parse().map(Literal::Int).map_err(|_| invalid())
+}
+
+/// resolve the escapes java allows in string and char literals
+fn unescape(str: &str) -> String {
+    let mut output = String::with_capacity(str.len());
+    let mut chars = str.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            output.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => output.push('\n'),
+            Some('t') => output.push('\t'),
+            Some('r') => output.push('\r'),
+            Some('0') => output.push('\0'),
+            Some(other) => output.push(other),
+            None => output.push('\\'),
+        }
+    }
+    output
+}
+
+/// whether a literal can be passed as a parameter of type `ty`, allowing widening conversions. A
+/// string can be passed as any class or interface that `String` extends or implements
+fn fits(vm: &JavaVm, arg: &Literal, ty: &FieldType) -> bool {
+    match arg {
+        Literal::Int(_) => matches!(
+            ty,
+            FieldType::Int | FieldType::Long | FieldType::Float | FieldType::Double
+        ),
+        Literal::Char(_) => matches!(
+            ty,
+            FieldType::Char
+                | FieldType::Int
+                | FieldType::Long
+                | FieldType::Float
+                | FieldType::Double
+        ),
+        Literal::Long(_) => matches!(ty, FieldType::Long | FieldType::Float | FieldType::Double),
+        Literal::Float(_) => matches!(ty, FieldType::Float | FieldType::Double),
+        Literal::Double(_) => matches!(ty, FieldType::Double),
+        Literal::Boolean(_) => matches!(ty, FieldType::Boolean),
+        Literal::String(_) => matches!(
+            ty,
+            FieldType::Object(class) if is_assignable(&vm.class_area, "java/lang/String", class, false)
+        ),
+        Literal::Null => matches!(ty, FieldType::Object(_) | FieldType::Array(_)),
+    }
+}
+
+/// the local variable slots for a literal passed as a parameter of type `ty`
+fn to_slots(vm: &JavaVm, ty: &FieldType, arg: Literal) -> Vec<u32> {
+    let wide = match arg {
+        Literal::Int(i) => i64::from(i),
+        Literal::Char(c) => c as i64,
+        Literal::Long(l) => l,
+        Literal::Float(f) => return float_slots(ty, f64::from(f)),
+        Literal::Double(d) => return float_slots(ty, d),
+        Literal::Boolean(b) => return vec![u32::from(b)],
+        Literal::String(str) => {
+            return vec![vm.heap.lock().unwrap().allocate_str(str.into())];
+        }
+        Literal::Null => return vec![0],
+    };
+    match ty {
+        FieldType::Long => vec![(wide >> 32) as u32, wide as u32],
+        FieldType::Float | FieldType::Double => float_slots(ty, wide as f64),
+        _ => vec![wide as u32],
+    }
+}
+
+fn float_slots(ty: &FieldType, value: f64) -> Vec<u32> {
+    if ty == &FieldType::Float {
+        vec![(value as f32).to_bits()]
+    } else {
+        let bits = value.to_bits();
+        vec![(bits >> 32) as u32, bits as u32]
+    }
+}
+
+/// show a value the way jshell does, quoting strings and calling `toString` on other objects
+fn describe(vm: &JavaVm, ty: &FieldType, value: JValue, verbose: bool) -> Result<String, String> {
+    Ok(match (ty, value) {
+        (FieldType::Boolean, JValue::Int(b)) => (b != 0).to_string(),
+        (FieldType::Char, JValue::Int(c)) => {
+            format!("'{}'", char::from_u32(c as u32).unwrap_or('?'))
+        }
+        (_, JValue::Int(i)) => i.to_string(),
+        (_, JValue::Long(l)) => l.to_string(),
+        (_, JValue::Float(f)) => format!("{}f", JavaFloat(f)),
+        (_, JValue::Double(d)) => JavaFloat(d).to_string(),
+        (_, JValue::Reference(0)) => String::from("null"),
+        (FieldType::Object(class), JValue::Reference(ptr)) if &**class == "java/lang/String" => {
+            format!("{:?}", read_string(vm, ptr)?)
+        }
+        (_, JValue::Reference(ptr)) => {
+            let string = vm
+                .invoke_static(
+                    "java/lang/String",
+                    "valueOf",
+                    &method!(((Object("java/lang/Object".into()))) -> Object("java/lang/String".into())),
+                    &[ptr],
+                    verbose,
+                )
+                .map_err(|err| err.to_string())?;
+            read_string(vm, string.first().copied().unwrap_or_default())?
+        }
+        (_, JValue::Top | JValue::Raw(_)) => return Err(String::from("Method returned no value")),
+    })
+}
+
+fn read_string(vm: &JavaVm, ptr: u32) -> Result<String, String> {
+    StringObj::inspect(&vm.heap, ptr as usize, |str| str.to_string()).map_err(|err| err.to_string())
+}
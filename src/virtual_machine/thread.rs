@@ -1,18 +1,29 @@
-use std::{cmp::Ordering, fmt::Write, sync::Arc};
+use std::{
+    cmp::Ordering,
+    fmt::Write,
+    io,
+    sync::{Arc, Mutex, OnceLock},
+};
 
 use crate::{
-    class::{BootstrapMethod, Class, Method},
+    class::{code::NativeMethod, BootstrapMethod, Class, Method},
     data::{Heap, SharedClassArea, SharedHeap, SharedMethodArea, NULL},
     virtual_machine::object::LambdaOverride,
 };
 
 use super::{
     error,
-    instruction::Type,
+    instruction::{mnemonic, Type},
+    native::{
+        get_class,
+        primitives::JavaFloat,
+        record::{RecordMethod, RecordMethodKind},
+    },
     object::{AnyObj, Array1, Array2, ArrayType, Object, ObjectFinder, StringObj},
     Cmp, Instruction, Op, StackFrame,
 };
 
+pub mod frames;
 pub mod stacking;
 
 use jvmrs_lib::{method, Constant, FieldType, MethodDescriptor, MethodHandle};
@@ -25,32 +36,135 @@ pub struct Thread {
     pub method_area: SharedMethodArea,
     pub class_area: SharedClassArea,
     pub heap: SharedHeap,
+    /// throw an `ArithmeticException` when int or long arithmetic overflows instead of wrapping
+    pub trap_overflow: bool,
+    /// fail with a `VerifyError` when a method's operand stack grows past its declared `max_stack`
+    pub verify: bool,
+    /// make `Object.hashCode` count up from 1 in the order objects are hashed, instead of hashing
+    /// the object's pointer
+    pub deterministic_hash: bool,
+    /// print each bytecode instruction's offset and `javap` name to stderr as it runs, along with
+    /// the operand stack top it leaves behind
+    pub trace: bool,
+    /// the deepest the call stack can get before a call throws a `StackOverflowError`
+    pub max_depth: usize,
 }
 
+/// where the trace goes, if not stderr
+static TRACE_OUTPUT: Mutex<Option<Box<dyn io::Write + Send>>> = Mutex::new(None);
+
+/// Write the instruction trace to the given writer instead of stderr
+/// # Panics
+pub fn set_trace_output(writer: impl io::Write + Send + 'static) {
+    *TRACE_OUTPUT.lock().unwrap() = Some(Box::new(writer));
+}
+
+/// rearrange the top of the operand stack. Values are named from deepest to topmost. Longs and
+/// doubles take up two slots, so every category form of the `dup` instructions is a plain slot shuffle
 macro_rules! stack {
     ($stack: expr => [$($before:ident),*] => [$($after:ident),*]) => {
+        let depth = [$(stringify!($before)),*].len();
+        let mut popped = $stack.split_off($stack.len() - depth).into_iter();
         $(
-            let $before = $stack.pop().unwrap();
+            let $before = popped.next().unwrap();
         )*
         $stack.extend([$($after),*]);
     };
 }
 
 impl Thread {
-    #[allow(clippy::too_many_lines, clippy::cognitive_complexity)]
+    /// run a tracing collection if enough objects have been allocated since the last one, using
+    /// every value on the call stack as a root
+    fn collect_cycles(&self) {
+        let mut heap = self.heap.lock().unwrap();
+        if !heap.gc_due() {
+            return;
+        }
+        let roots = self
+            .stack
+            .iter()
+            .chain([&self.stackframe])
+            .flat_map(|frame| {
+                frame
+                    .locals
+                    .iter()
+                    .chain(&frame.operand_stack)
+                    .chain(&frame.garbage)
+                    .chain(&frame.monitor)
+            })
+            .copied();
+        heap.gc(roots);
+    }
+
     /// # Panics
     /// # Errors
     pub fn tick(&mut self, verbose: bool) -> super::error::Result<()> {
+        if self.pc_register == 0 && self.stack.len() > self.max_depth {
+            return self.throw_stack_overflow(verbose);
+        }
         // this way we can mutate the stack frame without angering the borrow checker
         let method = self.stackframe.method.clone();
         if let Some(native_method) = method.code.as_native() {
             // return self.invoke_native(&stackframe, verbose);
+            if cfg!(debug_assertions) {
+                return self.run_native_checked(&**native_method, verbose);
+            }
             return native_method.run(self, verbose);
         }
+        if self.verify && self.verify_operand_stack(verbose)? {
+            return Ok(());
+        }
+        if self.pc_register == 0
+            && self.stackframe.monitor.is_none()
+            && method.access_flags.is_synchronized()
+        {
+            self.enter_method_monitor();
+        }
+        // every frame is in place when a method starts, so this is a safe point to trace from
+        if self.pc_register == 0 {
+            self.collect_cycles();
+        }
+        let pc = self.pc_register;
         let opcode = self.get_pc_byte();
         if verbose {
             println!("{opcode:?}");
         }
+        if !self.trace {
+            return self.execute(opcode, verbose);
+        }
+        let depth = self.stack.len();
+        let location = format!("{}.{}", self.stackframe.class.this, method.name);
+        // name the instruction the way javap does, unless the code didn't come from a class file
+        let instruction = method
+            .code
+            .as_bytecode()
+            .and_then(|code| code.opcodes.get(pc))
+            .and_then(|&(offset, opcode)| Some((offset, mnemonic(opcode)?)))
+            .map_or_else(
+                || format!("{pc:>4}: {opcode:?}"),
+                |(offset, name)| format!("{offset:>4}: {name}"),
+            );
+        let result = self.execute(opcode, verbose);
+        // only show the stack top if the instruction didn't call or return from a method
+        let top = if self.stack.len() == depth {
+            self.stackframe
+                .operand_stack
+                .last()
+                .map_or_else(String::new, |top| format!(" -> {top}"))
+        } else {
+            String::new()
+        };
+        let line = format!("{location} {instruction}{top}");
+        match &mut *TRACE_OUTPUT.lock().unwrap() {
+            Some(output) => writeln!(output, "{line}").map_err(|err| err.to_string())?,
+            None => eprintln!("{line}"),
+        }
+        result
+    }
+
+    /// run a single bytecode instruction
+    #[allow(clippy::too_many_lines, clippy::cognitive_complexity)]
+    fn execute(&mut self, opcode: Instruction, verbose: bool) -> super::error::Result<()> {
         match opcode {
             Instruction::Noop => {
                 // nope
@@ -71,6 +185,13 @@ impl Thread {
                 self.rember(str_ptr, verbose);
                 self.stackframe.operand_stack.push(str_ptr);
             }
+            Instruction::LoadClass(class) => {
+                let class_ptr = get_class(&self.heap, &self.class_area, class)
+                    .ok_or_else(|| error::Error::class_resolution(&"java/lang/Class"))?;
+                // class objects are cached, so they have to outlive this frame like static strings
+                self.rember(class_ptr, verbose);
+                self.stackframe.operand_stack.push(class_ptr);
+            }
             Instruction::Load2(index) => {
                 // load a double from locals to stack
                 long_load(&mut self.stackframe, index);
@@ -126,7 +247,13 @@ impl Thread {
                 // int add
                 let rhs = self.stackframe.operand_stack.popd::<i32>().unwrap();
                 let lhs = self.stackframe.operand_stack.popd::<i32>().unwrap();
-                let result = lhs.wrapping_add(rhs);
+                let result = match lhs.checked_add(rhs) {
+                    Some(result) => result,
+                    None if self.trap_overflow => {
+                        return self.throw_arithmetic_exception("integer overflow", verbose)
+                    }
+                    None => lhs.wrapping_add(rhs),
+                };
                 self.stackframe.operand_stack.pushd(result);
             }
             Instruction::LOp(Op::Add) => {
@@ -134,12 +261,22 @@ impl Thread {
                 // long add
                 let rhs = self.stackframe.operand_stack.popd::<i64>().unwrap();
                 let lhs = self.stackframe.operand_stack.popd::<i64>().unwrap();
-                let result = lhs.wrapping_add(rhs);
+                let result = match lhs.checked_add(rhs) {
+                    Some(result) => result,
+                    None if self.trap_overflow => {
+                        return self.throw_arithmetic_exception("long overflow", verbose)
+                    }
+                    None => lhs.wrapping_add(rhs),
+                };
                 self.stackframe.operand_stack.pushd(result);
             }
             Instruction::FOp(Op::Add) => {
                 // fadd
                 // float add
+                // Rust's f32 and f64 round every operation to the nearest representable value, with
+                // no extended precision in between. That's the strictfp behavior java 17 made the
+                // only one, so float and double results match the JVM's bit for bit in either
+                // `FloatMode`
                 let rhs = self.stackframe.operand_stack.popd::<f32>().unwrap();
                 let lhs = self.stackframe.operand_stack.popd::<f32>().unwrap();
                 let result = lhs + rhs;
@@ -158,7 +295,13 @@ impl Thread {
                 // int subtract
                 let rhs = self.stackframe.operand_stack.popd::<i32>().unwrap();
                 let lhs = self.stackframe.operand_stack.popd::<i32>().unwrap();
-                let result = lhs.wrapping_sub(rhs);
+                let result = match lhs.checked_sub(rhs) {
+                    Some(result) => result,
+                    None if self.trap_overflow => {
+                        return self.throw_arithmetic_exception("integer overflow", verbose)
+                    }
+                    None => lhs.wrapping_sub(rhs),
+                };
                 self.stackframe.operand_stack.pushd(result);
             }
             Instruction::LOp(Op::Sub) => {
@@ -166,7 +309,13 @@ impl Thread {
                 // long subtract
                 let rhs = self.stackframe.operand_stack.popd::<i64>().unwrap();
                 let lhs = self.stackframe.operand_stack.popd::<i64>().unwrap();
-                let result = lhs.wrapping_sub(rhs);
+                let result = match lhs.checked_sub(rhs) {
+                    Some(result) => result,
+                    None if self.trap_overflow => {
+                        return self.throw_arithmetic_exception("long overflow", verbose)
+                    }
+                    None => lhs.wrapping_sub(rhs),
+                };
                 self.stackframe.operand_stack.pushd(result);
             }
             Instruction::FOp(Op::Sub) => {
@@ -190,7 +339,13 @@ impl Thread {
                 // int multiply
                 let rhs = self.stackframe.operand_stack.popd::<i32>().unwrap();
                 let lhs = self.stackframe.operand_stack.popd::<i32>().unwrap();
-                let result = lhs.wrapping_mul(rhs);
+                let result = match lhs.checked_mul(rhs) {
+                    Some(result) => result,
+                    None if self.trap_overflow => {
+                        return self.throw_arithmetic_exception("integer overflow", verbose)
+                    }
+                    None => lhs.wrapping_mul(rhs),
+                };
                 self.stackframe.operand_stack.pushd(result);
             }
             Instruction::LOp(Op::Mul) => {
@@ -198,7 +353,13 @@ impl Thread {
                 // long multiply
                 let rhs = self.stackframe.operand_stack.popd::<i64>().unwrap();
                 let lhs = self.stackframe.operand_stack.popd::<i64>().unwrap();
-                let result = lhs.wrapping_mul(rhs);
+                let result = match lhs.checked_mul(rhs) {
+                    Some(result) => result,
+                    None if self.trap_overflow => {
+                        return self.throw_arithmetic_exception("long overflow", verbose)
+                    }
+                    None => lhs.wrapping_mul(rhs),
+                };
                 self.stackframe.operand_stack.pushd(result);
             }
             Instruction::FOp(Op::Mul) => {
@@ -222,11 +383,10 @@ impl Thread {
                 // int divide
                 let rhs = self.stackframe.operand_stack.popd::<i32>().unwrap();
                 let lhs = self.stackframe.operand_stack.popd::<i32>().unwrap();
-                // TODO: Check for zero division
                 if rhs == 0 {
-                    todo!("throw new ArithmeticException");
+                    return self.throw_arithmetic_exception("/ by zero", verbose);
                 }
-                let result = lhs / rhs;
+                let result = lhs.wrapping_div(rhs);
                 self.stackframe.operand_stack.pushd(result);
             }
             Instruction::LOp(Op::Div) => {
@@ -235,18 +395,10 @@ impl Thread {
 
                 let rhs = self.stackframe.operand_stack.popd::<i64>().unwrap();
                 let lhs = self.stackframe.operand_stack.popd::<i64>().unwrap();
-                // TODO: Check for zero division
                 if rhs == 0 {
-                    let exception = Object::from_class(
-                        &self
-                            .class_area
-                            .search("java/lang/ArithmeticException")
-                            .unwrap(),
-                    );
-                    self.throw_obj(exception, verbose)?;
-                    return Ok(());
+                    return self.throw_arithmetic_exception("/ by zero", verbose);
                 }
-                let result = lhs / rhs;
+                let result = lhs.wrapping_div(rhs);
                 self.stackframe.operand_stack.pushd(result);
             }
             Instruction::FOp(Op::Div) => {
@@ -270,11 +422,10 @@ impl Thread {
                 // int remainder
                 let rhs = self.stackframe.operand_stack.popd::<i32>().unwrap();
                 let lhs = self.stackframe.operand_stack.popd::<i32>().unwrap();
-                // TODO: Check for zero division
                 if rhs == 0 {
-                    todo!("throw new ArithmeticException");
+                    return self.throw_arithmetic_exception("/ by zero", verbose);
                 }
-                let result = lhs % rhs;
+                let result = lhs.wrapping_rem(rhs);
                 self.stackframe.operand_stack.pushd(result);
             }
             Instruction::LOp(Op::Mod) => {
@@ -283,11 +434,10 @@ impl Thread {
 
                 let rhs = self.stackframe.operand_stack.popd::<i64>().unwrap();
                 let lhs = self.stackframe.operand_stack.popd::<i64>().unwrap();
-                // TODO: Check for zero division
                 if rhs == 0 {
-                    todo!("throw new ArithmeticException");
+                    return self.throw_arithmetic_exception("/ by zero", verbose);
                 }
-                let result = lhs % rhs;
+                let result = lhs.wrapping_rem(rhs);
                 self.stackframe.operand_stack.pushd(result);
             }
             Instruction::FOp(Op::Mod) => {
@@ -310,14 +460,26 @@ impl Thread {
                 // ineg
                 // negate int
                 let f = self.stackframe.operand_stack.popd::<i32>().unwrap();
-                let result = -f;
+                let result = match f.checked_neg() {
+                    Some(result) => result,
+                    None if self.trap_overflow => {
+                        return self.throw_arithmetic_exception("integer overflow", verbose)
+                    }
+                    None => f.wrapping_neg(),
+                };
                 self.stackframe.operand_stack.pushd(result);
             }
             Instruction::LOp(Op::Neg) => {
                 // lneg
                 // negate long
                 let l = self.stackframe.operand_stack.popd::<i64>().unwrap();
-                let result = -l;
+                let result = match l.checked_neg() {
+                    Some(result) => result,
+                    None if self.trap_overflow => {
+                        return self.throw_arithmetic_exception("long overflow", verbose)
+                    }
+                    None => l.wrapping_neg(),
+                };
                 self.stackframe.operand_stack.pushd(result);
             }
             Instruction::FOp(Op::Neg) => {
@@ -531,7 +693,7 @@ impl Thread {
                 // i2c
                 // int to char
                 let int = self.stackframe.operand_stack.popd::<u32>().unwrap();
-                let char = int as u8 as u32;
+                let char = int as u16 as u32;
                 self.stackframe.operand_stack.pushd(char);
             }
             Instruction::Convert(Type::Int, Type::Short) => {
@@ -558,15 +720,7 @@ impl Thread {
                 // float comparison
                 let rhs = self.stackframe.operand_stack.popd::<f32>().unwrap();
                 let lhs = self.stackframe.operand_stack.popd::<f32>().unwrap();
-                let value = if lhs > rhs {
-                    1
-                } else if (lhs - rhs).abs() < f32::EPSILON {
-                    0
-                } else if lhs < rhs || is_rev {
-                    -1
-                } else {
-                    1
-                };
+                let value = fcmp(lhs.partial_cmp(&rhs), is_rev);
                 self.stackframe.operand_stack.pushd(value);
             }
             Instruction::DCmp(is_rev) => {
@@ -574,15 +728,7 @@ impl Thread {
                 // double comparison
                 let rhs = self.stackframe.operand_stack.popd::<f64>().unwrap();
                 let lhs = self.stackframe.operand_stack.popd::<f64>().unwrap();
-                let value = if lhs > rhs {
-                    1
-                } else if (lhs - rhs).abs() < f64::EPSILON {
-                    0
-                } else if lhs < rhs || is_rev {
-                    -1
-                } else {
-                    1
-                };
+                let value = fcmp(lhs.partial_cmp(&rhs), is_rev);
                 self.stackframe.operand_stack.pushd(value);
             }
             Instruction::IfCmpZ(cmp, branch) => {
@@ -598,7 +744,7 @@ impl Thread {
                     Cmp::Le => lhs <= 0,
                 };
                 if cond {
-                    self.pc_register = branch as usize;
+                    self.branch(branch as usize);
                 }
             }
             Instruction::ICmp(cnd, branch) => {
@@ -614,12 +760,40 @@ impl Thread {
                     Cmp::Gt => lhs > rhs,
                     Cmp::Le => lhs <= rhs,
                 } {
-                    self.pc_register = branch as usize;
+                    self.branch(branch as usize);
                 }
             }
             Instruction::Goto(goto) => {
                 // goto bb1 bb2
-                self.pc_register = goto as usize;
+                self.branch(goto as usize);
+            }
+            Instruction::Ret(index) => {
+                // ret
+                // jump to the return address a `jsr` left in a local
+                self.pc_register = self.stackframe.locals[index] as usize;
+            }
+            Instruction::TableSwitch {
+                default,
+                low,
+                high,
+                offsets,
+            } => {
+                // tableswitch
+                let key = self.stackframe.operand_stack.popd::<i32>().unwrap();
+                let target = if (low..=high).contains(&key) {
+                    offsets[(key - low) as usize]
+                } else {
+                    default
+                };
+                self.branch(target as usize);
+            }
+            Instruction::LookupSwitch { default, pairs } => {
+                // lookupswitch
+                let key = self.stackframe.operand_stack.popd::<i32>().unwrap();
+                let target = pairs
+                    .binary_search_by_key(&key, |&(pair_key, _)| pair_key)
+                    .map_or(default, |pair| pairs[pair].1);
+                self.branch(target as usize);
             }
             Instruction::Return0 => {
                 // return void
@@ -629,6 +803,10 @@ impl Thread {
                 // return one thing
                 self.return_one(verbose);
             }
+            Instruction::Return2 => {
+                // return a long or double
+                self.return_two(verbose);
+            }
             Instruction::PutStatic(class, name, field_type, index_lock) => {
                 // putstatic
                 // put a static field to a class
@@ -636,28 +814,18 @@ impl Thread {
                     return Err(error::Error::class_resolution(&class));
                 };
 
+                let (class, staticindex) = self.resolve_static(&class, &name, &index_lock)?;
                 if self.maybe_initialize_class(&class) {
                     return Ok(());
                 }
-
-                let &staticindex = index_lock.get_or_init(|| {
-                    class
-                        .statics
-                        .iter()
-                        .find(|(field, _)| field.name == name)
-                        .ok_or_else(|| {
-                            format!("Couldn't find static `{name}` on class `{}`", class.this)
-                        })
-                        .unwrap()
-                        .1
-                });
                 if verbose {
                     println!("Putting Static {name} of {}", class.this);
                 }
                 let mut static_fields = class.static_data.lock().unwrap();
 
                 if field_type.get_size() == 1 {
-                    let value = self.stackframe.operand_stack.pop().unwrap();
+                    let value =
+                        narrow_int(&field_type, self.stackframe.operand_stack.pop().unwrap());
                     if field_type.is_reference() {
                         self.forgor(static_fields[staticindex], verbose);
                         self.rember(value, verbose);
@@ -671,28 +839,34 @@ impl Thread {
                     static_fields[staticindex + 1] = lower;
                 }
             }
-            Instruction::GetStatic(class, name, field_type, staticindex) => {
+            Instruction::GetStatic(class, name, field_type, index_lock) => {
                 // getstatic
                 // get a static field from a class
                 let Some(class) = self.class_area.search(&class) else {
                     return Err(error::Error::class_resolution(&class));
                 };
 
-                if self.maybe_initialize_class(&class) {
-                    return Ok(());
+                let (class, staticindex) = self.resolve_static(&class, &name, &index_lock)?;
+                // reading a compile-time constant doesn't initialize its class (JLS 12.4.1)
+                let constant = class
+                    .statics
+                    .iter()
+                    .find(|(_, idx)| *idx == staticindex)
+                    .and_then(|(field, _)| field.constant_value.clone());
+                match constant {
+                    Some(Constant::String(str) | Constant::StringRef(str)) => {
+                        let str_ptr = self.heap.lock().unwrap().allocate_str(str);
+                        self.rember(str_ptr, verbose);
+                        self.stackframe.operand_stack.push(str_ptr);
+                        return Ok(());
+                    }
+                    Some(_) => {}
+                    None => {
+                        if self.maybe_initialize_class(&class) {
+                            return Ok(());
+                        }
+                    }
                 }
-
-                let &staticindex = staticindex.get_or_init(|| {
-                    class
-                        .statics
-                        .iter()
-                        .find(|(field, _)| field.name == name)
-                        .ok_or_else(|| {
-                            format!("Couldn't find static `{name}` on class `{}`", class.this)
-                        })
-                        .unwrap()
-                        .1
-                });
                 if verbose {
                     println!("Getting Static {name} of {}", class.this);
                 }
@@ -712,9 +886,15 @@ impl Thread {
                     self.stackframe.operand_stack.extend([upper, lower]);
                 }
             }
-            Instruction::GetField(Some(idx), _class, _name, field_type) => {
+            Instruction::GetField(Some(idx), _class, name, field_type) => {
                 // get a field from an object
                 let object_index = self.stackframe.operand_stack.pop().unwrap();
+                if object_index == NULL {
+                    return self.throw_null_pointer_exception(
+                        &format!("Cannot read field \"{name}\""),
+                        verbose,
+                    );
+                }
 
                 let rember =
                     AnyObj.inspect(&self.heap, object_index as usize, |object_borrow| {
@@ -737,21 +917,25 @@ impl Thread {
                     self.rember_temp(value, verbose);
                 }
             }
-            Instruction::PutField(Some(idx), _class, _name, field_type) => {
+            Instruction::PutField(Some(idx), _class, name, field_type) => {
                 // putfield
                 // set a field in an object
 
                 let value = if field_type.get_size() == 1 {
-                    self.stackframe.operand_stack.pop().unwrap() as u64
+                    narrow_int(&field_type, self.stackframe.operand_stack.pop().unwrap()) as u64
                 } else {
                     self.stackframe.operand_stack.popd::<u64>().unwrap()
                 };
                 let object_index = self.stackframe.operand_stack.pop().unwrap();
+                if object_index == NULL {
+                    return self.throw_null_pointer_exception(
+                        &format!("Cannot assign field \"{name}\""),
+                        verbose,
+                    );
+                }
 
-                let forgor_rember = AnyObj.inspect(
-                    &self.heap,
-                    object_index as usize,
-                    |object_borrow| -> Result<Option<(u32, u32)>, String> {
+                let forgor_rember =
+                    AnyObj.inspect(&self.heap, object_index as usize, |object_borrow| {
                         if verbose {
                             println!("Object class: {}", object_borrow.this_class());
                         }
@@ -768,15 +952,14 @@ impl Thread {
                             object_borrow.fields[idx] = (value >> 32) as u32;
                             object_borrow.fields[idx + 1] = value as u32;
                         }
-                        Ok(forgor_rember)
-                    },
-                )??;
+                        forgor_rember
+                    })?;
                 if let Some((forgor, rember)) = forgor_rember {
                     self.forgor(forgor, verbose);
                     self.rember(rember, verbose);
                 }
             }
-            Instruction::InvokeVirtual(Some(idx), _class, _name, method_type) => {
+            Instruction::InvokeVirtual(Some(idx), class, name, method_type) => {
                 let arg_count = method_type.parameter_size;
                 let obj_pointer = *self
                     .stackframe
@@ -785,18 +968,34 @@ impl Thread {
                     .rev()
                     .nth(arg_count)
                     .unwrap();
+                if obj_pointer == NULL {
+                    return self.throw_null_pointer_exception(
+                        &format!("Cannot invoke \"{}.{name}()\"", class.replace('/', ".")),
+                        verbose,
+                    );
+                }
                 let this_class =
                     AnyObj.inspect(&self.heap, obj_pointer as usize, |o| o.class.clone())?;
-                let this_class = self.class_area.search(&this_class).expect(&this_class);
+                let this_class = self
+                    .class_area
+                    .search(&this_class)
+                    .ok_or_else(|| error::Error::class_resolution(&this_class))?;
                 let entry = &this_class.vtable[idx];
-                let (resolved_class, resolved_method) = entry
-                    .value
-                    .get_or_init(|| {
-                        self.method_area
-                            .search(&entry.name.class, &entry.name.name, &entry.name.descriptor)
-                            .unwrap()
-                    })
-                    .clone();
+                let (resolved_class, resolved_method) = if let Some(resolved) = entry.value.get() {
+                    resolved.clone()
+                } else {
+                    let resolved = self
+                        .method_area
+                        .search(&entry.name.class, &entry.name.name, &entry.name.descriptor)
+                        .ok_or_else(|| {
+                            self.no_such_method(
+                                &entry.name.class,
+                                &entry.name.name,
+                                &entry.name.descriptor,
+                            )
+                        })?;
+                    entry.value.get_or_init(|| resolved).clone()
+                };
                 let args_start = self.stackframe.operand_stack.len() - arg_count - 1;
                 if verbose {
                     println!(
@@ -827,8 +1026,8 @@ impl Thread {
                     println!("new locals: {:?}", self.stackframe.locals);
                 }
             }
-            Instruction::InvokeVirtual(None, _class, name, method_type)
-            | Instruction::InvokeInterface(_class, name, method_type) => {
+            Instruction::InvokeVirtual(None, class, name, method_type)
+            | Instruction::InvokeInterface(class, name, method_type) => {
                 // invokevirtual
                 // invoke a method virtually I guess
                 let arg_count = method_type.parameter_size;
@@ -839,6 +1038,12 @@ impl Thread {
                     .rev()
                     .nth(arg_count)
                     .unwrap();
+                if obj_pointer == NULL {
+                    return self.throw_null_pointer_exception(
+                        &format!("Cannot invoke \"{}.{name}()\"", class.replace('/', ".")),
+                        verbose,
+                    );
+                }
                 let (resolved_class, resolved_method) =
                     AnyObj.inspect(&self.heap, obj_pointer as usize, |obj| {
                         obj.resolve_method(
@@ -848,7 +1053,7 @@ impl Thread {
                             &method_type,
                             verbose,
                         )
-                    })?;
+                    })??;
                 let args_start = self.stackframe.operand_stack.len() - arg_count - 1;
                 if verbose {
                     println!(
@@ -893,7 +1098,7 @@ impl Thread {
                 let (class_ref, method_ref) = self
                     .method_area
                     .search(&current_class, &name, &method_type)
-                    .ok_or_else(|| format!("Error during InvokeSpecial; {current_class}.{name}"))?;
+                    .ok_or_else(|| self.no_such_method(&current_class, &name, &method_type))?;
                 let args_start =
                     self.stackframe.operand_stack.len() - method_type.parameter_size - 1;
                 let stack = &mut self.stackframe.operand_stack;
@@ -924,18 +1129,15 @@ impl Thread {
             }
             Instruction::InvokeStatic(class, name, method_type, resolved_method) => {
                 // make a static method
-                let (class_ref, method_ref) = resolved_method
-                    .get_or_init(|| {
-                        self.method_area
-                            .search(&class, &name, &method_type)
-                            .ok_or_else(|| {
-                                format!(
-                                    "Error during InvokeStatic; {class}.{name}: {method_type:?}"
-                                )
-                            })
-                            .unwrap()
-                    })
-                    .clone();
+                let (class_ref, method_ref) = if let Some(resolved) = resolved_method.get() {
+                    resolved.clone()
+                } else {
+                    let resolved = self
+                        .method_area
+                        .search(&class, &name, &method_type)
+                        .ok_or_else(|| self.no_such_method(&class, &name, &method_type))?;
+                    resolved_method.get_or_init(|| resolved).clone()
+                };
 
                 if self.maybe_initialize_class(&class_ref) {
                     return Ok(());
@@ -975,9 +1177,41 @@ impl Thread {
                     verbose,
                 )?;
             }
+            Instruction::LoadDynamic(bootstrap_index, name, field_type, cache) => {
+                // ldc of a dynamic constant
+                let value = if let Some(&value) = cache.get() {
+                    value
+                } else {
+                    let bootstrap_method =
+                        self.stackframe.class.bootstrap_methods[bootstrap_index as usize].clone();
+                    let Some(value) =
+                        self.resolve_dynamic_constant(&name, &bootstrap_method, &field_type)?
+                    else {
+                        // the class that holds the constant is being initialized first
+                        return Ok(());
+                    };
+                    *cache.get_or_init(|| value)
+                };
+                if field_type.get_size() == 1 {
+                    if field_type.is_reference() {
+                        self.rember(value as u32, verbose);
+                    }
+                    self.stackframe.operand_stack.push(value as u32);
+                } else {
+                    self.stackframe.operand_stack.pushd(value);
+                }
+            }
             Instruction::New(class, class_lock) => {
                 // make a new object instance
-                let class = class_lock.get_or_init(|| self.class_area.search(&class).unwrap());
+                let class = if let Some(class) = class_lock.get() {
+                    class
+                } else {
+                    let found = self
+                        .class_area
+                        .search(&class)
+                        .ok_or_else(|| error::Error::class_resolution(&class))?;
+                    class_lock.get_or_init(|| found)
+                };
                 if self.maybe_initialize_class(class) {
                     return Ok(());
                 }
@@ -993,7 +1227,7 @@ impl Thread {
                 // ifnull | ifnonnull
                 let ptr = self.stackframe.operand_stack.pop().unwrap();
                 if (ptr == NULL) ^ (is_rev) {
-                    self.pc_register = branch as usize;
+                    self.branch(branch as usize);
                 }
             }
             Instruction::NewArray1(field_type) => {
@@ -1017,13 +1251,24 @@ impl Thread {
                 let value = self.stackframe.operand_stack.pop().unwrap();
                 let index = self.stackframe.operand_stack.pop().unwrap();
                 let array_ref = self.stackframe.operand_stack.pop().unwrap();
+                if array_ref == NULL {
+                    return self.throw_null_pointer_exception("Cannot store to array", verbose);
+                }
 
-                let (old, is_reference) =
-                    Array1.inspect(&self.heap, array_ref as usize, |arr| {
-                        let old = arr.contents[index as usize];
-                        arr.contents[index as usize] = value;
-                        (old, arr.arr_type.is_reference())
-                    })?;
+                let stored = Array1.inspect(&self.heap, array_ref as usize, |arr| {
+                    let length = arr.contents.len();
+                    let Some(slot) = arr.contents.get_mut(index as usize) else {
+                        return Err(length);
+                    };
+                    let old = core::mem::replace(slot, narrow_int(arr.arr_type, value));
+                    Ok((old, arr.arr_type.is_reference()))
+                })?;
+                let (old, is_reference) = match stored {
+                    Ok(stored) => stored,
+                    Err(length) => {
+                        return self.throw_array_index_exception(index, length, verbose);
+                    }
+                };
                 if is_reference {
                     // if it's a reference type, increment the ref count
                     self.rember(value, verbose);
@@ -1035,19 +1280,39 @@ impl Thread {
                 let value = self.stackframe.operand_stack.popd::<u64>().unwrap();
                 let index = self.stackframe.operand_stack.pop().unwrap();
                 let array_ref = self.stackframe.operand_stack.pop().unwrap();
+                if array_ref == NULL {
+                    return self.throw_null_pointer_exception("Cannot store to array", verbose);
+                }
 
-                Array2.inspect(&self.heap, array_ref as usize, |arr| {
-                    arr.contents[index as usize] = value;
+                let stored = Array2.inspect(&self.heap, array_ref as usize, |arr| {
+                    let length = arr.contents.len();
+                    arr.contents
+                        .get_mut(index as usize)
+                        .map(|slot| *slot = value)
+                        .ok_or(length)
                 })?;
+                if let Err(length) = stored {
+                    return self.throw_array_index_exception(index, length, verbose);
+                }
             }
             Instruction::ArrayLoad1 => {
                 // load 1 value from an array
                 let index = self.stackframe.operand_stack.pop().unwrap();
                 let array_ref = self.stackframe.operand_stack.pop().unwrap();
+                if array_ref == NULL {
+                    return self.throw_null_pointer_exception("Cannot load from array", verbose);
+                }
 
-                let value = Array1.inspect(&self.heap, array_ref as usize, |arr| {
-                    arr.contents[index as usize]
+                let loaded = Array1.inspect(&self.heap, array_ref as usize, |arr| {
+                    let length = arr.contents.len();
+                    arr.contents.get(index as usize).copied().ok_or(length)
                 })?;
+                let value = match loaded {
+                    Ok(value) => value,
+                    Err(length) => {
+                        return self.throw_array_index_exception(index, length, verbose);
+                    }
+                };
                 self.stackframe.operand_stack.push(value);
                 if ArrayType::SELF.inspect(&self.heap, array_ref as usize, |f| f.is_reference())? {
                     self.rember_temp(value, verbose);
@@ -1057,16 +1322,28 @@ impl Thread {
                 // load 2 values from an array
                 let index = self.stackframe.operand_stack.pop().unwrap();
                 let array_ref = self.stackframe.operand_stack.pop().unwrap();
+                if array_ref == NULL {
+                    return self.throw_null_pointer_exception("Cannot load from array", verbose);
+                }
 
-                let value = Array2.inspect(&self.heap, array_ref as usize, |arr| {
-                    arr.contents[index as usize]
+                let loaded = Array2.inspect(&self.heap, array_ref as usize, |arr| {
+                    let length = arr.contents.len();
+                    arr.contents.get(index as usize).copied().ok_or(length)
                 })?;
+                let value = match loaded {
+                    Ok(value) => value,
+                    Err(length) => {
+                        return self.throw_array_index_exception(index, length, verbose);
+                    }
+                };
                 self.stackframe.operand_stack.pushd(value);
             }
             Instruction::NewMultiArray(dimensions, arr_type) => {
-                let dimension_sizes = (0..dimensions)
-                    .map(|_| self.stackframe.operand_stack.pop().unwrap())
-                    .collect::<Vec<_>>();
+                // the outermost dimension's size is deepest on the stack
+                let dimension_sizes = self
+                    .stackframe
+                    .operand_stack
+                    .split_off(self.stackframe.operand_stack.len() - dimensions as usize);
                 // make all the thingies
                 let allocation = allocate_multi_array(
                     &mut self.heap.lock().unwrap(),
@@ -1078,6 +1355,10 @@ impl Thread {
             }
             Instruction::ArrayLength => {
                 let arr_ref = self.stackframe.operand_stack.pop().unwrap() as usize;
+                if arr_ref == NULL as usize {
+                    return self
+                        .throw_null_pointer_exception("Cannot read the array length", verbose);
+                }
                 let length = Array1.inspect(&self.heap, arr_ref, |arr| arr.contents.len())? as u32;
                 self.stackframe.operand_stack.push(length);
             }
@@ -1092,24 +1373,71 @@ impl Thread {
                     if !obj_works {
                         let obj_type =
                             AnyObj.inspect(&self.heap, objref as usize, |o| o.this_class())?;
-                        return Err(format!(
-                            "CheckedCast failed; expected a(n) {ty} but got a(n) {obj_type}"
-                        )
-                        .into());
+                        return self.throw_new(
+                            "java/lang/ClassCastException",
+                            &format!(
+                                "class {} cannot be cast to class {}",
+                                obj_type.replace('/', "."),
+                                ty.replace('/', ".")
+                            ),
+                            verbose,
+                        );
                     }
                 }
             }
+            Instruction::Instanceof(ty) => {
+                // instanceof
+                // check if an object is an instance of a given type
+                let objref = self.stackframe.operand_stack.pop().unwrap();
+                // null isn't an instance of anything
+                let is_instance = objref != NULL
+                    && AnyObj.inspect(&self.heap, objref as usize, |o| {
+                        o.isinstance(&self.class_area, &ty, verbose)
+                    })?;
+                self.stackframe.operand_stack.push(u32::from(is_instance));
+            }
             Instruction::AThrow => {
                 let objref = self.stackframe.operand_stack.pop().unwrap();
                 self.throw(objref, verbose)?;
             }
-            other => return Err(format!("Invalid Opcode: {other:?}").into()),
+            Instruction::MonitorEnter => {
+                // there's only one thread, so entering always succeeds; just count the entries
+                let objref = self.stackframe.operand_stack.pop().unwrap();
+                if objref == NULL {
+                    return self
+                        .throw_null_pointer_exception("Cannot enter synchronized block", verbose);
+                }
+                self.heap.lock().unwrap().monitor_enter(objref);
+            }
+            Instruction::MonitorExit => {
+                let objref = self.stackframe.operand_stack.pop().unwrap();
+                if objref == NULL {
+                    return self
+                        .throw_null_pointer_exception("Cannot exit synchronized block", verbose);
+                }
+                if !self.heap.lock().unwrap().monitor_exit(objref) {
+                    return self.throw_new(
+                        "java/lang/IllegalMonitorStateException",
+                        "current thread is not owner",
+                        verbose,
+                    );
+                }
+            }
+            other => {
+                return Err(error::Error::class_format(format!(
+                    "Invalid Opcode: {other:?}"
+                )))
+            }
         }
         Ok(())
     }
 
+    /// keep `value` alive until the current method returns. `null` isn't counted
     /// # Panics
     pub fn rember_temp(&mut self, value: u32, verbose: bool) {
+        if value == NULL {
+            return;
+        }
         self.heap.lock().unwrap().inc_ref(value);
         self.stackframe.garbage.push(value);
         if verbose {
@@ -1117,22 +1445,74 @@ impl Thread {
         }
     }
 
+    /// add a reference to `value`. `null` isn't counted
     /// # Panics
     pub fn rember(&self, value: u32, verbose: bool) {
+        if value == NULL {
+            return;
+        }
         self.heap.lock().unwrap().inc_ref(value);
         if verbose {
             println!("Rember {value}");
         }
     }
 
+    /// drop a reference to `value`, collecting it if that was the last one. `null` isn't counted
     /// # Panics
     pub fn forgor(&self, value: u32, verbose: bool) {
+        if value == NULL {
+            return;
+        }
         self.heap.lock().unwrap().dec_ref(value);
         if verbose {
             println!("forgor {value}");
         }
     }
 
+    /// find the class that declares a static field referenced through `class`, along with the
+    /// field's index. Java looks on the class itself, then its superinterfaces, then its superclass
+    fn resolve_static(
+        &self,
+        class: &Arc<Class>,
+        name: &str,
+        index_lock: &OnceLock<(Arc<str>, usize)>,
+    ) -> error::Result<(Arc<Class>, usize)> {
+        if let Some((owner, index)) = index_lock.get() {
+            let owner = self
+                .class_area
+                .search(owner)
+                .ok_or_else(|| error::Error::class_resolution(owner))?;
+            return Ok((owner, *index));
+        }
+        let (owner, index) = self.find_static(class.clone(), name).ok_or_else(|| {
+            error::Error::LinkageError(format!(
+                "Couldn't find static `{name}` on class `{}`",
+                class.this
+            ))
+        })?;
+        index_lock.get_or_init(|| (owner.this.clone(), index));
+        Ok((owner, index))
+    }
+
+    fn find_static(&self, class: Arc<Class>, name: &str) -> Option<(Arc<Class>, usize)> {
+        if let Some(&(_, index)) = class.statics.iter().find(|(field, _)| &*field.name == name) {
+            return Some((class, index));
+        }
+        for interface in &class.interfaces {
+            if let Some(found) = self
+                .class_area
+                .search(interface)
+                .and_then(|interface| self.find_static(interface, name))
+            {
+                return Some(found);
+            }
+        }
+        if &*class.this == "java/lang/Object" {
+            return None;
+        }
+        self.find_static(self.class_area.search(&class.super_class)?, name)
+    }
+
     /// # Panics
     pub fn maybe_initialize_class(&mut self, class: &Class) -> bool {
         if class.initialized.is_completed() {
@@ -1181,7 +1561,7 @@ impl Thread {
                     descriptor,
                     verbose,
                 )
-            })?;
+            })??;
         self.invoke_method(resolved_method, resolved_class);
         Ok(())
     }
@@ -1195,21 +1575,89 @@ impl Thread {
         self.pc_register = 0;
     }
 
-    fn throw_obj(&mut self, exception: Object, verbose: bool) -> Result<(), String> {
+    fn throw_arithmetic_exception(&mut self, message: &str, verbose: bool) -> error::Result<()> {
+        self.throw_new("java/lang/ArithmeticException", message, verbose)
+    }
+
+    /// throw an `ArrayIndexOutOfBoundsException` for an `index` that isn't in an array of `length`
+    fn throw_array_index_exception(
+        &mut self,
+        index: u32,
+        length: usize,
+        verbose: bool,
+    ) -> error::Result<()> {
+        self.throw_new(
+            "java/lang/ArrayIndexOutOfBoundsException",
+            &format!("Index {} out of bounds for length {length}", index as i32),
+            verbose,
+        )
+    }
+
+    /// throw a `StackOverflowError` from the call that created the current frame. The frame never
+    /// ran, so it's dropped and the caller throws from its call instruction
+    fn throw_stack_overflow(&mut self, verbose: bool) -> error::Result<()> {
+        let class = self
+            .class_area
+            .search("java/lang/StackOverflowError")
+            .ok_or_else(|| error::Error::class_resolution(&"java/lang/StackOverflowError"))?;
+        if let Some(caller) = self.stack.pop() {
+            self.stackframe = caller;
+            self.pc_register = self.stackframe.operand_stack.pop().unwrap() as usize;
+        }
+        self.throw_obj(Object::from_class(&class), verbose)
+    }
+
+    /// throw a new `NullPointerException` with a message. Native methods that call this should
+    /// return `Ok(None)` afterwards, like with `throw_new`
+    /// # Errors
+    /// if nothing catches the exception
+    pub fn throw_null_pointer_exception(
+        &mut self,
+        message: &str,
+        verbose: bool,
+    ) -> error::Result<()> {
+        self.throw_new("java/lang/NullPointerException", message, verbose)
+    }
+
+    /// throw a new exception of the given class with a message. Native methods that call this
+    /// should return `Ok(None)` afterwards, since the thread has already moved to the handler
+    /// # Panics
+    /// # Errors
+    /// if the exception class doesn't exist or nothing catches the exception
+    pub fn throw_new(&mut self, class: &str, message: &str, verbose: bool) -> error::Result<()> {
+        let class = self
+            .class_area
+            .search(class)
+            .ok_or_else(|| error::Error::class_resolution(&class))?;
+        let mut exception = Object::from_class(&class);
+        if let Some(&(_, message_idx)) = class
+            .fields
+            .iter()
+            .find(|(field, _)| &*field.name == "message")
+        {
+            let message = self.heap.lock().unwrap().allocate_str(message.into());
+            self.rember(message, verbose);
+            exception.fields[message_idx] = message;
+        }
+        self.throw_obj(exception, verbose)?;
+        Ok(())
+    }
+
+    fn throw_obj(&mut self, exception: Object, verbose: bool) -> error::Result<()> {
         let idx = self.heap.lock().unwrap().allocate(exception);
         self.throw(idx, verbose)
     }
 
-    fn throw(&mut self, exception_ptr: u32, verbose: bool) -> Result<(), String> {
+    fn throw(&mut self, exception_ptr: u32, verbose: bool) -> error::Result<()> {
         loop {
-            for entry in &self
+            // native methods don't have an exception table
+            let exception_table = self
                 .stackframe
                 .method
                 .code
                 .as_bytecode()
-                .unwrap()
-                .exception_table
-            {
+                .map_or(&[][..], |code| &code.exception_table);
+            for entry in exception_table {
                 if !(entry.start_pc..=entry.end_pc).contains(&(self.pc_register as u16)) {
                     continue;
                 }
@@ -1236,9 +1684,10 @@ impl Thread {
                     self.stackframe.method.name
                 );
             }
+            self.exit_method_monitor();
             match self.stack.pop() {
                 Some(s) => self.stackframe = s,
-                None => return Err(String::from("Exception propagated past main")),
+                None => return Err(error::Error::UncaughtException(exception_ptr)),
             }
             self.pc_register = self.stackframe.operand_stack.pop().unwrap() as usize;
         }
@@ -1252,7 +1701,7 @@ impl Thread {
         method_descriptor: MethodDescriptor,
         _callsite_number: u16,
         verbose: bool,
-    ) -> Result<(), String> {
+    ) -> error::Result<()> {
         match (method_name, method_handle, method_descriptor) {
             (
                 "makeConcatWithConstants",
@@ -1277,9 +1726,14 @@ impl Thread {
                 if verbose {
                     println!("{:?}", self.heap);
                 }
-                let [Constant::String(str) | Constant::StringRef(str)] = &args[..] else {
-                    return Err(format!("Expected a single template string; got {args:?}"));
+                // the recipe comes first, then a constant for each `\u{2}` in it
+                let [Constant::String(str) | Constant::StringRef(str), constants @ ..] = &args[..]
+                else {
+                    return Err(error::Error::LinkageError(format!(
+                        "Expected a template string; got {args:?}"
+                    )));
                 };
+                let mut constants_iter = constants.iter();
                 let mut output = String::new();
                 let mut parameters_iter = parameters.iter();
                 let mut args_iter = (0..parameter_size)
@@ -1287,12 +1741,26 @@ impl Thread {
                     .collect::<Vec<_>>();
 
                 for c in str.chars() {
+                    if c == '\u{2}' {
+                        let result = match constants_iter.next() {
+                            Some(Constant::String(constant) | Constant::StringRef(constant)) => {
+                                write!(output, "{constant}")
+                            }
+                            Some(Constant::Int(i)) => write!(output, "{i}"),
+                            Some(Constant::Long(l)) => write!(output, "{l}"),
+                            Some(Constant::Float(f)) => write!(output, "{}", JavaFloat(*f)),
+                            Some(Constant::Double(d)) => write!(output, "{}", JavaFloat(*d)),
+                            other => return Err(error::Error::LinkageError(format!("Expected a constant for java/lang/invoke/StringConcatFactory.makeConcatWithConstants: {str:?} {other:?}"))),
+                        };
+                        result.map_err(|err| format!("{err:?}"))?;
+                        continue;
+                    }
                     if c != '\u{1}' {
                         output.push(c);
                         continue;
                     }
                     let Some(field_type) = parameters_iter.next() else {
-                        return Err(format!("Not enough parameters for java/lang/invoke/StringConcatFactory.makeConcatWithConstants: {str:?} {parameters:?}"));
+                        return Err(error::Error::LinkageError(format!("Not enough parameters for java/lang/invoke/StringConcatFactory.makeConcatWithConstants: {str:?} {parameters:?}")));
                     };
                     if field_type.get_size() == 2 {
                         let value = args_iter.popd::<u64>().unwrap();
@@ -1304,7 +1772,7 @@ impl Thread {
                                     .map_err(|err| format!("{err:?}"))?;
                             }
                             FieldType::Double => {
-                                write!(output, "{}", f64::from_bits(value))
+                                write!(output, "{}", JavaFloat(f64::from_bits(value)))
                                     .map_err(|err| format!("{err:?}"))?;
                             }
                             _ => unreachable!(),
@@ -1324,7 +1792,7 @@ impl Thread {
                                 output.push(char::from_u32(value).unwrap());
                             }
                             FieldType::Float => {
-                                write!(output, "{}", f32::from_bits(value))
+                                write!(output, "{}", JavaFloat(f32::from_bits(value)))
                                     .map_err(|err| format!("{err:?}"))?;
                             }
                             FieldType::Object(class) if &**class == "java/lang/String" => {
@@ -1335,7 +1803,7 @@ impl Thread {
                     write!(output, "{str}").map_err(|err| format!("{err:?}"))
                 }).unwrap()?;
                             }
-                            other => return Err(format!("Unsupported item for java/lang/invoke/StringConcatFactory.makeConcatWithConstants: {other:?}")),
+                            other => return Err(error::Error::LinkageError(format!("Unsupported item for java/lang/invoke/StringConcatFactory.makeConcatWithConstants: {other:?}"))),
                         }
                     }
                 }
@@ -1369,25 +1837,28 @@ impl Thread {
                     println!("LambdaMetafactory:\nMethod name: {method_name}\nBootstrap Arguments: {args:#?}\nMethod Descriptor: {return_type:?} {parameters:?}");
                 }
                 let Some(FieldType::Object(lambda_class)) = return_type else {
-                    return Err(format!(
+                    return Err(error::Error::LinkageError(format!(
                         "LambdaMetaFactory expects an object return; got {return_type:?}"
-                    ));
+                    )));
                 };
                 let [Constant::MethodType(interface_descriptor), Constant::MethodHandle(method_handle), Constant::MethodType(_enforced_type)] =
                     &args[..]
                 else {
-                    return Err("Wrong parameters for LambdaMetaFactory".to_string());
+                    return Err(error::Error::LinkageError(
+                        "Wrong parameters for LambdaMetaFactory".to_string(),
+                    ));
                 };
+                // `parameter_size` counts longs and doubles as two slots, so taking the slots as
+                // they lie keeps each one's halves together and in order, ready to become locals
+                let captures_start = self.stackframe.operand_stack.len() - parameter_size;
+                let captures = self.stackframe.operand_stack.split_off(captures_start);
                 let lambda_object = Object {
                     fields: Vec::new(),
                     native_fields: vec![Box::new(LambdaOverride {
                         method_name: Arc::from(method_name),
                         method_descriptor: interface_descriptor.clone(),
                         invoke: method_handle.clone(),
-                        captures: (0..parameter_size)
-                            .map(|_| self.stackframe.operand_stack.pop().unwrap())
-                            .rev()
-                            .collect(),
+                        captures,
                     })],
                     class: lambda_class,
                 };
@@ -1395,15 +1866,280 @@ impl Thread {
                 self.rember_temp(lambda_index, verbose);
                 self.stackframe.operand_stack.push(lambda_index);
             }
+            (
+                method_name,
+                BootstrapMethod {
+                    method:
+                        MethodHandle::InvokeStatic {
+                            class: bootstrap_class,
+                            name: bootstrap_name,
+                            method_type: _,
+                        },
+                    args,
+                },
+                MethodDescriptor { parameter_size, .. },
+            ) if &*bootstrap_name == "bootstrap"
+                && &*bootstrap_class == "java/lang/runtime/ObjectMethods" =>
+            {
+                let [Constant::ClassRef(record_class), _names, getters @ ..] = &args[..] else {
+                    return Err(error::Error::LinkageError(format!(
+                        "Wrong parameters for ObjectMethods: {args:?}"
+                    )));
+                };
+                let components = getters
+                    .iter()
+                    .map(|getter| match getter {
+                        Constant::MethodHandle(MethodHandle::GetField {
+                            name, field_type, ..
+                        }) => Ok((name.clone(), field_type.clone())),
+                        other => Err(error::Error::LinkageError(format!(
+                            "Expected a record component getter; got {other:?}"
+                        ))),
+                    })
+                    .collect::<error::Result<Vec<_>>>()?;
+                let kind = match method_name {
+                    "toString" => RecordMethodKind::ToString,
+                    "hashCode" => RecordMethodKind::HashCode,
+                    "equals" => RecordMethodKind::Equals,
+                    other => {
+                        return Err(error::Error::LinkageError(format!(
+                            "Unsupported ObjectMethods method: {other}"
+                        )))
+                    }
+                };
+                let record_method = RecordMethod {
+                    kind,
+                    class: record_class.clone(),
+                    components,
+                }
+                .as_method();
+                let class = self
+                    .class_area
+                    .search(record_class)
+                    .ok_or_else(|| error::Error::class_resolution(record_class))?;
+                // call the generated method right away with the arguments on the stack
+                let args_start = self.stackframe.operand_stack.len() - parameter_size;
+                let call_args = self.stackframe.operand_stack.split_off(args_start);
+                self.stackframe.operand_stack.push(self.pc_register as u32);
+                self.invoke_method(Arc::new(record_method), class);
+                self.stackframe
+                    .locals
+                    .iter_mut()
+                    .zip(call_args)
+                    .for_each(|(l, a)| *l = a);
+            }
             (n, h, d) => {
-                return Err(format!(
+                return Err(error::Error::LinkageError(format!(
                     "Error during InvokeDynamic: can't resolve method: {n}: {d:?}; {h:?}"
-                ))
+                )))
             }
         }
         Ok(())
     }
 
+    /// the error for a method that couldn't be resolved; either its class is missing entirely or
+    /// the class doesn't have it
+    fn no_such_method(
+        &self,
+        class: &str,
+        name: &str,
+        descriptor: &MethodDescriptor,
+    ) -> error::Error {
+        if self.class_area.search(class).is_none() {
+            return error::Error::class_resolution(&class);
+        }
+        error::Error::LinkageError(format!(
+            "java.lang.NoSuchMethodError: {class}.{name}: {descriptor:?}"
+        ))
+    }
+
+    /// Run the bootstrap method of a dynamic constant. Returns `None` if a class needs to be
+    /// initialized first, in which case the instruction will run again.
+    fn resolve_dynamic_constant(
+        &mut self,
+        name: &str,
+        bootstrap_method: &BootstrapMethod,
+        field_type: &FieldType,
+    ) -> error::Result<Option<u64>> {
+        let MethodHandle::InvokeStatic {
+            class: bootstrap_class,
+            name: bootstrap_name,
+            method_type: _,
+        } = &bootstrap_method.method
+        else {
+            return Err(error::Error::LinkageError(format!(
+                "Unsupported dynamic constant bootstrap: {bootstrap_method:?}"
+            )));
+        };
+        if &**bootstrap_class != "java/lang/invoke/ConstantBootstraps" {
+            return Err(error::Error::LinkageError(format!(
+                "Unsupported dynamic constant bootstrap: {bootstrap_class}.{bootstrap_name}"
+            )));
+        }
+        match &**bootstrap_name {
+            "nullConstant" => Ok(Some(NULL as u64)),
+            "primitiveClass" => {
+                let class_name = match name {
+                    "B" => "byte",
+                    "C" => "char",
+                    "D" => "double",
+                    "F" => "float",
+                    "I" => "int",
+                    "J" => "long",
+                    "S" => "short",
+                    "Z" => "boolean",
+                    "V" => "void",
+                    other => {
+                        return Err(error::Error::LinkageError(format!(
+                            "Invalid primitive descriptor: {other}"
+                        )))
+                    }
+                };
+                let class_ptr = get_class(&self.heap, &self.class_area, class_name.into())
+                    .ok_or_else(|| error::Error::class_resolution(&"java/lang/Class"))?;
+                Ok(Some(class_ptr as u64))
+            }
+            "enumConstant" | "getStaticFinal" => {
+                // the declaring class is either given or the same as the constant's type
+                let declaring_class = match (&bootstrap_method.args[..], field_type) {
+                    ([Constant::ClassRef(class)], _) | ([], FieldType::Object(class)) => class,
+                    (args, _) => {
+                        return Err(error::Error::LinkageError(format!(
+                            "Invalid arguments for {bootstrap_name}: {args:?}"
+                        )))
+                    }
+                };
+                let Some(class) = self.class_area.search(declaring_class) else {
+                    return Err(error::Error::class_resolution(declaring_class));
+                };
+                if self.maybe_initialize_class(&class) {
+                    return Ok(None);
+                }
+                let &(_, staticindex) = class
+                    .statics
+                    .iter()
+                    .find(|(field, _)| &*field.name == name)
+                    .ok_or_else(|| {
+                        error::Error::LinkageError(format!(
+                            "Couldn't find static `{name}` on class `{}`",
+                            class.this
+                        ))
+                    })?;
+                let static_fields = class.static_data.lock().unwrap();
+                let value = if field_type.get_size() == 1 {
+                    static_fields[staticindex] as u64
+                } else {
+                    (static_fields[staticindex] as u64) << 32
+                        | static_fields[staticindex + 1] as u64
+                };
+                drop(static_fields);
+                Ok(Some(value))
+            }
+            other => Err(error::Error::LinkageError(format!(
+                "Unsupported dynamic constant bootstrap: {bootstrap_class}.{other}"
+            ))),
+        }
+    }
+
+    /// run one tick of a native method, failing if its pc isn't one of the method's stages. A
+    /// staged native that set the wrong pc or pushed the wrong return address would otherwise fall
+    /// through to a stage it didn't mean, or spin forever
+    fn run_native_checked(
+        &mut self,
+        native_method: &dyn NativeMethod,
+        verbose: bool,
+    ) -> error::Result<()> {
+        let stages = native_method.stages();
+        if self.pc_register >= stages {
+            return Err(error::Error::Internal(format!(
+                "Native method {}.{} reached stage {}, but only has stages 0 to {}",
+                self.stackframe.class.this,
+                self.stackframe.method.name,
+                self.pc_register,
+                stages - 1
+            )));
+        }
+        native_method.run(self, verbose)
+    }
+
+    /// lock the receiver of a synchronized method, or its class if it's static. The receiver is only
+    /// known once the caller has filled in the locals, so this happens on the method's first tick
+    fn enter_method_monitor(&mut self) {
+        let monitor = if self.stackframe.method.access_flags.is_static() {
+            get_class(
+                &self.heap,
+                &self.class_area,
+                self.stackframe.class.this.clone(),
+            )
+            .unwrap_or(NULL)
+        } else {
+            self.stackframe.locals[0]
+        };
+        self.heap.lock().unwrap().monitor_enter(monitor);
+        self.stackframe.monitor = Some(monitor);
+    }
+
+    /// release the monitor held by the current stack frame, if it belongs to a synchronized method
+    fn exit_method_monitor(&mut self) {
+        if let Some(monitor) = self.stackframe.monitor.take() {
+            self.heap.lock().unwrap().monitor_exit(monitor);
+        }
+    }
+
+    /// throw a `VerifyError` into the caller if the operand stack has outgrown `max_stack`. Returns
+    /// whether it threw
+    fn verify_operand_stack(&mut self, verbose: bool) -> error::Result<bool> {
+        let Some(code) = self.stackframe.method.code.as_bytecode() else {
+            return Ok(false);
+        };
+        let depth = self.stackframe.operand_stack.len();
+        if depth <= code.max_stack as usize {
+            return Ok(false);
+        }
+        let message = format!(
+            "operand stack of {}.{} grew to {depth}, past its max_stack of {}",
+            self.stackframe.class.this, self.stackframe.method.name, code.max_stack
+        );
+        // the method can't be trusted to run any further, so its own handlers don't get a say
+        self.exit_method_monitor();
+        self.collect_garbage();
+        if let Some(caller) = self.stack.pop() {
+            self.stackframe = caller;
+            self.pc_register = self.stackframe.operand_stack.pop().unwrap() as usize;
+        } else {
+            self.stackframe.operand_stack.clear();
+        }
+        self.throw_new("java/lang/VerifyError", &message, verbose)?;
+        Ok(true)
+    }
+
+    /// jump to `target`. A backward jump closes a loop iteration, so release the temporaries it left behind
+    fn branch(&mut self, target: usize) {
+        if target < self.pc_register {
+            self.collect_loop_garbage();
+        }
+        self.pc_register = target;
+    }
+
+    /// collect temporary strings that are no longer held by a local or the operand stack. Without this,
+    /// every intermediate string from `s = s + x` in a loop would stay alive until the method returns
+    fn collect_loop_garbage(&mut self) {
+        let frame = &mut self.stackframe;
+        if frame.garbage.is_empty() {
+            return;
+        }
+        let mut heap_borrow = self.heap.lock().unwrap();
+        frame.garbage.retain(|ptr| {
+            let live = !heap_borrow.is_cached_string(*ptr)
+                || frame.locals.contains(ptr)
+                || frame.operand_stack.contains(ptr);
+            if !live {
+                heap_borrow.dec_ref(*ptr);
+            }
+            live
+        });
+    }
+
     fn collect_garbage(&mut self) {
         let mut heap_borrow = self.heap.lock().unwrap();
         for ptr in core::mem::take(&mut self.stackframe.garbage) {
@@ -1415,6 +2151,7 @@ impl Thread {
     /// # Panics
     /// # Errors
     pub fn return_void(&mut self) -> error::Result<()> {
+        self.exit_method_monitor();
         self.collect_garbage();
         let Some(next_method) = self.stack.pop() else {
             return Err(error::Error::ThreadKill);
@@ -1457,6 +2194,7 @@ impl Thread {
         self.stackframe.operand_stack.push(ret_value);
         // now self.stackframe is the method that was called and outer_stackframe is the calling method
         core::mem::swap(&mut outer_stackframe, &mut self.stackframe);
+        self.exit_method_monitor();
         self.collect_garbage();
         // now self.stackframe is the calling method and outer_stackframe is the method that was called
         core::mem::swap(&mut outer_stackframe, &mut self.stackframe);
@@ -1472,6 +2210,7 @@ impl Thread {
         if verbose {
             println!("{ret_value}");
         }
+        self.exit_method_monitor();
         self.collect_garbage();
         self.stackframe = outer_stackframe;
         let ret_address = self.stackframe.operand_stack.pop().unwrap();
@@ -1480,31 +2219,55 @@ impl Thread {
     }
 }
 
-fn allocate_multi_array(
-    heap: &mut Heap,
-    depth: &[u32],
-    arr_type: FieldType,
-) -> Result<u32, String> {
+/// allocate a multi-dimensional array of type `arr_type`. `depth` holds the size of each dimension,
+/// outermost first; the innermost arrays are filled with the default value of their element type
+fn allocate_multi_array(heap: &mut Heap, depth: &[u32], arr_type: FieldType) -> error::Result<u32> {
+    let FieldType::Array(inner_type) = arr_type else {
+        return Err(error::Error::class_format(format!(
+            "Expected an array type; got {arr_type:?}"
+        )));
+    };
     match depth {
-        [size] => Ok(heap.allocate(if arr_type.get_size() == 2 {
-            Array2::new(*size as usize, arr_type)
+        [size] => Ok(heap.allocate(if inner_type.get_size() == 2 {
+            Array2::new(*size as usize, *inner_type)
         } else {
-            Array1::new(*size as usize, arr_type)
+            Array1::new(*size as usize, *inner_type)
         })),
         [size, rest @ ..] => {
-            let FieldType::Array(inner_type) = arr_type else {
-                return Err(format!("Expected an array type; got {arr_type:?}"));
-            };
             let current_array = (0..*size)
                 .map(|_| {
                     let idx = allocate_multi_array(heap, rest, *inner_type.clone())?;
                     heap.inc_ref(idx);
                     Ok(idx)
                 })
-                .collect::<Result<Vec<_>, String>>()?;
+                .collect::<error::Result<Vec<_>>>()?;
             Ok(heap.allocate(Array1::from_vec(current_array, *inner_type)))
         }
-        [] => Err(String::from("Can't create 0-dimensional array")),
+        [] => Err(error::Error::class_format(
+            "Can't create 0-dimensional array",
+        )),
+    }
+}
+
+/// narrow an int to the width of a `byte`, `short`, `char`, or `boolean` field or array element, the
+/// way storing it would on a real JVM. Every other type is stored as-is
+const fn narrow_int(field_type: &FieldType, value: u32) -> u32 {
+    match field_type {
+        FieldType::Byte => value as i8 as i32 as u32,
+        FieldType::Short => value as i16 as i32 as u32,
+        FieldType::Char => value as u16 as u32,
+        FieldType::Boolean => value & 1,
+        _ => value,
+    }
+}
+
+/// the result of a `fcmp<op>` or `dcmp<op>`. If either operand is NaN, the `l` form pushes -1 and
+/// the `g` form pushes 1
+const fn fcmp(ordering: Option<Ordering>, is_rev: bool) -> i32 {
+    match ordering {
+        Some(ordering) => ordering as i32,
+        None if is_rev => -1,
+        None => 1,
     }
 }
 
@@ -0,0 +1,148 @@
+use std::sync::Arc;
+
+use jvmrs_lib::FieldType;
+
+use crate::{
+    class::{Class, Method},
+    virtual_machine::StackFrame,
+};
+
+use super::Thread;
+
+/// A single slot of a stack frame, typed as well as the class file allows
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum JValue {
+    /// an `int`, or a `byte`, `short`, `char`, or `boolean` widened to one
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    /// a pointer into the heap; 0 is `null`
+    Reference(u32),
+    /// the second slot of a long or double
+    Top,
+    /// a slot whose type isn't known
+    Raw(u32),
+}
+
+/// A snapshot of one frame of a thread's call stack
+#[derive(Debug)]
+pub struct FrameInfo {
+    pub class: Arc<Class>,
+    pub method: Arc<Method>,
+    /// the index of the next instruction the frame will run
+    pub pc: usize,
+    /// the local variables, one per slot
+    pub locals: Vec<JValue>,
+    /// the operand stack, bottom first. The JVM doesn't record these types, so every slot is raw
+    pub operand_stack: Vec<JValue>,
+}
+
+impl JValue {
+    /// the value of type `ty` held in `slots`. Longs and doubles take two slots, upper half first
+    #[must_use]
+    pub fn from_slots(ty: &FieldType, slots: &[u32]) -> Self {
+        match (ty, slots) {
+            (FieldType::Long | FieldType::Double, &[upper, lower]) => {
+                let bits = ((upper as u64) << 32) | lower as u64;
+                if ty == &FieldType::Long {
+                    Self::Long(bits as i64)
+                } else {
+                    Self::Double(f64::from_bits(bits))
+                }
+            }
+            (FieldType::Float, &[bits]) => Self::Float(f32::from_bits(bits)),
+            (FieldType::Object(_) | FieldType::Array(_), &[ptr]) => Self::Reference(ptr),
+            (FieldType::Long | FieldType::Double, _) | (_, [] | [_, _, ..]) => {
+                Self::Raw(slots.first().copied().unwrap_or_default())
+            }
+            (_, &[value]) => Self::Int(value as i32),
+        }
+    }
+}
+
+impl Thread {
+    /// take a snapshot of every frame on the call stack, outermost first and ending with the frame
+    /// that's currently running
+    #[must_use]
+    pub fn frames(&self) -> Vec<FrameInfo> {
+        let mut frames = Vec::with_capacity(self.stack.len() + 1);
+        // each caller is waiting on the return address it pushed before making its call
+        for frame in &self.stack {
+            let (&pc, operands) = frame
+                .operand_stack
+                .split_last()
+                .unwrap_or((&0, &frame.operand_stack[..]));
+            frames.push(FrameInfo::new(frame, pc as usize, operands));
+        }
+        frames.push(FrameInfo::new(
+            &self.stackframe,
+            self.pc_register,
+            &self.stackframe.operand_stack,
+        ));
+        frames
+    }
+}
+
+impl FrameInfo {
+    fn new(frame: &StackFrame, pc: usize, operands: &[u32]) -> Self {
+        Self {
+            class: frame.class.clone(),
+            method: frame.method.clone(),
+            pc,
+            locals: type_locals(frame),
+            operand_stack: operands.iter().copied().map(JValue::Raw).collect(),
+        }
+    }
+}
+
+/// give each local a type from the method's parameters, or from the local variable table when every
+/// variable stored in that slot has the same type
+fn type_locals(frame: &StackFrame) -> Vec<JValue> {
+    let mut types: Vec<Option<FieldType>> = vec![None; frame.locals.len()];
+    let mut index = 0;
+    if !frame.method.access_flags.is_static() {
+        if let Some(slot) = types.first_mut() {
+            *slot = Some(FieldType::Object(frame.class.this.clone()));
+        }
+        index += 1;
+    }
+    for param in &frame.method.descriptor.parameters {
+        if let Some(slot) = types.get_mut(index) {
+            *slot = Some(param.clone());
+        }
+        index += param.get_size();
+    }
+    if let Some(code) = frame.method.code.as_bytecode() {
+        for slot in index..types.len() {
+            let mut entries = code
+                .local_var_table
+                .iter()
+                .filter(|entry| entry.index as usize == slot);
+            let Some(first) = entries.next() else {
+                continue;
+            };
+            if entries.all(|entry| entry.ty == first.ty) {
+                types[slot] = Some(first.ty.clone());
+            }
+        }
+    }
+
+    let mut locals = Vec::with_capacity(frame.locals.len());
+    let mut slots = frame.locals.iter().copied().zip(types);
+    while let Some((value, ty)) = slots.next() {
+        match ty {
+            Some(wide @ (FieldType::Long | FieldType::Double)) => {
+                let Some((lower, _)) = slots.next() else {
+                    locals.push(JValue::Raw(value));
+                    continue;
+                };
+                locals.push(JValue::from_slots(&wide, &[value, lower]));
+                locals.push(JValue::Top);
+            }
+            Some(ty) => locals.push(JValue::from_slots(&ty, &[value])),
+            None => locals.push(JValue::Raw(value)),
+        }
+    }
+    locals
+}
@@ -0,0 +1,37 @@
+mod common;
+
+const ARRAY_NAMES: &[u8] = include_bytes!("java/ArrayNames.class");
+
+#[test]
+fn arrays_print_their_type_and_hash() {
+    let vm = common::vm(&[ARRAY_NAMES]);
+    let results = common::eval(
+        &vm,
+        &[
+            "ArrayNames.ints()",
+            "ArrayNames.strings()",
+            "ArrayNames.nested()",
+        ],
+    );
+    assert_eq!(results.len(), 3);
+    for (result, prefix) in results
+        .iter()
+        .zip(["\"[I@", "\"[Ljava.lang.String;@", "\"[[D@"])
+    {
+        let hash = result
+            .strip_prefix(prefix)
+            .and_then(|rest| rest.strip_suffix('"'))
+            .unwrap_or_else(|| panic!("{result} should start with {prefix}"));
+        assert!(u32::from_str_radix(hash, 16).is_ok(), "{result}");
+    }
+}
+
+#[test]
+fn multi_arrays_of_references_start_out_null() {
+    let vm = common::vm(&[ARRAY_NAMES]);
+    // one of the six cells is set, and the rows are separate arrays of strings
+    assert_eq!(
+        common::eval(&vm, &["ArrayNames.stringGrid()"]),
+        ["\"2 3 5 true\""]
+    );
+}
@@ -0,0 +1,12 @@
+mod common;
+
+const BOXES: &[u8] = include_bytes!("java/Boxes.class");
+
+#[test]
+fn boxed_primitives_are_comparable() {
+    let vm = common::vm(&[BOXES]);
+    assert_eq!(
+        common::eval(&vm, &["Boxes.bridged()", "Boxes.typed()"]),
+        ["\"-1 1 -1 -2 1 4\"", "1"]
+    );
+}
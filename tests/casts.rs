@@ -0,0 +1,46 @@
+mod common;
+
+const CASTS: &[u8] = include_bytes!("java/Casts.class");
+const UNRELATED: &[u8] = include_bytes!("java/Casts$Unrelated.class");
+const ANIMAL: &[u8] = include_bytes!("java/Casts$Animal.class");
+const DOG: &[u8] = include_bytes!("java/Casts$Dog.class");
+
+#[test]
+fn casts_to_interfaces_check_what_the_class_implements() {
+    let vm = common::vm(&[CASTS, UNRELATED]);
+    assert_eq!(
+        common::eval(
+            &vm,
+            &[
+                "Casts.toList()",
+                "Casts.toIterable()",
+                "Casts.toMap()",
+                "Casts.toUnrelated()",
+            ]
+        ),
+        ["0", "true", "\"thrown\"", "\"thrown\""]
+    );
+}
+
+#[test]
+fn null_is_no_instance_and_failed_casts_name_both_classes() {
+    let vm = common::vm(&[CASTS, UNRELATED]);
+    let results = common::eval(&vm, &["Casts.nullInstanceOf()", "Casts.castMessage()"]);
+    assert_eq!(results[0], "false");
+    // a real JVM goes on to say which modules the classes are in
+    assert!(
+        results[1]
+            .starts_with("\"class java.lang.Integer cannot be cast to class java.lang.String"),
+        "{}",
+        results[1]
+    );
+}
+
+#[test]
+fn instanceof_follows_superclasses_interfaces_and_array_elements() {
+    let vm = common::vm(&[CASTS, UNRELATED, ANIMAL, DOG]);
+    assert_eq!(
+        common::eval(&vm, &["Casts.instanceOfs()"]),
+        ["\"true false true false true true false true true false true\""]
+    );
+}
@@ -0,0 +1,30 @@
+mod common;
+
+const CATCHES: &[u8] = include_bytes!("java/Catches.class");
+
+#[test]
+fn errors_reach_throwable_handlers() {
+    let vm = common::vm(&[CATCHES]);
+    assert_eq!(
+        common::eval(&vm, &["Catches.throwable()", "Catches.error()"]),
+        [
+            "\"java.lang.StackOverflowError true\"",
+            "\"java.lang.StackOverflowError\""
+        ]
+    );
+}
+
+#[test]
+fn null_accesses_throw_catchable_exceptions() {
+    let vm = common::vm(&[CATCHES]);
+    assert_eq!(common::eval(&vm, &["Catches.nullAccesses()"]), ["6"]);
+}
+
+#[test]
+fn storing_past_the_end_throws_a_catchable_exception() {
+    let vm = common::vm(&[CATCHES]);
+    assert_eq!(
+        common::eval(&vm, &["Catches.pastTheEnd()"]),
+        ["\"Index 3 out of bounds for length 3\""]
+    );
+}
@@ -0,0 +1,19 @@
+mod common;
+
+const CHARS: &[u8] = include_bytes!("java/Chars.class");
+
+#[test]
+fn digits_and_letters_follow_general_categories() {
+    let vm = common::vm(&[CHARS]);
+    assert_eq!(
+        common::eval(
+            &vm,
+            &["Chars.digits()", "Chars.letters()", "Chars.values()"]
+        ),
+        [
+            "\"true true false false\"",
+            "\"true true false false true false\"",
+            "\"10 -1 3 10 35 -1\""
+        ]
+    );
+}
@@ -0,0 +1,55 @@
+mod common;
+
+use std::{fs::File, io::BufReader, path::PathBuf};
+
+use javarust::{class_loader, JavaVm, VmOptions};
+use jvmrs_lib::method;
+
+const LAMBDAS: &[u8] = include_bytes!("java/Lambdas.class");
+
+#[test]
+fn loads_interfaces_named_only_in_descriptors() {
+    let classpath = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/java/classpath");
+    let vm = JavaVm::with_classpath(
+        vec![LAMBDAS.to_vec()],
+        vec![classpath],
+        VmOptions::default(),
+        false,
+    )
+    .unwrap();
+    assert!(vm.class_area.search("IntSource").is_some());
+    let lambda = vm
+        .invoke_static(
+            "Lambdas",
+            "make",
+            &method!(() -> Object("java/lang/Object".into())),
+            &[],
+            false,
+        )
+        .unwrap();
+    assert_ne!(lambda, [0]);
+}
+
+#[test]
+fn follows_manifest_class_paths_through_every_jar() {
+    // `app.jar` lists `lib/first%20dep.jar`, which lists `second.jar` next to it
+    let app = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/java/manifest/app.jar");
+    let jar = class_loader::read_jar(BufReader::new(File::open(&app).unwrap())).unwrap();
+    let classpath = class_loader::manifest_class_path(&app, &jar.class_path);
+    let vm = JavaVm::with_classpath(jar.classes, classpath, VmOptions::default(), false).unwrap();
+    let total = vm
+        .invoke_static("App", "total", &method!(() -> int), &[], false)
+        .unwrap();
+    assert_eq!(total, [3]);
+}
+
+#[test]
+fn multi_release_jars_load_only_the_base_classes() {
+    // `release.jar` also has a `Greeting` under `META-INF/versions/11/` that says "versioned"
+    let release =
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/java/multi_release/release.jar");
+    let jar = class_loader::read_jar(BufReader::new(File::open(release).unwrap())).unwrap();
+    assert_eq!(jar.classes.len(), 1);
+    let vm = JavaVm::with_classpath(jar.classes, Vec::new(), VmOptions::default(), false).unwrap();
+    assert_eq!(common::eval(&vm, &["Greeting.greet()"]), ["\"base\""]);
+}
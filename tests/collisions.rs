@@ -0,0 +1,38 @@
+mod common;
+
+const COLLISIONS: &[u8] = include_bytes!("java/Collisions.class");
+const KEY: &[u8] = include_bytes!("java/Collisions$Key.class");
+
+#[test]
+fn colliding_keys_are_told_apart_with_equals() {
+    let vm = common::vm(&[COLLISIONS, KEY]);
+    assert_eq!(
+        common::eval(
+            &vm,
+            &[
+                "Collisions.mapSize()",
+                "Collisions.replaced()",
+                "Collisions.lookup(3)",
+                "Collisions.lookup(4)",
+                "Collisions.hasStrings()",
+            ]
+        ),
+        ["5", "\"two\"", "\"three\"", "null", "true"]
+    );
+}
+
+#[test]
+fn colliding_elements_are_told_apart_with_equals() {
+    let vm = common::vm(&[COLLISIONS, KEY]);
+    assert_eq!(
+        common::eval(
+            &vm,
+            &[
+                "Collisions.setSize()",
+                "Collisions.setContains(1)",
+                "Collisions.setContains(2)",
+            ]
+        ),
+        ["33", "true", "false"]
+    );
+}
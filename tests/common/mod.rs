@@ -0,0 +1,33 @@
+#![allow(dead_code)]
+
+use javarust::{virtual_machine::repl, JavaVm, VmOptions};
+
+/// a VM holding the native library and the given class files
+pub fn vm(class_files: &[&[u8]]) -> JavaVm {
+    vm_with_options(class_files, VmOptions::default())
+}
+
+pub fn vm_with_options(class_files: &[&[u8]], options: VmOptions) -> JavaVm {
+    JavaVm::new(
+        class_files.iter().map(|bytes| bytes.to_vec()).collect(),
+        options,
+        false,
+    )
+    .unwrap()
+}
+
+/// run each `Class.method(args)` call through the repl and return what it printed for each one,
+/// which is empty for `void` methods
+pub fn eval(vm: &JavaVm, calls: &[&str]) -> Vec<String> {
+    let mut output = Vec::new();
+    repl(vm, calls.join("\n").as_bytes(), &mut output, false).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    // every call's output comes after a prompt, and the last prompt is answered by the end of input
+    let mut results = output
+        .split("> ")
+        .skip(1)
+        .map(|result| result.trim_end_matches('\n').to_string())
+        .collect::<Vec<_>>();
+    results.pop();
+    results
+}
@@ -0,0 +1,15 @@
+mod common;
+
+const COMPARE: &[u8] = include_bytes!("java/Compare.class");
+
+#[test]
+fn nan_compares_false_both_ways() {
+    let vm = common::vm(&[COMPARE]);
+    assert_eq!(
+        common::eval(&vm, &["Compare.floats()", "Compare.doubles()"]),
+        [
+            "\"false false false false false true true true\"",
+            "\"false false false false false true false true\""
+        ]
+    );
+}
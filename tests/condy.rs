@@ -0,0 +1,21 @@
+mod common;
+
+// written by `java/condy/GenerateCondy.java`, since javac never emits `CONSTANT_Dynamic`
+const CONDY: &[u8] = include_bytes!("java/condy/Condy.class");
+
+#[test]
+fn loads_dynamic_constants() {
+    let vm = common::vm(&[CONDY]);
+    assert_eq!(
+        common::eval(
+            &vm,
+            &[
+                "Condy.maxInt()",
+                "Condy.minLong()",
+                "Condy.isNull()",
+                "Condy.sum()"
+            ]
+        ),
+        ["2147483647", "-9223372036854775808", "true", "-2"]
+    );
+}
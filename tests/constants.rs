@@ -0,0 +1,51 @@
+mod common;
+
+use javarust::{class_loader, data::WorkingMethodArea};
+use jvmrs_lib::Constant;
+
+const CONSTANTS: &[u8] = include_bytes!("java/Constants.class");
+const LOUD: &[u8] = include_bytes!("java/Constants$Loud.class");
+
+#[test]
+fn constants_are_there_before_initialization() {
+    let vm = common::vm(&[CONSTANTS, LOUD]);
+    // a `getstatic` of `VALUE` reads this slot without running `<clinit>`
+    let loud = vm.class_area.search("Constants$Loud").unwrap();
+    let (value, slot) = loud
+        .statics
+        .iter()
+        .find(|(field, _)| &*field.name == "VALUE")
+        .unwrap();
+    assert!(value.constant_value.is_some());
+    assert_eq!(loud.static_data.lock().unwrap()[*slot], 42);
+    // reading `counter` is what runs `<clinit>`
+    assert_eq!(common::eval(&vm, &["Constants.read()"]), ["\"42 0 7 1\""]);
+}
+
+const POOL_WIDTHS: &[u8] = include_bytes!("java/PoolWidths.class");
+
+#[test]
+fn long_and_double_constants_take_two_slots() {
+    let class = class_loader::load_class(
+        &mut WorkingMethodArea::default(),
+        &mut POOL_WIDTHS.iter().copied(),
+        false,
+    )
+    .unwrap();
+    // #7 is the long, #9 the double, and #11 the int right after them
+    assert!(matches!(
+        class.constants[6],
+        Constant::Long(1_234_567_890_123)
+    ));
+    assert!(matches!(class.constants[7], Constant::Placeholder));
+    assert!(
+        matches!(class.constants[8], Constant::Double(d) if d.to_bits() == 6.022_140_76e23_f64.to_bits())
+    );
+    assert!(matches!(class.constants[9], Constant::Placeholder));
+    assert!(matches!(class.constants[10], Constant::Int(123_456_789)));
+    let vm = common::vm(&[POOL_WIDTHS]);
+    assert_eq!(
+        common::eval(&vm, &["PoolWidths.all()"]),
+        ["\"1234567890123 6.02214076E23 123456789 after\""]
+    );
+}
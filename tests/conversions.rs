@@ -0,0 +1,57 @@
+mod common;
+
+const CONVERSIONS: &[u8] = include_bytes!("java/Conversions.class");
+const BITS: &[u8] = include_bytes!("java/Bits.class");
+
+#[test]
+fn int_to_char_keeps_sixteen_bits() {
+    let vm = common::vm(&[CONVERSIONS]);
+    assert_eq!(
+        common::eval(
+            &vm,
+            &[
+                "Conversions.toChar(20013)",
+                "Conversions.lowBits(20013)",
+                "Conversions.lowBits(128512)",
+                "Conversions.lowBits(65601)",
+                "Conversions.lowBits(-1)",
+            ]
+        ),
+        ["'中'", "20013", "62976", "65", "65535"]
+    );
+}
+
+#[test]
+fn to_bits_canonicalizes_nan_but_raw_bits_dont() {
+    let vm = common::vm(&[BITS]);
+    assert_eq!(
+        common::eval(
+            &vm,
+            &[
+                // a quiet NaN with a payload, a negative NaN, and 1.0
+                "Bits.floatBits(2143289345)",
+                "Bits.rawFloatBits(2143289345)",
+                "Bits.floatBits(-4194304)",
+                "Bits.rawFloatBits(-4194304)",
+                "Bits.floatBits(1065353216)",
+                "Bits.doubleBits(9221120237041090561L)",
+                "Bits.rawDoubleBits(9221120237041090561L)",
+                "Bits.doubleBits(-2251799813685248L)",
+                "Bits.rawDoubleBits(-2251799813685248L)",
+                "Bits.doubleBits(4607182418800017408L)",
+            ]
+        ),
+        [
+            "2143289344",
+            "2143289345",
+            "2143289344",
+            "-4194304",
+            "1065353216",
+            "9221120237041090560",
+            "9221120237041090561",
+            "9221120237041090560",
+            "-2251799813685248",
+            "4607182418800017408"
+        ]
+    );
+}
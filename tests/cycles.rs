@@ -0,0 +1,25 @@
+mod common;
+
+use jvmrs_lib::method;
+
+const CYCLES: &[u8] = include_bytes!("java/Cycles.class");
+const NODE: &[u8] = include_bytes!("java/Cycles$Node.class");
+
+#[test]
+fn cycles_are_collected() {
+    let vm = common::vm(&[CYCLES, NODE]);
+    for make in ["nodeCycles", "listCycles", "mapCycles"] {
+        let make = |times: u32| {
+            vm.invoke_static("Cycles", make, &method!((int) -> int), &[times], false)
+                .unwrap()
+        };
+        // the first call initializes whatever it needs to
+        assert_eq!(make(10), [10]);
+        let live = vm.heap.lock().unwrap().live_count();
+        assert_eq!(make(10_000), [10_000]);
+        // reference counting never frees a cycle, so only the ones made since the last tracing
+        // collection are left
+        let after = vm.heap.lock().unwrap().live_count();
+        assert!(after < live + 5000, "{after} objects are live, from {live}");
+    }
+}
@@ -0,0 +1,13 @@
+mod common;
+
+// written by `java/dups/GenerateDups.java`
+const DUPS: &[u8] = include_bytes!("java/dups/Dups.class");
+
+#[test]
+fn dups_move_longs_and_doubles_whole() {
+    let vm = common::vm(&[DUPS]);
+    assert_eq!(
+        common::eval(&vm, &["Dups.dupX2()", "Dups.dup2X1()", "Dups.dup2X2()"]),
+        ["-94", "197", "0.05263157894736842"]
+    );
+}
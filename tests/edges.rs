@@ -0,0 +1,31 @@
+mod common;
+
+const EDGES: &[u8] = include_bytes!("java/Edges.class");
+
+#[test]
+fn negative_zero_and_subnormals_print_like_the_jvm() {
+    let vm = common::vm(&[EDGES]);
+    assert_eq!(
+        common::eval(
+            &vm,
+            &[
+                "Edges.negate(0.0)",
+                "Edges.negate(-0.0)",
+                "Edges.negateFloat(0.0f)",
+                "Edges.halve(2.2250738585072014E-308)",
+                "Edges.halve(1.0E-323)",
+                "Edges.halveFloat(1.17549435E-38f)",
+                "Edges.halveFloat(2.8E-45f)",
+            ]
+        ),
+        [
+            "\"-0.0\"",
+            "\"0.0\"",
+            "\"-0.0\"",
+            "\"1.1125369292536007E-308\"",
+            "\"4.9E-324\"",
+            "\"5.877472E-39\"",
+            "\"1.4E-45\""
+        ]
+    );
+}
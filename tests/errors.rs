@@ -0,0 +1,22 @@
+mod common;
+
+use javarust::virtual_machine::error::Error;
+use jvmrs_lib::method;
+
+// compiled alongside `Absent`, whose class file was then left out
+const MISSING: &[u8] = include_bytes!("java/Missing.class");
+
+#[test]
+fn missing_classes_fail_to_resolve() {
+    let vm = common::vm(&[MISSING]);
+    let call = vm.invoke_static("Missing", "callAbsent", &method!(() -> int), &[], false);
+    assert!(matches!(call, Err(Error::ClassResolution(class)) if class == "Absent"));
+    let new = vm.invoke_static(
+        "Missing",
+        "makeAbsent",
+        &method!(() -> Object("java/lang/Object".into())),
+        &[],
+        false,
+    );
+    assert!(matches!(new, Err(Error::ClassResolution(class)) if class == "Absent"));
+}
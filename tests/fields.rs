@@ -0,0 +1,14 @@
+mod common;
+
+const SHADOWS: &[u8] = include_bytes!("java/Shadows.class");
+const BASE: &[u8] = include_bytes!("java/Shadows$Base.class");
+const DERIVED: &[u8] = include_bytes!("java/Shadows$Derived.class");
+
+#[test]
+fn shadowed_fields_are_kept_apart() {
+    let vm = common::vm(&[SHADOWS, BASE, DERIVED]);
+    assert_eq!(
+        common::eval(&vm, &["Shadows.read()", "Shadows.write()"]),
+        ["121", "575"]
+    );
+}
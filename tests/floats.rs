@@ -0,0 +1,47 @@
+mod common;
+
+use jvmrs_lib::method;
+
+const FLOATS: &[u8] = include_bytes!("java/Floats.class");
+
+/// `op lhs rhs result` lines from running `tests/java/Floats.java` on a reference JVM, with each
+/// value written as its raw bits
+const EXPECTED: &str = include_str!("java/floats.txt");
+
+#[test]
+fn float_arithmetic_matches_the_jvm() {
+    let vm = common::vm(&[FLOATS]);
+    let float_op = method!((float, float) -> float);
+    let double_op = method!((double, double) -> double);
+    for line in EXPECTED.lines() {
+        let [op, lhs, rhs, expected] = line.split(' ').collect::<Vec<_>>()[..] else {
+            panic!("malformed line {line:?}");
+        };
+        if op.starts_with('f') {
+            let [lhs, rhs, expected] =
+                [lhs, rhs, expected].map(|bits| u32::from_str_radix(bits, 16).unwrap());
+            let result = vm
+                .invoke_static("Floats", op, &float_op, &[lhs, rhs], false)
+                .unwrap()[0];
+            // the JVM doesn't pin down which NaN an operation produces
+            if f32::from_bits(expected).is_nan() {
+                assert!(f32::from_bits(result).is_nan(), "{line}: got {result:08x}");
+            } else {
+                assert_eq!(result, expected, "{line}: got {result:08x}");
+            }
+        } else {
+            let [lhs, rhs, expected] =
+                [lhs, rhs, expected].map(|bits| u64::from_str_radix(bits, 16).unwrap());
+            let slots = [lhs, rhs].map(|bits| [(bits >> 32) as u32, bits as u32]);
+            let result = vm
+                .invoke_static("Floats", op, &double_op, &slots.concat(), false)
+                .unwrap();
+            let result = (u64::from(result[0]) << 32) | u64::from(result[1]);
+            if f64::from_bits(expected).is_nan() {
+                assert!(f64::from_bits(result).is_nan(), "{line}: got {result:016x}");
+            } else {
+                assert_eq!(result, expected, "{line}: got {result:016x}");
+            }
+        }
+    }
+}
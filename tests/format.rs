@@ -0,0 +1,17 @@
+use std::process::Command;
+
+/// `printf` output from the JVM for `java/Format.java`
+const EXPECTED: &str = include_str!("java/format.txt");
+
+#[test]
+fn printf_matches_the_jvm() {
+    let output = Command::new(env!("CARGO_BIN_EXE_javarust"))
+        .arg(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/java/Format.class"
+        ))
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), EXPECTED);
+}
@@ -0,0 +1,21 @@
+use std::process::Command;
+
+fn run_hashes() -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_javarust"))
+        .arg("--deterministic-hash")
+        .arg(format!(
+            "{}/tests/java/Hashes.class",
+            env!("CARGO_MANIFEST_DIR")
+        ))
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn deterministic_hashes_repeat_across_runs() {
+    let first = run_hashes();
+    assert_eq!(first, "1\n[I@2\n3\n1\n");
+    assert_eq!(run_hashes(), first);
+}
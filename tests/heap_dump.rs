@@ -0,0 +1,44 @@
+mod common;
+
+use javarust::virtual_machine::object::Object;
+
+const CYCLES: &[u8] = include_bytes!("java/Cycles.class");
+const NODE: &[u8] = include_bytes!("java/Cycles$Node.class");
+
+#[test]
+fn dump_shows_classes_refcounts_and_contents() {
+    let vm = common::vm(&[CYCLES, NODE]);
+    let node_class = vm.class_area.search("Cycles$Node").unwrap();
+    let mut heap = vm.heap.lock().unwrap();
+    let tail = heap.allocate(Object::from_class(&node_class));
+    let mut head = Object::from_class(&node_class);
+    head.fields[0] = tail;
+    let head = heap.allocate(head);
+    heap.inc_ref(head);
+    heap.inc_ref(tail);
+    let string = heap.allocate_str("dumped".into());
+    heap.inc_ref(string);
+    heap.inc_ref(string);
+
+    let mut dump = Vec::new();
+    heap.dump(&mut dump).unwrap();
+    let dump = String::from_utf8(dump).unwrap();
+    let lines = dump.lines().collect::<Vec<_>>();
+    assert_eq!(lines[0], format!("{} live objects", heap.live_count()));
+    // each object's header is followed by its contents
+    let after = |header: String| {
+        let at = lines
+            .iter()
+            .position(|line| *line == header)
+            .unwrap_or_else(|| panic!("no `{header}` in\n{dump}"));
+        lines[at + 1]
+    };
+    assert!(
+        after(format!("{head:0>8X} Cycles$Node (refs: 1)")).ends_with(&format!(" next = {tail}"))
+    );
+    assert!(after(format!("{tail:0>8X} Cycles$Node (refs: 1)")).ends_with(" next = 0"));
+    assert_eq!(
+        after(format!("{string:0>8X} java/lang/String (refs: 2)")),
+        "    \"dumped\""
+    );
+}
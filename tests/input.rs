@@ -0,0 +1,17 @@
+mod common;
+
+use std::io::Cursor;
+
+use javarust::virtual_machine::set_stdin;
+
+const INPUT: &[u8] = include_bytes!("java/Input.class");
+
+#[test]
+fn mismatched_token_stays_in_the_input() {
+    set_stdin(Cursor::new("hello 5\n"));
+    let vm = common::vm(&[INPUT]);
+    assert_eq!(
+        common::eval(&vm, &["Input.intOrWord()", "Input.intOrWord()"]),
+        ["\"word hello\"", "\"int 5\""]
+    );
+}
@@ -0,0 +1,31 @@
+mod common;
+
+const SHAPES: &[u8] = include_bytes!("java/Shapes.class");
+const SHAPE: &[u8] = include_bytes!("java/Shapes$Shape.class");
+const NAMED: &[u8] = include_bytes!("java/Shapes$Named.class");
+const SQUARE: &[u8] = include_bytes!("java/Shapes$Square.class");
+const BLOB: &[u8] = include_bytes!("java/Shapes$Blob.class");
+const INTERFACE_CONSTANTS: &[u8] = include_bytes!("java/InterfaceConstants.class");
+const BASE: &[u8] = include_bytes!("java/InterfaceConstants$Base.class");
+const SIZED: &[u8] = include_bytes!("java/InterfaceConstants$Sized.class");
+const BOX: &[u8] = include_bytes!("java/InterfaceConstants$Box.class");
+const SMALL_BOX: &[u8] = include_bytes!("java/InterfaceConstants$SmallBox.class");
+
+#[test]
+fn object_methods_on_interface_values() {
+    let vm = common::vm(&[SHAPES, SHAPE, NAMED, SQUARE, BLOB]);
+    assert_eq!(
+        common::eval(&vm, &["Shapes.squareName()", "Shapes.blobEqualsItself()"]),
+        ["\"Square Square\"", "true"]
+    );
+}
+
+#[test]
+fn interface_constants_are_inherited() {
+    let vm = common::vm(&[INTERFACE_CONSTANTS, BASE, SIZED, BOX, SMALL_BOX]);
+    // through a class, a superclass, and a superinterface
+    assert_eq!(
+        common::eval(&vm, &["InterfaceConstants.read()"]),
+        ["\"4 3 4\""]
+    );
+}
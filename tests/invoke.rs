@@ -0,0 +1,30 @@
+mod common;
+
+use javarust::VmOptions;
+
+const INVOKE: &[u8] = include_bytes!("java/Invoke.class");
+
+#[test]
+fn string_passes_as_object() {
+    // the verifier only checks stack depth, but it's the one place a type check could creep in
+    for verify in [false, true] {
+        let vm = common::vm_with_options(
+            &[INVOKE],
+            VmOptions {
+                verify,
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            common::eval(
+                &vm,
+                &[
+                    r#"Invoke.identity("hello")"#,
+                    r#"Invoke.throughObject("hello")"#,
+                    r#"Invoke.lengthThroughObject("hello")"#,
+                ]
+            ),
+            ["hello", "\"hello\"", "5"]
+        );
+    }
+}
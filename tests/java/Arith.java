@@ -0,0 +1,5 @@
+public class Arith {
+    static int addTwice(int a, int b) {
+        return (a + b) * 2;
+    }
+}
@@ -0,0 +1,30 @@
+public class ArrayNames {
+    static String ints() {
+        int[] values = new int[3];
+        return values.toString();
+    }
+
+    static String strings() {
+        Object values = new String[] { "a" };
+        return String.valueOf(values);
+    }
+
+    static String nested() {
+        double[][] values = new double[2][2];
+        return String.valueOf((Object) values);
+    }
+
+    static String stringGrid() {
+        String[][] grid = new String[2][3];
+        grid[0][1] = "x";
+        int nulls = 0;
+        for (String[] row : grid) {
+            for (String cell : row) {
+                if (cell == null) {
+                    nulls++;
+                }
+            }
+        }
+        return grid.length + " " + grid[1].length + " " + nulls + " " + (grid[1] instanceof String[]);
+    }
+}
@@ -0,0 +1,17 @@
+public class Bits {
+    static int floatBits(int bits) {
+        return Float.floatToIntBits(Float.intBitsToFloat(bits));
+    }
+
+    static int rawFloatBits(int bits) {
+        return Float.floatToRawIntBits(Float.intBitsToFloat(bits));
+    }
+
+    static long doubleBits(long bits) {
+        return Double.doubleToLongBits(Double.longBitsToDouble(bits));
+    }
+
+    static long rawDoubleBits(long bits) {
+        return Double.doubleToRawLongBits(Double.longBitsToDouble(bits));
+    }
+}
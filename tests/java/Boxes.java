@@ -0,0 +1,16 @@
+public class Boxes {
+    // erased to `Comparable.compareTo(Object)`
+    static <T extends Comparable<T>> int compare(T lhs, T rhs) {
+        return lhs.compareTo(rhs);
+    }
+
+    static String bridged() {
+        return compare(1L, 2L) + " " + compare(2.5, -0.0) + " " + compare(-0.0f, 0.0f) + " "
+                + compare('a', 'c') + " " + compare(true, false) + " " + compare((byte) 5, (byte) 1);
+    }
+
+    static int typed() {
+        Long lhs = 5L;
+        return lhs.compareTo(3L);
+    }
+}
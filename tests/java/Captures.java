@@ -0,0 +1,37 @@
+import java.util.function.IntUnaryOperator;
+
+public class Captures {
+    private int base;
+
+    Captures(int base) {
+        this.base = base;
+    }
+
+    // captures `this`, so it sees later changes to `base`
+    IntUnaryOperator adder() {
+        return y -> y + base;
+    }
+
+    public static int captureThis(int x) {
+        Captures captures = new Captures(10);
+        IntUnaryOperator f = captures.adder();
+        int before = f.applyAsInt(x);
+        captures.base = 20;
+        return before * 100 + f.applyAsInt(x);
+    }
+
+    public static int captureLong(int x) {
+        long big = 5_000_000_000L;
+        int small = 3;
+        IntUnaryOperator f = y -> (int) (big / 1_000_000L * y + small);
+        return f.applyAsInt(x);
+    }
+
+    public static int captureDouble(int x) {
+        int before = 2;
+        double scale = 1.5;
+        long after = 100L;
+        IntUnaryOperator f = y -> (int) (before * scale * y + after);
+        return f.applyAsInt(x);
+    }
+}
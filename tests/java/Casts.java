@@ -0,0 +1,72 @@
+import java.util.ArrayList;
+import java.util.List;
+import java.util.Map;
+
+public class Casts {
+    interface Unrelated {
+    }
+
+    static class Animal {
+    }
+
+    static class Dog extends Animal implements Unrelated {
+    }
+
+    static Object list() {
+        return new ArrayList<String>();
+    }
+
+    public static int toList() {
+        List<?> list = (List<?>) list();
+        return list.size();
+    }
+
+    public static boolean toIterable() {
+        Iterable<?> iterable = (Iterable<?>) list();
+        return iterable != null;
+    }
+
+    public static String toMap() {
+        try {
+            Map<?, ?> map = (Map<?, ?>) list();
+            return "cast";
+        } catch (ClassCastException e) {
+            return "thrown";
+        }
+    }
+
+    public static String toUnrelated() {
+        try {
+            Unrelated unrelated = (Unrelated) list();
+            return "cast";
+        } catch (ClassCastException e) {
+            return "thrown";
+        }
+    }
+
+    public static boolean nullInstanceOf() {
+        Object nothing = null;
+        return nothing instanceof String;
+    }
+
+    public static String castMessage() {
+        Object boxed = Integer.valueOf(3);
+        try {
+            return (String) boxed;
+        } catch (ClassCastException e) {
+            return e.getMessage();
+        }
+    }
+
+    public static String instanceOfs() {
+        Object dog = new Dog();
+        Object animal = new Animal();
+        Object strings = new String[1];
+        Object grid = new int[2][];
+        return (dog instanceof Animal) + " " + (animal instanceof Dog) + " " + (dog instanceof Unrelated) + " "
+                + (animal instanceof Unrelated) + " " + (strings instanceof String[]) + " "
+                + (strings instanceof Object[]) + " " + (strings instanceof Integer[]) + " "
+                + (grid instanceof int[][]) + " " + (grid instanceof Object[]) + " " + (grid instanceof long[][])
+                + " " + (strings instanceof Object);
+    }
+}
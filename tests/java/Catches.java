@@ -0,0 +1,78 @@
+public class Catches {
+    int count;
+
+    static Catches none() {
+        return null;
+    }
+
+    static int recurse(int depth) {
+        return recurse(depth + 1) + 1;
+    }
+
+    static String throwable() {
+        try {
+            recurse(0);
+            return "returned";
+        } catch (Throwable t) {
+            return t.getClass().getName() + " " + (t instanceof Error);
+        }
+    }
+
+    static String error() {
+        try {
+            recurse(0);
+            return "returned";
+        } catch (Exception e) {
+            return "exception";
+        } catch (Error e) {
+            return e.getClass().getName();
+        }
+    }
+
+    static int nullAccesses() {
+        int caught = 0;
+        Catches none = none();
+        int[] array = null;
+        try {
+            caught += none.count;
+        } catch (NullPointerException e) {
+            caught++;
+        }
+        try {
+            none.count = 1;
+        } catch (NullPointerException e) {
+            caught++;
+        }
+        try {
+            caught += none.hashCode();
+        } catch (NullPointerException e) {
+            caught++;
+        }
+        try {
+            caught += array.length;
+        } catch (NullPointerException e) {
+            caught++;
+        }
+        try {
+            caught += array[0];
+        } catch (NullPointerException e) {
+            caught++;
+        }
+        try {
+            array[0] = 1;
+        } catch (NullPointerException e) {
+            caught++;
+        }
+        return caught;
+    }
+
+    static String pastTheEnd() {
+        int[] array = new int[3];
+        try {
+            array[3] = 1;
+            return "stored";
+        } catch (ArrayIndexOutOfBoundsException e) {
+            return e.getMessage();
+        }
+    }
+}
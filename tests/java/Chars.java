@@ -0,0 +1,20 @@
+public class Chars {
+    // '٣' is ARABIC-INDIC DIGIT THREE, 'Ⅻ' is ROMAN NUMERAL TWELVE, '½' is VULGAR
+    // FRACTION ONE HALF, 'ͅ' is a combining mark, and 'ª' is FEMININE ORDINAL INDICATOR
+    static String digits() {
+        return Character.isDigit('7') + " " + Character.isDigit('٣') + " "
+                + Character.isDigit('Ⅻ') + " " + Character.isDigit('½');
+    }
+
+    static String letters() {
+        return Character.isLetter('q') + " " + Character.isLetter('ª') + " "
+                + Character.isLetter('Ⅻ') + " " + Character.isLetter('ͅ') + " "
+                + Character.isLetterOrDigit('٣') + " " + Character.isLetterOrDigit('½');
+    }
+
+    static String values() {
+        return Character.digit('a', 16) + " " + Character.digit('g', 16) + " "
+                + Character.digit('٣', 10) + " " + Character.digit('Ａ', 16) + " "
+                + Character.digit('z', 36) + " " + Character.digit('7', 7);
+    }
+}
@@ -0,0 +1,71 @@
+import java.util.HashMap;
+import java.util.HashSet;
+
+public class Collisions {
+    static class Key {
+        final int id;
+
+        Key(int id) {
+            this.id = id;
+        }
+
+        @Override
+        public int hashCode() {
+            return 7;
+        }
+
+        @Override
+        public boolean equals(Object other) {
+            return other instanceof Key key && key.id == id;
+        }
+    }
+
+    public static int mapSize() {
+        HashMap<Key, String> map = new HashMap<>();
+        for (int i = 0; i < 5; i++) {
+            map.put(new Key(i), "value");
+        }
+        map.put(new Key(2), "again");
+        return map.size();
+    }
+
+    public static String replaced() {
+        HashMap<Key, String> map = new HashMap<>();
+        map.put(new Key(1), "one");
+        map.put(new Key(2), "two");
+        return map.put(new Key(2), "deux");
+    }
+
+    public static String lookup(int id) {
+        HashMap<Key, String> map = new HashMap<>();
+        map.put(new Key(1), "one");
+        map.put(new Key(2), "two");
+        map.put(new Key(3), "three");
+        return map.get(new Key(id));
+    }
+
+    public static boolean hasStrings() {
+        // "Aa" and "BB" have the same hash code
+        HashMap<String, Integer> map = new HashMap<>();
+        map.put("Aa", 1);
+        return map.containsKey("Aa") && !map.containsKey("BB");
+    }
+
+    public static int setSize() {
+        HashSet<Key> set = new HashSet<>();
+        int added = 0;
+        for (int i = 0; i < 6; i++) {
+            if (set.add(new Key(i % 3))) {
+                added++;
+            }
+        }
+        return added * 10 + set.size();
+    }
+
+    public static boolean setContains(int id) {
+        HashSet<Key> set = new HashSet<>();
+        set.add(new Key(0));
+        set.add(new Key(1));
+        return set.contains(new Key(id));
+    }
+}
@@ -0,0 +1,24 @@
+public class Compare {
+    static float floatNaN = Float.NaN;
+    static double doubleNaN = Double.NaN;
+    static float floatTenth = 0.1f;
+    static double tenth = 0.1;
+    static double fifth = 0.2;
+    static double threeTenths = 0.3;
+
+    // `<` and `<=` compile to `fcmpg`/`dcmpg`, `>` and `>=` to `fcmpl`/`dcmpl`
+    static String floats() {
+        float one = 1.0f;
+        return (floatNaN < one) + " " + (floatNaN <= one) + " " + (floatNaN > one) + " "
+                + (floatNaN >= one) + " " + (floatNaN == floatNaN) + " " + (floatNaN != floatNaN) + " "
+                + (floatTenth * 3 == 0.3f) + " " + (floatTenth + floatTenth == 0.2f);
+    }
+
+    static String doubles() {
+        double one = 1.0;
+        return (doubleNaN < one) + " " + (doubleNaN <= one) + " " + (doubleNaN > one) + " "
+                + (doubleNaN >= one) + " " + (doubleNaN == doubleNaN) + " "
+                + (doubleNaN != doubleNaN) + " " + (tenth + fifth == threeTenths) + " "
+                + (tenth + fifth > threeTenths);
+    }
+}
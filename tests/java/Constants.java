@@ -0,0 +1,19 @@
+public class Constants {
+    static int initialized = 0;
+
+    static class Loud {
+        static final int VALUE = 42;
+        static int counter = 7;
+
+        static {
+            initialized++;
+        }
+    }
+
+    static String read() {
+        int value = Loud.VALUE;
+        int before = initialized;
+        int counter = Loud.counter;
+        return value + " " + before + " " + counter + " " + initialized;
+    }
+}
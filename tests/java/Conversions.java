@@ -0,0 +1,9 @@
+public class Conversions {
+    static char toChar(int code) {
+        return (char) code;
+    }
+
+    static int lowBits(int code) {
+        return (char) code;
+    }
+}
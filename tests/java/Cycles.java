@@ -0,0 +1,46 @@
+import java.util.ArrayList;
+import java.util.HashMap;
+
+public class Cycles {
+    static class Node {
+        Node next;
+    }
+
+    static void nodeCycle() {
+        Node first = new Node();
+        Node second = new Node();
+        first.next = second;
+        second.next = first;
+    }
+
+    static void listCycle() {
+        ArrayList<Object> list = new ArrayList<>();
+        list.add(list);
+    }
+
+    static void mapCycle() {
+        HashMap<String, Object> map = new HashMap<>();
+        map.put("self", map);
+    }
+
+    static int nodeCycles(int times) {
+        for (int i = 0; i < times; i++) {
+            nodeCycle();
+        }
+        return times;
+    }
+
+    static int listCycles(int times) {
+        for (int i = 0; i < times; i++) {
+            listCycle();
+        }
+        return times;
+    }
+
+    static int mapCycles(int times) {
+        for (int i = 0; i < times; i++) {
+            mapCycle();
+        }
+        return times;
+    }
+}
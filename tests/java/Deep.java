@@ -0,0 +1,19 @@
+public class Deep {
+    static class Link {
+        Link next;
+    }
+
+    static Link chain(int length) {
+        if (length == 0) {
+            return null;
+        }
+        Link link = new Link();
+        link.next = chain(length - 1);
+        return link;
+    }
+
+    public static void main(String[] args) {
+        chain(300000);
+        System.out.println("done");
+    }
+}
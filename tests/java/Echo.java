@@ -0,0 +1,10 @@
+import java.util.Scanner;
+
+public class Echo {
+    public static void main(String[] args) {
+        Scanner scanner = new Scanner(System.in);
+        while (scanner.hasNextLine()) {
+            System.out.println("echo " + scanner.nextLine());
+        }
+    }
+}
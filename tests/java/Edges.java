@@ -0,0 +1,17 @@
+public class Edges {
+    static String negate(double value) {
+        return "" + (-1.0 * value);
+    }
+
+    static String negateFloat(float value) {
+        return "" + (-1.0f * value);
+    }
+
+    static String halve(double value) {
+        return "" + value / 2;
+    }
+
+    static String halveFloat(float value) {
+        return "" + value / 2;
+    }
+}
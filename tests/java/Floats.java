@@ -0,0 +1,79 @@
+public class Floats {
+    static float fadd(float a, float b) {
+        return a + b;
+    }
+
+    static float fsub(float a, float b) {
+        return a - b;
+    }
+
+    static float fmul(float a, float b) {
+        return a * b;
+    }
+
+    static float fdiv(float a, float b) {
+        return a / b;
+    }
+
+    static float frem(float a, float b) {
+        return a % b;
+    }
+
+    static double dadd(double a, double b) {
+        return a + b;
+    }
+
+    static double dsub(double a, double b) {
+        return a - b;
+    }
+
+    static double dmul(double a, double b) {
+        return a * b;
+    }
+
+    static double ddiv(double a, double b) {
+        return a / b;
+    }
+
+    static double drem(double a, double b) {
+        return a % b;
+    }
+
+    static float[] FLOATS = { 0.1f, 0.2f, -0.0f, 1.0f / 3.0f, 3.0e38f, 1.0e-45f, 16777217.0f,
+            Float.POSITIVE_INFINITY, Float.NaN };
+    static double[] DOUBLES = { 0.1, 0.2, -0.0, 1.0 / 3.0, 1.7e308, 4.9e-324, 9007199254740993.0,
+            Double.NEGATIVE_INFINITY, Double.NaN };
+
+    // run on a reference JVM to make floats.txt: one `op lhs rhs result` line per operation, with
+    // each value written as its raw bits
+    public static void main(String[] args) {
+        String[] floatOps = { "fadd", "fsub", "fmul", "fdiv", "frem" };
+        for (String op : floatOps) {
+            for (float a : FLOATS) {
+                for (float b : FLOATS) {
+                    float result = op.equals("fadd") ? fadd(a, b)
+                            : op.equals("fsub") ? fsub(a, b)
+                            : op.equals("fmul") ? fmul(a, b)
+                            : op.equals("fdiv") ? fdiv(a, b)
+                            : frem(a, b);
+                    System.out.printf("%s %08x %08x %08x%n", op, Float.floatToRawIntBits(a),
+                            Float.floatToRawIntBits(b), Float.floatToRawIntBits(result));
+                }
+            }
+        }
+        String[] doubleOps = { "dadd", "dsub", "dmul", "ddiv", "drem" };
+        for (String op : doubleOps) {
+            for (double a : DOUBLES) {
+                for (double b : DOUBLES) {
+                    double result = op.equals("dadd") ? dadd(a, b)
+                            : op.equals("dsub") ? dsub(a, b)
+                            : op.equals("dmul") ? dmul(a, b)
+                            : op.equals("ddiv") ? ddiv(a, b)
+                            : drem(a, b);
+                    System.out.printf("%s %016x %016x %016x%n", op, Double.doubleToRawLongBits(a),
+                            Double.doubleToRawLongBits(b), Double.doubleToRawLongBits(result));
+                }
+            }
+        }
+    }
+}
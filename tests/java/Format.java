@@ -0,0 +1,23 @@
+public class Format {
+    public static void main(String[] args) {
+        System.out.printf("%.10f %f %.3f %.20f%n", 0.1f, 0.1f, 1.0005f, 0.1f);
+        System.out.printf("%+d %+d %,d %,d %+,d%n", 5, -5, 1234567, -1234, 1234567);
+        System.out.printf("%,010d %+010d %-+5d|%n", 1234, 42, 5);
+        System.out.printf("%,.2f %+.1f %+f %,f%n", 1234567.891, 2.0, Double.NaN, 999.5);
+        try {
+            System.out.printf("%,x%n", 5);
+        } catch (Exception e) {
+            System.out.println(e);
+        }
+        try {
+            System.out.printf("%+s%n", "a");
+        } catch (Exception e) {
+            System.out.println(e);
+        }
+        try {
+            System.out.printf("%d%n", 1.5f);
+        } catch (Exception e) {
+            System.out.println(e);
+        }
+    }
+}
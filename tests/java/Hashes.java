@@ -0,0 +1,10 @@
+public class Hashes {
+    public static void main(String[] args) {
+        Object first = new Object();
+        int[] array = new int[3];
+        System.out.println(first.hashCode());
+        System.out.println(array.toString());
+        System.out.println(new Object().hashCode());
+        System.out.println(first.hashCode());
+    }
+}
@@ -0,0 +1,13 @@
+import java.util.InputMismatchException;
+import java.util.Scanner;
+
+public class Input {
+    static String intOrWord() {
+        Scanner scanner = new Scanner(System.in);
+        try {
+            return "int " + scanner.nextInt();
+        } catch (InputMismatchException e) {
+            return "word " + scanner.next();
+        }
+    }
+}
@@ -0,0 +1,16 @@
+import java.util.function.IntBinaryOperator;
+import java.util.function.IntPredicate;
+import java.util.function.IntUnaryOperator;
+
+public class IntLambdas {
+    public static boolean positive(int i) {
+        IntPredicate positive = x -> x > 0;
+        return positive.test(i);
+    }
+
+    public static int squareSum(int i) {
+        IntUnaryOperator square = x -> x * x;
+        IntBinaryOperator add = (a, b) -> a + b;
+        return add.applyAsInt(square.applyAsInt(i), i);
+    }
+}
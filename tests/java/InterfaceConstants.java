@@ -0,0 +1,20 @@
+public class InterfaceConstants {
+    interface Base {
+        // neither of these is a compile-time constant, so reading them is a `getstatic`
+        String NAME = String.valueOf("base".length());
+        int[] SIZES = { 1, 2, 3 };
+    }
+
+    interface Sized extends Base {
+    }
+
+    static class Box implements Sized {
+    }
+
+    static class SmallBox extends Box {
+    }
+
+    static String read() {
+        return Box.NAME + " " + SmallBox.SIZES.length + " " + Sized.NAME;
+    }
+}
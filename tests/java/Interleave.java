@@ -0,0 +1,13 @@
+public class Interleave {
+    public static void main(String[] args) {
+        for (int i = 0; i < 3; i++) {
+            System.out.print(i);
+            System.out.print(':');
+            System.out.println(" line " + i);
+            System.out.print("[");
+            System.out.print(i * i);
+            System.out.println("]");
+        }
+        System.out.print("done");
+    }
+}
@@ -0,0 +1,13 @@
+public class Invoke {
+    static Object identity(Object value) {
+        return value;
+    }
+
+    static String throughObject(String str) {
+        return (String) identity(str);
+    }
+
+    static int lengthThroughObject(String str) {
+        return ((String) identity(str)).length();
+    }
+}
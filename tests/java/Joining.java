@@ -0,0 +1,20 @@
+import java.util.ArrayList;
+import java.util.stream.Collectors;
+
+public class Joining {
+    static ArrayList<String> words() {
+        ArrayList<String> words = new ArrayList<>();
+        words.add("a");
+        words.add(null);
+        words.add("b");
+        return words;
+    }
+
+    static String nulls() {
+        return words().stream().collect(Collectors.joining(", "));
+    }
+
+    static String builderDelimiter() {
+        return words().stream().collect(Collectors.joining(new StringBuilder("-")));
+    }
+}
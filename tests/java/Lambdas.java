@@ -0,0 +1,6 @@
+public class Lambdas {
+    // `IntSource` only appears in the invokedynamic's descriptor
+    static Object make() {
+        return (IntSource) () -> 7;
+    }
+}
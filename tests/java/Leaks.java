@@ -0,0 +1,46 @@
+import java.util.ArrayList;
+import java.util.HashMap;
+import java.util.HashSet;
+import java.util.LinkedList;
+
+public class Leaks {
+    static int fillList(int times) {
+        ArrayList<Object> list = new ArrayList<>();
+        for (int i = 0; i < times; i++) {
+            list.add(new Object());
+        }
+        return list.size();
+    }
+
+    static int fillLinkedList(int times) {
+        LinkedList<Object> list = new LinkedList<>();
+        for (int i = 0; i < times; i++) {
+            list.addFirst(new Object());
+        }
+        return list.size();
+    }
+
+    static int fillMap(int times) {
+        HashMap<Object, Object> map = new HashMap<>();
+        for (int i = 0; i < times; i++) {
+            map.put(new Object(), new Object());
+        }
+        return map.size();
+    }
+
+    static int fillSet(int times) {
+        HashSet<Object> set = new HashSet<>();
+        for (int i = 0; i < times; i++) {
+            set.add(new Object());
+        }
+        return set.size();
+    }
+
+    static int concat(int times) {
+        String out = "";
+        for (int i = 0; i < times; i++) {
+            out = out + "x";
+        }
+        return out.length();
+    }
+}
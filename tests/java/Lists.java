@@ -0,0 +1,54 @@
+import java.util.Collections;
+import java.util.LinkedList;
+import java.util.Random;
+
+public class Lists {
+    static LinkedList<String> names() {
+        LinkedList<String> names = new LinkedList<>();
+        names.add("b");
+        names.addFirst("a");
+        names.addLast("c");
+        return names;
+    }
+
+    static String walk() {
+        String out = "";
+        for (String name : names()) {
+            out += name;
+        }
+        return out;
+    }
+
+    static String second() {
+        return names().get(1);
+    }
+
+    static String show() {
+        return names().toString();
+    }
+
+    static int frequency() {
+        LinkedList<String> names = names();
+        names.add("b");
+        return Collections.frequency(names, "b");
+    }
+
+    static int shuffled() {
+        LinkedList<String> names = names();
+        Collections.shuffle(names);
+        return names.size() * 10 + Collections.frequency(names, "c");
+    }
+
+    static String seeded(long seed) {
+        LinkedList<String> names = names();
+        for (String name : new String[] {"d", "e", "f", "g", "h", "i", "j"}) {
+            names.add(name);
+        }
+        Collections.shuffle(names, new Random(seed));
+        return names.toString();
+    }
+
+    static boolean sameSeedSameOrder() {
+        return seeded(42).equals(seeded(42));
+    }
+}
@@ -0,0 +1,5 @@
+public class MainOne {
+    public static void main(String[] args) {
+        System.out.println("main One");
+    }
+}
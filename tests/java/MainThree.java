@@ -0,0 +1,5 @@
+public class MainThree {
+    public static void main(String[] args) {
+        System.out.println("main Three");
+    }
+}
@@ -0,0 +1,5 @@
+public class MainTwo {
+    public static void main(String[] args) {
+        System.out.println("main Two");
+    }
+}
@@ -0,0 +1,9 @@
+public class Maths {
+    static double hypot(double x, double y) {
+        return Math.hypot(x, y);
+    }
+
+    static double log10(double x) {
+        return Math.log10(x);
+    }
+}
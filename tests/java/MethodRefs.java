@@ -0,0 +1,16 @@
+import java.util.function.Function;
+
+public class MethodRefs {
+    static int length(String str) {
+        Function<String, Integer> length = String::length;
+        return length.apply(str);
+    }
+
+    static String nullLength() {
+        try {
+            return "length " + length(null);
+        } catch (NullPointerException e) {
+            return "caught";
+        }
+    }
+}
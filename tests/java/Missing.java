@@ -0,0 +1,9 @@
+public class Missing {
+    static int callAbsent() {
+        return Absent.value();
+    }
+
+    static Object makeAbsent() {
+        return new Absent();
+    }
+}
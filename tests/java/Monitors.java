@@ -0,0 +1,56 @@
+public class Monitors {
+    int count;
+
+    synchronized int increment() {
+        count++;
+        return Thread.holdsLock(this) ? count : -1;
+    }
+
+    synchronized void fail() {
+        throw new IllegalStateException("failed while holding the lock");
+    }
+
+    static synchronized boolean holdsClass() {
+        return Thread.holdsLock(Monitors.class);
+    }
+
+    static String completed() {
+        Monitors monitors = new Monitors();
+        int inside = monitors.increment();
+        return inside + " " + Thread.holdsLock(monitors);
+    }
+
+    static String thrown() {
+        Monitors monitors = new Monitors();
+        try {
+            monitors.fail();
+        } catch (IllegalStateException e) {
+            return e.getMessage() + " " + Thread.holdsLock(monitors);
+        }
+        return "not thrown";
+    }
+
+    static String staticCompleted() {
+        return holdsClass() + " " + Thread.holdsLock(Monitors.class);
+    }
+
+    static String block() {
+        Object lock = new Object();
+        boolean inside;
+        synchronized (lock) {
+            inside = Thread.holdsLock(lock);
+        }
+        return inside + " " + Thread.holdsLock(lock);
+    }
+
+    static String blockThrown() {
+        Object lock = new Object();
+        try {
+            synchronized (lock) {
+                throw new IllegalStateException("thrown from the block");
+            }
+        } catch (IllegalStateException e) {
+            return e.getMessage() + " " + Thread.holdsLock(lock);
+        }
+    }
+}
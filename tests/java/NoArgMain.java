@@ -0,0 +1,6 @@
+public class NoArgMain {
+    // a real JVM won't start here before Java 21, but javarust falls back to it
+    public static void main() {
+        System.out.println("main without args");
+    }
+}
@@ -0,0 +1,8 @@
+import java.util.Objects;
+
+public class NullSafe {
+    static String toStrings() {
+        return Objects.toString(null) + " " + Objects.toString(null, "d") + " " + Objects.toString(5) + " "
+                + Objects.toString(new StringBuilder("x"), "d");
+    }
+}
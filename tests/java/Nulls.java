@@ -0,0 +1,22 @@
+public class Nulls {
+    static Object nothing() {
+        return null;
+    }
+
+    static Object everyOther(int i) {
+        return i % 2 == 0 ? null : new Object();
+    }
+
+    static int countNulls(int times) {
+        int count = 0;
+        for (int i = 0; i < times; i++) {
+            if (nothing() == null) {
+                count++;
+            }
+            if (everyOther(i) == null) {
+                count++;
+            }
+        }
+        return count;
+    }
+}
@@ -0,0 +1,51 @@
+import java.util.NoSuchElementException;
+import java.util.Optional;
+
+public class Optionals {
+    static Optional<String> half(String str) {
+        if (str.length() % 2 == 0) {
+            return Optional.of(str.substring(0, str.length() / 2));
+        }
+        return Optional.empty();
+    }
+
+    static String flatMapped(String str) {
+        return Optional.of(str).flatMap(Optionals::half).orElse("odd");
+    }
+
+    static boolean flatMappedPresent(String str) {
+        return Optional.of(str).flatMap(Optionals::half).isPresent();
+    }
+
+    static String orSupplied(String str) {
+        return Optional.ofNullable(str).or(() -> Optional.of("fallback")).orElse("none");
+    }
+
+    static int mappedLength(String str) {
+        return Optional.ofNullable(str).map(String::length).orElse(-1);
+    }
+
+    static boolean mappedToNullPresent(String str) {
+        return Optional.of(str).map(s -> null).isPresent();
+    }
+
+    static String got(String str) {
+        return Optional.of(str).get();
+    }
+
+    static String emptyGet() {
+        try {
+            return Optional.<String>empty().get();
+        } catch (NoSuchElementException e) {
+            return "thrown";
+        }
+    }
+
+    static String emptyOrElseThrow() {
+        try {
+            return Optional.<String>empty().orElseThrow();
+        } catch (NoSuchElementException e) {
+            return "thrown";
+        }
+    }
+}
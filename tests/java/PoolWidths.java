@@ -0,0 +1,21 @@
+public class PoolWidths {
+    static long big() {
+        return 1234567890123L;
+    }
+
+    static double precise() {
+        return 6.02214076E23;
+    }
+
+    static int bigInt() {
+        return 123456789;
+    }
+
+    static String after() {
+        return "after";
+    }
+
+    static String all() {
+        return big() + " " + precise() + " " + bigInt() + " " + after();
+    }
+}
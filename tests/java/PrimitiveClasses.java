@@ -0,0 +1,14 @@
+public class PrimitiveClasses {
+    static String names() {
+        return int.class.getName() + " " + void.class.getName() + " " + double.class.getName() + " "
+                + Integer.class.getName();
+    }
+
+    static boolean distinctFromBoxes() {
+        return int.class != Integer.class;
+    }
+
+    static boolean sameAsType() {
+        return int.class == Integer.TYPE;
+    }
+}
@@ -0,0 +1,20 @@
+public class Printing {
+    public static void main(String[] args) {
+        System.out.print("text ");
+        System.out.print(1);
+        System.out.print(' ');
+        System.out.print(2L);
+        System.out.print(' ');
+        System.out.print(2.5);
+        System.out.print(' ');
+        System.out.print(0.5f);
+        System.out.print(' ');
+        System.out.print(true);
+        System.out.print(' ');
+        System.out.print((Object) "object");
+        System.out.println();
+        System.err.println("to stderr");
+        System.err.print("without a newline");
+        System.out.print("no newline at the end");
+    }
+}
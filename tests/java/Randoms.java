@@ -0,0 +1,36 @@
+import java.util.Random;
+
+public class Randoms {
+    static boolean inRange() {
+        for (int i = 0; i < 100; i++) {
+            double random = Math.random();
+            if (random < 0.0 || random >= 1.0) {
+                return false;
+            }
+        }
+        return true;
+    }
+
+    static boolean advances() {
+        return Math.random() != Math.random();
+    }
+
+    static double gaussianMean(int samples) {
+        Random random = new Random();
+        double sum = 0.0;
+        for (int i = 0; i < samples; i++) {
+            sum += random.nextGaussian();
+        }
+        return sum / samples;
+    }
+
+    static double gaussianVariance(int samples) {
+        Random random = new Random();
+        double sumOfSquares = 0.0;
+        for (int i = 0; i < samples; i++) {
+            double value = random.nextGaussian();
+            sumOfSquares += value * value;
+        }
+        return sumOfSquares / samples;
+    }
+}
@@ -0,0 +1,28 @@
+public class Records {
+    record Point(int x, int y) {
+    }
+
+    record Named(String name, long id) {
+    }
+
+    static int sum() {
+        Point point = new Point(3, 4);
+        return point.x() + point.y();
+    }
+
+    static String show() {
+        return new Point(3, -4) + " " + new Named("first", 5L) + " " + new Named(null, 0L);
+    }
+
+    static String equality() {
+        Point point = new Point(1, 2);
+        return point.equals(new Point(1, 2)) + " " + point.equals(new Point(2, 1)) + " "
+                + point.equals(null) + " " + point.equals("Point[x=1, y=2]") + " "
+                + new Named("a", 1L).equals(new Named("a", 1L));
+    }
+
+    static boolean equalHashes() {
+        return new Point(1, 2).hashCode() == new Point(1, 2).hashCode()
+                && new Named("a", 1L).hashCode() == new Named("a", 1L).hashCode();
+    }
+}
@@ -0,0 +1,16 @@
+import java.io.InputStream;
+
+public class Resources {
+    static String read(String name) throws Exception {
+        InputStream in = Resources.class.getResourceAsStream(name);
+        if (in == null) {
+            return "null";
+        }
+        StringBuilder text = new StringBuilder();
+        int next;
+        while ((next = in.read()) != -1) {
+            text.append((char) next);
+        }
+        return text.toString();
+    }
+}
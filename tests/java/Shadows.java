@@ -0,0 +1,27 @@
+public class Shadows {
+    static class Base {
+        int value = 1;
+
+        int baseValue() {
+            return value;
+        }
+    }
+
+    static class Derived extends Base {
+        int value = 2;
+    }
+
+    public static int read() {
+        Derived derived = new Derived();
+        Base base = derived;
+        return base.value * 100 + derived.value * 10 + derived.baseValue();
+    }
+
+    public static int write() {
+        Derived derived = new Derived();
+        Base base = derived;
+        base.value = 5;
+        derived.value = 7;
+        return base.value * 100 + derived.value * 10 + derived.baseValue();
+    }
+}
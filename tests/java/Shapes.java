@@ -0,0 +1,45 @@
+public class Shapes {
+    interface Shape {
+        int sides();
+    }
+
+    // redeclares `toString`, so calls through it are `invokeinterface`
+    interface Named {
+        String toString();
+    }
+
+    static class Square implements Shape, Named {
+        public int sides() {
+            return 4;
+        }
+
+        public String toString() {
+            return "Square";
+        }
+    }
+
+    // gets `toString` and `equals` from Object
+    static class Blob implements Shape, Named {
+        public int sides() {
+            return 0;
+        }
+    }
+
+    static String describe(Shape shape) {
+        return shape.toString();
+    }
+
+    static String name(Named named) {
+        return named.toString();
+    }
+
+    static String squareName() {
+        return describe(new Square()) + " " + name(new Square());
+    }
+
+    static boolean blobEqualsItself() {
+        Shape blob = new Blob();
+        return blob.equals(blob) && describe(blob).startsWith("Shapes$Blob@")
+                && name(new Blob()).startsWith("Shapes$Blob@");
+    }
+}
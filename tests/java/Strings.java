@@ -0,0 +1,55 @@
+public class Strings {
+    static int splitCount(String str) {
+        return str.split(",").length;
+    }
+
+    static String splitPieces(String str) {
+        String out = "";
+        for (String piece : str.split(",")) {
+            out += "[" + piece + "]";
+        }
+        return out;
+    }
+
+    static String cases(String str) {
+        return str.toUpperCase() + " " + str.toLowerCase();
+    }
+
+    // `trim` only removes spaces and control characters, `strip` removes any whitespace
+    static String trimmedLengths(String str) {
+        return str.trim().length() + " " + str.strip().length();
+    }
+
+    static String affixes(String str) {
+        return str.startsWith("ab") + " " + str.endsWith("yz") + " " + str.equalsIgnoreCase("ABCXYZ")
+                + " " + str.isEmpty();
+    }
+
+    static String equality(String str) {
+        return (str + "!").equals(str + "!") + " " + str.equals(str + "?") + " "
+                + str.equals(new Object()) + " " + str.equals(null);
+    }
+
+    static int hash(String str) {
+        return str.hashCode();
+    }
+
+    static String interned() {
+        String copy = new String("x");
+        return (copy == "x") + " " + copy.equals("x") + " " + (copy.intern() == "x");
+    }
+
+    static String fromChars() {
+        char[] letters = { 'w', 'h', 'i', 'c', 'h' };
+        return String.valueOf(new char[] { 'h', 'i' }) + " " + String.valueOf(letters, 1, 3) + " "
+                + String.valueOf(letters, 5, 0).isEmpty();
+    }
+
+    static String charsOutOfRange() {
+        try {
+            return String.valueOf(new char[4], 3, 5);
+        } catch (StringIndexOutOfBoundsException e) {
+            return e.getMessage();
+        }
+    }
+}
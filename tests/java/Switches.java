@@ -0,0 +1,84 @@
+public class Switches {
+    static String dense(int key) {
+        switch (key) {
+            case -1:
+                return "minus one";
+            case 0:
+                return "zero";
+            case 1:
+                return "one";
+            case 2:
+                return "two";
+            default:
+                return "other";
+        }
+    }
+
+    // each of these starts its switch at a different offset mod 4, so each skips a different amount of padding
+    static int sparse(int key) {
+        switch (key) {
+            case -1000:
+                return 1;
+            case 7:
+                return 2;
+            case 1000000:
+                return 3;
+            default:
+                return 0;
+        }
+    }
+
+    static int negated(int key) {
+        switch (-key) {
+            case -1000:
+                return 1;
+            case 7:
+                return 2;
+            case 1000000:
+                return 3;
+            default:
+                return 0;
+        }
+    }
+
+    static int incremented(int key) {
+        switch (key + 1) {
+            case -1000:
+                return 1;
+            case 7:
+                return 2;
+            case 1000000:
+                return 3;
+            default:
+                return 0;
+        }
+    }
+
+    static int scaled(int key) {
+        switch (key * 100) {
+            case -1000:
+                return 1;
+            case 700:
+                return 2;
+            case 1000000:
+                return 3;
+            default:
+                return 0;
+        }
+    }
+
+    static String shiftedDense(int key) {
+        switch (key - 100) {
+            case 0:
+                return "zero";
+            case 1:
+                return "one";
+            case 2:
+                return "two";
+            case 3:
+                return "three";
+            default:
+                return "other";
+        }
+    }
+}
@@ -0,0 +1,22 @@
+public class Threads {
+    static String name() {
+        return Thread.currentThread().getName();
+    }
+
+    static String renamed() {
+        Thread current = Thread.currentThread();
+        current.setName("worker");
+        String renamed = Thread.currentThread().getName();
+        current.setName("main");
+        return renamed;
+    }
+
+    static String nullName() {
+        try {
+            Thread.currentThread().setName(null);
+            return "renamed";
+        } catch (NullPointerException e) {
+            return e.getMessage() + " " + Thread.currentThread().getName();
+        }
+    }
+}
@@ -0,0 +1,10 @@
+import java.io.IOException;
+
+public class Throws {
+    static void read() throws IOException {
+        throw new IOException("unreadable");
+    }
+
+    static void quiet() {
+    }
+}
@@ -0,0 +1,11 @@
+public class Times {
+    static boolean nanoTimeAdvances() {
+        long first = System.nanoTime();
+        long second = System.nanoTime();
+        return second >= first;
+    }
+
+    static long millis() {
+        return System.currentTimeMillis();
+    }
+}
@@ -0,0 +1,28 @@
+import java.util.ArrayList;
+import java.util.HashMap;
+import java.util.stream.Collectors;
+
+public class ToMap {
+    static ArrayList<String> words() {
+        ArrayList<String> words = new ArrayList<>();
+        words.add("apple");
+        words.add("banana");
+        words.add("avocado");
+        return words;
+    }
+
+    static int byWord() {
+        HashMap<String, Integer> lengths = (HashMap<String, Integer>) words().stream()
+                .collect(Collectors.toMap(word -> word, word -> word.length()));
+        return lengths.size();
+    }
+
+    static String byInitial() {
+        try {
+            words().stream().collect(Collectors.toMap(word -> word.substring(0, 1), word -> word));
+            return "collected";
+        } catch (IllegalStateException e) {
+            return e.getMessage();
+        }
+    }
+}
@@ -0,0 +1,21 @@
+public class Verify {
+    static int one() {
+        return 1;
+    }
+
+    static int caught() {
+        try {
+            return one();
+        } catch (VerifyError e) {
+            return -1;
+        }
+    }
+
+    static String message() {
+        try {
+            return "ran " + one();
+        } catch (VerifyError e) {
+            return e.getMessage();
+        }
+    }
+}
@@ -0,0 +1,536 @@
+// every method fills slots 0 to 255 first, so the locals after them need `wide` to reach
+public class Wide {
+    static String iinc() {
+        int p0 = 0;
+        int p1 = 1;
+        int p2 = 2;
+        int p3 = 3;
+        int p4 = 4;
+        int p5 = 5;
+        int p6 = 6;
+        int p7 = 7;
+        int p8 = 8;
+        int p9 = 9;
+        int p10 = 10;
+        int p11 = 11;
+        int p12 = 12;
+        int p13 = 13;
+        int p14 = 14;
+        int p15 = 15;
+        int p16 = 16;
+        int p17 = 17;
+        int p18 = 18;
+        int p19 = 19;
+        int p20 = 20;
+        int p21 = 21;
+        int p22 = 22;
+        int p23 = 23;
+        int p24 = 24;
+        int p25 = 25;
+        int p26 = 26;
+        int p27 = 27;
+        int p28 = 28;
+        int p29 = 29;
+        int p30 = 30;
+        int p31 = 31;
+        int p32 = 32;
+        int p33 = 33;
+        int p34 = 34;
+        int p35 = 35;
+        int p36 = 36;
+        int p37 = 37;
+        int p38 = 38;
+        int p39 = 39;
+        int p40 = 40;
+        int p41 = 41;
+        int p42 = 42;
+        int p43 = 43;
+        int p44 = 44;
+        int p45 = 45;
+        int p46 = 46;
+        int p47 = 47;
+        int p48 = 48;
+        int p49 = 49;
+        int p50 = 50;
+        int p51 = 51;
+        int p52 = 52;
+        int p53 = 53;
+        int p54 = 54;
+        int p55 = 55;
+        int p56 = 56;
+        int p57 = 57;
+        int p58 = 58;
+        int p59 = 59;
+        int p60 = 60;
+        int p61 = 61;
+        int p62 = 62;
+        int p63 = 63;
+        int p64 = 64;
+        int p65 = 65;
+        int p66 = 66;
+        int p67 = 67;
+        int p68 = 68;
+        int p69 = 69;
+        int p70 = 70;
+        int p71 = 71;
+        int p72 = 72;
+        int p73 = 73;
+        int p74 = 74;
+        int p75 = 75;
+        int p76 = 76;
+        int p77 = 77;
+        int p78 = 78;
+        int p79 = 79;
+        int p80 = 80;
+        int p81 = 81;
+        int p82 = 82;
+        int p83 = 83;
+        int p84 = 84;
+        int p85 = 85;
+        int p86 = 86;
+        int p87 = 87;
+        int p88 = 88;
+        int p89 = 89;
+        int p90 = 90;
+        int p91 = 91;
+        int p92 = 92;
+        int p93 = 93;
+        int p94 = 94;
+        int p95 = 95;
+        int p96 = 96;
+        int p97 = 97;
+        int p98 = 98;
+        int p99 = 99;
+        int p100 = 100;
+        int p101 = 101;
+        int p102 = 102;
+        int p103 = 103;
+        int p104 = 104;
+        int p105 = 105;
+        int p106 = 106;
+        int p107 = 107;
+        int p108 = 108;
+        int p109 = 109;
+        int p110 = 110;
+        int p111 = 111;
+        int p112 = 112;
+        int p113 = 113;
+        int p114 = 114;
+        int p115 = 115;
+        int p116 = 116;
+        int p117 = 117;
+        int p118 = 118;
+        int p119 = 119;
+        int p120 = 120;
+        int p121 = 121;
+        int p122 = 122;
+        int p123 = 123;
+        int p124 = 124;
+        int p125 = 125;
+        int p126 = 126;
+        int p127 = 127;
+        int p128 = 128;
+        int p129 = 129;
+        int p130 = 130;
+        int p131 = 131;
+        int p132 = 132;
+        int p133 = 133;
+        int p134 = 134;
+        int p135 = 135;
+        int p136 = 136;
+        int p137 = 137;
+        int p138 = 138;
+        int p139 = 139;
+        int p140 = 140;
+        int p141 = 141;
+        int p142 = 142;
+        int p143 = 143;
+        int p144 = 144;
+        int p145 = 145;
+        int p146 = 146;
+        int p147 = 147;
+        int p148 = 148;
+        int p149 = 149;
+        int p150 = 150;
+        int p151 = 151;
+        int p152 = 152;
+        int p153 = 153;
+        int p154 = 154;
+        int p155 = 155;
+        int p156 = 156;
+        int p157 = 157;
+        int p158 = 158;
+        int p159 = 159;
+        int p160 = 160;
+        int p161 = 161;
+        int p162 = 162;
+        int p163 = 163;
+        int p164 = 164;
+        int p165 = 165;
+        int p166 = 166;
+        int p167 = 167;
+        int p168 = 168;
+        int p169 = 169;
+        int p170 = 170;
+        int p171 = 171;
+        int p172 = 172;
+        int p173 = 173;
+        int p174 = 174;
+        int p175 = 175;
+        int p176 = 176;
+        int p177 = 177;
+        int p178 = 178;
+        int p179 = 179;
+        int p180 = 180;
+        int p181 = 181;
+        int p182 = 182;
+        int p183 = 183;
+        int p184 = 184;
+        int p185 = 185;
+        int p186 = 186;
+        int p187 = 187;
+        int p188 = 188;
+        int p189 = 189;
+        int p190 = 190;
+        int p191 = 191;
+        int p192 = 192;
+        int p193 = 193;
+        int p194 = 194;
+        int p195 = 195;
+        int p196 = 196;
+        int p197 = 197;
+        int p198 = 198;
+        int p199 = 199;
+        int p200 = 200;
+        int p201 = 201;
+        int p202 = 202;
+        int p203 = 203;
+        int p204 = 204;
+        int p205 = 205;
+        int p206 = 206;
+        int p207 = 207;
+        int p208 = 208;
+        int p209 = 209;
+        int p210 = 210;
+        int p211 = 211;
+        int p212 = 212;
+        int p213 = 213;
+        int p214 = 214;
+        int p215 = 215;
+        int p216 = 216;
+        int p217 = 217;
+        int p218 = 218;
+        int p219 = 219;
+        int p220 = 220;
+        int p221 = 221;
+        int p222 = 222;
+        int p223 = 223;
+        int p224 = 224;
+        int p225 = 225;
+        int p226 = 226;
+        int p227 = 227;
+        int p228 = 228;
+        int p229 = 229;
+        int p230 = 230;
+        int p231 = 231;
+        int p232 = 232;
+        int p233 = 233;
+        int p234 = 234;
+        int p235 = 235;
+        int p236 = 236;
+        int p237 = 237;
+        int p238 = 238;
+        int p239 = 239;
+        int p240 = 240;
+        int p241 = 241;
+        int p242 = 242;
+        int p243 = 243;
+        int p244 = 244;
+        int p245 = 245;
+        int p246 = 246;
+        int p247 = 247;
+        int p248 = 248;
+        int p249 = 249;
+        int p250 = 250;
+        int p251 = 251;
+        int p252 = 252;
+        int p253 = 253;
+        int p254 = 254;
+        int p255 = 255;
+        long before = 1L << 40;
+        int counter = 7;
+        long after = -(1L << 40);
+        counter -= 1000;
+        counter += 30000;
+        p255 -= 1000;
+        return before + " " + counter + " " + after + " " + p254 + " " + p255;
+    }
+
+    static String category2() {
+        int p0 = 0;
+        int p1 = 1;
+        int p2 = 2;
+        int p3 = 3;
+        int p4 = 4;
+        int p5 = 5;
+        int p6 = 6;
+        int p7 = 7;
+        int p8 = 8;
+        int p9 = 9;
+        int p10 = 10;
+        int p11 = 11;
+        int p12 = 12;
+        int p13 = 13;
+        int p14 = 14;
+        int p15 = 15;
+        int p16 = 16;
+        int p17 = 17;
+        int p18 = 18;
+        int p19 = 19;
+        int p20 = 20;
+        int p21 = 21;
+        int p22 = 22;
+        int p23 = 23;
+        int p24 = 24;
+        int p25 = 25;
+        int p26 = 26;
+        int p27 = 27;
+        int p28 = 28;
+        int p29 = 29;
+        int p30 = 30;
+        int p31 = 31;
+        int p32 = 32;
+        int p33 = 33;
+        int p34 = 34;
+        int p35 = 35;
+        int p36 = 36;
+        int p37 = 37;
+        int p38 = 38;
+        int p39 = 39;
+        int p40 = 40;
+        int p41 = 41;
+        int p42 = 42;
+        int p43 = 43;
+        int p44 = 44;
+        int p45 = 45;
+        int p46 = 46;
+        int p47 = 47;
+        int p48 = 48;
+        int p49 = 49;
+        int p50 = 50;
+        int p51 = 51;
+        int p52 = 52;
+        int p53 = 53;
+        int p54 = 54;
+        int p55 = 55;
+        int p56 = 56;
+        int p57 = 57;
+        int p58 = 58;
+        int p59 = 59;
+        int p60 = 60;
+        int p61 = 61;
+        int p62 = 62;
+        int p63 = 63;
+        int p64 = 64;
+        int p65 = 65;
+        int p66 = 66;
+        int p67 = 67;
+        int p68 = 68;
+        int p69 = 69;
+        int p70 = 70;
+        int p71 = 71;
+        int p72 = 72;
+        int p73 = 73;
+        int p74 = 74;
+        int p75 = 75;
+        int p76 = 76;
+        int p77 = 77;
+        int p78 = 78;
+        int p79 = 79;
+        int p80 = 80;
+        int p81 = 81;
+        int p82 = 82;
+        int p83 = 83;
+        int p84 = 84;
+        int p85 = 85;
+        int p86 = 86;
+        int p87 = 87;
+        int p88 = 88;
+        int p89 = 89;
+        int p90 = 90;
+        int p91 = 91;
+        int p92 = 92;
+        int p93 = 93;
+        int p94 = 94;
+        int p95 = 95;
+        int p96 = 96;
+        int p97 = 97;
+        int p98 = 98;
+        int p99 = 99;
+        int p100 = 100;
+        int p101 = 101;
+        int p102 = 102;
+        int p103 = 103;
+        int p104 = 104;
+        int p105 = 105;
+        int p106 = 106;
+        int p107 = 107;
+        int p108 = 108;
+        int p109 = 109;
+        int p110 = 110;
+        int p111 = 111;
+        int p112 = 112;
+        int p113 = 113;
+        int p114 = 114;
+        int p115 = 115;
+        int p116 = 116;
+        int p117 = 117;
+        int p118 = 118;
+        int p119 = 119;
+        int p120 = 120;
+        int p121 = 121;
+        int p122 = 122;
+        int p123 = 123;
+        int p124 = 124;
+        int p125 = 125;
+        int p126 = 126;
+        int p127 = 127;
+        int p128 = 128;
+        int p129 = 129;
+        int p130 = 130;
+        int p131 = 131;
+        int p132 = 132;
+        int p133 = 133;
+        int p134 = 134;
+        int p135 = 135;
+        int p136 = 136;
+        int p137 = 137;
+        int p138 = 138;
+        int p139 = 139;
+        int p140 = 140;
+        int p141 = 141;
+        int p142 = 142;
+        int p143 = 143;
+        int p144 = 144;
+        int p145 = 145;
+        int p146 = 146;
+        int p147 = 147;
+        int p148 = 148;
+        int p149 = 149;
+        int p150 = 150;
+        int p151 = 151;
+        int p152 = 152;
+        int p153 = 153;
+        int p154 = 154;
+        int p155 = 155;
+        int p156 = 156;
+        int p157 = 157;
+        int p158 = 158;
+        int p159 = 159;
+        int p160 = 160;
+        int p161 = 161;
+        int p162 = 162;
+        int p163 = 163;
+        int p164 = 164;
+        int p165 = 165;
+        int p166 = 166;
+        int p167 = 167;
+        int p168 = 168;
+        int p169 = 169;
+        int p170 = 170;
+        int p171 = 171;
+        int p172 = 172;
+        int p173 = 173;
+        int p174 = 174;
+        int p175 = 175;
+        int p176 = 176;
+        int p177 = 177;
+        int p178 = 178;
+        int p179 = 179;
+        int p180 = 180;
+        int p181 = 181;
+        int p182 = 182;
+        int p183 = 183;
+        int p184 = 184;
+        int p185 = 185;
+        int p186 = 186;
+        int p187 = 187;
+        int p188 = 188;
+        int p189 = 189;
+        int p190 = 190;
+        int p191 = 191;
+        int p192 = 192;
+        int p193 = 193;
+        int p194 = 194;
+        int p195 = 195;
+        int p196 = 196;
+        int p197 = 197;
+        int p198 = 198;
+        int p199 = 199;
+        int p200 = 200;
+        int p201 = 201;
+        int p202 = 202;
+        int p203 = 203;
+        int p204 = 204;
+        int p205 = 205;
+        int p206 = 206;
+        int p207 = 207;
+        int p208 = 208;
+        int p209 = 209;
+        int p210 = 210;
+        int p211 = 211;
+        int p212 = 212;
+        int p213 = 213;
+        int p214 = 214;
+        int p215 = 215;
+        int p216 = 216;
+        int p217 = 217;
+        int p218 = 218;
+        int p219 = 219;
+        int p220 = 220;
+        int p221 = 221;
+        int p222 = 222;
+        int p223 = 223;
+        int p224 = 224;
+        int p225 = 225;
+        int p226 = 226;
+        int p227 = 227;
+        int p228 = 228;
+        int p229 = 229;
+        int p230 = 230;
+        int p231 = 231;
+        int p232 = 232;
+        int p233 = 233;
+        int p234 = 234;
+        int p235 = 235;
+        int p236 = 236;
+        int p237 = 237;
+        int p238 = 238;
+        int p239 = 239;
+        int p240 = 240;
+        int p241 = 241;
+        int p242 = 242;
+        int p243 = 243;
+        int p244 = 244;
+        int p245 = 245;
+        int p246 = 246;
+        int p247 = 247;
+        int p248 = 248;
+        int p249 = 249;
+        int p250 = 250;
+        int p251 = 251;
+        int p252 = 252;
+        int p253 = 253;
+        int p254 = 254;
+        int p255 = 255;
+        double half = 0.5;
+        long big = 1L << 50;
+        float third = 1.0f / 3;
+        String name = "wide";
+        half *= p255;
+        big += p1;
+        third += p2;
+        name += p3;
+        return half + " " + big + " " + third + " " + name;
+    }
+}
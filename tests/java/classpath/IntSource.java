@@ -0,0 +1,3 @@
+public interface IntSource {
+    int get();
+}
@@ -0,0 +1,64 @@
+import jdk.internal.org.objectweb.asm.*;
+import java.nio.file.*;
+
+// javac has no syntax for dynamic constants, so this writes `Condy.class` with ASM:
+//   javac --add-exports java.base/jdk.internal.org.objectweb.asm=ALL-UNNAMED GenerateCondy.java
+//   java --add-exports java.base/jdk.internal.org.objectweb.asm=ALL-UNNAMED GenerateCondy
+public class GenerateCondy implements Opcodes {
+    static final Handle BOOTSTRAPS_GET_STATIC_FINAL = new Handle(H_INVOKESTATIC,
+        "java/lang/invoke/ConstantBootstraps", "getStaticFinal",
+        "(Ljava/lang/invoke/MethodHandles$Lookup;Ljava/lang/String;Ljava/lang/Class;Ljava/lang/Class;)Ljava/lang/Object;", false);
+    static final Handle BOOTSTRAPS_NULL = new Handle(H_INVOKESTATIC,
+        "java/lang/invoke/ConstantBootstraps", "nullConstant",
+        "(Ljava/lang/invoke/MethodHandles$Lookup;Ljava/lang/String;Ljava/lang/Class;)Ljava/lang/Object;", false);
+
+    public static void main(String[] args) throws Exception {
+        ClassWriter cw = new ClassWriter(ClassWriter.COMPUTE_MAXS | ClassWriter.COMPUTE_FRAMES);
+        cw.visit(V17, ACC_PUBLIC | ACC_SUPER, "Condy", null, "java/lang/Object", null);
+        cw.visitSource("Condy.java", null);
+
+        // static int maxInt() { return ldc Integer.MAX_VALUE via getStaticFinal; }
+        MethodVisitor mv = cw.visitMethod(ACC_PUBLIC | ACC_STATIC, "maxInt", "()I", null, null);
+        mv.visitCode();
+        mv.visitLdcInsn(new ConstantDynamic("MAX_VALUE", "I", BOOTSTRAPS_GET_STATIC_FINAL, Type.getType("Ljava/lang/Integer;")));
+        mv.visitInsn(IRETURN);
+        mv.visitMaxs(0, 0);
+        mv.visitEnd();
+
+        // static long minLong() { return ldc2_w Long.MIN_VALUE via getStaticFinal; }
+        mv = cw.visitMethod(ACC_PUBLIC | ACC_STATIC, "minLong", "()J", null, null);
+        mv.visitCode();
+        mv.visitLdcInsn(new ConstantDynamic("MIN_VALUE", "J", BOOTSTRAPS_GET_STATIC_FINAL, Type.getType("Ljava/lang/Long;")));
+        mv.visitInsn(LRETURN);
+        mv.visitMaxs(0, 0);
+        mv.visitEnd();
+
+        // static boolean isNull() { return ldc nullConstant == null; }
+        mv = cw.visitMethod(ACC_PUBLIC | ACC_STATIC, "isNull", "()Z", null, null);
+        mv.visitCode();
+        mv.visitLdcInsn(new ConstantDynamic("nothing", "Ljava/lang/Object;", BOOTSTRAPS_NULL));
+        Label nonNull = new Label();
+        mv.visitJumpInsn(IFNONNULL, nonNull);
+        mv.visitInsn(ICONST_1);
+        mv.visitInsn(IRETURN);
+        mv.visitLabel(nonNull);
+        mv.visitInsn(ICONST_0);
+        mv.visitInsn(IRETURN);
+        mv.visitMaxs(0, 0);
+        mv.visitEnd();
+
+        // static int sum() { return MAX_VALUE + MAX_VALUE, loading the same dynamic constant twice; }
+        mv = cw.visitMethod(ACC_PUBLIC | ACC_STATIC, "sum", "()I", null, null);
+        mv.visitCode();
+        ConstantDynamic size = new ConstantDynamic("MAX_VALUE", "I", BOOTSTRAPS_GET_STATIC_FINAL, Type.getType("Ljava/lang/Integer;"));
+        mv.visitLdcInsn(size);
+        mv.visitLdcInsn(size);
+        mv.visitInsn(IADD);
+        mv.visitInsn(IRETURN);
+        mv.visitMaxs(0, 0);
+        mv.visitEnd();
+
+        cw.visitEnd();
+        Files.write(Path.of("Condy.class"), cw.toByteArray());
+    }
+}
@@ -0,0 +1,57 @@
+import jdk.internal.org.objectweb.asm.*;
+import java.nio.file.*;
+
+// javac only uses the category-2 forms of the `dup_x` instructions in a few spots, so this writes
+// `Dups.class` with ASM:
+//   javac --add-exports java.base/jdk.internal.org.objectweb.asm=ALL-UNNAMED GenerateDups.java
+//   java --add-exports java.base/jdk.internal.org.objectweb.asm=ALL-UNNAMED GenerateDups
+public class GenerateDups implements Opcodes {
+    public static void main(String[] args) throws Exception {
+        ClassWriter cw = new ClassWriter(ClassWriter.COMPUTE_MAXS | ClassWriter.COMPUTE_FRAMES);
+        cw.visit(V17, ACC_PUBLIC | ACC_SUPER, "Dups", null, "java/lang/Object", null);
+        cw.visitSource("Dups.java", null);
+
+        // 100L, 3 -> dup_x2 -> 3, 100L, 3 -> 3 - (100 - 3) = -94
+        MethodVisitor mv = cw.visitMethod(ACC_PUBLIC | ACC_STATIC, "dupX2", "()I", null, null);
+        mv.visitCode();
+        mv.visitLdcInsn(100L);
+        mv.visitInsn(ICONST_3);
+        mv.visitInsn(DUP_X2);
+        mv.visitInsn(I2L);
+        mv.visitInsn(LSUB);
+        mv.visitInsn(L2I);
+        mv.visitInsn(ISUB);
+        mv.visitInsn(IRETURN);
+        mv.visitMaxs(0, 0);
+        mv.visitEnd();
+
+        // 3, 100L -> dup2_x1 -> 100L, 3, 100L -> 100 - (3 - 100) = 197
+        mv = cw.visitMethod(ACC_PUBLIC | ACC_STATIC, "dup2X1", "()J", null, null);
+        mv.visitCode();
+        mv.visitInsn(ICONST_3);
+        mv.visitLdcInsn(100L);
+        mv.visitInsn(DUP2_X1);
+        mv.visitInsn(L2I);
+        mv.visitInsn(ISUB);
+        mv.visitInsn(I2L);
+        mv.visitInsn(LSUB);
+        mv.visitInsn(LRETURN);
+        mv.visitMaxs(0, 0);
+        mv.visitEnd();
+
+        // 10.0, 0.5 -> dup2_x2 -> 0.5, 10.0, 0.5 -> 0.5 / (10.0 - 0.5) = 0.05263157894736842
+        mv = cw.visitMethod(ACC_PUBLIC | ACC_STATIC, "dup2X2", "()D", null, null);
+        mv.visitCode();
+        mv.visitLdcInsn(10.0);
+        mv.visitLdcInsn(0.5);
+        mv.visitInsn(DUP2_X2);
+        mv.visitInsn(DSUB);
+        mv.visitInsn(DDIV);
+        mv.visitInsn(DRETURN);
+        mv.visitMaxs(0, 0);
+        mv.visitEnd();
+
+        cw.visitEnd();
+        Files.write(Path.of("Dups.class"), cw.toByteArray());
+    }
+}
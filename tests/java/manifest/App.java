@@ -0,0 +1,5 @@
+public class App {
+    static int total() {
+        return First.value() + Second.value();
+    }
+}
@@ -0,0 +1,5 @@
+public class First {
+    static int value() {
+        return 1;
+    }
+}
@@ -0,0 +1,5 @@
+public class Second {
+    static int value() {
+        return 2;
+    }
+}
@@ -0,0 +1,4 @@
+module greeter {
+    requires java.base;
+    exports com.example;
+}
@@ -0,0 +1,10 @@
+// `release.jar` has this class as its base entry and `versions/11/Greeting.java` under
+// `META-INF/versions/11/`. To rebuild it:
+//   javac --release 8 -d base Greeting.java
+//   javac --release 11 -d 11 versions/11/Greeting.java
+//   jar --create --file release.jar -C base . --release 11 -C 11 .
+public class Greeting {
+    static String greet() {
+        return "base";
+    }
+}
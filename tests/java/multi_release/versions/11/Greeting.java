@@ -0,0 +1,5 @@
+public class Greeting {
+    static String greet() {
+        return "versioned";
+    }
+}
@@ -0,0 +1,41 @@
+import jdk.internal.org.objectweb.asm.*;
+import java.nio.file.*;
+
+// javac always pairs `monitorexit` with `monitorenter`, so this writes `Unbalanced.class` with ASM:
+//   javac --add-exports java.base/jdk.internal.org.objectweb.asm=ALL-UNNAMED GenerateUnbalanced.java
+//   java --add-exports java.base/jdk.internal.org.objectweb.asm=ALL-UNNAMED GenerateUnbalanced
+public class GenerateUnbalanced implements Opcodes {
+    public static void main(String[] args) throws Exception {
+        ClassWriter cw = new ClassWriter(ClassWriter.COMPUTE_MAXS | ClassWriter.COMPUTE_FRAMES);
+        cw.visit(V17, ACC_PUBLIC | ACC_SUPER, "Unbalanced", null, "java/lang/Object", null);
+        cw.visitSource("Unbalanced.java", null);
+
+        // static String exitUnheld() {
+        //     try { monitorexit(new Object()); return "exited"; }
+        //     catch (IllegalMonitorStateException e) { return "caught"; }
+        // }
+        MethodVisitor mv = cw.visitMethod(ACC_PUBLIC | ACC_STATIC, "exitUnheld", "()Ljava/lang/String;", null, null);
+        mv.visitCode();
+        Label start = new Label();
+        Label end = new Label();
+        Label handler = new Label();
+        mv.visitTryCatchBlock(start, end, handler, "java/lang/IllegalMonitorStateException");
+        mv.visitLabel(start);
+        mv.visitTypeInsn(NEW, "java/lang/Object");
+        mv.visitInsn(DUP);
+        mv.visitMethodInsn(INVOKESPECIAL, "java/lang/Object", "<init>", "()V", false);
+        mv.visitInsn(MONITOREXIT);
+        mv.visitLabel(end);
+        mv.visitLdcInsn("exited");
+        mv.visitInsn(ARETURN);
+        mv.visitLabel(handler);
+        mv.visitInsn(POP);
+        mv.visitLdcInsn("caught");
+        mv.visitInsn(ARETURN);
+        mv.visitMaxs(0, 0);
+        mv.visitEnd();
+
+        cw.visitEnd();
+        Files.write(Path.of("Unbalanced.class"), cw.toByteArray());
+    }
+}
@@ -0,0 +1,12 @@
+mod common;
+
+const JOINING: &[u8] = include_bytes!("java/Joining.class");
+
+#[test]
+fn joining_writes_null_and_takes_any_char_sequence() {
+    let vm = common::vm(&[JOINING]);
+    assert_eq!(
+        common::eval(&vm, &["Joining.nulls()", "Joining.builderDelimiter()"]),
+        ["\"a, null, b\"", "\"a-null-b\""]
+    );
+}
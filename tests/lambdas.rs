@@ -0,0 +1,53 @@
+mod common;
+
+const CAPTURES: &[u8] = include_bytes!("java/Captures.class");
+const INT_LAMBDAS: &[u8] = include_bytes!("java/IntLambdas.class");
+const METHOD_REFS: &[u8] = include_bytes!("java/MethodRefs.class");
+
+#[test]
+fn lambdas_bind_to_primitive_interfaces() {
+    let vm = common::vm(&[INT_LAMBDAS]);
+    assert_eq!(
+        common::eval(
+            &vm,
+            &[
+                "IntLambdas.positive(5)",
+                "IntLambdas.positive(0)",
+                "IntLambdas.positive(-3)",
+                "IntLambdas.squareSum(4)",
+            ]
+        ),
+        ["true", "false", "false", "20"]
+    );
+}
+
+#[test]
+fn captured_wide_values_keep_their_order() {
+    let vm = common::vm(&[CAPTURES]);
+    assert_eq!(
+        common::eval(
+            &vm,
+            &["Captures.captureLong(2)", "Captures.captureDouble(10)"]
+        ),
+        ["10003", "130"]
+    );
+}
+
+#[test]
+fn lambdas_capture_this() {
+    let vm = common::vm(&[CAPTURES]);
+    // 5 + 10 before `base` changes, then 5 + 20 after
+    assert_eq!(common::eval(&vm, &["Captures.captureThis(5)"]), ["1525"]);
+}
+
+#[test]
+fn unbound_method_references_take_their_receiver_as_an_argument() {
+    let vm = common::vm(&[METHOD_REFS]);
+    assert_eq!(
+        common::eval(
+            &vm,
+            &["MethodRefs.length(\"hello\")", "MethodRefs.nullLength()"]
+        ),
+        ["5", "\"caught\""]
+    );
+}
@@ -0,0 +1,73 @@
+mod common;
+
+use std::sync::Arc;
+
+use javarust::{
+    class::Method,
+    virtual_machine::{StackFrame, Thread, DEFAULT_MAX_DEPTH},
+};
+use jvmrs_lib::method;
+
+const LEAKS: &[u8] = include_bytes!("java/Leaks.class");
+
+#[test]
+fn dropped_collections_release_their_elements() {
+    let vm = common::vm(&[LEAKS]);
+    for fill in ["fillList", "fillLinkedList", "fillMap", "fillSet"] {
+        let fill = |times: u32| {
+            vm.invoke_static("Leaks", fill, &method!((int) -> int), &[times], false)
+                .unwrap()
+        };
+        // the first call initializes whatever it needs to
+        assert_eq!(fill(10), [10]);
+        let live = vm.heap.lock().unwrap().live_count();
+        assert_eq!(fill(1000), [1000]);
+        assert_eq!(vm.heap.lock().unwrap().live_count(), live);
+    }
+}
+
+#[test]
+fn concat_in_a_loop_keeps_the_heap_flat() {
+    let vm = common::vm(&[LEAKS]);
+    let descriptor = method!((int) -> int);
+    // the first call initializes whatever it needs to
+    assert_eq!(
+        vm.invoke_static("Leaks", "concat", &descriptor, &[10], false)
+            .unwrap(),
+        [10]
+    );
+    let live = vm.heap.lock().unwrap().live_count();
+    // step through the call by hand, so the heap can be checked while the loop is running
+    let (class, method) = vm
+        .method_area
+        .search("Leaks", "concat", &descriptor)
+        .unwrap();
+    let mut caller = StackFrame::from_method(Arc::new(Method::default()), class.clone());
+    caller.operand_stack.push(0);
+    let mut thread = Thread {
+        pc_register: 0,
+        stack: vec![caller],
+        stackframe: StackFrame::from_method(method, class),
+        method_area: vm.method_area.clone(),
+        class_area: vm.class_area.clone(),
+        heap: vm.heap.clone(),
+        trap_overflow: false,
+        verify: false,
+        deterministic_hash: false,
+        trace: false,
+        max_depth: DEFAULT_MAX_DEPTH,
+    };
+    thread.stackframe.locals[0] = 1000;
+    let mut peak = live;
+    while !thread.stack.is_empty() {
+        thread.tick(false).unwrap();
+        peak = peak.max(vm.heap.lock().unwrap().live_count());
+    }
+    assert_eq!(thread.stackframe.operand_stack, [1000]);
+    // each iteration's string replaces the last one instead of piling up until the method returns
+    assert!(
+        peak < live + 10,
+        "{peak} objects were live at once, from {live}"
+    );
+    assert_eq!(vm.heap.lock().unwrap().live_count(), live);
+}
@@ -0,0 +1,38 @@
+mod common;
+
+const LISTS: &[u8] = include_bytes!("java/Lists.class");
+
+#[test]
+fn linked_list_is_a_list() {
+    let vm = common::vm(&[LISTS]);
+    assert_eq!(
+        common::eval(&vm, &["Lists.walk()", "Lists.second()", "Lists.show()"]),
+        ["\"abc\"", "\"b\"", "\"[a, b, c]\""]
+    );
+}
+
+#[test]
+fn collections_helpers_take_linked_lists() {
+    let vm = common::vm(&[LISTS]);
+    assert_eq!(
+        common::eval(&vm, &["Lists.frequency()", "Lists.shuffled()"]),
+        ["2", "31"]
+    );
+}
+
+#[test]
+fn seeded_shuffles_repeat() {
+    let vm = common::vm(&[LISTS]);
+    let [first, second, other] = &common::eval(
+        &vm,
+        &[
+            "Lists.seeded(7)",
+            "Lists.seeded(7)",
+            "Lists.sameSeedSameOrder()",
+        ],
+    )[..] else {
+        panic!("expected three results");
+    };
+    assert_eq!(first, second);
+    assert_eq!(other, "true");
+}
@@ -0,0 +1,30 @@
+use std::process::{Command, Output};
+
+fn run(main_class: &str) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_javarust"))
+        .args(["--main-class", main_class])
+        .args(
+            ["MainOne", "MainTwo", "MainThree"]
+                .map(|class| format!("{}/tests/java/{class}.class", env!("CARGO_MANIFEST_DIR"))),
+        )
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn main_class_picks_the_entry_point() {
+    let output = run("MainTwo");
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "main Two\n");
+}
+
+#[test]
+fn missing_main_class_is_an_error() {
+    let output = run("MainFour");
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "Error: main class `MainFour` was not loaded\n"
+    );
+}
@@ -0,0 +1,80 @@
+mod common;
+
+use javarust::JavaVm;
+
+const RANDOMS: &[u8] = include_bytes!("java/Randoms.class");
+const MATHS: &[u8] = include_bytes!("java/Maths.class");
+
+/// the `java/util/Random` that `Math.random` keeps in a static field
+fn math_random(vm: &JavaVm) -> u32 {
+    let math = vm.class_area.search("java/lang/Math").unwrap();
+    let (_, slot) = math
+        .statics
+        .iter()
+        .find(|(field, _)| &*field.name == "$random")
+        .unwrap();
+    let random = math.static_data.lock().unwrap()[*slot];
+    random
+}
+
+#[test]
+fn math_random_lives_in_the_vm() {
+    let mut vm = common::vm(&[RANDOMS]);
+    assert_eq!(math_random(&vm), 0);
+    assert_eq!(
+        common::eval(&vm, &["Randoms.inRange()", "Randoms.advances()"]),
+        ["true", "true"]
+    );
+    assert_ne!(math_random(&vm), 0);
+    vm.reset(false).unwrap();
+    assert_eq!(math_random(&vm), 0);
+}
+
+#[test]
+fn gaussians_are_standard_normal() {
+    let vm = common::vm(&[RANDOMS]);
+    let results = common::eval(
+        &vm,
+        &[
+            "Randoms.gaussianMean(10000)",
+            "Randoms.gaussianVariance(10000)",
+        ],
+    );
+    let [mean, variance] = &results[..] else {
+        panic!("expected two results, got {results:?}");
+    };
+    // each bound is at least five standard errors for 10000 samples
+    let mean: f64 = mean.parse().unwrap();
+    assert!(mean.abs() < 0.05, "mean {mean}");
+    let variance: f64 = variance.parse().unwrap();
+    assert!((variance - 1.0).abs() < 0.1, "variance {variance}");
+}
+
+#[test]
+fn hypot_and_log10_match_the_jvm() {
+    let vm = common::vm(&[MATHS]);
+    assert_eq!(
+        common::eval(
+            &vm,
+            &[
+                "Maths.hypot(3, 4)",
+                "Maths.hypot(-5, 12)",
+                // squaring either side first would overflow
+                "Maths.hypot(1e200, 1e200)",
+                "Maths.log10(1000)",
+                "Maths.log10(1e-5)",
+                "Maths.log10(0)",
+                "Maths.log10(-1)",
+            ]
+        ),
+        [
+            "5.0",
+            "13.0",
+            "1.414213562373095E200",
+            "3.0",
+            "-5.0",
+            "-Infinity",
+            "NaN"
+        ]
+    );
+}
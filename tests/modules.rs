@@ -0,0 +1,28 @@
+use javarust::{class_loader, data::WorkingMethodArea, virtual_machine::error};
+
+const MODULE_INFO: &[u8] = include_bytes!("java/modules/module-info.class");
+/// `module-info.class` with its `ACC_MODULE` flag cleared, but its module constants left behind
+const NOT_A_MODULE: &[u8] = include_bytes!("java/modules/NotAModule.class");
+
+#[test]
+fn loads_module_info() {
+    let class = class_loader::load_class(
+        &mut WorkingMethodArea::default(),
+        &mut MODULE_INFO.iter().copied(),
+        false,
+    )
+    .unwrap();
+    assert_eq!(&*class.this, "module-info");
+    assert!(class.methods.is_empty());
+}
+
+#[test]
+fn rejects_module_constants_outside_a_module() {
+    let err = class_loader::load_class(
+        &mut WorkingMethodArea::default(),
+        &mut NOT_A_MODULE.iter().copied(),
+        false,
+    )
+    .unwrap_err();
+    assert!(matches!(err, error::Error::ClassFormat(_)), "{err:?}");
+}
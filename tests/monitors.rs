@@ -0,0 +1,43 @@
+mod common;
+
+const MONITORS: &[u8] = include_bytes!("java/Monitors.class");
+// written by `java/unbalanced/GenerateUnbalanced.java`, since javac never emits a lone `monitorexit`
+const UNBALANCED: &[u8] = include_bytes!("java/unbalanced/Unbalanced.class");
+
+#[test]
+fn synchronized_methods_release_their_monitors() {
+    let vm = common::vm(&[MONITORS]);
+    assert_eq!(
+        common::eval(
+            &vm,
+            &[
+                "Monitors.completed()",
+                "Monitors.thrown()",
+                "Monitors.staticCompleted()"
+            ]
+        ),
+        [
+            "\"1 false\"",
+            "\"failed while holding the lock false\"",
+            "\"true false\""
+        ]
+    );
+}
+
+#[test]
+fn synchronized_blocks_release_their_monitors() {
+    let vm = common::vm(&[MONITORS]);
+    assert_eq!(
+        common::eval(&vm, &["Monitors.block()", "Monitors.blockThrown()"]),
+        ["\"true false\"", "\"thrown from the block false\""]
+    );
+}
+
+#[test]
+fn exiting_an_unheld_monitor_throws() {
+    let vm = common::vm(&[UNBALANCED]);
+    assert_eq!(
+        common::eval(&vm, &["Unbalanced.exitUnheld()"]),
+        ["\"caught\""]
+    );
+}
@@ -0,0 +1,52 @@
+mod common;
+
+use std::sync::Arc;
+
+use javarust::{
+    class::{
+        code::{Code, NativeSingleMethod, Staged},
+        Method,
+    },
+    virtual_machine::{error::Error, StackFrame, Thread, DEFAULT_MAX_DEPTH},
+};
+
+// the check only runs in debug builds
+#[cfg(debug_assertions)]
+#[test]
+fn natives_fail_outside_their_stages() {
+    let vm = common::vm(&[]);
+    // stage 0 should move on to stage 1, but goes to stage 2 instead
+    let broken = Staged(
+        2,
+        NativeSingleMethod(|thread: &mut Thread, [_]: [u32; 1], _| {
+            thread.pc_register = 2;
+            Ok(None)
+        }),
+    );
+    let method = Method {
+        max_locals: 1,
+        name: "broken".into(),
+        code: Code::native(broken),
+        ..Default::default()
+    };
+    let class = vm.class_area.search("java/lang/Object").unwrap();
+    let mut thread = Thread {
+        pc_register: 0,
+        stack: Vec::new(),
+        stackframe: StackFrame::from_method(Arc::new(method), class),
+        method_area: vm.method_area.clone(),
+        class_area: vm.class_area.clone(),
+        heap: vm.heap.clone(),
+        trap_overflow: false,
+        verify: false,
+        deterministic_hash: false,
+        trace: false,
+        max_depth: DEFAULT_MAX_DEPTH,
+    };
+    thread.tick(false).unwrap();
+    assert!(matches!(
+        thread.tick(false),
+        Err(Error::Internal(message))
+            if message == "Native method java/lang/Object.broken reached stage 2, but only has stages 0 to 1"
+    ));
+}
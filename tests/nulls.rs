@@ -0,0 +1,58 @@
+mod common;
+
+use javarust::VmOptions;
+use jvmrs_lib::method;
+
+const NULLS: &[u8] = include_bytes!("java/Nulls.class");
+const NULL_SAFE: &[u8] = include_bytes!("java/NullSafe.class");
+
+#[test]
+fn returning_null_leaves_the_heap_alone() {
+    let vm = common::vm(&[NULLS]);
+    let count_nulls = |times: u32| {
+        vm.invoke_static(
+            "Nulls",
+            "countNulls",
+            &method!((int) -> int),
+            &[times],
+            false,
+        )
+        .unwrap()
+    };
+    // the first call initializes whatever it needs to
+    assert_eq!(count_nulls(10), [15]);
+    let live = vm.heap.lock().unwrap().live_count();
+    assert_eq!(count_nulls(1000), [1500]);
+    assert_eq!(vm.heap.lock().unwrap().live_count(), live);
+}
+
+#[test]
+fn disabling_gc_keeps_every_object() {
+    let vm = common::vm_with_options(
+        &[NULLS],
+        VmOptions {
+            disable_gc: true,
+            ..Default::default()
+        },
+    );
+    let live = vm.heap.lock().unwrap().live_count();
+    let result = vm.invoke_static(
+        "Nulls",
+        "countNulls",
+        &method!((int) -> int),
+        &[1000],
+        false,
+    );
+    assert_eq!(result.unwrap(), [1500]);
+    // every other iteration made an object that nothing refers to anymore
+    assert!(vm.heap.lock().unwrap().live_count() >= live + 500);
+}
+
+#[test]
+fn objects_to_string_handles_null() {
+    let vm = common::vm(&[NULL_SAFE]);
+    assert_eq!(
+        common::eval(&vm, &["NullSafe.toStrings()"]),
+        ["\"null d 5 x\""]
+    );
+}
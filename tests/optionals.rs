@@ -0,0 +1,48 @@
+mod common;
+
+const OPTIONALS: &[u8] = include_bytes!("java/Optionals.class");
+
+#[test]
+fn flat_map_and_or_unwrap_what_they_get() {
+    let vm = common::vm(&[OPTIONALS]);
+    assert_eq!(
+        common::eval(
+            &vm,
+            &[
+                r#"Optionals.flatMapped("abcd")"#,
+                r#"Optionals.flatMapped("abc")"#,
+                r#"Optionals.flatMappedPresent("abcd")"#,
+                r#"Optionals.flatMappedPresent("abc")"#,
+                r#"Optionals.orSupplied("given")"#,
+                "Optionals.orSupplied(null)",
+            ]
+        ),
+        [
+            "\"ab\"",
+            "\"odd\"",
+            "true",
+            "false",
+            "\"given\"",
+            "\"fallback\""
+        ]
+    );
+}
+
+#[test]
+fn map_get_and_or_else_throw_handle_empty_optionals() {
+    let vm = common::vm(&[OPTIONALS]);
+    assert_eq!(
+        common::eval(
+            &vm,
+            &[
+                r#"Optionals.mappedLength("abcd")"#,
+                "Optionals.mappedLength(null)",
+                r#"Optionals.mappedToNullPresent("x")"#,
+                r#"Optionals.got("here")"#,
+                "Optionals.emptyGet()",
+                "Optionals.emptyOrElseThrow()",
+            ]
+        ),
+        ["4", "-1", "false", "\"here\"", "\"thrown\"", "\"thrown\""]
+    );
+}
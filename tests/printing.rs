@@ -0,0 +1,37 @@
+use std::process::{Command, Output};
+
+fn run(class: &str, flags: &[&str]) -> Output {
+    let output = Command::new(env!("CARGO_BIN_EXE_javarust"))
+        .args(flags)
+        .arg(format!(
+            "{}/tests/java/{class}.class",
+            env!("CARGO_MANIFEST_DIR")
+        ))
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    output
+}
+
+#[test]
+fn print_leaves_off_the_newline() {
+    let output = run("Printing", &[]);
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "text 1 2 2.5 0.5 true object\nno newline at the end"
+    );
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "to stderr\nwithout a newline"
+    );
+}
+
+#[test]
+fn print_and_println_come_out_in_order() {
+    let expected = "0: line 0\n[0]\n1: line 1\n[1]\n2: line 2\n[4]\ndone";
+    let flags: [&[&str]; 2] = [&[], &["--line-buffered"]];
+    for flags in flags {
+        let output = run("Interleave", flags);
+        assert_eq!(String::from_utf8(output.stdout).unwrap(), expected);
+    }
+}
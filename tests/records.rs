@@ -0,0 +1,27 @@
+mod common;
+
+const RECORDS: &[u8] = include_bytes!("java/Records.class");
+const POINT: &[u8] = include_bytes!("java/Records$Point.class");
+const NAMED: &[u8] = include_bytes!("java/Records$Named.class");
+
+#[test]
+fn records_get_accessors_to_string_and_equals() {
+    let vm = common::vm(&[RECORDS, POINT, NAMED]);
+    assert_eq!(
+        common::eval(
+            &vm,
+            &[
+                "Records.sum()",
+                "Records.show()",
+                "Records.equality()",
+                "Records.equalHashes()",
+            ]
+        ),
+        [
+            "7",
+            "\"Point[x=3, y=-4] Named[name=first, id=5] Named[name=null, id=0]\"",
+            "\"true false false false true\"",
+            "true"
+        ]
+    );
+}
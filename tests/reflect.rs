@@ -0,0 +1,40 @@
+mod common;
+
+use std::sync::Arc;
+
+use jvmrs_lib::method;
+
+const THROWS: &[u8] = include_bytes!("java/Throws.class");
+const PRIMITIVE_CLASSES: &[u8] = include_bytes!("java/PrimitiveClasses.class");
+
+#[test]
+fn methods_keep_their_declared_exceptions() {
+    let vm = common::vm(&[THROWS]);
+    // these are what `Method.getExceptionTypes` turns into `Class` objects
+    let (_, read) = vm
+        .method_area
+        .search("Throws", "read", &method!(() -> void))
+        .unwrap();
+    assert_eq!(read.exceptions, [Arc::<str>::from("java/io/IOException")]);
+    let (_, quiet) = vm
+        .method_area
+        .search("Throws", "quiet", &method!(() -> void))
+        .unwrap();
+    assert!(quiet.exceptions.is_empty());
+}
+
+#[test]
+fn primitive_types_have_their_own_classes() {
+    let vm = common::vm(&[PRIMITIVE_CLASSES]);
+    assert_eq!(
+        common::eval(
+            &vm,
+            &[
+                "PrimitiveClasses.names()",
+                "PrimitiveClasses.distinctFromBoxes()",
+                "PrimitiveClasses.sameAsType()",
+            ]
+        ),
+        ["\"int void double java.lang.Integer\"", "true", "true"]
+    );
+}
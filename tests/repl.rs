@@ -0,0 +1,30 @@
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+#[test]
+fn repl_evaluates_each_line() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_javarust"))
+        .arg("--repl")
+        .arg(format!(
+            "{}/tests/java/Arith.class",
+            env!("CARGO_MANIFEST_DIR")
+        ))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"Arith.addTwice(1, 2)\nArith.addTwice(1)\n\nArith.addTwice(-4, 1);\n/exit\nArith.addTwice(0, 0)\n")
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "> 6\n> Error: No static method Arith.addTwice takes those arguments\n> > -6\n> "
+    );
+}
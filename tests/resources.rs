@@ -0,0 +1,32 @@
+mod common;
+
+use std::path::PathBuf;
+
+use javarust::virtual_machine::{set_resource_classpath, set_resource_root};
+
+const RESOURCES: &[u8] = include_bytes!("java/Resources.class");
+
+#[test]
+fn resources_come_from_the_root_then_the_classpath() {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/java/resources");
+    set_resource_root(dir.join("root"));
+    set_resource_classpath(vec![dir.join("bundle.jar")]);
+    let vm = common::vm(&[RESOURCES]);
+    assert_eq!(
+        common::eval(
+            &vm,
+            &[
+                "Resources.read(\"root.txt\")",
+                "Resources.read(\"/greeting.txt\")",
+                "Resources.read(\"../secret.txt\")",
+                "Resources.read(\"missing.txt\")",
+            ]
+        ),
+        [
+            "\"from the root\"",
+            "\"from a jar\"",
+            "\"null\"",
+            "\"null\""
+        ]
+    );
+}
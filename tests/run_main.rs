@@ -0,0 +1,36 @@
+mod common;
+
+use std::process::Command;
+
+use javarust::virtual_machine::error::Error;
+
+const INVOKE: &[u8] = include_bytes!("java/Invoke.class");
+
+#[test]
+fn missing_main_is_a_linkage_error() {
+    let vm = common::vm(&[INVOKE]);
+    assert!(matches!(
+        vm.run_main("Invoke", Vec::new(), false),
+        Err(Error::LinkageError(_))
+    ));
+    assert!(matches!(
+        vm.run_main("NotAClass", Vec::new(), false),
+        Err(Error::LinkageError(_))
+    ));
+}
+
+#[test]
+fn falls_back_to_a_main_without_args() {
+    let output = Command::new(env!("CARGO_BIN_EXE_javarust"))
+        .arg(format!(
+            "{}/tests/java/NoArgMain.class",
+            env!("CARGO_MANIFEST_DIR")
+        ))
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "main without args\n"
+    );
+}
@@ -0,0 +1,46 @@
+use std::process::{Command, Output};
+
+fn run(flags: &[&str], classes: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_javarust"))
+        .args(flags)
+        .args(
+            classes
+                .iter()
+                .map(|class| format!("{}/tests/java/{class}.class", env!("CARGO_MANIFEST_DIR"))),
+        )
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn oversized_stack_is_an_error() {
+    let output = run(&["--stack-size", &usize::MAX.to_string()], &["Arith"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("is too many megabytes"));
+}
+
+// an exabyte of stack fits in a 64-bit `usize`, but no system can give it to a thread
+#[cfg(target_pointer_width = "64")]
+#[test]
+fn unspawnable_stack_is_an_error() {
+    let output = run(&["--stack-size", &(1u64 << 40).to_string()], &["Arith"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("Couldn't spawn the main thread"),
+        "{stderr}"
+    );
+    assert!(!stderr.contains("panicked"), "{stderr}");
+}
+
+#[test]
+fn larger_stack_fits_deeper_structures() {
+    // freeing the 300000-link chain `Deep` builds recurses once per link on the host stack
+    let flags = ["--max-depth", "400000"];
+    let classes = ["Deep", "Deep$Link"];
+    assert!(!run(&flags, &classes).status.success());
+    let output = run(&[&flags[..], &["--stack-size", "2048"]].concat(), &classes);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "done\n");
+}
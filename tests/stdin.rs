@@ -0,0 +1,22 @@
+use std::{fs, process::Command};
+
+#[test]
+fn stdin_file_feeds_the_scanner() {
+    let input = std::env::temp_dir().join(format!("javarust-stdin-{}.txt", std::process::id()));
+    fs::write(&input, "first line\nsecond line\n").unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_javarust"))
+        .arg("--stdin")
+        .arg(&input)
+        .arg(format!(
+            "{}/tests/java/Echo.class",
+            env!("CARGO_MANIFEST_DIR")
+        ))
+        .output()
+        .unwrap();
+    fs::remove_file(&input).unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "echo first line\necho second line\n"
+    );
+}
@@ -0,0 +1,86 @@
+mod common;
+
+const STRINGS: &[u8] = include_bytes!("java/Strings.class");
+
+#[test]
+fn split_on_a_delimiter() {
+    let vm = common::vm(&[STRINGS]);
+    assert_eq!(
+        common::eval(
+            &vm,
+            &[
+                r#"Strings.splitCount("a,b,c")"#,
+                r#"Strings.splitPieces("a,b,c")"#,
+                r#"Strings.splitPieces("a,,b,,")"#,
+                r#"Strings.splitCount("")"#,
+                r#"Strings.splitPieces("abc")"#,
+            ]
+        ),
+        ["3", "\"[a][b][c]\"", "\"[a][][b]\"", "1", "\"[abc]\""]
+    );
+}
+
+#[test]
+fn case_whitespace_and_affixes() {
+    let vm = common::vm(&[STRINGS]);
+    assert_eq!(
+        common::eval(
+            &vm,
+            &[
+                r#"Strings.cases("Straße")"#,
+                // an em space is whitespace, but it isn't below ' '
+                "Strings.trimmedLengths(\"\u{2003} hi \\t\")",
+                r#"Strings.affixes("abcXYZ")"#,
+                r#"Strings.affixes("")"#,
+            ]
+        ),
+        [
+            "\"STRASSE straße\"",
+            "\"4 2\"",
+            "\"true false true false\"",
+            "\"false false false true\""
+        ]
+    );
+}
+
+#[test]
+fn equals_compares_contents_and_hash_code_matches_the_jvm() {
+    let vm = common::vm(&[STRINGS]);
+    assert_eq!(
+        common::eval(
+            &vm,
+            &[
+                r#"Strings.equality("abc")"#,
+                r#"Strings.hash("Aa")"#,
+                r#"Strings.hash("BB")"#,
+                r#"Strings.hash("")"#,
+                r#"Strings.hash("hello world")"#,
+            ]
+        ),
+        [
+            "\"true false false false\"",
+            "2112",
+            "2112",
+            "0",
+            "1794106052"
+        ]
+    );
+}
+
+#[test]
+fn a_copied_string_interns_to_the_literal() {
+    let vm = common::vm(&[STRINGS]);
+    assert_eq!(
+        common::eval(&vm, &["Strings.interned()"]),
+        ["\"false true true\""]
+    );
+}
+
+#[test]
+fn value_of_decodes_a_char_array() {
+    let vm = common::vm(&[STRINGS]);
+    assert_eq!(
+        common::eval(&vm, &["Strings.fromChars()", "Strings.charsOutOfRange()"]),
+        ["\"hi hic true\"", "\"offset 3, count 5, length 4\""]
+    );
+}
@@ -0,0 +1,56 @@
+mod common;
+
+const SWITCHES: &[u8] = include_bytes!("java/Switches.class");
+
+#[test]
+fn tableswitch_jumps_by_key() {
+    let vm = common::vm(&[SWITCHES]);
+    assert_eq!(
+        common::eval(
+            &vm,
+            &[
+                "Switches.dense(-2)",
+                "Switches.dense(-1)",
+                "Switches.dense(0)",
+                "Switches.dense(2)",
+                "Switches.dense(3)",
+            ]
+        ),
+        [
+            "\"other\"",
+            "\"minus one\"",
+            "\"zero\"",
+            "\"two\"",
+            "\"other\""
+        ]
+    );
+}
+
+#[test]
+fn switches_read_past_padding_at_any_offset() {
+    let vm = common::vm(&[SWITCHES]);
+    // the switches start at code offsets 1, 2, 3, and 4, so they skip 2, 1, 0, and 3 bytes
+    let cases = [
+        ("sparse", ["-1000", "7", "1000000", "8"]),
+        ("negated", ["1000", "-7", "-1000000", "0"]),
+        ("incremented", ["-1001", "6", "999999", "7"]),
+        ("scaled", ["-10", "7", "10000", "70"]),
+    ];
+    for (method, keys) in cases {
+        let calls = keys.map(|key| format!("Switches.{method}({key})"));
+        let calls = calls.each_ref().map(String::as_str);
+        assert_eq!(common::eval(&vm, &calls), ["1", "2", "3", "0"], "{method}");
+    }
+    assert_eq!(
+        common::eval(
+            &vm,
+            &[
+                "Switches.shiftedDense(100)",
+                "Switches.shiftedDense(103)",
+                "Switches.shiftedDense(99)",
+                "Switches.shiftedDense(104)",
+            ]
+        ),
+        ["\"zero\"", "\"three\"", "\"other\"", "\"other\""]
+    );
+}
@@ -0,0 +1,15 @@
+mod common;
+
+const THREADS: &[u8] = include_bytes!("java/Threads.class");
+
+#[test]
+fn the_main_thread_has_a_name() {
+    let vm = common::vm(&[THREADS]);
+    assert_eq!(
+        common::eval(
+            &vm,
+            &["Threads.name()", "Threads.renamed()", "Threads.nullName()"]
+        ),
+        ["\"main\"", "\"worker\"", "\"name cannot be null main\""]
+    );
+}
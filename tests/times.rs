@@ -0,0 +1,16 @@
+mod common;
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const TIMES: &[u8] = include_bytes!("java/Times.class");
+
+#[test]
+fn clocks_tell_the_time() {
+    let vm = common::vm(&[TIMES]);
+    let before = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+    let results = common::eval(&vm, &["Times.nanoTimeAdvances()", "Times.millis()"]);
+    let after = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+    assert_eq!(results[0], "true");
+    let millis: u128 = results[1].parse().unwrap();
+    assert!((before.as_millis()..=after.as_millis()).contains(&millis));
+}
@@ -0,0 +1,15 @@
+mod common;
+
+const TO_MAP: &[u8] = include_bytes!("java/ToMap.class");
+
+#[test]
+fn duplicate_keys_throw() {
+    let vm = common::vm(&[TO_MAP]);
+    assert_eq!(
+        common::eval(&vm, &["ToMap.byWord()", "ToMap.byInitial()"]),
+        [
+            "3",
+            "\"Duplicate key a (attempted merging values apple and avocado)\""
+        ]
+    );
+}
@@ -0,0 +1,59 @@
+mod common;
+
+use std::{
+    io::{self, Write},
+    sync::{Arc, Mutex},
+};
+
+use javarust::{virtual_machine::set_trace_output, VmOptions};
+use jvmrs_lib::method;
+
+const ARITH: &[u8] = include_bytes!("java/Arith.class");
+
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn trace_shows_offsets_and_mnemonics() {
+    let buffer = SharedBuffer::default();
+    set_trace_output(buffer.clone());
+    let vm = common::vm_with_options(
+        &[ARITH],
+        VmOptions {
+            trace: true,
+            ..Default::default()
+        },
+    );
+    let result = vm
+        .invoke_static(
+            "Arith",
+            "addTwice",
+            &method!((int, int) -> int),
+            &[3, 4],
+            false,
+        )
+        .unwrap();
+    assert_eq!(result, [14]);
+    let trace = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+    assert_eq!(
+        trace.lines().collect::<Vec<_>>(),
+        [
+            "Arith.addTwice    0: iload_0 -> 3",
+            "Arith.addTwice    1: iload_1 -> 4",
+            "Arith.addTwice    2: iadd -> 7",
+            "Arith.addTwice    3: iconst_2 -> 2",
+            "Arith.addTwice    4: imul -> 14",
+            "Arith.addTwice    5: ireturn",
+        ]
+    );
+}
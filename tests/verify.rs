@@ -0,0 +1,29 @@
+mod common;
+
+use javarust::VmOptions;
+
+// `one`'s max_stack was patched from 1 to 0 after compiling, so it overflows as soon as it pushes
+const VERIFY: &[u8] = include_bytes!("java/Verify.class");
+
+#[test]
+fn overflowing_max_stack_throws_verify_error() {
+    let vm = common::vm_with_options(
+        &[VERIFY],
+        VmOptions {
+            verify: true,
+            ..Default::default()
+        },
+    );
+    assert_eq!(
+        common::eval(&vm, &["Verify.caught()", "Verify.message()"]),
+        [
+            "-1",
+            "\"operand stack of Verify.one grew to 1, past its max_stack of 0\""
+        ]
+    );
+    let unverified = common::vm(&[VERIFY]);
+    assert_eq!(
+        common::eval(&unverified, &["Verify.caught()", "Verify.message()"]),
+        ["1", "\"ran 1\""]
+    );
+}
@@ -0,0 +1,22 @@
+mod common;
+
+const WIDE: &[u8] = include_bytes!("java/Wide.class");
+
+#[test]
+fn wide_iinc_leaves_neighboring_longs_alone() {
+    // `counter` sits in slot 258, between longs in 256-257 and 259-260
+    let vm = common::vm(&[WIDE]);
+    assert_eq!(
+        common::eval(&vm, &["Wide.iinc()"]),
+        ["\"1099511627776 29007 -1099511627776 254 -745\""]
+    );
+}
+
+#[test]
+fn wide_loads_and_stores_reach_every_kind_of_local() {
+    let vm = common::vm(&[WIDE]);
+    assert_eq!(
+        common::eval(&vm, &["Wide.category2()"]),
+        ["\"127.5 1125899906842625 2.3333333 wide3\""]
+    );
+}